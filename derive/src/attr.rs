@@ -0,0 +1,184 @@
+//! The `#[serde]` attributes affecting schemas.
+
+use crate::case::RenameRule;
+use syn::{meta::ParseNestedMeta, Attribute, Expr, Lit, LitStr, Meta, Result, Token};
+
+/// Attributes of a struct or an enum.
+#[derive(Default)]
+pub struct Container {
+    pub rename: Option<String>,
+    pub rename_all: Option<RenameRule>,
+    pub tag: Option<String>,
+    pub content: Option<String>,
+    pub untagged: bool,
+    pub transparent: bool,
+    pub default: bool,
+}
+
+/// How the variants of an enum are represented.
+pub enum Tagging<'a> {
+    External,
+    Internal { tag: &'a str },
+    Adjacent { tag: &'a str, content: &'a str },
+    Untagged,
+}
+
+impl Container {
+    pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut container = Container::default();
+        serde(attrs, |meta| {
+            if meta.path.is_ident("rename") {
+                container.rename = name(&meta)?;
+            } else if meta.path.is_ident("rename_all") {
+                container.rename_all = rename_rule(&meta)?;
+            } else if meta.path.is_ident("tag") {
+                container.tag = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("content") {
+                container.content = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("untagged") {
+                container.untagged = true;
+            } else if meta.path.is_ident("transparent") {
+                container.transparent = true;
+            } else if meta.path.is_ident("default") {
+                container.default = true;
+                ignore(&meta)?;
+            } else {
+                ignore(&meta)?;
+            }
+            Ok(())
+        })?;
+        Ok(container)
+    }
+
+    pub fn tagging(&self) -> Tagging<'_> {
+        match (self.untagged, &self.tag, &self.content) {
+            (true, _, _) => Tagging::Untagged,
+            (false, Some(tag), Some(content)) => Tagging::Adjacent { tag, content },
+            (false, Some(tag), None) => Tagging::Internal { tag },
+            (false, None, _) => Tagging::External,
+        }
+    }
+}
+
+/// Attributes of a field.
+#[derive(Default)]
+pub struct Field {
+    pub rename: Option<String>,
+    pub skip: bool,
+    pub flatten: bool,
+    /// Whether the field may be left out.
+    pub default: bool,
+}
+
+impl Field {
+    pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut field = Field::default();
+        serde(attrs, |meta| {
+            if meta.path.is_ident("rename") {
+                field.rename = name(&meta)?;
+            } else if meta.path.is_ident("skip") || meta.path.is_ident("skip_serializing") {
+                field.skip = true;
+            } else if meta.path.is_ident("flatten") {
+                field.flatten = true;
+            } else if meta.path.is_ident("default") || meta.path.is_ident("skip_serializing_if") {
+                field.default = true;
+                ignore(&meta)?;
+            } else {
+                ignore(&meta)?;
+            }
+            Ok(())
+        })?;
+        Ok(field)
+    }
+}
+
+/// Attributes of a variant.
+#[derive(Default)]
+pub struct Variant {
+    pub rename: Option<String>,
+    pub rename_all: Option<RenameRule>,
+    pub skip: bool,
+}
+
+impl Variant {
+    pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut variant = Variant::default();
+        serde(attrs, |meta| {
+            if meta.path.is_ident("rename") {
+                variant.rename = name(&meta)?;
+            } else if meta.path.is_ident("rename_all") {
+                variant.rename_all = rename_rule(&meta)?;
+            } else if meta.path.is_ident("skip") || meta.path.is_ident("skip_serializing") {
+                variant.skip = true;
+            } else {
+                ignore(&meta)?;
+            }
+            Ok(())
+        })?;
+        Ok(variant)
+    }
+}
+
+/// Returns the documentation of an item, which becomes the description of its schema.
+pub fn docs(attrs: &[Attribute]) -> String {
+    let mut lines = Vec::new();
+    for attr in attrs {
+        if let Meta::NameValue(doc) = &attr.meta {
+            if !doc.path.is_ident("doc") {
+                continue;
+            }
+            if let Expr::Lit(lit) = &doc.value {
+                if let Lit::Str(line) = &lit.lit {
+                    lines.push(line.value().trim().to_owned());
+                }
+            }
+        }
+    }
+    lines.join("\n").trim().to_owned()
+}
+
+fn serde(attrs: &[Attribute], mut f: impl FnMut(ParseNestedMeta) -> Result<()>) -> Result<()> {
+    for attr in attrs {
+        if attr.path().is_ident("serde") {
+            attr.parse_nested_meta(&mut f)?;
+        }
+    }
+    Ok(())
+}
+
+/// Parses `name = "..."` or `name(serialize = "...")`, the serialized name.
+fn name(meta: &ParseNestedMeta) -> Result<Option<String>> {
+    if meta.input.peek(Token![=]) {
+        return Ok(Some(meta.value()?.parse::<LitStr>()?.value()));
+    }
+    let mut name = None;
+    meta.parse_nested_meta(|meta| {
+        if meta.path.is_ident("serialize") {
+            name = Some(meta.value()?.parse::<LitStr>()?.value());
+        } else {
+            ignore(&meta)?;
+        }
+        Ok(())
+    })?;
+    Ok(name)
+}
+
+fn rename_rule(meta: &ParseNestedMeta) -> Result<Option<RenameRule>> {
+    match name(meta)? {
+        Some(rule) => match RenameRule::from_name(&rule) {
+            Some(rule) => Ok(Some(rule)),
+            None => Err(meta.error(format!("unknown rename rule `{}`", rule))),
+        },
+        None => Ok(None),
+    }
+}
+
+/// Skips the value of an attribute without effect on the schema.
+fn ignore(meta: &ParseNestedMeta) -> Result<()> {
+    if meta.input.peek(Token![=]) {
+        meta.value()?.parse::<Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.parse_nested_meta(|meta| ignore(&meta))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,162 @@
+//! Case conventions of `#[serde(rename_all = "...")]`.
+
+use self::RenameRule::*;
+
+/// A case convention which fields and variants are renamed to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+const RULES: [(&str, RenameRule); 8] = [
+    ("lowercase", Lower),
+    ("UPPERCASE", Upper),
+    ("Pascal", Pascal),
+    ("camelCase", Camel),
+    ("snake_case", Snake),
+    ("SCREAMING_SNAKE_CASE", ScreamingSnake),
+    ("kebab-case", Kebab),
+    ("SCREAMING-KEBAB-CASE", ScreamingKebab),
+];
+
+impl RenameRule {
+    /// Returns the rule named as in serde, if any.
+    pub fn from_name(name: &str) -> Option<Self> {
+        RULES
+            .iter()
+            .find(|(rule, _)| *rule == name)
+            .map(|(_, rule)| *rule)
+    }
+
+    /// Renames a variant, which is in `Pascal`.
+    pub fn apply_to_variant(self, variant: &str) -> String {
+        match self {
+            Pascal => variant.to_owned(),
+            Lower => variant.to_ascii_lowercase(),
+            Upper => variant.to_ascii_uppercase(),
+            Camel => lower_first(variant),
+            Snake => {
+                let mut snake = String::new();
+                for (i, ch) in variant.char_indices() {
+                    if i > 0 && ch.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(ch.to_ascii_lowercase());
+                }
+                snake
+            }
+            ScreamingSnake => Snake.apply_to_variant(variant).to_ascii_uppercase(),
+            Kebab => Snake.apply_to_variant(variant).replace('_', "-"),
+            ScreamingKebab => ScreamingSnake.apply_to_variant(variant).replace('_', "-"),
+        }
+    }
+
+    /// Renames a field, which is in `snake_case`.
+    pub fn apply_to_field(self, field: &str) -> String {
+        match self {
+            Lower | Snake => field.to_owned(),
+            Upper => field.to_ascii_uppercase(),
+            Pascal => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for ch in field.chars() {
+                    if ch == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(ch);
+                    }
+                }
+                pascal
+            }
+            Camel => lower_first(&Pascal.apply_to_field(field)),
+            ScreamingSnake => field.to_ascii_uppercase(),
+            Kebab => field.replace('_', "-"),
+            ScreamingKebab => ScreamingSnake.apply_to_field(field).replace('_', "-"),
+        }
+    }
+}
+
+fn lower_first(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rename_variants() {
+        for (original, lower, upper, camel, snake, screaming, kebab, screaming_kebab) in &[
+            (
+                "Outcome", "outcome", "OUTCOME", "outcome", "outcome", "OUTCOME", "outcome",
+                "OUTCOME",
+            ),
+            (
+                "VeryTasty",
+                "verytasty",
+                "VERYTASTY",
+                "veryTasty",
+                "very_tasty",
+                "VERY_TASTY",
+                "very-tasty",
+                "VERY-TASTY",
+            ),
+            ("A", "a", "A", "a", "a", "A", "a", "A"),
+            ("Z42", "z42", "Z42", "z42", "z42", "Z42", "z42", "Z42"),
+        ] {
+            assert_eq!(Lower.apply_to_variant(original), *lower);
+            assert_eq!(Upper.apply_to_variant(original), *upper);
+            assert_eq!(Pascal.apply_to_variant(original), *original);
+            assert_eq!(Camel.apply_to_variant(original), *camel);
+            assert_eq!(Snake.apply_to_variant(original), *snake);
+            assert_eq!(ScreamingSnake.apply_to_variant(original), *screaming);
+            assert_eq!(Kebab.apply_to_variant(original), *kebab);
+            assert_eq!(ScreamingKebab.apply_to_variant(original), *screaming_kebab);
+        }
+    }
+
+    #[test]
+    fn test_rename_fields() {
+        for (original, upper, pascal, camel, screaming, kebab, screaming_kebab) in &[
+            (
+                "outcome", "OUTCOME", "Outcome", "outcome", "OUTCOME", "outcome", "OUTCOME",
+            ),
+            (
+                "very_tasty",
+                "VERY_TASTY",
+                "VeryTasty",
+                "veryTasty",
+                "VERY_TASTY",
+                "very-tasty",
+                "VERY-TASTY",
+            ),
+            ("a", "A", "A", "a", "A", "a", "A"),
+            ("z42", "Z42", "Z42", "z42", "Z42", "z42", "Z42"),
+        ] {
+            assert_eq!(Lower.apply_to_field(original), *original);
+            assert_eq!(Upper.apply_to_field(original), *upper);
+            assert_eq!(Pascal.apply_to_field(original), *pascal);
+            assert_eq!(Camel.apply_to_field(original), *camel);
+            assert_eq!(Snake.apply_to_field(original), *original);
+            assert_eq!(ScreamingSnake.apply_to_field(original), *screaming);
+            assert_eq!(Kebab.apply_to_field(original), *kebab);
+            assert_eq!(ScreamingKebab.apply_to_field(original), *screaming_kebab);
+        }
+        assert_eq!(RenameRule::from_name("camelCase"), Some(Camel));
+        assert_eq!(RenameRule::from_name("Camel"), None);
+    }
+}
@@ -0,0 +1,252 @@
+//! Derive macro for the `ToSchema` trait of `rweb_openapi`, honouring `#[serde]` attributes.
+//!
+//! Supported attributes are `rename`, `rename_all`, `tag`, `content`, `untagged` and
+//! `transparent` on containers, `rename`, `skip`, `flatten`, `default` and
+//! `skip_serializing_if` on fields and `rename`, `rename_all` and `skip` on variants. Others are
+//! ignored. Documentation comments become the descriptions of schemas.
+
+extern crate proc_macro;
+
+mod attr;
+mod case;
+
+use crate::{
+    attr::{Container, Field, Tagging, Variant},
+    case::RenameRule,
+};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse_macro_input, parse_quote, Data, DeriveInput, Error, Fields, FieldsNamed, GenericParam,
+    Result,
+};
+
+#[proc_macro_derive(ToSchema, attributes(serde))]
+pub fn derive_to_schema(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(mut input: DeriveInput) -> Result<TokenStream> {
+    let container = Container::parse(&input.attrs)?;
+    let schema = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) if container.transparent => unnamed(fields.named.iter())?,
+            Fields::Named(fields) => named(fields, container.rename_all, container.default)?,
+            Fields::Unnamed(fields) => unnamed(fields.unnamed.iter())?,
+            Fields::Unit => quote!(__derive::null()),
+        },
+        Data::Enum(data) => {
+            let mut variants = Vec::new();
+            for variant in &data.variants {
+                let attrs = Variant::parse(&variant.attrs)?;
+                if !attrs.skip {
+                    variants.push((variant, attrs));
+                }
+            }
+            enumeration(&container, &variants)?
+        }
+        Data::Union(data) => {
+            return Err(Error::new(
+                data.union_token.span,
+                "unions do not implement `Serialize`",
+            ))
+        }
+    };
+    let schema = described(schema, &attr::docs(&input.attrs));
+
+    // Generic types are inlined, as their schema depends on their parameters.
+    let generic = input
+        .generics
+        .params
+        .iter()
+        .any(|param| !matches!(param, GenericParam::Lifetime(_)));
+    let component_name = if generic {
+        quote!(None)
+    } else {
+        let name = container
+            .rename
+            .clone()
+            .unwrap_or_else(|| input.ident.to_string());
+        quote!(Some(#name.into()))
+    };
+    let params: Vec<_> = input
+        .generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect();
+    let where_clause = input.generics.make_where_clause();
+    for param in params {
+        where_clause
+            .predicates
+            .push(parse_quote!(#param: ::rweb_openapi::v3_0::ToSchema));
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::rweb_openapi::v3_0::ToSchema for #ident #ty_generics #where_clause {
+            fn component_name() -> Option<::rweb_openapi::Str> {
+                #component_name
+            }
+
+            fn schema(
+                components: &mut ::rweb_openapi::v3_0::Components,
+            ) -> ::rweb_openapi::v3_0::Schema {
+                use ::rweb_openapi::v3_0::__derive;
+                #schema
+            }
+        }
+    })
+}
+
+/// Adds a description to an expression of type `Schema`.
+fn described(schema: TokenStream, docs: &str) -> TokenStream {
+    if docs.is_empty() {
+        return schema;
+    }
+    quote!({
+        let mut schema = #schema;
+        __derive::describe(&mut schema, #docs);
+        schema
+    })
+}
+
+/// Schema of named fields, serialized as an object.
+fn named(
+    fields: &FieldsNamed,
+    rename_all: Option<RenameRule>,
+    default: bool,
+) -> Result<TokenStream> {
+    let mut properties = Vec::new();
+    for field in &fields.named {
+        let attrs = Field::parse(&field.attrs)?;
+        let ty = &field.ty;
+        if attrs.skip {
+            continue;
+        }
+        if attrs.flatten {
+            properties.push(quote!(__derive::flatten::<#ty>(&mut schema, components);));
+            continue;
+        }
+        let name = match (attrs.rename, &field.ident) {
+            (Some(name), _) => name,
+            (None, Some(ident)) => {
+                let ident = ident.to_string();
+                let ident = ident.trim_start_matches("r#");
+                match rename_all {
+                    Some(rule) => rule.apply_to_field(ident),
+                    None => ident.to_owned(),
+                }
+            }
+            (None, None) => continue,
+        };
+        let required = !(attrs.default || default);
+        let docs = attr::docs(&field.attrs);
+        properties.push(quote! {
+            __derive::property::<#ty>(&mut schema, components, #name, #required, #docs);
+        });
+    }
+    Ok(quote!({
+        let mut schema = __derive::object();
+        #(#properties)*
+        schema
+    }))
+}
+
+/// Schema of unnamed fields, serialized as the only one or as an array.
+fn unnamed<'a>(fields: impl Iterator<Item = &'a syn::Field>) -> Result<TokenStream> {
+    let mut types = Vec::new();
+    for field in fields {
+        if !Field::parse(&field.attrs)?.skip {
+            types.push(&field.ty);
+        }
+    }
+    Ok(match types.as_slice() {
+        [ty] => quote!(__derive::newtype(
+            <#ty as ::rweb_openapi::v3_0::ToSchema>::to_schema(components)
+        )),
+        types => quote!(__derive::tuple(vec![
+            #(<#types as ::rweb_openapi::v3_0::ToSchema>::to_schema(components)),*
+        ])),
+    })
+}
+
+fn enumeration(
+    container: &Container,
+    variants: &[(&syn::Variant, Variant)],
+) -> Result<TokenStream> {
+    let tagging = container.tagging();
+    let mut units = Vec::new();
+    let mut schemas = Vec::new();
+    for (variant, attrs) in variants {
+        let name = match (&attrs.rename, container.rename_all) {
+            (Some(name), _) => name.clone(),
+            (None, Some(rule)) => rule.apply_to_variant(&variant.ident.to_string()),
+            (None, None) => variant.ident.to_string(),
+        };
+        // The serialized fields, as a `ComponentOrInlineSchema`.
+        let content = match &variant.fields {
+            Fields::Named(fields) => {
+                let schema = named(fields, attrs.rename_all, false)?;
+                Some(quote!(::rweb_openapi::v3_0::ComponentOrInlineSchema::from(#schema)))
+            }
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                let ty = &fields.unnamed[0].ty;
+                Some(quote!(<#ty as ::rweb_openapi::v3_0::ToSchema>::to_schema(components)))
+            }
+            Fields::Unnamed(fields) => {
+                if let Tagging::Internal { .. } = tagging {
+                    return Err(Error::new_spanned(
+                        fields,
+                        "internally tagged enums cannot have tuple variants",
+                    ));
+                }
+                let schema = unnamed(fields.unnamed.iter())?;
+                Some(quote!(::rweb_openapi::v3_0::ComponentOrInlineSchema::from(#schema)))
+            }
+            Fields::Unit => None,
+        };
+        let schema = match (&tagging, content) {
+            (Tagging::External, None) => {
+                units.push(name);
+                continue;
+            }
+            (Tagging::External, Some(content)) => quote!(__derive::external(#name, #content)),
+            (Tagging::Internal { tag }, None) => {
+                quote!(__derive::internal(#tag, #name, __derive::object().into()))
+            }
+            (Tagging::Internal { tag }, Some(content)) => {
+                quote!(__derive::internal(#tag, #name, #content))
+            }
+            (Tagging::Adjacent { tag, content: key }, None) => {
+                quote!(__derive::adjacent(#tag, #key, #name, None))
+            }
+            (Tagging::Adjacent { tag, content: key }, Some(content)) => {
+                quote!(__derive::adjacent(#tag, #key, #name, Some(#content)))
+            }
+            (Tagging::Untagged, None) => quote!(__derive::null()),
+            (Tagging::Untagged, Some(content)) => {
+                schemas.push(content);
+                continue;
+            }
+        };
+        let schema = described(schema, &attr::docs(&variant.attrs));
+        schemas.push(quote!(::rweb_openapi::v3_0::ComponentOrInlineSchema::from(#schema)));
+    }
+
+    // Unit variants of externally tagged enums serialize as their names.
+    if !units.is_empty() {
+        let units = quote!(__derive::string_enum(&[#(#units),*]));
+        if schemas.is_empty() {
+            return Ok(units);
+        }
+        schemas.insert(
+            0,
+            quote!(::rweb_openapi::v3_0::ComponentOrInlineSchema::from(#units)),
+        );
+    }
+    Ok(quote!(__derive::one_of(vec![#(#schemas),*])))
+}
@@ -0,0 +1,399 @@
+// Client of `Pets` 1.0.0, generated from its specification.
+
+use rweb_openapi::v3_0::ParameterStyle;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CreatePetDefault {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Response of [`Client::create_pet`].
+#[derive(Debug)]
+pub enum CreatePetResponse {
+    /// Created.
+    Created(Id),
+    /// An error.
+    Default(u16, CreatePetDefault),
+}
+
+/// Response of [`Client::get_health`].
+#[derive(Debug)]
+pub enum GetHealthResponse {
+    /// The health.
+    Default(u16, String),
+}
+
+/// Response of [`Client::get_pet`].
+#[derive(Debug)]
+pub enum GetPetResponse {
+    /// The pet.
+    Ok(Pet),
+    /// No such pet.
+    NotFound,
+    /// Server error.
+    Status5xx(u16, String),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Id {
+    Integer(i64),
+    String(String),
+    Object(IdObject),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IdObject {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uuid: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Kind {
+    #[serde(rename = "cat")]
+    Cat,
+    #[serde(rename = "dog")]
+    Dog,
+    #[serde(rename = "guinea-pig")]
+    GuineaPig,
+}
+
+/// Response of [`Client::list_pets`].
+#[derive(Debug)]
+pub enum ListPetsResponse {
+    /// The pets.
+    Ok(Vec<Pet>),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LoginBody {
+    pub user: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+}
+
+/// Response of [`Client::login`].
+#[derive(Debug)]
+pub enum LoginResponse {
+    /// Logged in.
+    NoContent,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NewPet {
+    #[serde(flatten)]
+    pub pet: Pet,
+    pub id: Id,
+}
+
+/// A pet.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Pet {
+    /// Name of the pet.
+    #[serde(rename = "petName")]
+    pub pet_name: String,
+    pub kind: Kind,
+    pub weight: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub age: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<PetType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<Box<Pet>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub children: Option<Vec<Pet>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub labels: Option<std::collections::BTreeMap<String, String>>,
+}
+
+pub type PetAlias = Pet;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PetType {
+    #[serde(rename = "house")]
+    House,
+    #[serde(rename = "farm")]
+    Farm,
+}
+
+/// Response of [`Client::put_pets_pet_id`].
+#[derive(Debug)]
+pub enum PutPetsPetIdResponse {
+    /// Updated.
+    NoContent,
+}
+
+pub type Tags = Vec<String>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum UploadPhotoFormat {
+    #[serde(rename = "png")]
+    Png,
+    #[serde(rename = "jpg")]
+    Jpg,
+}
+
+/// Response of [`Client::upload_photo`].
+#[derive(Debug)]
+pub enum UploadPhotoResponse {
+    /// Uploaded.
+    Created,
+}
+
+/// Errors of the requests of [`Client`].
+#[derive(Debug)]
+pub enum Error {
+    /// The base URL of the client cannot have a path.
+    BaseUrl,
+    /// A parameter could not be serialized.
+    Parameter(serde_json::Error),
+    /// The request could not be sent, or its response could not be read.
+    Request(reqwest::Error),
+    /// The response has a status code which the operation does not define.
+    UnexpectedStatus(u16),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Error::BaseUrl => f.write_str("the base URL cannot have a path"),
+            Error::Parameter(error) => write!(f, "cannot serialize a parameter: {}", error),
+            Error::Request(error) => error.fmt(f),
+            Error::UnexpectedStatus(status) => write!(f, "unexpected status code {}", status),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        Error::Parameter(error)
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(error: reqwest::Error) -> Self {
+        Error::Request(error)
+    }
+}
+
+/// Client of `Pets` 1.0.0.
+#[derive(Clone, Debug)]
+pub struct Client {
+    http: reqwest::Client,
+    base_url: reqwest::Url,
+}
+
+impl Client {
+    /// Creates a client of the server at `base_url`.
+    pub fn new(base_url: reqwest::Url) -> Self {
+        Self::with_client(reqwest::Client::new(), base_url)
+    }
+
+    /// Creates a client of the server at `base_url`, sending requests with `http`.
+    pub fn with_client(http: reqwest::Client, base_url: reqwest::Url) -> Self {
+        Client { http, base_url }
+    }
+
+    pub async fn list_pets(
+        &self,
+        kind: Option<Kind>,
+        limit: Option<i32>,
+    ) -> Result<ListPetsResponse, Error> {
+        let mut url = self.base_url.clone();
+        {
+            let mut segments = url.path_segments_mut().map_err(|_| Error::BaseUrl)?;
+            segments.pop_if_empty();
+            segments.push("pets");
+        }
+        let mut query = Vec::new();
+        if let Some(kind) = kind {
+            query.extend(parameter("kind", ParameterStyle::Form, true, &kind)?);
+        }
+        if let Some(limit) = limit {
+            query.extend(parameter("limit", ParameterStyle::Form, true, &limit)?);
+        }
+        if !query.is_empty() {
+            url.query_pairs_mut().extend_pairs(query);
+        }
+        let request = self.http.request(reqwest::Method::GET, url);
+        let response = request.send().await?;
+        let status = response.status().as_u16();
+        Ok(match status {
+            200 => ListPetsResponse::Ok(response.json().await?),
+            _ => return Err(Error::UnexpectedStatus(status)),
+        })
+    }
+
+    pub async fn create_pet(&self, body: &NewPet) -> Result<CreatePetResponse, Error> {
+        let mut url = self.base_url.clone();
+        {
+            let mut segments = url.path_segments_mut().map_err(|_| Error::BaseUrl)?;
+            segments.pop_if_empty();
+            segments.push("pets");
+        }
+        let mut request = self.http.request(reqwest::Method::POST, url);
+        request = request.json(body);
+        let response = request.send().await?;
+        let status = response.status().as_u16();
+        Ok(match status {
+            201 => CreatePetResponse::Created(response.json().await?),
+            _ => CreatePetResponse::Default(status, response.json().await?),
+        })
+    }
+
+    /// Returns a pet.
+    pub async fn get_pet(
+        &self,
+        pet_id: i64,
+        fields: Option<Vec<String>>,
+        x_request_id: serde_json::Value,
+        session: Option<String>,
+    ) -> Result<GetPetResponse, Error> {
+        let mut url = self.base_url.clone();
+        {
+            let mut segments = url.path_segments_mut().map_err(|_| Error::BaseUrl)?;
+            segments.pop_if_empty();
+            segments.push("pets");
+            segments.push(&path_parameter("petId", ParameterStyle::Simple, false, &pet_id)?);
+        }
+        let mut query = Vec::new();
+        if let Some(fields) = fields {
+            query.extend(parameter("fields", ParameterStyle::Form, true, &fields)?);
+        }
+        if !query.is_empty() {
+            url.query_pairs_mut().extend_pairs(query);
+        }
+        let mut request = self.http.request(reqwest::Method::GET, url);
+        for (_, value) in parameter("X-Request-Id", ParameterStyle::Simple, false, &x_request_id)? {
+            request = request.header("X-Request-Id", value);
+        }
+        let mut cookies = Vec::new();
+        if let Some(session) = session {
+            for (name, value) in parameter("session", ParameterStyle::Form, true, &session)? {
+                cookies.push(format!("{}={}", name, value));
+            }
+        }
+        if !cookies.is_empty() {
+            request = request.header("Cookie", cookies.join("; "));
+        }
+        let response = request.send().await?;
+        let status = response.status().as_u16();
+        Ok(match status {
+            200 => GetPetResponse::Ok(response.json().await?),
+            404 => GetPetResponse::NotFound,
+            500..=599 => GetPetResponse::Status5xx(status, response.text().await?),
+            _ => return Err(Error::UnexpectedStatus(status)),
+        })
+    }
+
+    #[deprecated]
+    pub async fn put_pets_pet_id(
+        &self,
+        pet_id: i64,
+        tags: Option<Vec<String>>,
+        body: &Pet,
+    ) -> Result<PutPetsPetIdResponse, Error> {
+        let mut url = self.base_url.clone();
+        {
+            let mut segments = url.path_segments_mut().map_err(|_| Error::BaseUrl)?;
+            segments.pop_if_empty();
+            segments.push("pets");
+            segments.push(&path_parameter("petId", ParameterStyle::Simple, false, &pet_id)?);
+        }
+        let mut query = Vec::new();
+        if let Some(tags) = tags {
+            query.extend(parameter("tags", ParameterStyle::PipeDelimited, false, &tags)?);
+        }
+        if !query.is_empty() {
+            url.query_pairs_mut().extend_pairs(query);
+        }
+        let mut request = self.http.request(reqwest::Method::PUT, url);
+        request = request.json(body);
+        let response = request.send().await?;
+        let status = response.status().as_u16();
+        Ok(match status {
+            204 => PutPetsPetIdResponse::NoContent,
+            _ => return Err(Error::UnexpectedStatus(status)),
+        })
+    }
+
+    pub async fn upload_photo(
+        &self,
+        pet_id: serde_json::Value,
+        format: UploadPhotoFormat,
+        body: Option<Vec<u8>>,
+    ) -> Result<UploadPhotoResponse, Error> {
+        let mut url = self.base_url.clone();
+        {
+            let mut segments = url.path_segments_mut().map_err(|_| Error::BaseUrl)?;
+            segments.pop_if_empty();
+            segments.push("pets");
+            segments.push(&path_parameter("petId", ParameterStyle::Label, false, &pet_id)?);
+            segments.push(&format!("photo.{}", path_parameter("format", ParameterStyle::Simple, false, &format)?));
+        }
+        let mut request = self.http.request(reqwest::Method::POST, url);
+        if let Some(body) = body {
+            request = request.header("Content-Type", "image/png").body(body);
+        }
+        let response = request.send().await?;
+        let status = response.status().as_u16();
+        Ok(match status {
+            201 => UploadPhotoResponse::Created,
+            _ => return Err(Error::UnexpectedStatus(status)),
+        })
+    }
+
+    pub async fn login(&self, body: &LoginBody) -> Result<LoginResponse, Error> {
+        let mut url = self.base_url.clone();
+        {
+            let mut segments = url.path_segments_mut().map_err(|_| Error::BaseUrl)?;
+            segments.pop_if_empty();
+            segments.push("login");
+        }
+        let mut request = self.http.request(reqwest::Method::POST, url);
+        request = request.form(body);
+        let response = request.send().await?;
+        let status = response.status().as_u16();
+        Ok(match status {
+            204 => LoginResponse::NoContent,
+            _ => return Err(Error::UnexpectedStatus(status)),
+        })
+    }
+
+    pub async fn get_health(&self) -> Result<GetHealthResponse, Error> {
+        let mut url = self.base_url.clone();
+        {
+            let mut segments = url.path_segments_mut().map_err(|_| Error::BaseUrl)?;
+            segments.pop_if_empty();
+            segments.push("health");
+        }
+        let request = self.http.request(reqwest::Method::GET, url);
+        let response = request.send().await?;
+        let status = response.status().as_u16();
+        Ok(GetHealthResponse::Default(status, response.text().await?))
+    }
+}
+
+fn parameter(
+    name: &str,
+    style: ParameterStyle,
+    explode: bool,
+    value: &impl Serialize,
+) -> Result<Vec<(String, String)>, Error> {
+    let value = serde_json::to_value(value)?;
+    Ok(rweb_openapi::v3_0::serialize_parameter(name, style, explode, &value))
+}
+
+fn path_parameter(
+    name: &str,
+    style: ParameterStyle,
+    explode: bool,
+    value: &impl Serialize,
+) -> Result<String, Error> {
+    let pairs = parameter(name, style, explode, value)?;
+    Ok(pairs.into_iter().map(|(_, value)| value).collect())
+}
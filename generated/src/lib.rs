@@ -0,0 +1,9 @@
+//! Models, client and server generated from `pets.json` by `rweb-openapi`, checked in so that
+//! building the workspace checks that the generated code compiles.
+//!
+//! The tests of `rweb-openapi` check that these files are up to date. Run them with
+//! `UPDATE_GENERATED=1` to regenerate the files.
+
+pub mod client;
+pub mod models;
+pub mod server;
@@ -0,0 +1,956 @@
+// Server of `Pets` 1.0.0, generated from its specification.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use warp::{Filter, Reply};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CreatePetDefault {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Response of [`PetsApi::create_pet`].
+#[derive(Debug)]
+pub enum CreatePetResponse {
+    /// Created.
+    Created(Id),
+    /// An error.
+    Default(u16, CreatePetDefault),
+}
+
+impl warp::Reply for CreatePetResponse {
+    fn into_response(self) -> warp::reply::Response {
+        match self {
+            CreatePetResponse::Created(body) => {
+                reply(201, "application/json", serde_json::to_vec(&body).unwrap_or_default())
+            }
+            CreatePetResponse::Default(status, body) => {
+                reply(status, "application/json", serde_json::to_vec(&body).unwrap_or_default())
+            }
+        }
+    }
+}
+
+/// Response of [`DefaultApi::get_health`].
+#[derive(Debug)]
+pub enum GetHealthResponse {
+    /// The health.
+    Default(u16, String),
+}
+
+impl warp::Reply for GetHealthResponse {
+    fn into_response(self) -> warp::reply::Response {
+        match self {
+            GetHealthResponse::Default(status, body) => {
+                reply(status, "text/plain", body.into_bytes())
+            }
+        }
+    }
+}
+
+/// Response of [`PetsApi::get_pet`].
+#[derive(Debug)]
+pub enum GetPetResponse {
+    /// The pet.
+    Ok(Pet),
+    /// No such pet.
+    NotFound,
+    /// Server error.
+    Status5xx(u16, String),
+}
+
+impl warp::Reply for GetPetResponse {
+    fn into_response(self) -> warp::reply::Response {
+        match self {
+            GetPetResponse::Ok(body) => {
+                reply(200, "application/json", serde_json::to_vec(&body).unwrap_or_default())
+            }
+            GetPetResponse::NotFound => reply(404, "", Vec::new()),
+            GetPetResponse::Status5xx(status, body) => {
+                reply(status, "text/plain", body.into_bytes())
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Id {
+    Integer(i64),
+    String(String),
+    Object(IdObject),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IdObject {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uuid: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Kind {
+    #[serde(rename = "cat")]
+    Cat,
+    #[serde(rename = "dog")]
+    Dog,
+    #[serde(rename = "guinea-pig")]
+    GuineaPig,
+}
+
+/// Response of [`PetsApi::list_pets`].
+#[derive(Debug)]
+pub enum ListPetsResponse {
+    /// The pets.
+    Ok(Vec<Pet>),
+}
+
+impl warp::Reply for ListPetsResponse {
+    fn into_response(self) -> warp::reply::Response {
+        match self {
+            ListPetsResponse::Ok(body) => {
+                reply(200, "application/json", serde_json::to_vec(&body).unwrap_or_default())
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LoginBody {
+    pub user: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+}
+
+/// Response of [`DefaultApi::login`].
+#[derive(Debug)]
+pub enum LoginResponse {
+    /// Logged in.
+    NoContent,
+}
+
+impl warp::Reply for LoginResponse {
+    fn into_response(self) -> warp::reply::Response {
+        match self {
+            LoginResponse::NoContent => reply(204, "", Vec::new()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NewPet {
+    #[serde(flatten)]
+    pub pet: Pet,
+    pub id: Id,
+}
+
+/// A pet.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Pet {
+    /// Name of the pet.
+    #[serde(rename = "petName")]
+    pub pet_name: String,
+    pub kind: Kind,
+    pub weight: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub age: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<PetType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<Box<Pet>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub children: Option<Vec<Pet>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub labels: Option<std::collections::BTreeMap<String, String>>,
+}
+
+pub type PetAlias = Pet;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PetType {
+    #[serde(rename = "house")]
+    House,
+    #[serde(rename = "farm")]
+    Farm,
+}
+
+/// Response of [`PetsApi::put_pets_pet_id`].
+#[derive(Debug)]
+pub enum PutPetsPetIdResponse {
+    /// Updated.
+    NoContent,
+}
+
+impl warp::Reply for PutPetsPetIdResponse {
+    fn into_response(self) -> warp::reply::Response {
+        match self {
+            PutPetsPetIdResponse::NoContent => reply(204, "", Vec::new()),
+        }
+    }
+}
+
+pub type Tags = Vec<String>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum UploadPhotoFormat {
+    #[serde(rename = "png")]
+    Png,
+    #[serde(rename = "jpg")]
+    Jpg,
+}
+
+/// Response of [`DefaultApi::upload_photo`].
+#[derive(Debug)]
+pub enum UploadPhotoResponse {
+    /// Uploaded.
+    Created,
+}
+
+impl warp::Reply for UploadPhotoResponse {
+    fn into_response(self) -> warp::reply::Response {
+        match self {
+            UploadPhotoResponse::Created => reply(201, "", Vec::new()),
+        }
+    }
+}
+
+/// Everything about pets.
+pub trait PetsApi: Send + Sync + 'static {
+    fn list_pets(
+        &self,
+        kind: Option<Kind>,
+        limit: Option<i32>,
+    ) -> impl std::future::Future<Output = ListPetsResponse> + Send;
+
+    fn create_pet(
+        &self,
+        body: NewPet,
+    ) -> impl std::future::Future<Output = CreatePetResponse> + Send;
+
+    /// Returns a pet.
+    fn get_pet(
+        &self,
+        pet_id: i64,
+        fields: Option<Vec<String>>,
+        x_request_id: serde_json::Value,
+        session: Option<String>,
+    ) -> impl std::future::Future<Output = GetPetResponse> + Send;
+
+    #[deprecated]
+    fn put_pets_pet_id(
+        &self,
+        pet_id: i64,
+        tags: Option<Vec<String>>,
+        body: Pet,
+    ) -> impl std::future::Future<Output = PutPetsPetIdResponse> + Send;
+
+    /// The maximum length of the bodies of requests, in bytes. Longer requests are answered
+    /// with a `413 Payload Too Large`, without calling the trait.
+    fn body_limit(&self) -> u64 {
+        1024 * 1024
+    }
+}
+
+/// Serves the operations of [`PetsApi`] with `service`.
+pub fn pets_api<S: PetsApi>(
+    service: Arc<S>,
+) -> impl Filter<Extract = (warp::reply::Response,), Error = warp::Rejection> + Clone {
+    list_pets(service.clone())
+        .or(create_pet(service.clone()))
+        .unify()
+        .or(get_pet(service.clone()))
+        .unify()
+        .or(put_pets_pet_id(service))
+        .unify()
+}
+
+/// Serves [`PetsApi::list_pets`] with `service`.
+pub fn list_pets<S: PetsApi>(
+    service: Arc<S>,
+) -> impl Filter<Extract = (warp::reply::Response,), Error = warp::Rejection> + Clone {
+    let body_limit = service.body_limit();
+    route("get", "/pets", body_limit, move |request: Request| {
+        let service = service.clone();
+        async move {
+            let kind = parameter(&request.values.query, "kind")?;
+            let limit = parameter(&request.values.query, "limit")?;
+            Ok(service.list_pets(kind, limit).await.into_response())
+        }
+    })
+}
+
+/// Serves [`PetsApi::create_pet`] with `service`.
+pub fn create_pet<S: PetsApi>(
+    service: Arc<S>,
+) -> impl Filter<Extract = (warp::reply::Response,), Error = warp::Rejection> + Clone {
+    let body_limit = service.body_limit();
+    route("post", "/pets", body_limit, move |request: Request| {
+        let service = service.clone();
+        async move {
+            let body = json_body(request.values.body)?;
+            Ok(service.create_pet(body).await.into_response())
+        }
+    })
+}
+
+/// Serves [`PetsApi::get_pet`] with `service`.
+pub fn get_pet<S: PetsApi>(
+    service: Arc<S>,
+) -> impl Filter<Extract = (warp::reply::Response,), Error = warp::Rejection> + Clone {
+    let body_limit = service.body_limit();
+    route("get", "/pets/{petId}", body_limit, move |request: Request| {
+        let service = service.clone();
+        async move {
+            let pet_id = parameter(&request.values.path, "petId")?;
+            let fields = parameter(&request.values.query, "fields")?;
+            let x_request_id = parameter(&request.values.header, "X-Request-Id")?;
+            let session = parameter(&request.values.cookie, "session")?;
+            Ok(service.get_pet(pet_id, fields, x_request_id, session).await.into_response())
+        }
+    })
+}
+
+/// Serves [`PetsApi::put_pets_pet_id`] with `service`.
+#[allow(deprecated)]
+pub fn put_pets_pet_id<S: PetsApi>(
+    service: Arc<S>,
+) -> impl Filter<Extract = (warp::reply::Response,), Error = warp::Rejection> + Clone {
+    let body_limit = service.body_limit();
+    route("put", "/pets/{petId}", body_limit, move |request: Request| {
+        let service = service.clone();
+        async move {
+            let pet_id = parameter(&request.values.path, "petId")?;
+            let tags = parameter(&request.values.query, "tags")?;
+            let body = json_body(request.values.body)?;
+            Ok(service.put_pets_pet_id(pet_id, tags, body).await.into_response())
+        }
+    })
+}
+
+/// Operations without tags.
+pub trait DefaultApi: Send + Sync + 'static {
+    fn upload_photo(
+        &self,
+        pet_id: serde_json::Value,
+        format: UploadPhotoFormat,
+        body: Option<Vec<u8>>,
+    ) -> impl std::future::Future<Output = UploadPhotoResponse> + Send;
+
+    fn login(&self, body: LoginBody) -> impl std::future::Future<Output = LoginResponse> + Send;
+
+    fn get_health(&self) -> impl std::future::Future<Output = GetHealthResponse> + Send;
+
+    /// The maximum length of the bodies of requests, in bytes. Longer requests are answered
+    /// with a `413 Payload Too Large`, without calling the trait.
+    fn body_limit(&self) -> u64 {
+        1024 * 1024
+    }
+}
+
+/// Serves the operations of [`DefaultApi`] with `service`.
+pub fn default_api<S: DefaultApi>(
+    service: Arc<S>,
+) -> impl Filter<Extract = (warp::reply::Response,), Error = warp::Rejection> + Clone {
+    upload_photo(service.clone())
+        .or(login(service.clone()))
+        .unify()
+        .or(get_health(service))
+        .unify()
+}
+
+/// Serves [`DefaultApi::upload_photo`] with `service`.
+pub fn upload_photo<S: DefaultApi>(
+    service: Arc<S>,
+) -> impl Filter<Extract = (warp::reply::Response,), Error = warp::Rejection> + Clone {
+    let body_limit = service.body_limit();
+    route("post", "/pets/{petId}/photo.{format}", body_limit, move |request: Request| {
+        let service = service.clone();
+        async move {
+            let pet_id = parameter(&request.values.path, "petId")?;
+            let format = parameter(&request.values.path, "format")?;
+            let body = (!request.body.is_empty()).then_some(request.body);
+            Ok(service.upload_photo(pet_id, format, body).await.into_response())
+        }
+    })
+}
+
+/// Serves [`DefaultApi::login`] with `service`.
+pub fn login<S: DefaultApi>(
+    service: Arc<S>,
+) -> impl Filter<Extract = (warp::reply::Response,), Error = warp::Rejection> + Clone {
+    let body_limit = service.body_limit();
+    route("post", "/login", body_limit, move |request: Request| {
+        let service = service.clone();
+        async move {
+            let body = json_body(request.values.body)?;
+            Ok(service.login(body).await.into_response())
+        }
+    })
+}
+
+/// Serves [`DefaultApi::get_health`] with `service`.
+pub fn get_health<S: DefaultApi>(
+    service: Arc<S>,
+) -> impl Filter<Extract = (warp::reply::Response,), Error = warp::Rejection> + Clone {
+    let body_limit = service.body_limit();
+    route("get", "/health", body_limit, move |_request: Request| {
+        let service = service.clone();
+        async move {
+            Ok(service.get_health().await.into_response())
+        }
+    })
+}
+
+/// The parameters and body of a request to an operation, read and validated.
+struct Request {
+    values: rweb_openapi::v3_0::RequestValues,
+    body: Vec<u8>,
+}
+
+/// The filter serving the operation at `template` with `method` by calling `handler` with its
+/// requests, once they are read and validated.
+///
+/// `template` is matched against the rest of the path of requests. Only when it matches is the
+/// operation of the specification for the path looked up, in case a more specific template
+/// matches it as well.
+fn route<H, F>(
+    method: &'static str,
+    template: &'static str,
+    body_limit: u64,
+    handler: H,
+) -> impl Filter<Extract = (warp::reply::Response,), Error = warp::Rejection> + Clone
+where
+    H: Fn(Request) -> F + Clone + Send + Sync + 'static,
+    F: std::future::Future<Output = Result<warp::reply::Response, warp::reply::Response>> + Send,
+{
+    let parsed = rweb_openapi::v3_0::PathTemplate::parse(template);
+    warp::method()
+        .and(warp::path::tail())
+        .and_then(move |request_method: warp::http::Method, tail: warp::path::Tail| {
+            let path = format!("/{}", tail.as_str());
+            let matches = request_method.as_str().eq_ignore_ascii_case(method)
+                && parsed.matches(&path).is_some();
+            async move {
+                if !matches {
+                    return Err(warp::reject::not_found());
+                }
+                match spec().find_operation(method, &path) {
+                    Some(found) if found.path == template => Ok((found, path)),
+                    _ => Err(warp::reject::not_found()),
+                }
+            }
+        })
+        .untuple_one()
+        .and(warp::query::raw().or(warp::any().map(String::new)).unify())
+        .and(warp::header::headers_cloned())
+        .and(warp::body::stream())
+        .and_then(
+            move |found: rweb_openapi::v3_0::OperationMatch<'static>,
+                  path: String,
+                  query: String,
+                  headers: warp::http::HeaderMap,
+                  body| {
+                let handler = handler.clone();
+                async move {
+                    let body = match read_body(body, body_limit).await {
+                        Ok(body) => body,
+                        Err(response) => return Ok(response),
+                    };
+                    let parts = rweb_openapi::v3_0::RequestParts {
+                        method: found.method.to_ascii_uppercase(),
+                        path,
+                        query,
+                        headers: headers
+                            .iter()
+                            .filter_map(|(name, value)| {
+                                Some((name.to_string(), value.to_str().ok()?.to_string()))
+                            })
+                            .collect(),
+                        body,
+                    };
+                    let response = match spec().read_request(&found, &parts) {
+                        Ok(values) => {
+                            let request = Request {
+                                values,
+                                body: parts.body,
+                            };
+                            handler(request).await.unwrap_or_else(|response| response)
+                        }
+                        Err(errors) => invalid(errors.iter().map(ToString::to_string).collect()),
+                    };
+                    Ok::<_, warp::Rejection>(response)
+                }
+            },
+        )
+}
+
+/// Reads `body`, unless it is longer than `limit` bytes.
+#[allow(clippy::result_large_err)]
+async fn read_body<B: warp::Buf>(
+    body: impl futures_util::Stream<Item = Result<B, warp::Error>>,
+    limit: u64,
+) -> Result<Vec<u8>, warp::reply::Response> {
+    let mut body = std::pin::pin!(body);
+    let mut read = Vec::new();
+    while let Some(chunk) = futures_util::StreamExt::next(&mut body).await {
+        let mut chunk = chunk.map_err(|error| invalid(vec![format!("request body: {}", error)]))?;
+        if (read.len() + chunk.remaining()) as u64 > limit {
+            return Err(reply(413, "", Vec::new()));
+        }
+        while chunk.has_remaining() {
+            let bytes = chunk.chunk();
+            read.extend_from_slice(bytes);
+            let length = bytes.len();
+            chunk.advance(length);
+        }
+    }
+    Ok(read)
+}
+
+/// Deserializes the parameter `name` from the values of its location.
+#[allow(clippy::result_large_err)]
+fn parameter<T: serde::de::DeserializeOwned>(
+    values: &serde_json::Map<String, serde_json::Value>,
+    name: &str,
+) -> Result<T, warp::reply::Response> {
+    let value = values.get(name).cloned().unwrap_or_default();
+    serde_json::from_value(value)
+        .map_err(|error| invalid(vec![format!("parameter `{}`: {}", name, error)]))
+}
+
+/// Deserializes a JSON or form body.
+#[allow(clippy::result_large_err)]
+fn json_body<T: serde::de::DeserializeOwned>(
+    body: Option<serde_json::Value>,
+) -> Result<T, warp::reply::Response> {
+    serde_json::from_value(body.unwrap_or_default())
+        .map_err(|error| invalid(vec![format!("request body: {}", error)]))
+}
+
+fn reply(status: u16, content_type: &'static str, body: Vec<u8>) -> warp::reply::Response {
+    let mut response = warp::reply::Response::new(body.into());
+    *response.status_mut() = warp::http::StatusCode::from_u16(status)
+        .unwrap_or(warp::http::StatusCode::INTERNAL_SERVER_ERROR);
+    if !content_type.is_empty() {
+        response.headers_mut().insert(
+            warp::http::header::CONTENT_TYPE,
+            warp::http::HeaderValue::from_static(content_type),
+        );
+    }
+    response
+}
+
+/// The response to an invalid request, listing its `errors`.
+fn invalid(errors: Vec<String>) -> warp::reply::Response {
+    let body = serde_json::json!({ "errors": errors });
+    reply(400, "application/json", serde_json::to_vec(&body).unwrap_or_default())
+}
+
+/// The specification of the server, parsed once.
+fn spec() -> &'static rweb_openapi::v3_0::Spec {
+    static SPEC: std::sync::OnceLock<rweb_openapi::v3_0::Spec> = std::sync::OnceLock::new();
+    SPEC.get_or_init(|| {
+        serde_json::from_str(SPECIFICATION).expect("the specification of the server is valid")
+    })
+}
+
+/// The specification of the server.
+const SPECIFICATION: &str = r##"{
+  "openapi": "3.0.3",
+  "info": {
+    "title": "Pets",
+    "version": "1.0.0"
+  },
+  "paths": {
+    "/pets": {
+      "get": {
+        "tags": [
+          "pets"
+        ],
+        "operationId": "listPets",
+        "parameters": [
+          {
+            "name": "kind",
+            "in": "query",
+            "schema": {
+              "$ref": "#/components/schemas/Kind"
+            },
+            "example": null
+          },
+          {
+            "name": "limit",
+            "in": "query",
+            "schema": {
+              "format": "int32",
+              "type": "integer"
+            },
+            "example": null
+          }
+        ],
+        "responses": {
+          "200": {
+            "description": "The pets.",
+            "content": {
+              "application/json": {
+                "schema": {
+                  "items": {
+                    "$ref": "#/components/schemas/Pet"
+                  },
+                  "type": "array"
+                }
+              }
+            }
+          }
+        }
+      },
+      "post": {
+        "tags": [
+          "pets"
+        ],
+        "operationId": "createPet",
+        "requestBody": {
+          "content": {
+            "application/json": {
+              "schema": {
+                "$ref": "#/components/schemas/new-pet"
+              }
+            }
+          },
+          "required": true
+        },
+        "responses": {
+          "201": {
+            "description": "Created.",
+            "content": {
+              "application/json": {
+                "schema": {
+                  "$ref": "#/components/schemas/Id"
+                }
+              }
+            }
+          },
+          "default": {
+            "description": "An error.",
+            "content": {
+              "application/json": {
+                "schema": {
+                  "properties": {
+                    "message": {
+                      "type": "string"
+                    }
+                  },
+                  "type": "object"
+                }
+              }
+            }
+          }
+        }
+      }
+    },
+    "/pets/{petId}": {
+      "get": {
+        "tags": [
+          "pets"
+        ],
+        "summary": "Returns a pet.",
+        "operationId": "getPet",
+        "parameters": [
+          {
+            "name": "fields",
+            "in": "query",
+            "schema": {
+              "items": {
+                "type": "string"
+              },
+              "type": "array"
+            },
+            "example": null
+          },
+          {
+            "name": "X-Request-Id",
+            "in": "header",
+            "required": true,
+            "schema": {},
+            "example": null
+          },
+          {
+            "name": "session",
+            "in": "cookie",
+            "schema": {
+              "type": "string"
+            },
+            "example": null
+          }
+        ],
+        "responses": {
+          "200": {
+            "description": "The pet.",
+            "content": {
+              "application/json": {
+                "schema": {
+                  "$ref": "#/components/schemas/Pet"
+                }
+              }
+            }
+          },
+          "404": {
+            "description": "No such pet."
+          },
+          "5XX": {
+            "description": "Server error.",
+            "content": {
+              "text/plain": {
+                "schema": {
+                  "type": "string"
+                }
+              }
+            }
+          }
+        }
+      },
+      "put": {
+        "tags": [
+          "pets",
+          "admin"
+        ],
+        "parameters": [
+          {
+            "name": "tags",
+            "in": "query",
+            "style": "pipeDelimited",
+            "explode": false,
+            "schema": {
+              "items": {
+                "type": "string"
+              },
+              "type": "array"
+            },
+            "example": null
+          }
+        ],
+        "requestBody": {
+          "content": {
+            "application/json": {
+              "schema": {
+                "$ref": "#/components/schemas/Pet"
+              }
+            }
+          },
+          "required": true
+        },
+        "responses": {
+          "204": {
+            "description": "Updated."
+          }
+        },
+        "deprecated": true
+      },
+      "parameters": [
+        {
+          "name": "petId",
+          "in": "path",
+          "required": true,
+          "schema": {
+            "format": "int64",
+            "type": "integer"
+          },
+          "example": null
+        }
+      ]
+    },
+    "/pets/{petId}/photo.{format}": {
+      "post": {
+        "operationId": "upload_photo",
+        "parameters": [
+          {
+            "name": "petId",
+            "in": "path",
+            "required": true,
+            "style": "label",
+            "schema": {},
+            "example": null
+          },
+          {
+            "name": "format",
+            "in": "path",
+            "required": true,
+            "schema": {
+              "type": "string",
+              "enum": [
+                "png",
+                "jpg"
+              ]
+            },
+            "example": null
+          }
+        ],
+        "requestBody": {
+          "content": {
+            "image/png": {}
+          }
+        },
+        "responses": {
+          "201": {
+            "description": "Uploaded."
+          }
+        }
+      }
+    },
+    "/login": {
+      "post": {
+        "operationId": "login",
+        "requestBody": {
+          "content": {
+            "application/x-www-form-urlencoded": {
+              "schema": {
+                "properties": {
+                  "user": {
+                    "type": "string"
+                  },
+                  "password": {
+                    "type": "string"
+                  }
+                },
+                "type": "object",
+                "required": [
+                  "user"
+                ]
+              }
+            }
+          },
+          "required": true
+        },
+        "responses": {
+          "204": {
+            "description": "Logged in."
+          }
+        }
+      }
+    },
+    "/health": {
+      "get": {
+        "responses": {
+          "default": {
+            "description": "The health.",
+            "content": {
+              "text/plain": {}
+            }
+          }
+        }
+      }
+    }
+  },
+  "components": {
+    "schemas": {
+      "Pet": {
+        "description": "A pet.",
+        "properties": {
+          "petName": {
+            "description": "Name of the pet.",
+            "type": "string"
+          },
+          "kind": {
+            "$ref": "#/components/schemas/Kind"
+          },
+          "weight": {
+            "format": "float",
+            "nullable": true,
+            "type": "number"
+          },
+          "age": {
+            "format": "int32",
+            "type": "integer"
+          },
+          "type": {
+            "type": "string",
+            "enum": [
+              "house",
+              "farm"
+            ]
+          },
+          "parent": {
+            "$ref": "#/components/schemas/Pet"
+          },
+          "children": {
+            "items": {
+              "$ref": "#/components/schemas/Pet"
+            },
+            "type": "array"
+          },
+          "labels": {
+            "additionalProperties": {
+              "type": "string"
+            },
+            "type": "object"
+          }
+        },
+        "type": "object",
+        "required": [
+          "petName",
+          "kind",
+          "weight"
+        ]
+      },
+      "Kind": {
+        "type": "string",
+        "enum": [
+          "cat",
+          "dog",
+          "guinea-pig"
+        ]
+      },
+      "Id": {
+        "oneOf": [
+          {
+            "format": "int64",
+            "type": "integer"
+          },
+          {
+            "type": "string"
+          },
+          {
+            "properties": {
+              "uuid": {
+                "type": "string"
+              }
+            },
+            "type": "object"
+          }
+        ]
+      },
+      "new-pet": {
+        "allOf": [
+          {
+            "$ref": "#/components/schemas/Pet"
+          },
+          {
+            "properties": {
+              "id": {
+                "$ref": "#/components/schemas/Id"
+              }
+            },
+            "type": "object",
+            "required": [
+              "id"
+            ]
+          }
+        ]
+      },
+      "PetAlias": {
+        "$ref": "#/components/schemas/Pet"
+      },
+      "Tags": {
+        "items": {
+          "type": "string"
+        },
+        "type": "array"
+      }
+    }
+  },
+  "tags": [
+    {
+      "name": "pets",
+      "description": "Everything about pets."
+    }
+  ]
+}"##;
@@ -0,0 +1,47 @@
+//! Serves mock responses for the operations of a specification.
+//!
+//! ```text
+//! rweb-openapi-mock <specification> [address]
+//! ```
+//!
+//! The address defaults to `127.0.0.1:4010`. See [`Spec::mock_response`] for how requests are
+//! answered.
+//!
+//! [`Spec::mock_response`]: rweb_openapi::v3_0::Spec::mock_response
+
+use std::{env, net::TcpListener, process};
+
+const USAGE: &str = "usage: rweb-openapi-mock <specification> [address]";
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let (path, address) = match (args.next(), args.next(), args.next()) {
+        (Some(path), address, None) if path != "-h" && path != "--help" => (
+            path,
+            address.unwrap_or_else(|| "127.0.0.1:4010".to_string()),
+        ),
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+    let spec = match rweb_openapi::v3_0::bundle(&path) {
+        Ok(spec) => spec,
+        Err(err) => {
+            eprintln!("cannot load `{}`: {}", path, err);
+            process::exit(1);
+        }
+    };
+    let listener = match TcpListener::bind(&address) {
+        Ok(listener) => listener,
+        Err(err) => {
+            eprintln!("cannot listen on `{}`: {}", address, err);
+            process::exit(1);
+        }
+    };
+    eprintln!("serving mock responses of `{}` on http://{}", path, address);
+    if let Err(err) = spec.serve_mock(&listener) {
+        eprintln!("cannot accept connections: {}", err);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,265 @@
+//! Errors returned while loading or saving specifications.
+
+use crate::Format;
+use std::{
+    error, fmt, io,
+    path::{Path, PathBuf},
+};
+
+/// Result type used by the loading and saving functions of this crate.
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    /// Failed to read or write the document.
+    Io(io::Error),
+    /// The document is malformed, or does not describe a specification.
+    Parse(Box<ParseError>),
+    /// Failed to serialize a specification to JSON.
+    Json(serde_json::Error),
+    /// Failed to serialize a specification to YAML.
+    #[cfg(feature = "yaml")]
+    Yaml(serde_yaml::Error),
+    /// Support for the format was not enabled at compile time.
+    UnsupportedFormat(Format),
+    /// A reference to another document could not be followed while bundling a specification.
+    Bundle(Box<BundleError>),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(err) => err.fmt(f),
+            Error::Parse(err) => err.fmt(f),
+            Error::Json(err) => err.fmt(f),
+            #[cfg(feature = "yaml")]
+            Error::Yaml(err) => err.fmt(f),
+            Error::UnsupportedFormat(format) => write!(
+                f,
+                "{} support is not enabled, see the crate features",
+                format
+            ),
+            Error::Bundle(err) => err.fmt(f),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            Error::Parse(err) => Some(&**err),
+            Error::Json(err) => Some(err),
+            #[cfg(feature = "yaml")]
+            Error::Yaml(err) => Some(err),
+            Error::UnsupportedFormat(_) => None,
+            Error::Bundle(err) => Some(&**err),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(Box::new(err))
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Error::Json(err)
+    }
+}
+
+#[cfg(feature = "yaml")]
+impl From<serde_yaml::Error> for Error {
+    fn from(err: serde_yaml::Error) -> Self {
+        Error::Yaml(err)
+    }
+}
+
+impl From<BundleError> for Error {
+    fn from(err: BundleError) -> Self {
+        Error::Bundle(Box::new(err))
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The document is not well-formed JSON or YAML.
+    Syntax,
+    /// The document is well-formed, but a node does not have the expected shape.
+    Data,
+}
+
+/// Position in the source of a document. Both fields start at 1.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// Why a node could not be deserialized as one of the variants of an untagged enum.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VariantError {
+    /// Name of the variant.
+    pub variant: &'static str,
+    /// JSON pointer of the node that failed for this variant.
+    pub pointer: String,
+    pub message: String,
+}
+
+/// Failure to read a document, with the location of the failing node.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub(crate) format: Format,
+    pub(crate) kind: ParseErrorKind,
+    pub(crate) pointer: String,
+    pub(crate) location: Option<Location>,
+    pub(crate) message: String,
+    pub(crate) variants: Vec<VariantError>,
+}
+
+impl ParseError {
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    /// JSON pointer of the deepest node which could not be deserialized, e.g.
+    /// `/paths/~1pets/get/responses/200`.
+    ///
+    /// Empty for syntax errors and for errors on the whole document.
+    pub fn pointer(&self) -> &str {
+        &self.pointer
+    }
+
+    /// Position of the failing node in the source, if it could be found.
+    pub fn location(&self) -> Option<Location> {
+        self.location
+    }
+
+    pub fn line(&self) -> Option<usize> {
+        self.location.map(|l| l.line)
+    }
+
+    pub fn column(&self) -> Option<usize> {
+        self.location.map(|l| l.column)
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Variants tried for the innermost untagged enum (such as `ObjectOrReference`) enclosing
+    /// the failing node. Empty if the failing node is not part of one.
+    pub fn variants(&self) -> &[VariantError] {
+        &self.variants
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ParseErrorKind::Syntax => write!(f, "invalid {}", self.format)?,
+            ParseErrorKind::Data if self.pointer.is_empty() => {
+                write!(f, "invalid {} document", self.format)?
+            }
+            ParseErrorKind::Data => {
+                write!(f, "invalid {} document at `{}`", self.format, self.pointer)?
+            }
+        }
+        if let Some(location) = self.location {
+            write!(f, " ({})", location)?;
+        }
+        write!(f, ": {}", self.message)?;
+        for (i, variant) in self.variants.iter().enumerate() {
+            f.write_str(if i == 0 { "; tried " } else { ", " })?;
+            write!(f, "{} ({}", variant.variant, variant.message)?;
+            if variant.pointer != self.pointer {
+                write!(f, " at `{}`", variant.pointer)?;
+            }
+            f.write_str(")")?;
+        }
+        Ok(())
+    }
+}
+
+impl error::Error for ParseError {}
+
+#[derive(Debug)]
+pub enum BundleErrorKind {
+    /// The referenced document could not be read or parsed.
+    Load(Error),
+    /// The referenced document has no node at the fragment of the reference.
+    Dangling,
+    /// The reference points to a remote document. Only files are loaded.
+    Remote,
+    /// The reference leads back to itself through objects which have to be inlined, such as
+    /// path items.
+    Cycle,
+}
+
+/// Failure to follow a reference found in a document.
+#[derive(Debug)]
+pub struct BundleError {
+    pub(crate) path: PathBuf,
+    pub(crate) reference: String,
+    pub(crate) kind: BundleErrorKind,
+}
+
+impl BundleError {
+    /// Path of the document containing the reference.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The reference, as written in the document.
+    pub fn reference(&self) -> &str {
+        &self.reference
+    }
+
+    pub fn kind(&self) -> &BundleErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for BundleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "cannot follow `{}` in {}: ",
+            self.reference,
+            self.path.display()
+        )?;
+        match &self.kind {
+            BundleErrorKind::Load(err) => err.fmt(f),
+            BundleErrorKind::Dangling => f.write_str("no such node"),
+            BundleErrorKind::Remote => f.write_str("remote documents are not supported"),
+            BundleErrorKind::Cycle => f.write_str("cyclic reference"),
+        }
+    }
+}
+
+impl error::Error for BundleError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match &self.kind {
+            BundleErrorKind::Load(err) => Some(err),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,209 @@
+//! [Specification Extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions)
+//! shared by every object of the specification that allows them.
+
+use crate::Str;
+use indexmap::IndexMap;
+use serde::{
+    de::{DeserializeOwned, IgnoredAny, MapAccess, Visitor},
+    ser::SerializeMap,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{error, fmt};
+
+/// Prefix every specification extension field name MUST start with.
+pub const EXTENSION_PREFIX: &str = "x-";
+
+/// Additional data attached to an object with `x-` prefixed fields.
+///
+/// The map is meant to be `#[serde(flatten)]`ed into the object it extends. While deserializing,
+/// only the fields starting with `x-` are kept, so that the map never captures regular (or
+/// unknown) fields of the object. Fields are serialized back in the order they were read or
+/// inserted.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Extensions(IndexMap<Str, serde_json::Value>);
+
+impl Extensions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.0.contains_key(key)
+    }
+
+    /// Returns the raw value of the extension `key`.
+    pub fn get_value(&self, key: &str) -> Option<&serde_json::Value> {
+        self.0.get(key)
+    }
+
+    /// Deserializes the value of the extension `key` into `T`.
+    ///
+    /// Returns `Ok(None)` if there is no such extension.
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, ExtensionError> {
+        match self.0.get(key) {
+            Some(value) => T::deserialize(value)
+                .map(Some)
+                .map_err(ExtensionError::Value),
+            None => Ok(None),
+        }
+    }
+
+    /// Serializes `value` and stores it as the extension `key`, returning the previous value.
+    ///
+    /// Fails if `key` does not start with [`EXTENSION_PREFIX`].
+    pub fn insert<K, T>(
+        &mut self,
+        key: K,
+        value: T,
+    ) -> Result<Option<serde_json::Value>, ExtensionError>
+    where
+        K: Into<Str>,
+        T: Serialize,
+    {
+        let key = key.into();
+        if !key.starts_with(EXTENSION_PREFIX) {
+            return Err(ExtensionError::InvalidKey(key));
+        }
+        let value = serde_json::to_value(value).map_err(ExtensionError::Value)?;
+        Ok(self.0.insert(key, value))
+    }
+
+    /// Removes the extension `key`, preserving the order of the remaining ones.
+    pub fn remove(&mut self, key: &str) -> Option<serde_json::Value> {
+        self.0.shift_remove(key)
+    }
+
+    pub fn iter(&self) -> indexmap::map::Iter<'_, Str, serde_json::Value> {
+        self.0.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Extensions {
+    type Item = (&'a Str, &'a serde_json::Value);
+    type IntoIter = indexmap::map::Iter<'a, Str, serde_json::Value>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl IntoIterator for Extensions {
+    type Item = (Str, serde_json::Value);
+    type IntoIter = indexmap::map::IntoIter<Str, serde_json::Value>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl Serialize for Extensions {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        let mut map = ser.serialize_map(Some(self.0.len()))?;
+        for (key, value) in &self.0 {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Extensions {
+    fn deserialize<D: Deserializer<'de>>(deser: D) -> Result<Self, D::Error> {
+        struct ExtensionsVisitor;
+
+        impl<'de> Visitor<'de> for ExtensionsVisitor {
+            type Value = Extensions;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a map of specification extensions")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Extensions, A::Error> {
+                let mut map = IndexMap::new();
+                while let Some(key) = access.next_key::<String>()? {
+                    if key.starts_with(EXTENSION_PREFIX) {
+                        map.insert(Str::Owned(key), access.next_value()?);
+                    } else {
+                        access.next_value::<IgnoredAny>()?;
+                    }
+                }
+                Ok(Extensions(map))
+            }
+        }
+
+        deser.deserialize_map(ExtensionsVisitor)
+    }
+}
+
+/// Failure to read or write a specification extension.
+#[derive(Debug)]
+pub enum ExtensionError {
+    /// The field name does not start with [`EXTENSION_PREFIX`].
+    InvalidKey(Str),
+    /// The value could not be converted from or to JSON.
+    Value(serde_json::Error),
+}
+
+impl fmt::Display for ExtensionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExtensionError::InvalidKey(key) => write!(
+                f,
+                "invalid extension name `{}`: must start with `{}`",
+                key, EXTENSION_PREFIX
+            ),
+            ExtensionError::Value(err) => write!(f, "invalid extension value: {}", err),
+        }
+    }
+}
+
+impl error::Error for ExtensionError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            ExtensionError::InvalidKey(_) => None,
+            ExtensionError::Value(err) => Some(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_keeps_prefixed_keys() {
+        let ext: Extensions =
+            serde_json::from_str(r#"{"x-b": 1, "title": "t", "x-a": {"c": true}}"#).unwrap();
+        assert_eq!(ext.len(), 2);
+        assert!(!ext.contains_key("title"));
+        assert_eq!(
+            serde_json::to_string(&ext).unwrap(),
+            r#"{"x-b":1,"x-a":{"c":true}}"#
+        );
+    }
+
+    #[test]
+    fn test_typed_get_insert() {
+        let mut ext = Extensions::new();
+        assert!(matches!(
+            ext.insert("internal", true),
+            Err(ExtensionError::InvalidKey(_))
+        ));
+        ext.insert("x-internal", true).unwrap();
+        ext.insert("x-codegen-names", vec!["a", "b"]).unwrap();
+        assert_eq!(ext.get::<bool>("x-internal").unwrap(), Some(true));
+        assert_eq!(
+            ext.get::<Vec<String>>("x-codegen-names").unwrap(),
+            Some(vec!["a".to_string(), "b".to_string()])
+        );
+        assert_eq!(ext.get::<bool>("x-missing").unwrap(), None);
+        assert!(ext.get::<u32>("x-internal").is_err());
+    }
+}
@@ -0,0 +1,57 @@
+//! Helpers for [JSON pointers](https://tools.ietf.org/html/rfc6901), used to report the location of
+//! nodes within a document.
+
+use std::borrow::Cow;
+
+/// Escapes a reference token, replacing `~` with `~0` and `/` with `~1`.
+pub fn escape(token: &str) -> Cow<'_, str> {
+    if token.contains(['~', '/']) {
+        Cow::Owned(token.replace('~', "~0").replace('/', "~1"))
+    } else {
+        Cow::Borrowed(token)
+    }
+}
+
+/// Reverts [`escape`].
+pub fn unescape(token: &str) -> Cow<'_, str> {
+    if token.contains('~') {
+        Cow::Owned(token.replace("~1", "/").replace("~0", "~"))
+    } else {
+        Cow::Borrowed(token)
+    }
+}
+
+/// Appends `token` to `pointer`, escaping it.
+pub fn push(pointer: &mut String, token: &str) {
+    pointer.push('/');
+    pointer.push_str(&escape(token));
+}
+
+/// Splits `pointer` into its unescaped reference tokens.
+///
+/// Returns `None` if `pointer` is neither empty nor starts with `/`.
+pub fn split(pointer: &str) -> Option<Vec<Cow<'_, str>>> {
+    if pointer.is_empty() {
+        return Some(Vec::new());
+    }
+    let rest = pointer.strip_prefix('/')?;
+    Some(rest.split('/').map(unescape).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escaping() {
+        let mut pointer = String::new();
+        push(&mut pointer, "paths");
+        push(&mut pointer, "/pets/{id}");
+        push(&mut pointer, "a~b");
+        assert_eq!(pointer, "/paths/~1pets~1{id}/a~0b");
+        assert_eq!(split(&pointer).unwrap(), vec!["paths", "/pets/{id}", "a~b"]);
+        assert_eq!(unescape("~01"), "~1");
+        assert_eq!(split("").unwrap(), Vec::<Cow<str>>::new());
+        assert!(split("paths").is_none());
+    }
+}
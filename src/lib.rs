@@ -5,10 +5,359 @@
 //! Typical use deserialing an existing to a persisted spec to rust form or
 //! visa versa
 //!
-//! The hyper client should be configured with tls.
+//! ```no_run
+//! let spec = rweb_openapi::from_path("path/to/openapi.json").unwrap();
+//! println!("{}", rweb_openapi::to_string(&spec, rweb_openapi::Format::Json).unwrap());
+//! ```
 //!
-use std::borrow::Cow;
+//! YAML documents are supported when the `yaml` feature is enabled.
+//! Validating instances and requests, and synthesizing instances, requires the `validate`
+//! feature, generating Rust models, clients and servers the `codegen` feature, and answering
+//! requests with mock responses the `mock` feature.
+//! Schemas can be derived from Rust types with the `derive` feature, see
+//! [`ToSchema`](v3_0::ToSchema).
+//!
+
+// Lets the code generated by `#[derive(ToSchema)]` refer to this crate by name in its own tests.
+extern crate self as rweb_openapi;
 
+use std::{
+    borrow::Cow,
+    fmt,
+    fs::File,
+    io::{BufReader, Read, Write},
+    path::Path,
+};
+
+pub mod error;
+pub mod extensions;
+pub mod json_pointer;
+mod parse;
+pub mod v2_0;
 pub mod v3_0;
+pub mod v3_1;
+
+pub use crate::{
+    error::{Error, Result},
+    extensions::Extensions,
+};
 
 pub type Str = Cow<'static, str>;
+
+/// Serialization format of a specification document.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    /// Requires the `yaml` feature.
+    Yaml,
+}
+
+impl Format {
+    /// Guesses the format from the extension of `path`.
+    pub fn from_extension<P: AsRef<Path>>(path: P) -> Option<Format> {
+        let ext = path.as_ref().extension()?.to_str()?;
+        if ext.eq_ignore_ascii_case("json") {
+            Some(Format::Json)
+        } else if ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml") {
+            Some(Format::Yaml)
+        } else {
+            None
+        }
+    }
+
+    /// Guesses the format from the content of a document.
+    ///
+    /// Documents whose first significant character opens a JSON object are JSON, everything
+    /// else is assumed to be YAML.
+    pub fn sniff(content: &str) -> Format {
+        let content = content.trim_start_matches('\u{feff}').trim_start();
+        if content.starts_with('{') {
+            Format::Json
+        } else {
+            Format::Yaml
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Format::Json => "JSON",
+            Format::Yaml => "YAML",
+        })
+    }
+}
+
+/// Deserializes the specification stored at `path`.
+///
+/// The format is taken from the file extension (`.json`, `.yaml` or `.yml`), and guessed from
+/// the content for any other extension.
+///
+/// References to other files are kept as they are, use [`v3_0::bundle`] to load them as well.
+pub fn from_path<P: AsRef<Path>>(path: P) -> Result<v3_0::Spec> {
+    Ok(load_path(path.as_ref())?.0)
+}
+
+/// Deserializes a specification from `reader`, guessing the format from the content.
+pub fn from_reader<R: Read>(mut reader: R) -> Result<v3_0::Spec> {
+    let mut content = String::new();
+    reader.read_to_string(&mut content)?;
+    from_str(&content)
+}
+
+/// Deserializes a specification from `content`, guessing the format from the content.
+pub fn from_str(content: &str) -> Result<v3_0::Spec> {
+    from_str_with_format(content, Format::sniff(content))
+}
+
+/// Deserializes a specification from `content` in the given `format`.
+pub fn from_str_with_format(content: &str, format: Format) -> Result<v3_0::Spec> {
+    deserialize(content, format)
+}
+
+/// Deserializes the document stored at `path`, returning it with its format.
+pub(crate) fn load_path<T: parse::Diagnose>(path: &Path) -> Result<(T, Format)> {
+    let mut content = String::new();
+    BufReader::new(File::open(path)?).read_to_string(&mut content)?;
+    let format = Format::from_extension(path).unwrap_or_else(|| Format::sniff(&content));
+    Ok((deserialize(&content, format)?, format))
+}
+
+fn deserialize<T: parse::Diagnose>(content: &str, format: Format) -> Result<T> {
+    match format {
+        Format::Json => Ok(parse::from_json(content)?),
+        #[cfg(feature = "yaml")]
+        Format::Yaml => Ok(parse::from_yaml(content)?),
+        #[cfg(not(feature = "yaml"))]
+        Format::Yaml => Err(Error::UnsupportedFormat(format)),
+    }
+}
+
+/// Serializes `spec` to a string. JSON output is pretty-printed.
+pub fn to_string(spec: &v3_0::Spec, format: Format) -> Result<String> {
+    match format {
+        Format::Json => Ok(serde_json::to_string_pretty(spec)?),
+        #[cfg(feature = "yaml")]
+        Format::Yaml => Ok(serde_yaml::to_string(spec)?),
+        #[cfg(not(feature = "yaml"))]
+        Format::Yaml => Err(Error::UnsupportedFormat(format)),
+    }
+}
+
+/// Serializes `spec` into `writer`. JSON output is pretty-printed.
+pub fn to_writer<W: Write>(writer: W, spec: &v3_0::Spec, format: Format) -> Result<()> {
+    match format {
+        Format::Json => Ok(serde_json::to_writer_pretty(writer, spec)?),
+        #[cfg(feature = "yaml")]
+        Format::Yaml => Ok(serde_yaml::to_writer(writer, spec)?),
+        #[cfg(not(feature = "yaml"))]
+        Format::Yaml => Err(Error::UnsupportedFormat(format)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PETSTORE_JSON: &str = r##"{
+      "openapi": "3.0.1",
+      "info": {"title": "Petstore", "version": "1.0.0"},
+      "paths": {
+        "/pets/{petId}": {
+          "get": {
+            "operationId": "showPetById",
+            "parameters": [
+              {"name": "petId", "in": "path", "required": true, "schema": {"type": "string"}}
+            ],
+            "responses": {
+              "200": {
+                "description": "Expected response to a valid request",
+                "content": {
+                  "application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}
+                }
+              }
+            }
+          }
+        }
+      },
+      "components": {
+        "schemas": {
+          "Pet": {
+            "type": "object",
+            "required": ["id", "name"],
+            "properties": {
+              "id": {"type": "integer", "format": "int64"},
+              "name": {"type": "string"}
+            }
+          }
+        }
+      }
+    }"##;
+
+    #[test]
+    fn test_format_detection() {
+        assert_eq!(Format::from_extension("spec.JSON"), Some(Format::Json));
+        assert_eq!(Format::from_extension("a/spec.yml"), Some(Format::Yaml));
+        assert_eq!(Format::from_extension("spec.txt"), None);
+        assert_eq!(Format::sniff("\u{feff}\n  {\"openapi\": 1}"), Format::Json);
+        assert_eq!(Format::sniff("openapi: 3.0.1"), Format::Yaml);
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let spec = from_str(PETSTORE_JSON).unwrap();
+        assert_eq!(spec.info.title, "Petstore");
+        let op = spec.paths["/pets/{petId}"].get.as_ref().unwrap();
+        assert_eq!(op.operation_id, "showPetById");
+
+        let mut out = Vec::new();
+        to_writer(&mut out, &spec, Format::Json).unwrap();
+        assert_eq!(from_reader(&out[..]).unwrap(), spec);
+    }
+
+    #[test]
+    fn test_from_path() {
+        let path = std::env::temp_dir().join(format!("rweb-openapi-{}.spec", std::process::id()));
+        std::fs::write(&path, PETSTORE_JSON).unwrap();
+        let spec = from_path(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(spec.unwrap(), from_str(PETSTORE_JSON).unwrap());
+    }
+
+    fn parse_error(result: Result<v3_0::Spec>) -> error::ParseError {
+        match result {
+            Err(Error::Parse(err)) => *err,
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_json_error_location() {
+        let err = parse_error(from_str("{\"openapi\": \"3.0.1\",\n \"info\": {]"));
+        assert_eq!(err.kind(), error::ParseErrorKind::Syntax);
+        assert_eq!(
+            err.location(),
+            Some(error::Location {
+                line: 2,
+                column: 11
+            })
+        );
+
+        let doc = PETSTORE_JSON.replace(
+            r#""name": {"type": "string"}"#,
+            r#""name": {"type": "strin"}"#,
+        );
+        let err = parse_error(from_str(&doc));
+        assert_eq!(err.kind(), error::ParseErrorKind::Data);
+        assert_eq!(
+            err.pointer(),
+            "/components/schemas/Pet/properties/name/type"
+        );
+        assert_eq!(
+            err.location(),
+            Some(error::Location {
+                line: 29,
+                column: 32
+            })
+        );
+        assert!(
+            err.message().starts_with("unknown variant `strin`"),
+            "{}",
+            err
+        );
+        let variants: Vec<_> = err.variants().iter().map(|v| v.variant).collect();
+        assert_eq!(variants, ["Component", "ExtRef", "Inline"]);
+
+        let doc = PETSTORE_JSON.replace(
+            r#""description": "Expected response to a valid request","#,
+            "",
+        );
+        let err = parse_error(from_str(&doc));
+        assert_eq!(err.pointer(), "/paths/~1pets~1{petId}/get/responses/200");
+        assert_eq!(err.message(), "missing field `description`");
+        let variants: Vec<_> = err.variants().iter().map(|v| v.variant).collect();
+        assert_eq!(variants, ["Ref", "Object"]);
+        assert_eq!(
+            err.to_string(),
+            "invalid JSON document at `/paths/~1pets~1{petId}/get/responses/200` \
+             (line 12, column 22): missing field `description`; \
+             tried Ref (missing field `$ref`), Object (missing field `description`)"
+        );
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_yaml_error_location() {
+        let doc = "openapi: 3.0.1\ninfo:\n  title: t\n  version: 1.0\npaths: {}\ncomponents:\n  schemas:\n    Pet:\n      properties:\n        tags:\n          items:\n            type: [string]\n";
+        let err = parse_error(from_str(doc));
+        assert_eq!(
+            err.pointer(),
+            "/components/schemas/Pet/properties/tags/items/type"
+        );
+        assert_eq!(
+            err.location(),
+            Some(error::Location {
+                line: 12,
+                column: 19
+            })
+        );
+        let variants: Vec<_> = err.variants().iter().map(|v| v.variant).collect();
+        assert_eq!(variants, ["Component", "ExtRef", "Inline"]);
+        assert_eq!(
+            err.variants()[0].pointer,
+            "/components/schemas/Pet/properties/tags/items"
+        );
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_yaml_round_trip() {
+        const PETSTORE_YAML: &str = r#"
+openapi: 3.0.1
+info:
+  title: Petstore
+  version: 1.0.0
+paths:
+  /pets/{petId}:
+    get:
+      operationId: showPetById
+      parameters:
+        - name: petId
+          in: path
+          required: true
+          schema:
+            type: string
+      responses:
+        200:
+          description: Expected response to a valid request
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/Pet'
+components:
+  schemas:
+    Pet:
+      type: object
+      required: [id, name]
+      properties:
+        id:
+          type: integer
+          format: int64
+        name:
+          type: string
+"#;
+        let spec = from_str(PETSTORE_YAML).unwrap();
+        assert_eq!(spec, from_str(PETSTORE_JSON).unwrap());
+        let yaml = to_string(&spec, Format::Yaml).unwrap();
+        assert_eq!(from_str_with_format(&yaml, Format::Yaml).unwrap(), spec);
+    }
+
+    #[cfg(not(feature = "yaml"))]
+    #[test]
+    fn test_yaml_disabled() {
+        assert!(matches!(
+            from_str("openapi: 3.0.1"),
+            Err(Error::UnsupportedFormat(Format::Yaml))
+        ));
+    }
+}
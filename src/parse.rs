@@ -0,0 +1,832 @@
+//! Deserialization of documents, locating the node responsible for a failure.
+//!
+//! serde only reports where a failure was detected, which for untagged enums is the enum itself
+//! and for flattened structs the whole object. When a document cannot be deserialized, it is
+//! parsed again into a tree which is then walked with [`Diagnose`] to find the deepest node that
+//! does not have the expected shape. The source position of that node is found afterwards by
+//! scanning the text of the document.
+
+use crate::{
+    error::{Location, ParseError, ParseErrorKind, VariantError},
+    json_pointer, Format, Str,
+};
+use indexmap::IndexMap;
+use serde::de::DeserializeOwned;
+
+/// A parsed document which sub-trees can be deserialized independently.
+pub(crate) trait Tree: Sized {
+    /// Child of an object node.
+    fn get(&self, key: &str) -> Option<&Self>;
+
+    /// Children of an object node. Empty for any other node.
+    fn entries(&self) -> Vec<(String, &Self)>;
+
+    /// Items of an array node. Empty for any other node.
+    fn items(&self) -> &[Self];
+
+    fn is_null(&self) -> bool;
+
+    fn as_str(&self) -> Option<&str>;
+
+    /// Deserializes this sub-tree, returning the error message on failure.
+    fn deserialize<T: DeserializeOwned>(&self) -> Result<T, String>;
+}
+
+impl Tree for serde_json::Value {
+    fn get(&self, key: &str) -> Option<&Self> {
+        self.as_object()?.get(key)
+    }
+
+    fn entries(&self) -> Vec<(String, &Self)> {
+        match self {
+            serde_json::Value::Object(map) => map.iter().map(|(k, v)| (k.clone(), v)).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn items(&self) -> &[Self] {
+        match self {
+            serde_json::Value::Array(items) => items,
+            _ => &[],
+        }
+    }
+
+    fn is_null(&self) -> bool {
+        self.is_null()
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        self.as_str()
+    }
+
+    fn deserialize<T: DeserializeOwned>(&self) -> Result<T, String> {
+        T::deserialize(self).map_err(|err| err.to_string())
+    }
+}
+
+#[cfg(feature = "yaml")]
+fn yaml_key(key: &serde_yaml::Value) -> Option<String> {
+    match key {
+        serde_yaml::Value::String(s) => Some(s.clone()),
+        serde_yaml::Value::Number(n) => Some(n.to_string()),
+        serde_yaml::Value::Bool(b) => Some(b.to_string()),
+        serde_yaml::Value::Null => Some("null".to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(feature = "yaml")]
+impl Tree for serde_yaml::Value {
+    fn get(&self, key: &str) -> Option<&Self> {
+        self.as_mapping()?
+            .iter()
+            .find(|(k, _)| yaml_key(k).as_deref() == Some(key))
+            .map(|(_, v)| v)
+    }
+
+    fn entries(&self) -> Vec<(String, &Self)> {
+        match self.as_mapping() {
+            Some(map) => map
+                .iter()
+                .filter_map(|(k, v)| Some((yaml_key(k)?, v)))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn items(&self) -> &[Self] {
+        match self.as_sequence() {
+            Some(items) => items,
+            None => &[],
+        }
+    }
+
+    fn is_null(&self) -> bool {
+        self.is_null()
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        self.as_str()
+    }
+
+    fn deserialize<T: DeserializeOwned>(&self) -> Result<T, String> {
+        // Going through the text keeps the leniency of the YAML deserializer, which reads plain
+        // scalars such as `1.0` as strings when a string is expected. Deserializing a
+        // `serde_yaml::Value` does not.
+        serde_yaml::to_string(self)
+            .and_then(|text| serde_yaml::from_str(&text))
+            .map_err(|err| strip_location(err.to_string()))
+    }
+}
+
+/// The deepest node of a document that could not be deserialized.
+#[derive(Debug)]
+pub(crate) struct Failure {
+    pub pointer: String,
+    pub message: String,
+    pub variants: Vec<VariantError>,
+}
+
+impl Failure {
+    pub fn new(pointer: &str, message: String) -> Self {
+        Failure {
+            pointer: pointer.to_string(),
+            message,
+            variants: Vec::new(),
+        }
+    }
+}
+
+/// Types that can find out why a tree does not deserialize into them.
+pub(crate) trait Diagnose: DeserializeOwned {
+    /// Returns the deepest node of `value` (located at `pointer`) which fails to deserialize, or
+    /// `None` if `value` deserializes into `Self`.
+    ///
+    /// The default implementation treats `Self` as a leaf.
+    fn diagnose<V: Tree>(value: &V, pointer: &mut String) -> Option<Failure> {
+        let message = value.deserialize::<Self>().err()?;
+        Some(Failure::new(pointer, message))
+    }
+}
+
+/// Diagnoses the child `key` of `value`, if present.
+pub(crate) fn child<T: Diagnose, V: Tree>(
+    value: &V,
+    key: &str,
+    pointer: &mut String,
+) -> Option<Failure> {
+    let child = value.get(key)?;
+    let len = pointer.len();
+    json_pointer::push(pointer, key);
+    let failure = T::diagnose(child, pointer);
+    pointer.truncate(len);
+    failure
+}
+
+/// Diagnoses a field which must be a string.
+pub(crate) fn required_str<V: Tree>(value: &V, key: &str, pointer: &mut String) -> Option<Failure> {
+    match value.get(key) {
+        Some(_) => child::<Str, V>(value, key, pointer),
+        None => Some(Failure::new(pointer, format!("missing field `{}`", key))),
+    }
+}
+
+/// Combines the failures of every variant of an untagged enum located at `pointer`, which
+/// failed to deserialize with `message`.
+///
+/// The failure of the variant at index `likely`, which is the one the document author most
+/// probably meant, is returned with the list of all attempts attached unless a more deeply
+/// nested untagged enum already attached its own.
+pub(crate) fn untagged(
+    pointer: &str,
+    message: String,
+    likely: usize,
+    attempts: Vec<(&'static str, Option<Failure>)>,
+) -> Failure {
+    let mut variants = Vec::with_capacity(attempts.len());
+    let mut result = None;
+    for (i, (variant, failure)) in attempts.into_iter().enumerate() {
+        let failure = match failure {
+            Some(failure) => failure,
+            // The variant accepts the value on its own, so the failure comes from the enum.
+            None => return Failure::new(pointer, message),
+        };
+        variants.push(VariantError {
+            variant,
+            pointer: failure.pointer.clone(),
+            message: failure.message.clone(),
+        });
+        if i == likely {
+            result = Some(failure);
+        }
+    }
+    let mut result = result.unwrap_or_else(|| Failure::new(pointer, message));
+    if result.variants.is_empty() {
+        result.variants = variants;
+    }
+    result
+}
+
+macro_rules! diagnose_leaf {
+    ($($ty:ty),* $(,)?) => {
+        $(impl $crate::parse::Diagnose for $ty {})*
+    };
+}
+
+/// Implements [`Diagnose`] for structs by descending into the listed fields, given with their
+/// serialized names.
+macro_rules! diagnose_struct {
+    ($($ty:ty { $($key:literal => $field:ty),* $(,)? })*) => {
+        $(impl $crate::parse::Diagnose for $ty {
+            fn diagnose<V: $crate::parse::Tree>(
+                value: &V,
+                pointer: &mut String,
+            ) -> Option<$crate::parse::Failure> {
+                let message = value.deserialize::<Self>().err()?;
+                $(
+                    if let Some(failure) = $crate::parse::child::<$field, V>(value, $key, pointer) {
+                        return Some(failure);
+                    }
+                )*
+                Some($crate::parse::Failure::new(pointer, message))
+            }
+        })*
+    };
+}
+
+pub(crate) use {diagnose_leaf, diagnose_struct};
+
+diagnose_leaf!(
+    bool,
+    usize,
+    Str,
+    url::Url,
+    serde_json::Value,
+    crate::Extensions
+);
+
+impl<T: Diagnose> Diagnose for Option<T> {
+    fn diagnose<V: Tree>(value: &V, pointer: &mut String) -> Option<Failure> {
+        if value.is_null() {
+            None
+        } else {
+            T::diagnose(value, pointer)
+        }
+    }
+}
+
+impl<T: Diagnose> Diagnose for Box<T> {
+    fn diagnose<V: Tree>(value: &V, pointer: &mut String) -> Option<Failure> {
+        T::diagnose(value, pointer)
+    }
+}
+
+impl<T: Diagnose> Diagnose for Vec<T> {
+    fn diagnose<V: Tree>(value: &V, pointer: &mut String) -> Option<Failure> {
+        let message = value.deserialize::<Self>().err()?;
+        for (i, item) in value.items().iter().enumerate() {
+            let len = pointer.len();
+            json_pointer::push(pointer, &i.to_string());
+            let failure = T::diagnose(item, pointer);
+            pointer.truncate(len);
+            if failure.is_some() {
+                return failure;
+            }
+        }
+        Some(Failure::new(pointer, message))
+    }
+}
+
+impl<T: Diagnose> Diagnose for IndexMap<Str, T> {
+    fn diagnose<V: Tree>(value: &V, pointer: &mut String) -> Option<Failure> {
+        let message = value.deserialize::<Self>().err()?;
+        for (key, item) in value.entries() {
+            let len = pointer.len();
+            json_pointer::push(pointer, &key);
+            let failure = T::diagnose(item, pointer);
+            pointer.truncate(len);
+            if failure.is_some() {
+                return failure;
+            }
+        }
+        Some(Failure::new(pointer, message))
+    }
+}
+
+/// Removes the ` at line X column Y` suffix serde_json and serde_yaml add to their messages.
+fn strip_location(mut message: String) -> String {
+    if let Some(i) = message.rfind(" at line ") {
+        if message[i + 9..]
+            .split(" column ")
+            .all(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+        {
+            message.truncate(i);
+        }
+    }
+    message
+}
+
+fn syntax_error(format: Format, message: String, location: Option<Location>) -> ParseError {
+    ParseError {
+        format,
+        kind: ParseErrorKind::Syntax,
+        pointer: String::new(),
+        location,
+        message: strip_location(message),
+        variants: Vec::new(),
+    }
+}
+
+fn data_error(format: Format, failure: Failure, location: Option<Location>) -> ParseError {
+    ParseError {
+        format,
+        kind: ParseErrorKind::Data,
+        pointer: failure.pointer,
+        location,
+        message: failure.message,
+        variants: failure.variants,
+    }
+}
+
+fn json_location(err: &serde_json::Error) -> Option<Location> {
+    if err.line() == 0 {
+        None
+    } else {
+        Some(Location {
+            line: err.line(),
+            column: err.column(),
+        })
+    }
+}
+
+/// Deserializes a JSON document.
+pub(crate) fn from_json<T: Diagnose>(content: &str) -> Result<T, ParseError> {
+    let err = match serde_json::from_str(content) {
+        Ok(doc) => return Ok(doc),
+        Err(err) => err,
+    };
+    if err.is_syntax() || err.is_eof() {
+        return Err(syntax_error(
+            Format::Json,
+            err.to_string(),
+            json_location(&err),
+        ));
+    }
+    let tree: serde_json::Value = serde_json::from_str(content)
+        .map_err(|err| syntax_error(Format::Json, err.to_string(), json_location(&err)))?;
+    match T::diagnose(&tree, &mut String::new()) {
+        Some(failure) => {
+            let location = locate_json(content, &failure.pointer);
+            Err(data_error(Format::Json, failure, location))
+        }
+        None => Err(data_error(
+            Format::Json,
+            Failure::new("", strip_location(err.to_string())),
+            json_location(&err),
+        )),
+    }
+}
+
+/// Deserializes a document which was already parsed, and possibly modified since. Failures have
+/// no source location.
+pub(crate) fn from_value<T: Diagnose, V: Tree>(value: &V, format: Format) -> Result<T, ParseError> {
+    let err = match value.deserialize::<T>() {
+        Ok(doc) => return Ok(doc),
+        Err(err) => err,
+    };
+    let failure = T::diagnose(value, &mut String::new())
+        .unwrap_or_else(|| Failure::new("", strip_location(err)));
+    Err(data_error(format, failure, None))
+}
+
+#[cfg(feature = "yaml")]
+fn yaml_location(err: &serde_yaml::Error) -> Option<Location> {
+    err.location().map(|l| Location {
+        line: l.line(),
+        column: l.column(),
+    })
+}
+
+/// Deserializes a YAML document.
+#[cfg(feature = "yaml")]
+pub(crate) fn from_yaml<T: Diagnose>(content: &str) -> Result<T, ParseError> {
+    let err = match serde_yaml::from_str(content) {
+        Ok(doc) => return Ok(doc),
+        Err(err) => err,
+    };
+    let tree: serde_yaml::Value = serde_yaml::from_str(content)
+        .map_err(|err| syntax_error(Format::Yaml, err.to_string(), yaml_location(&err)))?;
+    match T::diagnose(&tree, &mut String::new()) {
+        Some(failure) => {
+            let location = locate_yaml(content, &failure.pointer).or_else(|| yaml_location(&err));
+            Err(data_error(Format::Yaml, failure, location))
+        }
+        None => Err(data_error(
+            Format::Yaml,
+            Failure::new("", strip_location(err.to_string())),
+            yaml_location(&err),
+        )),
+    }
+}
+
+fn location_at(content: &str, offset: usize) -> Location {
+    let before = &content[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    Location {
+        line: before.matches('\n').count() + 1,
+        column: before[line_start..].chars().count() + 1,
+    }
+}
+
+/// Minimal JSON scanner, only keeping track of where values start.
+struct JsonScanner<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> JsonScanner<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn ws(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn eat(&mut self, byte: u8) -> Option<()> {
+        self.ws();
+        if self.peek()? == byte {
+            self.pos += 1;
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    fn string(&mut self) -> Option<String> {
+        self.eat(b'"')?;
+        let mut out = Vec::new();
+        loop {
+            let b = self.peek()?;
+            self.pos += 1;
+            match b {
+                b'"' => return String::from_utf8(out).ok(),
+                b'\\' => {
+                    let escaped = self.peek()?;
+                    self.pos += 1;
+                    let c = match escaped {
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => {
+                            let mut code = self.hex4()?;
+                            if (0xd800..0xdc00).contains(&code) {
+                                self.eat(b'\\')?;
+                                self.eat(b'u')?;
+                                let low = self.hex4()?;
+                                code = 0x10000
+                                    + ((code - 0xd800) << 10)
+                                    + (low.checked_sub(0xdc00)?);
+                            }
+                            char::from_u32(code)?
+                        }
+                        other => other as char,
+                    };
+                    let mut buf = [0; 4];
+                    out.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+                }
+                _ => out.push(b),
+            }
+        }
+    }
+
+    fn hex4(&mut self) -> Option<u32> {
+        let digits = self.bytes.get(self.pos..self.pos + 4)?;
+        self.pos += 4;
+        u32::from_str_radix(std::str::from_utf8(digits).ok()?, 16).ok()
+    }
+
+    fn skip_value(&mut self) -> Option<()> {
+        self.ws();
+        match self.peek()? {
+            b'"' => {
+                self.string()?;
+            }
+            open @ (b'{' | b'[') => {
+                self.pos += 1;
+                let close = if open == b'{' { b'}' } else { b']' };
+                self.ws();
+                if self.peek()? == close {
+                    self.pos += 1;
+                    return Some(());
+                }
+                loop {
+                    if open == b'{' {
+                        self.string()?;
+                        self.eat(b':')?;
+                    }
+                    self.skip_value()?;
+                    self.ws();
+                    match self.peek()? {
+                        b',' => self.pos += 1,
+                        b if b == close => {
+                            self.pos += 1;
+                            break;
+                        }
+                        _ => return None,
+                    }
+                }
+            }
+            _ => {
+                while let Some(b) = self.peek() {
+                    if matches!(b, b',' | b'}' | b']' | b' ' | b'\t' | b'\n' | b'\r') {
+                        break;
+                    }
+                    self.pos += 1;
+                }
+            }
+        }
+        Some(())
+    }
+
+    /// Moves to the start of the child `token` of the value at the current position.
+    fn enter(&mut self, token: &str) -> Option<()> {
+        self.ws();
+        match self.peek()? {
+            b'{' => {
+                self.pos += 1;
+                let mut found = None;
+                loop {
+                    self.ws();
+                    if self.peek()? == b'}' {
+                        break;
+                    }
+                    let key = self.string()?;
+                    self.eat(b':')?;
+                    self.ws();
+                    if key == token {
+                        // Keep looking: the last duplicate key wins.
+                        found = Some(self.pos);
+                    }
+                    self.skip_value()?;
+                    self.ws();
+                    if self.peek()? == b',' {
+                        self.pos += 1;
+                    }
+                }
+                self.pos = found?;
+                Some(())
+            }
+            b'[' => {
+                let index: usize = token.parse().ok()?;
+                self.pos += 1;
+                for _ in 0..index {
+                    self.skip_value()?;
+                    self.eat(b',')?;
+                }
+                self.ws();
+                if self.peek()? == b']' {
+                    return None;
+                }
+                Some(())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Finds the position of the node at `pointer` in a JSON document, or of its deepest ancestor
+/// present in the document.
+pub(crate) fn locate_json(content: &str, pointer: &str) -> Option<Location> {
+    let tokens = json_pointer::split(pointer)?;
+    let mut scanner = JsonScanner {
+        bytes: content.as_bytes(),
+        pos: 0,
+    };
+    if content.starts_with('\u{feff}') {
+        scanner.pos = '\u{feff}'.len_utf8();
+    }
+    scanner.ws();
+    let mut found = scanner.pos;
+    for token in tokens {
+        if scanner.enter(&token).is_none() {
+            break;
+        }
+        found = scanner.pos;
+    }
+    Some(location_at(content, found))
+}
+
+/// Line of a YAML document, with comments removed.
+#[cfg(feature = "yaml")]
+struct YamlLine<'a> {
+    offset: usize,
+    indent: usize,
+    text: &'a str,
+}
+
+#[cfg(feature = "yaml")]
+fn yaml_lines(content: &str) -> Vec<YamlLine<'_>> {
+    let mut lines = Vec::new();
+    let mut offset = 0;
+    for raw in content.split_inclusive('\n') {
+        let line = raw.trim_end_matches(['\n', '\r']);
+        let indent = line.len() - line.trim_start_matches(' ').len();
+        let mut end = line.len();
+        let mut quote = None;
+        let mut prev = b' ';
+        for (i, b) in line.bytes().enumerate().skip(indent) {
+            match (quote, b) {
+                (None, b'#') if prev == b' ' || prev == b'\t' || i == indent => {
+                    end = i;
+                    break;
+                }
+                (None, b'"' | b'\'')
+                    if prev == b' ' || prev == b':' || prev == b'-' || i == indent =>
+                {
+                    quote = Some(b)
+                }
+                (Some(q), b) if b == q => quote = None,
+                _ => {}
+            }
+            prev = b;
+        }
+        let text = line[indent..end].trim_end();
+        if !text.is_empty() && text != "---" && !text.starts_with('%') {
+            lines.push(YamlLine {
+                offset,
+                indent,
+                text,
+            });
+        }
+        offset += raw.len();
+    }
+    lines
+}
+
+/// Parses the key at the start of `text`, returning it with the length of `key:`.
+#[cfg(feature = "yaml")]
+fn yaml_key_at(text: &str) -> Option<(String, usize)> {
+    let (key, rest_start) = match text.as_bytes().first()? {
+        b'"' => {
+            let end = text[1..].find('"')? + 1;
+            (serde_json::from_str::<String>(&text[..=end]).ok()?, end + 1)
+        }
+        b'\'' => {
+            let mut end = 1;
+            loop {
+                end += text[end..].find('\'')?;
+                if text[end + 1..].starts_with('\'') {
+                    end += 2;
+                } else {
+                    break;
+                }
+            }
+            (text[1..end].replace("''", "'"), end + 1)
+        }
+        _ => {
+            let end = text
+                .find(": ")
+                .or_else(|| text.strip_suffix(':').map(str::len))?;
+            (text[..end].trim_end().to_string(), end)
+        }
+    };
+    let rest = &text[rest_start..];
+    let colon = rest.len() - rest.trim_start().len();
+    if rest[colon..].starts_with(':') {
+        Some((key, rest_start + colon + 1))
+    } else {
+        None
+    }
+}
+
+/// Finds the position of the node at `pointer` in a YAML document, or of its deepest ancestor
+/// present in the document. Only block collections are descended into.
+#[cfg(feature = "yaml")]
+pub(crate) fn locate_yaml(content: &str, pointer: &str) -> Option<Location> {
+    let tokens = json_pointer::split(pointer)?;
+    let lines = yaml_lines(content);
+    let first = lines.first()?;
+    // Current node: index of its line and column (in bytes) of its first character.
+    let (mut line, mut column) = (0, first.indent);
+    'tokens: for token in tokens {
+        let text = &lines[line].text[column - lines[line].indent..];
+        let next = if text.starts_with("- ") || text == "-" {
+            // Block sequence: items are the lines with a dash at the same column.
+            let index: usize = match token.parse() {
+                Ok(index) => index,
+                Err(_) => break,
+            };
+            let mut seen = 0;
+            let mut item = None;
+            for (i, l) in lines.iter().enumerate().skip(line) {
+                let at_column = if i == line {
+                    text
+                } else if l.indent == column {
+                    l.text
+                } else if l.indent < column {
+                    break;
+                } else {
+                    continue;
+                };
+                if !(at_column.starts_with("- ") || at_column == "-") {
+                    break;
+                }
+                if seen == index {
+                    item = Some((i, at_column));
+                    break;
+                }
+                seen += 1;
+            }
+            let (i, at_column) = match item {
+                Some(item) => item,
+                None => break,
+            };
+            let rest = &at_column[1..];
+            let skip = rest.len() - rest.trim_start().len();
+            if rest.trim_start().is_empty() {
+                value_below(&lines, i, column)
+            } else {
+                Some((i, column + 1 + skip))
+            }
+        } else {
+            // Block mapping: keys are the lines starting at the same column.
+            for (i, l) in lines.iter().enumerate().skip(line) {
+                let at_column = if i == line {
+                    text
+                } else if l.indent == column {
+                    l.text
+                } else if l.indent < column {
+                    break;
+                } else {
+                    continue;
+                };
+                if let Some((key, len)) = yaml_key_at(at_column) {
+                    if key == token {
+                        let rest = &at_column[len..];
+                        let next = if rest.trim_start().is_empty() {
+                            value_below(&lines, i, column)
+                        } else {
+                            Some((i, column + len + rest.len() - rest.trim_start().len()))
+                        };
+                        match next {
+                            Some(next) => {
+                                line = next.0;
+                                column = next.1;
+                                continue 'tokens;
+                            }
+                            None => break 'tokens,
+                        }
+                    }
+                }
+            }
+            None
+        };
+        match next {
+            Some(next) => {
+                line = next.0;
+                column = next.1;
+            }
+            None => break,
+        }
+    }
+    let l = &lines[line];
+    Some(location_at(content, l.offset + column))
+}
+
+/// Position of a collection value written on the lines following its key (or dash).
+#[cfg(feature = "yaml")]
+fn value_below(lines: &[YamlLine], line: usize, column: usize) -> Option<(usize, usize)> {
+    let next = lines.get(line + 1)?;
+    if next.indent > column || (next.indent == column && next.text.starts_with('-')) {
+        Some((line + 1, next.indent))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locate_json() {
+        let doc =
+            "{\n  \"a\": {\"b\": [1, {\"c/d\": true}],\n  \"e\": \"\\u00e9\"},\n  \"é\": 2\n}";
+        let at = |pointer| locate_json(doc, pointer).map(|l| (l.line, l.column));
+        assert_eq!(at(""), Some((1, 1)));
+        assert_eq!(at("/a"), Some((2, 8)));
+        assert_eq!(at("/a/b/1/c~1d"), Some((2, 26)));
+        assert_eq!(at("/a/e"), Some((3, 8)));
+        assert_eq!(at("/é"), Some((4, 8)));
+        // Missing nodes are located at their deepest ancestor.
+        assert_eq!(at("/a/b/5"), Some((2, 14)));
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_locate_yaml() {
+        let doc = "# comment\nopenapi: 3.0.1\npaths:\n  /pets: # pets\n    get:\n      parameters:\n      - name: a\n        in: query\n      -\n        name: 'b'\n      responses:\n        \"200\": {description: ok}\n";
+        let at = |pointer| locate_yaml(doc, pointer).map(|l| (l.line, l.column));
+        assert_eq!(at(""), Some((2, 1)));
+        assert_eq!(at("/openapi"), Some((2, 10)));
+        assert_eq!(at("/paths/~1pets/get"), Some((6, 7)));
+        assert_eq!(at("/paths/~1pets/get/parameters/0/in"), Some((8, 13)));
+        assert_eq!(at("/paths/~1pets/get/parameters/1/name"), Some((10, 15)));
+        assert_eq!(at("/paths/~1pets/get/responses/200"), Some((12, 16)));
+        assert_eq!(
+            at("/paths/~1pets/get/responses/200/description"),
+            Some((12, 16))
+        );
+    }
+
+    #[test]
+    fn test_strip_location() {
+        assert_eq!(strip_location("oops at line 1 column 2".into()), "oops");
+        assert_eq!(strip_location("look at line".into()), "look at line");
+    }
+}
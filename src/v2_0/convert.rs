@@ -0,0 +1,1263 @@
+//! Conversion of Swagger 2.0 documents to OpenAPI 3.0.
+
+use crate::{
+    json_pointer,
+    v2_0::{
+        AdditionalProperties, CollectionFormat, Flow, Items, Location, ObjectOrReference,
+        Operation, Parameter, PathItem, Response, Schema, Scheme, SecurityScheme, Spec, Type,
+    },
+    v3_0, Extensions, Str,
+};
+use indexmap::IndexMap;
+use serde_json::Value;
+use std::fmt;
+
+/// Version written to the `openapi` field of converted documents.
+const OPENAPI_VERSION: &str = "3.0.3";
+
+/// Media type used when neither the document nor the operation lists any.
+const DEFAULT_MEDIA_TYPE: &str = "application/json";
+
+const FORM_MEDIA_TYPES: [&str; 2] = ["application/x-www-form-urlencoded", "multipart/form-data"];
+
+/// Part of a Swagger 2.0 document that was lost by [`convert`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Warning {
+    /// JSON pointer of the node in the 2.0 document.
+    pub pointer: String,
+    pub message: String,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`{}`: {}", self.pointer, self.message)
+    }
+}
+
+/// Converts a Swagger 2.0 document to OpenAPI 3.0, with a warning for everything lost.
+///
+/// - `host`, `basePath` and `schemes` become `servers`.
+/// - `definitions`, `parameters`, `responses` and `securityDefinitions` move into
+///   `components`, and references to them are rewritten.
+/// - `body` parameters become request bodies, one media type per `consumes` entry.
+/// - `formData` parameters are merged into an object schema of a form request body.
+/// - `collectionFormat` becomes `style` and `explode`, and `type: file` a binary string.
+///
+/// Schema fields that [`v3_0::Schema`] does not model, like `discriminator` and `xml`, are
+/// dropped. So are, with a warning:
+/// - `additionalProperties: false`, which leaves additional properties allowed.
+/// - `enum` lists holding anything but strings, which leave the values unrestricted.
+pub fn convert(spec: Spec) -> (v3_0::Spec, Vec<Warning>) {
+    let mut scanner = Scanner::default();
+    scanner.spec(&spec);
+    let Spec {
+        swagger: _,
+        info,
+        host,
+        base_path,
+        schemes,
+        consumes,
+        produces,
+        paths,
+        definitions,
+        parameters,
+        responses,
+        security_definitions,
+        security,
+        tags,
+        external_docs,
+        extensions,
+    } = spec;
+    let converter = Converter {
+        host: &host,
+        base_path: &base_path,
+        consumes: &consumes,
+        produces: &produces,
+        parameters: &parameters,
+    };
+
+    let paths = paths
+        .into_iter()
+        .map(|(path, item)| (path, converter.path_item(item)))
+        .collect();
+
+    let mut components = v3_0::Components::default();
+    for (name, schema) in definitions {
+        let schema = match convert_schema(schema) {
+            v3_0::ComponentOrInlineSchema::Inline(schema) => {
+                v3_0::ObjectOrReference::Object(schema)
+            }
+            v3_0::ComponentOrInlineSchema::Component { name } => v3_0::ObjectOrReference::Ref {
+                ref_path: format!("#/components/schemas/{}", json_pointer::escape(&name)).into(),
+            },
+            v3_0::ComponentOrInlineSchema::ExtRef { reference } => v3_0::ObjectOrReference::Ref {
+                ref_path: reference,
+            },
+        };
+        components.schemas.insert(name, schema);
+    }
+    for (name, parameter) in &parameters {
+        match parameter.location {
+            Location::Body => {
+                let body = converter.request_body(parameter.clone(), &consumes);
+                components
+                    .request_bodies
+                    .insert(name.clone(), v3_0::ObjectOrReference::Object(body));
+            }
+            // Form fields have no 3.0 component, they are inlined where referenced.
+            Location::FormData => {}
+            _ => {
+                let parameter = converter.parameter(parameter.clone());
+                components
+                    .parameters
+                    .insert(name.clone(), v3_0::ObjectOrReference::Object(parameter));
+            }
+        }
+    }
+    for (name, response) in &responses {
+        let response = converter.response(response.clone(), &produces);
+        components
+            .responses
+            .insert(name.clone(), v3_0::ObjectOrReference::Object(response));
+    }
+    for (name, scheme) in security_definitions {
+        if let Some(scheme) = security_scheme(scheme) {
+            components
+                .security_schemes
+                .insert(name, v3_0::ObjectOrReference::Object(scheme));
+        }
+    }
+
+    let spec = v3_0::Spec {
+        openapi: Str::Borrowed(OPENAPI_VERSION),
+        info,
+        servers: servers(&schemes, &host, &base_path),
+        paths,
+        components: if components == v3_0::Components::default() {
+            None
+        } else {
+            Some(components)
+        },
+        security,
+        tags,
+        external_docs,
+        extensions,
+    };
+    (spec, scanner.warnings)
+}
+
+impl From<Spec> for v3_0::Spec {
+    fn from(spec: Spec) -> Self {
+        convert(spec).0
+    }
+}
+
+/// Collects the [`Warning`]s of a document before its conversion.
+#[derive(Default)]
+struct Scanner {
+    pointer: String,
+    warnings: Vec<Warning>,
+}
+
+impl Scanner {
+    fn at(&mut self, token: &str, f: impl FnOnce(&mut Self)) {
+        let len = self.pointer.len();
+        json_pointer::push(&mut self.pointer, token);
+        f(self);
+        self.pointer.truncate(len);
+    }
+
+    fn warn(&mut self, token: &str, message: &str) {
+        self.at(token, |scanner| {
+            let pointer = scanner.pointer.clone();
+            scanner.warnings.push(Warning {
+                pointer,
+                message: message.to_string(),
+            })
+        });
+    }
+
+    fn spec(&mut self, spec: &Spec) {
+        self.at("paths", |scanner| {
+            for (path, item) in &spec.paths {
+                scanner.at(path, |scanner| scanner.path_item(item));
+            }
+        });
+        self.at("definitions", |scanner| {
+            for (name, schema) in &spec.definitions {
+                scanner.at(name, |scanner| scanner.schema(schema));
+            }
+        });
+        self.at("parameters", |scanner| {
+            for (name, parameter) in &spec.parameters {
+                scanner.at(name, |scanner| scanner.parameter(parameter));
+            }
+        });
+        self.at("responses", |scanner| {
+            for (code, response) in &spec.responses {
+                scanner.at(code, |scanner| scanner.response(response));
+            }
+        });
+        self.at("securityDefinitions", |scanner| {
+            for (name, scheme) in &spec.security_definitions {
+                if security_scheme(scheme.clone()).is_none() {
+                    scanner.warn(
+                        name,
+                        "the URLs of its OAuth2 flow are missing, the scheme was dropped",
+                    );
+                }
+            }
+        });
+    }
+
+    fn path_item(&mut self, item: &PathItem) {
+        self.parameters(&item.parameters);
+        let operations = [
+            ("get", &item.get),
+            ("put", &item.put),
+            ("post", &item.post),
+            ("delete", &item.delete),
+            ("options", &item.options),
+            ("head", &item.head),
+            ("patch", &item.patch),
+        ];
+        for (method, operation) in operations.iter() {
+            if let Some(operation) = operation {
+                self.at(method, |scanner| scanner.operation(operation));
+            }
+        }
+    }
+
+    fn operation(&mut self, operation: &Operation) {
+        self.parameters(&operation.parameters);
+        self.at("responses", |scanner| {
+            for (code, response) in &operation.responses {
+                if let ObjectOrReference::Object(response) = response {
+                    scanner.at(code, |scanner| scanner.response(response));
+                }
+            }
+        });
+    }
+
+    fn parameters(&mut self, parameters: &[ObjectOrReference<Parameter>]) {
+        self.at("parameters", |scanner| {
+            for (i, parameter) in parameters.iter().enumerate() {
+                if let ObjectOrReference::Object(parameter) = parameter {
+                    scanner.at(&i.to_string(), |scanner| scanner.parameter(parameter));
+                }
+            }
+        });
+    }
+
+    fn parameter(&mut self, parameter: &Parameter) {
+        if let Some(schema) = &parameter.schema {
+            self.at("schema", |scanner| scanner.schema(schema));
+        }
+        self.enum_values(&parameter.enum_values);
+        if let Some(items) = &parameter.items {
+            self.at("items", |scanner| scanner.items(items));
+        }
+    }
+
+    fn items(&mut self, items: &Items) {
+        self.enum_values(&items.enum_values);
+        if let Some(items) = &items.items {
+            self.at("items", |scanner| scanner.items(items));
+        }
+    }
+
+    fn response(&mut self, response: &Response) {
+        if let Some(schema) = &response.schema {
+            self.at("schema", |scanner| scanner.schema(schema));
+        }
+        self.at("headers", |scanner| {
+            for (name, header) in &response.headers {
+                scanner.at(name, |scanner| {
+                    scanner.enum_values(&header.enum_values);
+                    if let Some(items) = &header.items {
+                        scanner.at("items", |scanner| scanner.items(items));
+                    }
+                });
+            }
+        });
+    }
+
+    fn schema(&mut self, schema: &Schema) {
+        self.enum_values(&schema.enum_values);
+        if let Some(items) = &schema.items {
+            self.at("items", |scanner| scanner.schema(items));
+        }
+        self.at("allOf", |scanner| {
+            for (i, schema) in schema.all_of.iter().enumerate() {
+                scanner.at(&i.to_string(), |scanner| scanner.schema(schema));
+            }
+        });
+        self.at("properties", |scanner| {
+            for (name, schema) in &schema.properties {
+                scanner.at(name, |scanner| scanner.schema(schema));
+            }
+        });
+        match &schema.additional_properties {
+            Some(AdditionalProperties::Schema(schema)) => {
+                self.at("additionalProperties", |scanner| scanner.schema(schema))
+            }
+            Some(AdditionalProperties::Bool(false)) => self.warn(
+                "additionalProperties",
+                "`false` was dropped, additional properties are allowed",
+            ),
+            Some(AdditionalProperties::Bool(true)) | None => {}
+        }
+    }
+
+    fn enum_values(&mut self, values: &[Value]) {
+        if values.iter().any(|value| !value.is_string()) {
+            self.warn(
+                "enum",
+                "values other than strings are not supported, it was dropped",
+            );
+        }
+    }
+}
+
+struct Converter<'a> {
+    host: &'a str,
+    base_path: &'a str,
+    consumes: &'a [Str],
+    produces: &'a [Str],
+    parameters: &'a IndexMap<Str, Parameter>,
+}
+
+impl<'a> Converter<'a> {
+    /// Returns the parameter itself, or the global parameter it refers to.
+    fn lookup<'p>(&'p self, parameter: &'p ObjectOrReference<Parameter>) -> Option<&'p Parameter> {
+        match parameter {
+            ObjectOrReference::Object(parameter) => Some(parameter),
+            ObjectOrReference::Ref { ref_path } => {
+                let name = ref_path.strip_prefix("#/parameters/")?;
+                self.parameters.get(&*json_pointer::unescape(name))
+            }
+        }
+    }
+
+    fn in_body(&self, parameter: &ObjectOrReference<Parameter>) -> bool {
+        match self.lookup(parameter) {
+            Some(parameter) => {
+                parameter.location == Location::Body || parameter.location == Location::FormData
+            }
+            None => false,
+        }
+    }
+
+    fn path_item(&self, item: PathItem) -> v3_0::PathItem {
+        let PathItem {
+            reference,
+            get,
+            put,
+            post,
+            delete,
+            options,
+            head,
+            patch,
+            parameters,
+            extensions,
+        } = item;
+        // Body and form parameters become part of the request body of each operation.
+        let (shared, body): (Vec<_>, Vec<_>) =
+            parameters.into_iter().partition(|p| !self.in_body(p));
+        let operation = |operation: Option<Operation>| {
+            operation.map(|operation| self.operation(operation, &body))
+        };
+        v3_0::PathItem {
+            reference,
+            get: operation(get),
+            put: operation(put),
+            post: operation(post),
+            delete: operation(delete),
+            options: operation(options),
+            head: operation(head),
+            patch: operation(patch),
+            parameters: shared
+                .into_iter()
+                .map(|p| self.parameter_or_ref(p))
+                .collect(),
+            extensions,
+            ..v3_0::PathItem::default()
+        }
+    }
+
+    fn operation(
+        &self,
+        operation: Operation,
+        inherited: &[ObjectOrReference<Parameter>],
+    ) -> v3_0::Operation {
+        let Operation {
+            tags,
+            summary,
+            description,
+            external_docs,
+            operation_id,
+            consumes,
+            produces,
+            parameters,
+            responses,
+            schemes,
+            deprecated,
+            security,
+            extensions,
+        } = operation;
+        let consumes = if consumes.is_empty() {
+            self.consumes
+        } else {
+            &consumes
+        };
+        let produces = if produces.is_empty() {
+            self.produces
+        } else {
+            &produces
+        };
+
+        let overridden = |inherited: &Parameter| {
+            parameters
+                .iter()
+                .filter_map(|p| self.lookup(p))
+                .any(|p| p.name == inherited.name && p.location == inherited.location)
+        };
+        let inherited: Vec<_> = inherited
+            .iter()
+            .filter(|p| match self.lookup(p) {
+                Some(p) => !overridden(p),
+                None => true,
+            })
+            .cloned()
+            .collect();
+
+        let mut body = None;
+        let mut form = Vec::new();
+        let mut converted = Vec::new();
+        for parameter in inherited.into_iter().chain(parameters) {
+            match self.lookup(&parameter).map(|p| p.location) {
+                Some(Location::Body) => {
+                    body = Some(match parameter {
+                        ObjectOrReference::Object(p) => {
+                            v3_0::ObjectOrReference::Object(self.request_body(p, consumes))
+                        }
+                        ObjectOrReference::Ref { ref_path } => v3_0::ObjectOrReference::Ref {
+                            ref_path: ref_path
+                                .replacen("#/parameters/", "#/components/requestBodies/", 1)
+                                .into(),
+                        },
+                    })
+                }
+                Some(Location::FormData) => form.extend(self.lookup(&parameter).cloned()),
+                _ => converted.push(self.parameter_or_ref(parameter)),
+            }
+        }
+        let request_body = body.or_else(|| {
+            if form.is_empty() {
+                None
+            } else {
+                Some(v3_0::ObjectOrReference::Object(
+                    self.form_body(form, consumes),
+                ))
+            }
+        });
+
+        let responses = responses
+            .into_iter()
+            .map(|(status, response)| {
+                let response = match response {
+                    ObjectOrReference::Object(response) => {
+                        v3_0::ObjectOrReference::Object(self.response(response, produces))
+                    }
+                    ObjectOrReference::Ref { ref_path } => v3_0::ObjectOrReference::Ref {
+                        ref_path: rewrite_ref(&ref_path),
+                    },
+                };
+                (status, response)
+            })
+            .collect();
+
+        v3_0::Operation {
+            tags,
+            summary,
+            description,
+            external_docs,
+            operation_id,
+            parameters: converted,
+            request_body,
+            responses,
+            callbacks: IndexMap::new(),
+            deprecated,
+            security,
+            servers: if schemes.is_empty() {
+                Vec::new()
+            } else {
+                servers(&schemes, self.host, self.base_path)
+            },
+            extensions,
+        }
+    }
+
+    fn parameter_or_ref(
+        &self,
+        parameter: ObjectOrReference<Parameter>,
+    ) -> v3_0::ObjectOrReference<v3_0::Parameter> {
+        match parameter {
+            ObjectOrReference::Object(parameter) => {
+                v3_0::ObjectOrReference::Object(self.parameter(parameter))
+            }
+            ObjectOrReference::Ref { ref_path } => v3_0::ObjectOrReference::Ref {
+                ref_path: rewrite_ref(&ref_path),
+            },
+        }
+    }
+
+    fn parameter(&self, parameter: Parameter) -> v3_0::Parameter {
+        let Parameter {
+            name,
+            location,
+            description,
+            required,
+            schema: _,
+            param_type,
+            format,
+            allow_empty_value: _,
+            items,
+            collection_format,
+            default,
+            maximum,
+            exclusive_maximum,
+            minimum,
+            exclusive_minimum,
+            max_length,
+            min_length,
+            pattern,
+            max_items,
+            min_items,
+            unique_items,
+            enum_values,
+            multiple_of,
+            extensions,
+        } = parameter;
+        let location = match location {
+            Location::Header => v3_0::Location::Header,
+            Location::Path => v3_0::Location::Path,
+            Location::FormData => v3_0::Location::FormData,
+            Location::Query | Location::Body => v3_0::Location::Query,
+        };
+        let (style, explode) = match collection_format {
+            None | Some(CollectionFormat::Tsv) => (None, None),
+            Some(CollectionFormat::Csv) => match location {
+                v3_0::Location::Query | v3_0::Location::FormData => {
+                    (Some(v3_0::ParameterStyle::Form), Some(false))
+                }
+                _ => (Some(v3_0::ParameterStyle::Simple), Some(false)),
+            },
+            Some(CollectionFormat::Ssv) => {
+                (Some(v3_0::ParameterStyle::SpaceDelimited), Some(false))
+            }
+            Some(CollectionFormat::Pipes) => {
+                (Some(v3_0::ParameterStyle::PipeDelimited), Some(false))
+            }
+            Some(CollectionFormat::Multi) => (Some(v3_0::ParameterStyle::Form), Some(true)),
+        };
+        let schema = items_schema(Items {
+            item_type: param_type,
+            format,
+            items,
+            collection_format: None,
+            default,
+            maximum,
+            exclusive_maximum,
+            minimum,
+            exclusive_minimum,
+            max_length,
+            min_length,
+            pattern,
+            max_items,
+            min_items,
+            unique_items,
+            enum_values,
+            multiple_of,
+            extensions: Extensions::default(),
+        });
+        v3_0::Parameter {
+            name,
+            location,
+            description,
+            required,
+            deprecated: None,
+            style,
+            explode,
+            allow_reserved: None,
+            representation: Some(v3_0::ParameterRepresentation::Simple {
+                schema: v3_0::ComponentOrInlineSchema::Inline(schema),
+            }),
+            example: None,
+            extensions,
+        }
+    }
+
+    fn request_body(&self, parameter: Parameter, consumes: &[Str]) -> v3_0::RequestBody {
+        let schema = parameter.schema.map(convert_schema);
+        v3_0::RequestBody {
+            description: parameter.description,
+            content: media_types(consumes)
+                .map(|media_type| {
+                    let media = v3_0::MediaType {
+                        schema: schema.clone(),
+                        ..v3_0::MediaType::default()
+                    };
+                    (media_type, media)
+                })
+                .collect(),
+            required: parameter.required,
+            extensions: parameter.extensions,
+        }
+    }
+
+    /// Merges `formData` parameters into the object schema of a form request body.
+    fn form_body(&self, form: Vec<Parameter>, consumes: &[Str]) -> v3_0::RequestBody {
+        let file = form.iter().any(|p| p.param_type == Some(Type::File));
+        let mut media_types: Vec<Str> = consumes
+            .iter()
+            .filter(|m| FORM_MEDIA_TYPES.contains(&&***m))
+            .filter(|m| !file || *m == "multipart/form-data")
+            .cloned()
+            .collect();
+        if media_types.is_empty() {
+            media_types.push(Str::Borrowed(FORM_MEDIA_TYPES[file as usize]));
+        }
+
+        let mut schema = v3_0::Schema {
+            schema_type: Some(Type::Object),
+            ..v3_0::Schema::default()
+        };
+        for parameter in form {
+            if parameter.required == Some(true) {
+                schema.required.push(parameter.name.clone());
+            }
+            let name = parameter.name.clone();
+            let description = parameter.description.clone();
+            let mut property = match self.parameter(parameter).representation {
+                Some(v3_0::ParameterRepresentation::Simple {
+                    schema: v3_0::ComponentOrInlineSchema::Inline(schema),
+                }) => schema,
+                _ => v3_0::Schema::default(),
+            };
+            property.description = description;
+            schema
+                .properties
+                .insert(name, v3_0::ComponentOrInlineSchema::Inline(property));
+        }
+
+        let required = !schema.required.is_empty();
+        let schema = v3_0::ComponentOrInlineSchema::Inline(schema);
+        v3_0::RequestBody {
+            content: media_types
+                .into_iter()
+                .map(|media_type| {
+                    let media = v3_0::MediaType {
+                        schema: Some(schema.clone()),
+                        ..v3_0::MediaType::default()
+                    };
+                    (media_type, media)
+                })
+                .collect(),
+            required: if required { Some(true) } else { None },
+            ..v3_0::RequestBody::default()
+        }
+    }
+
+    fn response(&self, response: Response, produces: &[Str]) -> v3_0::Response {
+        let Response {
+            description,
+            schema,
+            headers,
+            examples,
+            extensions,
+        } = response;
+        let schema = schema.map(convert_schema);
+        let mut content = IndexMap::new();
+        if schema.is_some() {
+            for media_type in media_types(produces) {
+                let media = v3_0::MediaType {
+                    schema: schema.clone(),
+                    ..v3_0::MediaType::default()
+                };
+                content.insert(media_type, media);
+            }
+        }
+        for (media_type, example) in examples {
+            content
+                .entry(media_type)
+                .or_insert_with(|| v3_0::MediaType {
+                    schema: schema.clone(),
+                    ..v3_0::MediaType::default()
+                })
+                .examples = Some(v3_0::MediaTypeExample::Example { example });
+        }
+
+        let headers = headers
+            .into_iter()
+            .map(|(name, header)| {
+                let schema = items_schema(Items {
+                    item_type: header.header_type,
+                    format: header.format,
+                    items: header.items,
+                    collection_format: None,
+                    default: header.default,
+                    maximum: header.maximum,
+                    exclusive_maximum: header.exclusive_maximum,
+                    minimum: header.minimum,
+                    exclusive_minimum: header.exclusive_minimum,
+                    max_length: header.max_length,
+                    min_length: header.min_length,
+                    pattern: header.pattern,
+                    max_items: header.max_items,
+                    min_items: header.min_items,
+                    unique_items: header.unique_items,
+                    enum_values: header.enum_values,
+                    multiple_of: header.multiple_of,
+                    extensions: Extensions::default(),
+                });
+                let header = v3_0::Header {
+                    schema: Some(v3_0::ComponentOrInlineSchema::Inline(schema)),
+                    description: header.description,
+                    extensions: header.extensions,
+                    ..v3_0::Header::default()
+                };
+                (name, v3_0::ObjectOrReference::Object(header))
+            })
+            .collect();
+
+        v3_0::Response {
+            description,
+            headers,
+            content,
+            links: IndexMap::new(),
+            extensions,
+        }
+    }
+}
+
+fn servers(schemes: &[Scheme], host: &str, base_path: &str) -> Vec<v3_0::Server> {
+    let urls = if host.is_empty() {
+        if base_path.is_empty() {
+            Vec::new()
+        } else {
+            vec![base_path.to_string()]
+        }
+    } else if schemes.is_empty() {
+        // Relative to the scheme the document was fetched with.
+        vec![format!("//{}{}", host, base_path)]
+    } else {
+        schemes
+            .iter()
+            .map(|scheme| format!("{}://{}{}", scheme.as_str(), host, base_path))
+            .collect()
+    };
+    urls.into_iter()
+        .map(|url| v3_0::Server {
+            url: url.into(),
+            ..v3_0::Server::default()
+        })
+        .collect()
+}
+
+fn media_types(media_types: &[Str]) -> impl Iterator<Item = Str> + '_ {
+    let default = if media_types.is_empty() {
+        Some(Str::Borrowed(DEFAULT_MEDIA_TYPE))
+    } else {
+        None
+    };
+    media_types.iter().cloned().chain(default)
+}
+
+fn rewrite_ref(reference: &str) -> Str {
+    const PREFIXES: [(&str, &str); 3] = [
+        ("#/definitions/", "#/components/schemas/"),
+        ("#/parameters/", "#/components/parameters/"),
+        ("#/responses/", "#/components/responses/"),
+    ];
+    for (from, to) in PREFIXES.iter() {
+        if let Some(rest) = reference.strip_prefix(from) {
+            return format!("{}{}", to, rest).into();
+        }
+    }
+    reference.to_string().into()
+}
+
+/// 3.0 has no `file` type, files are binary strings.
+fn schema_type(ty: Option<Type>, format: Str) -> (Option<Type>, Str) {
+    match ty {
+        Some(Type::File) => (Some(Type::String), Str::Borrowed("binary")),
+        ty => (ty, format),
+    }
+}
+
+/// Returns the values of a string `enum`, or none if it holds anything else, as
+/// [`v3_0::Schema::enum_values`] only holds strings.
+fn enum_strings(values: Vec<Value>) -> Vec<Str> {
+    values
+        .into_iter()
+        .map(|value| match value {
+            Value::String(value) => Some(value.into()),
+            _ => None,
+        })
+        .collect::<Option<_>>()
+        .unwrap_or_default()
+}
+
+fn items_schema(items: Items) -> v3_0::Schema {
+    let Items {
+        item_type,
+        format,
+        items,
+        collection_format: _,
+        default,
+        maximum,
+        exclusive_maximum,
+        minimum,
+        exclusive_minimum,
+        max_length,
+        min_length,
+        pattern,
+        max_items,
+        min_items,
+        unique_items,
+        enum_values,
+        multiple_of,
+        extensions,
+    } = items;
+    let (schema_type, format) = schema_type(item_type, format);
+    v3_0::Schema {
+        schema_type,
+        format,
+        items: items
+            .map(|items| Box::new(v3_0::ComponentOrInlineSchema::Inline(items_schema(*items)))),
+        default,
+        maximum: maximum.map(Value::Number),
+        exclusive_maximum: exclusive_maximum.map(Value::Bool),
+        minimum: minimum.map(Value::Number),
+        exclusive_minimum: exclusive_minimum.map(Value::Bool),
+        max_length,
+        min_length,
+        pattern,
+        max_items,
+        min_items,
+        unique_items,
+        enum_values: enum_strings(enum_values),
+        multiple_of: multiple_of.map(Value::Number),
+        extensions,
+        ..v3_0::Schema::default()
+    }
+}
+
+fn convert_schema(schema: Schema) -> v3_0::ComponentOrInlineSchema {
+    let Schema {
+        reference,
+        format,
+        title,
+        description,
+        default,
+        multiple_of,
+        maximum,
+        exclusive_maximum,
+        minimum,
+        exclusive_minimum,
+        max_length,
+        min_length,
+        pattern,
+        max_items,
+        min_items,
+        unique_items,
+        max_properties,
+        min_properties,
+        required,
+        enum_values,
+        schema_type: ty,
+        items,
+        all_of,
+        properties,
+        additional_properties,
+        discriminator: _,
+        read_only,
+        xml: _,
+        external_docs: _,
+        example,
+        extensions,
+    } = schema;
+    if !reference.is_empty() {
+        return match reference.strip_prefix("#/definitions/") {
+            Some(name) => v3_0::ComponentOrInlineSchema::Component {
+                name: json_pointer::unescape(name).into_owned().into(),
+            },
+            // References to other documents are kept as they are, their definitions being
+            // converted separately.
+            None => v3_0::ComponentOrInlineSchema::ExtRef { reference },
+        };
+    }
+    let (schema_type, format) = schema_type(ty, format);
+    v3_0::ComponentOrInlineSchema::Inline(v3_0::Schema {
+        description,
+        format,
+        items: items.map(|items| Box::new(convert_schema(*items))),
+        properties: properties
+            .into_iter()
+            .map(|(name, schema)| (name, convert_schema(schema)))
+            .collect(),
+        read_only,
+        additional_properties: match additional_properties {
+            Some(AdditionalProperties::Schema(schema)) => Some(Box::new(convert_schema(*schema))),
+            Some(AdditionalProperties::Bool(true)) => Some(Box::new(
+                v3_0::ComponentOrInlineSchema::Inline(v3_0::Schema::default()),
+            )),
+            Some(AdditionalProperties::Bool(false)) | None => None,
+        },
+        example,
+        title,
+        default,
+        all_of: all_of.into_iter().map(convert_schema).collect(),
+        schema_type,
+        enum_values: enum_strings(enum_values),
+        multiple_of: multiple_of.map(Value::Number),
+        minimum: minimum.map(Value::Number),
+        exclusive_minimum: exclusive_minimum.map(Value::Bool),
+        maximum: maximum.map(Value::Number),
+        exclusive_maximum: exclusive_maximum.map(Value::Bool),
+        max_length,
+        min_length,
+        pattern,
+        max_items,
+        min_items,
+        unique_items,
+        max_properties,
+        min_properties,
+        required,
+        extensions,
+        ..v3_0::Schema::default()
+    })
+}
+
+/// Returns `None` for OAuth2 schemes missing the URLs of their flow.
+fn security_scheme(scheme: SecurityScheme) -> Option<v3_0::SecurityScheme> {
+    Some(match scheme {
+        SecurityScheme::Basic { .. } => v3_0::SecurityScheme::Http {
+            scheme: Str::Borrowed("basic"),
+            bearer_format: Str::default(),
+        },
+        SecurityScheme::ApiKey { name, location, .. } => {
+            v3_0::SecurityScheme::ApiKey { name, location }
+        }
+        SecurityScheme::OAuth2 {
+            flow,
+            authorization_url,
+            token_url,
+            scopes,
+            ..
+        } => {
+            let mut flows = v3_0::Flows {
+                implicit: None,
+                password: None,
+                client_credentials: None,
+                authorization_code: None,
+                extensions: Extensions::default(),
+            };
+            match flow {
+                Flow::Implicit => {
+                    flows.implicit = Some(v3_0::ImplicitFlow {
+                        authorization_url: authorization_url?,
+                        refresh_url: None,
+                        scopes,
+                        extensions: Extensions::default(),
+                    })
+                }
+                Flow::Password => {
+                    flows.password = Some(v3_0::PasswordFlow {
+                        token_url: token_url?,
+                        refresh_url: None,
+                        scopes,
+                        extensions: Extensions::default(),
+                    })
+                }
+                Flow::Application => {
+                    flows.client_credentials = Some(v3_0::ClientCredentialsFlow {
+                        token_url: token_url?,
+                        refresh_url: None,
+                        scopes,
+                        extensions: Extensions::default(),
+                    })
+                }
+                Flow::AccessCode => {
+                    flows.authorization_code = Some(v3_0::AuthorizationCodeFlow {
+                        authorization_url: authorization_url?,
+                        token_url: token_url?,
+                        refresh_url: None,
+                        scopes,
+                        extensions: Extensions::default(),
+                    })
+                }
+            }
+            v3_0::SecurityScheme::OAuth2 { flows }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const SPEC: &str = r##"{
+      "swagger": "2.0",
+      "info": {"title": "Petstore", "version": "1.0.0"},
+      "host": "petstore.example.com",
+      "basePath": "/v1",
+      "schemes": ["https", "http"],
+      "consumes": ["application/json"],
+      "produces": ["application/json"],
+      "paths": {
+        "/pets": {
+          "parameters": [{"$ref": "#/parameters/limit"}],
+          "get": {
+            "operationId": "listPets",
+            "parameters": [
+              {"name": "tags", "in": "query", "type": "array", "items": {"type": "string"},
+               "collectionFormat": "multi"}
+            ],
+            "responses": {
+              "200": {
+                "description": "A list of pets",
+                "headers": {"X-Next": {"type": "string", "description": "Next page"}},
+                "schema": {"type": "array", "items": {"$ref": "#/definitions/Pet"}}
+              },
+              "default": {"$ref": "#/responses/Error"}
+            }
+          },
+          "post": {
+            "parameters": [
+              {"name": "pet", "in": "body", "required": true,
+               "schema": {"$ref": "#/definitions/Pet"}}
+            ],
+            "responses": {"201": {"description": "Created"}}
+          }
+        },
+        "/pets/{petId}/photo": {
+          "put": {
+            "consumes": ["multipart/form-data"],
+            "parameters": [
+              {"name": "petId", "in": "path", "required": true, "type": "integer"},
+              {"name": "photo", "in": "formData", "required": true, "type": "file"},
+              {"name": "caption", "in": "formData", "type": "string"}
+            ],
+            "responses": {"204": {"description": "Uploaded"}}
+          }
+        }
+      },
+      "definitions": {
+        "Pet": {
+          "type": "object",
+          "required": ["name"],
+          "properties": {
+            "name": {"type": "string"},
+            "status": {"type": "string", "enum": ["available", "sold"]}
+          }
+        },
+        "Animal/Pet": {"$ref": "#/definitions/Pet"},
+        "Owner": {"properties": {"pet": {"$ref": "#/definitions/Animal~1Pet"}}}
+      },
+      "parameters": {
+        "limit": {"name": "limit", "in": "query", "type": "integer", "maximum": 100}
+      },
+      "responses": {
+        "Error": {"description": "Unexpected error", "schema": {"type": "string"}}
+      },
+      "securityDefinitions": {
+        "basic": {"type": "basic"},
+        "oauth": {
+          "type": "oauth2",
+          "flow": "accessCode",
+          "authorizationUrl": "https://example.com/oauth/authorize",
+          "tokenUrl": "https://example.com/oauth/token",
+          "scopes": {"read:pets": "read your pets"}
+        }
+      }
+    }"##;
+
+    fn converted() -> serde_json::Value {
+        let spec: Spec = serde_json::from_str(SPEC).unwrap();
+        serde_json::to_value(v3_0::Spec::from(spec)).unwrap()
+    }
+
+    #[test]
+    fn test_convert_document() {
+        let spec = converted();
+        assert_eq!(spec["openapi"], "3.0.3");
+        assert_eq!(
+            spec["servers"],
+            json!([
+                {"url": "https://petstore.example.com/v1"},
+                {"url": "http://petstore.example.com/v1"}
+            ])
+        );
+        let components = &spec["components"];
+        assert_eq!(
+            components["schemas"]["Pet"]["properties"]["status"],
+            json!({"type": "string", "enum": ["available", "sold"]})
+        );
+        assert_eq!(
+            components["schemas"]["Owner"]["properties"]["pet"],
+            json!({"$ref": "#/components/schemas/Animal~1Pet"})
+        );
+        let converted: v3_0::Spec = serde_json::from_value(spec.clone()).unwrap();
+        let owner = converted.components.as_ref().unwrap().schemas["Owner"].clone();
+        let pet = match owner {
+            v3_0::ObjectOrReference::Object(owner) => owner.properties["pet"].clone(),
+            v3_0::ObjectOrReference::Ref { .. } => unreachable!(),
+        };
+        assert!(converted.resolve_schema(&pet).is_ok());
+        assert_eq!(
+            components["parameters"]["limit"],
+            json!({"name": "limit", "in": "query", "schema": {"type": "integer", "maximum": 100}})
+        );
+        assert_eq!(
+            components["securitySchemes"],
+            json!({
+                "basic": {"type": "http", "scheme": "basic"},
+                "oauth": {
+                    "type": "oauth2",
+                    "flows": {
+                        "authorizationCode": {
+                            "authorizationUrl": "https://example.com/oauth/authorize",
+                            "tokenUrl": "https://example.com/oauth/token",
+                            "scopes": {"read:pets": "read your pets"}
+                        }
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_convert_operations() {
+        let spec = converted();
+        let pets = &spec["paths"]["/pets"];
+        assert_eq!(
+            pets["parameters"],
+            json!([{"$ref": "#/components/parameters/limit"}])
+        );
+
+        let list = &pets["get"];
+        assert_eq!(
+            list["parameters"][0],
+            json!({
+                "name": "tags",
+                "in": "query",
+                "style": "form",
+                "explode": true,
+                "schema": {"type": "array", "items": {"type": "string"}}
+            })
+        );
+        let ok = &list["responses"]["200"];
+        assert_eq!(
+            ok["content"]["application/json"]["schema"],
+            json!({"type": "array", "items": {"$ref": "#/components/schemas/Pet"}})
+        );
+        assert_eq!(
+            ok["headers"]["X-Next"],
+            json!({"schema": {"type": "string"}, "description": "Next page"})
+        );
+        assert_eq!(
+            list["responses"]["default"],
+            json!({"$ref": "#/components/responses/Error"})
+        );
+
+        assert_eq!(
+            pets["post"]["requestBody"],
+            json!({
+                "content": {
+                    "application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}
+                },
+                "required": true
+            })
+        );
+
+        let upload = &spec["paths"]["/pets/{petId}/photo"]["put"];
+        assert_eq!(upload["parameters"].as_array().unwrap().len(), 1);
+        assert_eq!(
+            upload["requestBody"],
+            json!({
+                "content": {
+                    "multipart/form-data": {
+                        "schema": {
+                            "type": "object",
+                            "required": ["photo"],
+                            "properties": {
+                                "photo": {"type": "string", "format": "binary"},
+                                "caption": {"type": "string"}
+                            }
+                        }
+                    }
+                },
+                "required": true
+            })
+        );
+    }
+
+    #[test]
+    fn test_convert_warnings() {
+        let spec: Spec = serde_json::from_value(json!({
+            "swagger": "2.0",
+            "info": {"title": "Petstore", "version": "1.0.0"},
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "parameters": [
+                            {"name": "size", "in": "query", "type": "integer", "enum": [1, 2]}
+                        ],
+                        "responses": {"204": {"description": "No content"}}
+                    }
+                }
+            },
+            "definitions": {
+                "Pet": {
+                    "type": "object",
+                    "properties": {"name": {"type": "string", "enum": ["Rex"]}},
+                    "additionalProperties": false
+                }
+            },
+            "securityDefinitions": {
+                "oauth": {"type": "oauth2", "flow": "implicit", "scopes": {}}
+            }
+        }))
+        .unwrap();
+        let (spec, warnings) = convert(spec);
+        let warnings: Vec<_> = warnings.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            warnings,
+            [
+                "`/paths/~1pets/get/parameters/0/enum`: values other than strings are not \
+                 supported, it was dropped",
+                "`/definitions/Pet/additionalProperties`: `false` was dropped, additional \
+                 properties are allowed",
+                "`/securityDefinitions/oauth`: the URLs of its OAuth2 flow are missing, the \
+                 scheme was dropped",
+            ]
+        );
+
+        let spec = serde_json::to_value(spec).unwrap();
+        assert_eq!(
+            spec["paths"]["/pets"]["get"]["parameters"][0]["schema"],
+            json!({"type": "integer"})
+        );
+        assert_eq!(
+            spec["components"]["schemas"]["Pet"],
+            json!({
+                "type": "object",
+                "properties": {"name": {"type": "string", "enum": ["Rex"]}}
+            })
+        );
+        assert_eq!(spec["components"].get("securitySchemes"), None);
+    }
+}
@@ -0,0 +1,15 @@
+//! Support for Swagger version 2.0 specification, and its conversion to OpenApi 3.0.
+//!
+//! See the
+//! [specification](https://github.com/OAI/OpenAPI-Specification/blob/main/versions/2.0.md)
+//! for more information.
+//!
+//! Objects that did not change in 3.0 are re-exported from [`v3_0`](crate::v3_0).
+
+mod convert;
+mod schema;
+
+pub use crate::v2_0::{convert::*, schema::*};
+pub use crate::v3_0::{
+    Contact, ExternalDoc, Info, License, ObjectOrReference, SecurityRequirement, Tag, Type,
+};
@@ -0,0 +1,521 @@
+//! Schema specification for [Swagger 2.0](https://github.com/OAI/OpenAPI-Specification/blob/main/versions/2.0.md)
+
+use crate::{
+    v2_0::{ExternalDoc, Info, ObjectOrReference, SecurityRequirement, Tag, Type},
+    Extensions, Str,
+};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// top level document
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Spec {
+    /// Specifies the Swagger Specification version being used. Its value MUST be `"2.0"`.
+    pub swagger: Str,
+
+    /// Provides metadata about the API.
+    pub info: Info,
+
+    /// The host (name or ip) serving the API, optionally including the port. If not included,
+    /// the host serving the documentation is to be used.
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub host: Str,
+
+    /// The base path on which the API is served, relative to the host. It MUST start with a
+    /// leading slash.
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub base_path: Str,
+
+    /// The transfer protocol of the API. If not included, the scheme used to access the
+    /// documentation is to be used.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub schemes: Vec<Scheme>,
+
+    /// A list of MIME types the APIs can consume. Can be overridden by operations.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub consumes: Vec<Str>,
+
+    /// A list of MIME types the APIs can produce. Can be overridden by operations.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub produces: Vec<Str>,
+
+    /// The available paths and operations for the API.
+    pub paths: IndexMap<Str, PathItem>,
+
+    /// Data types produced and consumed by operations.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub definitions: IndexMap<Str, Schema>,
+
+    /// Parameters that can be used across operations.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub parameters: IndexMap<Str, Parameter>,
+
+    /// Responses that can be used across operations.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub responses: IndexMap<Str, Response>,
+
+    /// Security schemes that can be used across the specification.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub security_definitions: IndexMap<Str, SecurityScheme>,
+
+    /// A declaration of which security schemes are applied for the API as a whole.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub security: Vec<SecurityRequirement>,
+
+    /// A list of tags used by the specification with additional metadata.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<Tag>,
+
+    /// Additional external documentation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_docs: Option<ExternalDoc>,
+
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// Transfer protocol of the API.
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Scheme {
+    Http,
+    Https,
+    Ws,
+    Wss,
+}
+
+impl Scheme {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Scheme::Http => "http",
+            Scheme::Https => "https",
+            Scheme::Ws => "ws",
+            Scheme::Wss => "wss",
+        }
+    }
+}
+
+/// Describes the operations available on a single path.
+///
+/// See <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/2.0.md#pathItemObject>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct PathItem {
+    /// Allows for an external definition of this path item.
+    #[serde(default, skip_serializing_if = "str::is_empty", rename = "$ref")]
+    pub reference: Str,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub get: Option<Operation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub put: Option<Operation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post: Option<Operation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delete: Option<Operation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<Operation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub head: Option<Operation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub patch: Option<Operation>,
+
+    /// A list of parameters that are applicable for all the operations described under this
+    /// path. These parameters can be overridden at the operation level, but cannot be removed
+    /// there.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<ObjectOrReference<Parameter>>,
+
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// Describes a single API operation on a path.
+///
+/// See <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/2.0.md#operationObject>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Operation {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<Str>,
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub summary: Str,
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub description: Str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_docs: Option<ExternalDoc>,
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub operation_id: Str,
+
+    /// A list of MIME types the operation can consume, overriding the global `consumes`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub consumes: Vec<Str>,
+    /// A list of MIME types the operation can produce, overriding the global `produces`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub produces: Vec<Str>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<ObjectOrReference<Parameter>>,
+
+    /// The list of possible responses, by HTTP status code or `default`.
+    pub responses: IndexMap<Str, ObjectOrReference<Response>>,
+
+    /// The transfer protocol for the operation, overriding the global `schemes`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub schemes: Vec<Scheme>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub security: Option<Vec<SecurityRequirement>>,
+
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// The location of a parameter.
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum Location {
+    Query,
+    Header,
+    Path,
+    FormData,
+    Body,
+}
+
+/// Just for convenience.
+impl Default for Location {
+    fn default() -> Self {
+        Location::Query
+    }
+}
+
+/// Format of array parameters.
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CollectionFormat {
+    /// Comma separated values, `foo,bar`.
+    Csv,
+    /// Space separated values, `foo bar`.
+    Ssv,
+    /// Tab separated values, `foo\tbar`.
+    Tsv,
+    /// Pipe separated values, `foo|bar`.
+    Pipes,
+    /// Multiple parameter instances, `foo=bar&foo=baz`. Only for `query` or `formData`
+    /// parameters.
+    Multi,
+}
+
+/// Describes a single operation parameter.
+///
+/// Parameters `in: body` only use `schema`, all others only use the fields shared with
+/// [`Items`].
+///
+/// See <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/2.0.md#parameterObject>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Parameter {
+    pub name: Str,
+    #[serde(rename = "in")]
+    pub location: Location,
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub description: Str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+
+    /// The schema of a body parameter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<Schema>,
+
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub param_type: Option<Type>,
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub format: Str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_empty_value: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub items: Option<Box<Items>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collection_format: Option<CollectionFormat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<serde_json::Number>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclusive_maximum: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<serde_json::Number>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclusive_minimum: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<usize>,
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub pattern: Str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_items: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_items: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unique_items: Option<bool>,
+    #[serde(rename = "enum", default, skip_serializing_if = "Vec::is_empty")]
+    pub enum_values: Vec<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multiple_of: Option<serde_json::Number>,
+
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// Describes the type of items in an array parameter or header.
+///
+/// See <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/2.0.md#itemsObject>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Items {
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub item_type: Option<Type>,
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub format: Str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub items: Option<Box<Items>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collection_format: Option<CollectionFormat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<serde_json::Number>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclusive_maximum: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<serde_json::Number>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclusive_minimum: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<usize>,
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub pattern: Str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_items: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_items: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unique_items: Option<bool>,
+    #[serde(rename = "enum", default, skip_serializing_if = "Vec::is_empty")]
+    pub enum_values: Vec<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multiple_of: Option<serde_json::Number>,
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// Describes a single response from an API Operation.
+///
+/// See <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/2.0.md#responseObject>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Response {
+    pub description: Str,
+
+    /// A definition of the response structure. `type: file` indicates a file response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<Schema>,
+
+    /// A list of headers that are sent with the response.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub headers: IndexMap<Str, Header>,
+
+    /// Example response messages, by MIME type.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub examples: IndexMap<Str, serde_json::Value>,
+
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// Describes a response header.
+///
+/// See <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/2.0.md#headerObject>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Header {
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub description: Str,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub header_type: Option<Type>,
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub format: Str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub items: Option<Box<Items>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collection_format: Option<CollectionFormat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<serde_json::Number>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclusive_maximum: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<serde_json::Number>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclusive_minimum: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<usize>,
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub pattern: Str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_items: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_items: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unique_items: Option<bool>,
+    #[serde(rename = "enum", default, skip_serializing_if = "Vec::is_empty")]
+    pub enum_values: Vec<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multiple_of: Option<serde_json::Number>,
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// Value of `additionalProperties`.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum AdditionalProperties {
+    Bool(bool),
+    Schema(Box<Schema>),
+}
+
+/// The Schema Object allows the definition of input and output data types. It is based on
+/// [JSON Schema Draft 4](http://json-schema.org/).
+///
+/// See <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/2.0.md#schemaObject>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Schema {
+    /// [JSON reference](https://tools.ietf.org/html/draft-pbryan-zyp-json-ref-03), such as
+    /// `#/definitions/Pet`. Other fields are ignored when it is set.
+    #[serde(default, rename = "$ref", skip_serializing_if = "str::is_empty")]
+    pub reference: Str,
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub format: Str,
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub title: Str,
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub description: Str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multiple_of: Option<serde_json::Number>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<serde_json::Number>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclusive_maximum: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<serde_json::Number>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclusive_minimum: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<usize>,
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub pattern: Str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_items: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_items: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unique_items: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_properties: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_properties: Option<usize>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required: Vec<Str>,
+    #[serde(rename = "enum", default, skip_serializing_if = "Vec::is_empty")]
+    pub enum_values: Vec<serde_json::Value>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub schema_type: Option<Type>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub items: Option<Box<Schema>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub all_of: Vec<Schema>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub properties: IndexMap<Str, Schema>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub additional_properties: Option<AdditionalProperties>,
+    /// Name of the property used to tell apart the schemas inheriting from this one.
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub discriminator: Str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xml: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_docs: Option<ExternalDoc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example: Option<serde_json::Value>,
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// OAuth2 flow of a security scheme.
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum Flow {
+    Implicit,
+    Password,
+    Application,
+    AccessCode,
+}
+
+/// Defines a security scheme that can be used by the operations.
+///
+/// See <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/2.0.md#securitySchemeObject>.
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(tag = "type")]
+pub enum SecurityScheme {
+    #[serde(rename = "basic")]
+    Basic {
+        #[serde(default, skip_serializing_if = "str::is_empty")]
+        description: Str,
+    },
+    #[serde(rename = "apiKey")]
+    ApiKey {
+        name: Str,
+        #[serde(rename = "in")]
+        location: Str,
+        #[serde(default, skip_serializing_if = "str::is_empty")]
+        description: Str,
+    },
+    #[serde(rename = "oauth2")]
+    OAuth2 {
+        flow: Flow,
+        /// Required for the `implicit` and `accessCode` flows.
+        #[serde(rename = "authorizationUrl", skip_serializing_if = "Option::is_none")]
+        authorization_url: Option<Url>,
+        /// Required for the `password`, `application` and `accessCode` flows.
+        #[serde(rename = "tokenUrl", skip_serializing_if = "Option::is_none")]
+        token_url: Option<Url>,
+        scopes: IndexMap<Str, Str>,
+        #[serde(default, skip_serializing_if = "str::is_empty")]
+        description: Str,
+    },
+}
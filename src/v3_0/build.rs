@@ -0,0 +1,1054 @@
+//! Builders of the objects of a specification, for documents generated in code.
+//!
+//! Builders nest: the methods adding an object accept either the object or its builder. The
+//! `build` method of a builder checks the rules of [`Spec::validate`] on everything it holds,
+//! so nested builders need not be built first.
+//!
+//! ```
+//! use rweb_openapi::v3_0::{Operation, Parameter, Response, Schema, Spec};
+//!
+//! let spec = Spec::builder("Pets", "1.0.0")
+//!     .schema(
+//!         "Pet",
+//!         Schema::object()
+//!             .required_property("name", Schema::string())
+//!             .property("tags", Schema::array_of(Schema::string())),
+//!     )
+//!     .path("/pets/{id}")
+//!     .parameter(Parameter::path("id").schema(Schema::string().format("uuid")))
+//!     .get(
+//!         Operation::builder()
+//!             .operation_id("getPet")
+//!             .response(200, Response::builder("A pet").json(Schema::component("Pet")))
+//!             .response_ref(404, "NotFound"),
+//!     )
+//!     .end()
+//!     .response("NotFound", Response::builder("No such pet"))
+//!     .build()
+//!     .unwrap();
+//! assert!(spec.validate().is_empty());
+//! ```
+
+use crate::{
+    v3_0::{
+        resolve::component_ref, validate::validate_with, Component, ComponentOrInlineSchema,
+        Components, Header, Info, Link, LinkOperation, Location, MediaType, MediaTypeExample,
+        ObjectOrReference, Operation, Parameter, ParameterRepresentation, ParameterStyle, PathItem,
+        RequestBody, Response, RuntimeExpressionOrValue, Schema, SecurityScheme, Server, Spec, Tag,
+        Type, Violation,
+    },
+    Str,
+};
+use indexmap::IndexMap;
+use serde_json::Value;
+
+const JSON: &str = "application/json";
+
+/// Adds `object` as the component `name` of `spec`.
+fn add_component<T: Component>(spec: &mut Spec, name: Str, object: T) {
+    let components = spec.components.get_or_insert_with(Components::default);
+    T::components_mut(components).insert(name, ObjectOrReference::Object(object));
+}
+
+/// A reference to the component of kind `T` named `name`.
+fn reference<T: Component>(name: &str) -> ObjectOrReference<T> {
+    ObjectOrReference::Ref {
+        ref_path: component_ref::<T>(name),
+    }
+}
+
+fn checked<T>(object: T, violations: Vec<Violation>) -> Result<T, Vec<Violation>> {
+    if violations.is_empty() {
+        Ok(object)
+    } else {
+        Err(violations)
+    }
+}
+
+/// Defines `From<$builder> for $ty`, unchecked as the enclosing builder checks the object.
+macro_rules! unchecked_from {
+    ($($builder:ident => $ty:ident . $field:ident),*) => {
+        $(
+            impl From<$builder> for $ty {
+                fn from(builder: $builder) -> Self {
+                    builder.$field
+                }
+            }
+
+            impl From<$ty> for $builder {
+                fn from($field: $ty) -> Self {
+                    $builder { $field }
+                }
+            }
+        )*
+    };
+}
+
+unchecked_from!(
+    OperationBuilder => Operation.operation,
+    ParameterBuilder => Parameter.parameter,
+    RequestBodyBuilder => RequestBody.body,
+    ResponseBuilder => Response.response,
+    HeaderBuilder => Header.header,
+    MediaTypeBuilder => MediaType.media,
+    SchemaBuilder => Schema.schema,
+    LinkBuilder => Link.link
+);
+
+/// Builder of a [`Spec`].
+#[derive(Clone, Debug)]
+pub struct SpecBuilder {
+    spec: Spec,
+}
+
+impl Spec {
+    /// Starts an OpenAPI 3.0.3 document with the given title and `info.version`.
+    pub fn builder(title: impl Into<Str>, version: impl Into<Str>) -> SpecBuilder {
+        SpecBuilder {
+            spec: Spec {
+                openapi: "3.0.3".into(),
+                info: Info {
+                    title: title.into(),
+                    version: version.into(),
+                    ..Info::default()
+                },
+                ..Spec::default()
+            },
+        }
+    }
+}
+
+impl SpecBuilder {
+    pub fn description(mut self, description: impl Into<Str>) -> Self {
+        self.spec.info.description = description.into();
+        self
+    }
+
+    pub fn server(mut self, url: impl Into<Str>) -> Self {
+        self.spec.servers.push(Server {
+            url: url.into(),
+            ..Server::default()
+        });
+        self
+    }
+
+    pub fn tag(mut self, name: impl Into<Str>, description: impl Into<Str>) -> Self {
+        self.spec.tags.push(Tag {
+            name: name.into(),
+            description: description.into(),
+            ..Tag::default()
+        });
+        self
+    }
+
+    /// Requires the security scheme `name`, with `scopes`, for every operation.
+    pub fn security<S: Into<Str>>(
+        mut self,
+        name: impl Into<Str>,
+        scopes: impl IntoIterator<Item = S>,
+    ) -> Self {
+        let scopes = scopes.into_iter().map(Into::into).collect();
+        self.spec
+            .security
+            .push(IndexMap::from([(name.into(), scopes)]));
+        self
+    }
+
+    /// Starts building the item of `path`, keeping what was already built for it.
+    pub fn path(self, path: impl Into<Str>) -> PathItemBuilder {
+        let path = path.into();
+        let item = self.spec.paths.get(&path).cloned().unwrap_or_default();
+        PathItemBuilder {
+            spec: self,
+            path,
+            item,
+        }
+    }
+
+    /// Adds the component schema `name`.
+    pub fn schema(mut self, name: impl Into<Str>, schema: impl Into<Schema>) -> Self {
+        add_component(&mut self.spec, name.into(), schema.into());
+        self
+    }
+
+    /// Adds the component response `name`.
+    pub fn response(mut self, name: impl Into<Str>, response: impl Into<Response>) -> Self {
+        add_component(&mut self.spec, name.into(), response.into());
+        self
+    }
+
+    /// Adds the component parameter `name`.
+    pub fn parameter(mut self, name: impl Into<Str>, parameter: impl Into<Parameter>) -> Self {
+        add_component(&mut self.spec, name.into(), parameter.into());
+        self
+    }
+
+    /// Adds the component request body `name`.
+    pub fn request_body(mut self, name: impl Into<Str>, body: impl Into<RequestBody>) -> Self {
+        add_component(&mut self.spec, name.into(), body.into());
+        self
+    }
+
+    /// Adds the component header `name`.
+    pub fn header(mut self, name: impl Into<Str>, header: impl Into<Header>) -> Self {
+        add_component(&mut self.spec, name.into(), header.into());
+        self
+    }
+
+    /// Adds the component link `name`.
+    pub fn link(mut self, name: impl Into<Str>, link: impl Into<Link>) -> Self {
+        add_component(&mut self.spec, name.into(), link.into());
+        self
+    }
+
+    /// Adds the security scheme `name`.
+    pub fn security_scheme(mut self, name: impl Into<Str>, scheme: SecurityScheme) -> Self {
+        add_component(&mut self.spec, name.into(), scheme);
+        self
+    }
+
+    /// Returns the specification, unless it violates rules of [`Spec::validate`].
+    pub fn build(self) -> Result<Spec, Vec<Violation>> {
+        let violations = self.spec.validate();
+        checked(self.spec, violations)
+    }
+}
+
+/// Builder of a [`PathItem`], part of a [`SpecBuilder`].
+#[derive(Clone, Debug)]
+pub struct PathItemBuilder {
+    spec: SpecBuilder,
+    path: Str,
+    item: PathItem,
+}
+
+macro_rules! operations {
+    ($($method:ident),*) => {
+        $(
+            pub fn $method(mut self, operation: impl Into<Operation>) -> Self {
+                self.item.$method = Some(operation.into());
+                self
+            }
+        )*
+    };
+}
+
+impl PathItemBuilder {
+    operations!(get, put, post, delete, options, head, patch, trace);
+
+    pub fn summary(mut self, summary: impl Into<Str>) -> Self {
+        self.item.summary = summary.into();
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<Str>) -> Self {
+        self.item.description = description.into();
+        self
+    }
+
+    /// Adds a parameter shared by the operations of the path.
+    pub fn parameter(mut self, parameter: impl Into<Parameter>) -> Self {
+        let parameter = ObjectOrReference::Object(parameter.into());
+        self.item.parameters.push(parameter);
+        self
+    }
+
+    /// Adds a reference to the component parameter `name`, shared by the operations of the path.
+    pub fn parameter_ref(mut self, name: &str) -> Self {
+        self.item.parameters.push(reference(name));
+        self
+    }
+
+    /// Finishes this path, and starts building the item of `path`.
+    pub fn path(self, path: impl Into<Str>) -> PathItemBuilder {
+        self.end().path(path)
+    }
+
+    /// Finishes this path, to keep building the specification.
+    pub fn end(self) -> SpecBuilder {
+        let mut spec = self.spec;
+        spec.spec.paths.insert(self.path, self.item);
+        spec
+    }
+
+    /// Finishes this path and builds the specification, as [`SpecBuilder::build`] does.
+    pub fn build(self) -> Result<Spec, Vec<Violation>> {
+        self.end().build()
+    }
+}
+
+/// Builder of an [`Operation`].
+#[derive(Clone, Debug, Default)]
+pub struct OperationBuilder {
+    operation: Operation,
+}
+
+impl Operation {
+    pub fn builder() -> OperationBuilder {
+        OperationBuilder::default()
+    }
+}
+
+impl OperationBuilder {
+    pub fn operation_id(mut self, id: impl Into<Str>) -> Self {
+        self.operation.operation_id = id.into();
+        self
+    }
+
+    pub fn summary(mut self, summary: impl Into<Str>) -> Self {
+        self.operation.summary = summary.into();
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<Str>) -> Self {
+        self.operation.description = description.into();
+        self
+    }
+
+    pub fn tag(mut self, tag: impl Into<Str>) -> Self {
+        self.operation.tags.push(tag.into());
+        self
+    }
+
+    pub fn deprecated(mut self) -> Self {
+        self.operation.deprecated = Some(true);
+        self
+    }
+
+    pub fn parameter(mut self, parameter: impl Into<Parameter>) -> Self {
+        let parameter = ObjectOrReference::Object(parameter.into());
+        self.operation.parameters.push(parameter);
+        self
+    }
+
+    /// Adds a reference to the component parameter `name`.
+    pub fn parameter_ref(mut self, name: &str) -> Self {
+        self.operation.parameters.push(reference(name));
+        self
+    }
+
+    pub fn request_body(mut self, body: impl Into<RequestBody>) -> Self {
+        self.operation.request_body = Some(ObjectOrReference::Object(body.into()));
+        self
+    }
+
+    /// Uses the component request body `name`.
+    pub fn request_body_ref(mut self, name: &str) -> Self {
+        self.operation.request_body = Some(reference(name));
+        self
+    }
+
+    /// Sets the response for the HTTP status code `status`.
+    pub fn response(mut self, status: u16, response: impl Into<Response>) -> Self {
+        let response = ObjectOrReference::Object(response.into());
+        self.operation
+            .responses
+            .insert(status.to_string().into(), response);
+        self
+    }
+
+    /// Sets the response for the HTTP status code `status` to the component response `name`.
+    pub fn response_ref(mut self, status: u16, name: &str) -> Self {
+        self.operation
+            .responses
+            .insert(status.to_string().into(), reference(name));
+        self
+    }
+
+    /// Sets the response for the status codes without a response of their own.
+    pub fn default_response(mut self, response: impl Into<Response>) -> Self {
+        let response = ObjectOrReference::Object(response.into());
+        self.operation.responses.insert("default".into(), response);
+        self
+    }
+
+    /// Requires the security scheme `name`, with `scopes`, overriding that of the specification.
+    pub fn security<S: Into<Str>>(
+        mut self,
+        name: impl Into<Str>,
+        scopes: impl IntoIterator<Item = S>,
+    ) -> Self {
+        let scopes = scopes.into_iter().map(Into::into).collect();
+        let requirement = IndexMap::from([(name.into(), scopes)]);
+        self.operation
+            .security
+            .get_or_insert_with(Vec::new)
+            .push(requirement);
+        self
+    }
+
+    /// Returns the operation, unless it violates rules of [`Spec::validate`].
+    ///
+    /// References are not checked, and neither are the parameters of the path template.
+    pub fn build(self) -> Result<Operation, Vec<Violation>> {
+        let (spec, item) = (Spec::default(), PathItem::default());
+        let violations = validate_with(&spec, |v| v.operation(None, &item, &self.operation));
+        checked(self.operation, violations)
+    }
+}
+
+/// Builder of a [`Parameter`].
+#[derive(Clone, Debug)]
+pub struct ParameterBuilder {
+    parameter: Parameter,
+}
+
+impl Parameter {
+    fn located(name: Str, location: Location, required: Option<bool>) -> ParameterBuilder {
+        ParameterBuilder {
+            parameter: Parameter {
+                name,
+                location,
+                required,
+                ..Parameter::default()
+            },
+        }
+    }
+
+    /// Starts a required path parameter.
+    pub fn path(name: impl Into<Str>) -> ParameterBuilder {
+        Parameter::located(name.into(), Location::Path, Some(true))
+    }
+
+    pub fn query(name: impl Into<Str>) -> ParameterBuilder {
+        Parameter::located(name.into(), Location::Query, None)
+    }
+
+    pub fn header(name: impl Into<Str>) -> ParameterBuilder {
+        Parameter::located(name.into(), Location::Header, None)
+    }
+
+    pub fn cookie(name: impl Into<Str>) -> ParameterBuilder {
+        Parameter::located(name.into(), Location::Cookie, None)
+    }
+}
+
+impl ParameterBuilder {
+    pub fn description(mut self, description: impl Into<Str>) -> Self {
+        self.parameter.description = description.into();
+        self
+    }
+
+    pub fn required(mut self, required: bool) -> Self {
+        self.parameter.required = Some(required);
+        self
+    }
+
+    pub fn deprecated(mut self) -> Self {
+        self.parameter.deprecated = Some(true);
+        self
+    }
+
+    pub fn style(mut self, style: ParameterStyle) -> Self {
+        self.parameter.style = Some(style);
+        self
+    }
+
+    pub fn explode(mut self, explode: bool) -> Self {
+        self.parameter.explode = Some(explode);
+        self
+    }
+
+    /// Sets the schema of the parameter, replacing its content.
+    pub fn schema(mut self, schema: impl Into<ComponentOrInlineSchema>) -> Self {
+        let schema = schema.into();
+        self.parameter.representation = Some(ParameterRepresentation::Simple { schema });
+        self
+    }
+
+    /// Sets the media type of the parameter, replacing its schema.
+    pub fn content(mut self, media_type: impl Into<Str>, media: impl Into<MediaType>) -> Self {
+        let content = IndexMap::from([(media_type.into(), media.into())]);
+        self.parameter.representation = Some(ParameterRepresentation::Content { content });
+        self
+    }
+
+    /// Returns the parameter, unless it violates rules of [`Spec::validate`], such as when it
+    /// has neither a schema nor a content.
+    pub fn build(self) -> Result<Parameter, Vec<Violation>> {
+        let spec = Spec::default();
+        let violations = validate_with(&spec, |v| v.parameter(&self.parameter));
+        checked(self.parameter, violations)
+    }
+}
+
+/// Builder of a [`RequestBody`].
+#[derive(Clone, Debug, Default)]
+pub struct RequestBodyBuilder {
+    body: RequestBody,
+}
+
+impl RequestBody {
+    pub fn builder() -> RequestBodyBuilder {
+        RequestBodyBuilder::default()
+    }
+}
+
+impl RequestBodyBuilder {
+    pub fn description(mut self, description: impl Into<Str>) -> Self {
+        self.body.description = description.into();
+        self
+    }
+
+    pub fn required(mut self, required: bool) -> Self {
+        self.body.required = Some(required);
+        self
+    }
+
+    pub fn content(mut self, media_type: impl Into<Str>, media: impl Into<MediaType>) -> Self {
+        self.body.content.insert(media_type.into(), media.into());
+        self
+    }
+
+    /// Adds `application/json` content with `schema`.
+    pub fn json(self, schema: impl Into<ComponentOrInlineSchema>) -> Self {
+        self.content(JSON, MediaType::builder().schema(schema))
+    }
+
+    /// Returns the request body, unless it violates rules of [`Spec::validate`].
+    pub fn build(self) -> Result<RequestBody, Vec<Violation>> {
+        let spec = Spec::default();
+        let violations = validate_with(&spec, |v| v.request_body(&self.body));
+        checked(self.body, violations)
+    }
+}
+
+/// Builder of a [`Response`].
+#[derive(Clone, Debug)]
+pub struct ResponseBuilder {
+    response: Response,
+}
+
+impl Response {
+    pub fn builder(description: impl Into<Str>) -> ResponseBuilder {
+        ResponseBuilder {
+            response: Response {
+                description: description.into(),
+                ..Response::default()
+            },
+        }
+    }
+}
+
+impl ResponseBuilder {
+    pub fn header(mut self, name: impl Into<Str>, header: impl Into<Header>) -> Self {
+        let header = ObjectOrReference::Object(header.into());
+        self.response.headers.insert(name.into(), header);
+        self
+    }
+
+    pub fn content(mut self, media_type: impl Into<Str>, media: impl Into<MediaType>) -> Self {
+        self.response
+            .content
+            .insert(media_type.into(), media.into());
+        self
+    }
+
+    /// Adds `application/json` content with `schema`.
+    pub fn json(self, schema: impl Into<ComponentOrInlineSchema>) -> Self {
+        self.content(JSON, MediaType::builder().schema(schema))
+    }
+
+    pub fn link(mut self, name: impl Into<Str>, link: impl Into<Link>) -> Self {
+        let link = ObjectOrReference::Object(link.into());
+        self.response.links.insert(name.into(), link);
+        self
+    }
+
+    /// Returns the response, unless it violates rules of [`Spec::validate`].
+    pub fn build(self) -> Result<Response, Vec<Violation>> {
+        let spec = Spec::default();
+        let violations = validate_with(&spec, |v| v.response(&self.response));
+        checked(self.response, violations)
+    }
+}
+
+/// Builder of a [`Header`].
+#[derive(Clone, Debug, Default)]
+pub struct HeaderBuilder {
+    header: Header,
+}
+
+impl Header {
+    pub fn builder() -> HeaderBuilder {
+        HeaderBuilder::default()
+    }
+}
+
+impl HeaderBuilder {
+    pub fn description(mut self, description: impl Into<Str>) -> Self {
+        self.header.description = description.into();
+        self
+    }
+
+    pub fn required(mut self, required: bool) -> Self {
+        self.header.required = Some(required);
+        self
+    }
+
+    pub fn schema(mut self, schema: impl Into<ComponentOrInlineSchema>) -> Self {
+        self.header.schema = Some(schema.into());
+        self
+    }
+
+    /// Returns the header, unless it violates rules of [`Spec::validate`].
+    pub fn build(self) -> Result<Header, Vec<Violation>> {
+        let spec = Spec::default();
+        let violations = validate_with(&spec, |v| v.header(&self.header));
+        checked(self.header, violations)
+    }
+}
+
+/// Builder of a [`MediaType`].
+#[derive(Clone, Debug, Default)]
+pub struct MediaTypeBuilder {
+    media: MediaType,
+}
+
+impl MediaType {
+    pub fn builder() -> MediaTypeBuilder {
+        MediaTypeBuilder::default()
+    }
+}
+
+impl MediaTypeBuilder {
+    pub fn schema(mut self, schema: impl Into<ComponentOrInlineSchema>) -> Self {
+        self.media.schema = Some(schema.into());
+        self
+    }
+
+    pub fn example(mut self, example: impl Into<Value>) -> Self {
+        let example = example.into();
+        self.media.examples = Some(MediaTypeExample::Example { example });
+        self
+    }
+
+    /// Returns the media type, unless it violates rules of [`Spec::validate`].
+    pub fn build(self) -> Result<MediaType, Vec<Violation>> {
+        let spec = Spec::default();
+        let violations = validate_with(&spec, |v| v.media_type(&self.media));
+        checked(self.media, violations)
+    }
+}
+
+/// Builder of a [`Schema`].
+#[derive(Clone, Debug, Default)]
+pub struct SchemaBuilder {
+    schema: Schema,
+}
+
+impl From<Schema> for ComponentOrInlineSchema {
+    fn from(schema: Schema) -> Self {
+        ComponentOrInlineSchema::Inline(schema)
+    }
+}
+
+impl From<SchemaBuilder> for ComponentOrInlineSchema {
+    fn from(builder: SchemaBuilder) -> Self {
+        ComponentOrInlineSchema::Inline(builder.schema)
+    }
+}
+
+impl Schema {
+    /// Starts a schema without a type.
+    pub fn builder() -> SchemaBuilder {
+        SchemaBuilder::default()
+    }
+
+    fn typed(schema_type: Type) -> SchemaBuilder {
+        SchemaBuilder {
+            schema: Schema {
+                schema_type: Some(schema_type),
+                ..Schema::default()
+            },
+        }
+    }
+
+    pub fn string() -> SchemaBuilder {
+        Schema::typed(Type::String)
+    }
+
+    pub fn integer() -> SchemaBuilder {
+        Schema::typed(Type::Integer)
+    }
+
+    pub fn number() -> SchemaBuilder {
+        Schema::typed(Type::Number)
+    }
+
+    pub fn boolean() -> SchemaBuilder {
+        Schema::typed(Type::Boolean)
+    }
+
+    pub fn object() -> SchemaBuilder {
+        Schema::typed(Type::Object)
+    }
+
+    /// Starts an array schema of `items`.
+    pub fn array_of(items: impl Into<ComponentOrInlineSchema>) -> SchemaBuilder {
+        let mut builder = Schema::typed(Type::Array);
+        builder.schema.items = Some(Box::new(items.into()));
+        builder
+    }
+
+    /// A reference to the component schema `name`.
+    pub fn component(name: impl Into<Str>) -> ComponentOrInlineSchema {
+        ComponentOrInlineSchema::Component { name: name.into() }
+    }
+}
+
+/// Defines setters of optional fields of a [`Schema`].
+macro_rules! schema_setters {
+    ($($field:ident: $ty:ty),*) => {
+        $(
+            pub fn $field(mut self, $field: impl Into<$ty>) -> Self {
+                self.schema.$field = Some($field.into());
+                self
+            }
+        )*
+    };
+}
+
+impl SchemaBuilder {
+    schema_setters!(
+        nullable: bool,
+        read_only: bool,
+        write_only: bool,
+        example: Value,
+        const_value: Value,
+        multiple_of: Value,
+        minimum: Value,
+        exclusive_minimum: Value,
+        maximum: Value,
+        exclusive_maximum: Value,
+        min_length: usize,
+        max_length: usize,
+        min_items: usize,
+        max_items: usize,
+        unique_items: bool,
+        min_properties: usize,
+        max_properties: usize
+    );
+
+    /// Sets the `default` value.
+    pub fn default_value(mut self, value: impl Into<Value>) -> Self {
+        self.schema.default = Some(value.into());
+        self
+    }
+
+    pub fn title(mut self, title: impl Into<Str>) -> Self {
+        self.schema.title = title.into();
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<Str>) -> Self {
+        self.schema.description = description.into();
+        self
+    }
+
+    pub fn format(mut self, format: impl Into<Str>) -> Self {
+        self.schema.format = format.into();
+        self
+    }
+
+    pub fn pattern(mut self, pattern: impl Into<Str>) -> Self {
+        self.schema.pattern = pattern.into();
+        self
+    }
+
+    pub fn enum_values<S: Into<Str>>(mut self, values: impl IntoIterator<Item = S>) -> Self {
+        self.schema.enum_values = values.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn property(
+        mut self,
+        name: impl Into<Str>,
+        schema: impl Into<ComponentOrInlineSchema>,
+    ) -> Self {
+        self.schema.properties.insert(name.into(), schema.into());
+        self
+    }
+
+    /// Adds the property `name`, and makes it required.
+    pub fn required_property(
+        self,
+        name: impl Into<Str>,
+        schema: impl Into<ComponentOrInlineSchema>,
+    ) -> Self {
+        let name = name.into();
+        let mut builder = self.property(name.clone(), schema);
+        if !builder.schema.required.contains(&name) {
+            builder.schema.required.push(name);
+        }
+        builder
+    }
+
+    pub fn additional_properties(mut self, schema: impl Into<ComponentOrInlineSchema>) -> Self {
+        self.schema.additional_properties = Some(Box::new(schema.into()));
+        self
+    }
+
+    pub fn all_of(mut self, schema: impl Into<ComponentOrInlineSchema>) -> Self {
+        self.schema.all_of.push(schema.into());
+        self
+    }
+
+    pub fn one_of(mut self, schema: impl Into<ComponentOrInlineSchema>) -> Self {
+        self.schema.one_of.push(schema.into());
+        self
+    }
+
+    pub fn any_of(mut self, schema: impl Into<ComponentOrInlineSchema>) -> Self {
+        self.schema.any_of.push(schema.into());
+        self
+    }
+
+    /// Returns the schema, unless it violates rules of [`Spec::validate`].
+    pub fn build(self) -> Result<Schema, Vec<Violation>> {
+        let spec = Spec::default();
+        let violations = validate_with(&spec, |v| v.schema(&self.schema));
+        checked(self.schema, violations)
+    }
+}
+
+/// Builder of a [`Link`].
+#[derive(Clone, Debug)]
+pub struct LinkBuilder {
+    link: Link,
+}
+
+impl Link {
+    fn with_operation(operation: LinkOperation) -> LinkBuilder {
+        LinkBuilder {
+            link: Link {
+                operation,
+                parameters: IndexMap::new(),
+                request_body: None,
+                description: Str::default(),
+                server: None,
+                extensions: Default::default(),
+            },
+        }
+    }
+
+    /// Starts a link to the operation with the id `operation_id`.
+    pub fn to_operation(operation_id: impl Into<Str>) -> LinkBuilder {
+        let operation_id = operation_id.into();
+        Link::with_operation(LinkOperation::Id { operation_id })
+    }
+
+    /// Starts a link to the operation at `operation_ref`, such as
+    /// `#/paths/~1pets~1{id}/get`.
+    pub fn to_operation_ref(operation_ref: impl Into<Str>) -> LinkBuilder {
+        let operation_ref = operation_ref.into();
+        Link::with_operation(LinkOperation::Ref { operation_ref })
+    }
+}
+
+impl LinkBuilder {
+    /// Passes the value of the runtime `expression`, such as `$response.body#/id`, as the
+    /// parameter `name`.
+    pub fn parameter(mut self, name: impl Into<Str>, expression: impl Into<Str>) -> Self {
+        let expression = RuntimeExpressionOrValue::RuntimeExpression(expression.into());
+        self.link.parameters.insert(name.into(), expression);
+        self
+    }
+
+    /// Passes the constant `value` as the parameter `name`.
+    pub fn parameter_value(mut self, name: impl Into<Str>, value: impl Into<Value>) -> Self {
+        let value = RuntimeExpressionOrValue::LiteralValue(value.into());
+        self.link.parameters.insert(name.into(), value);
+        self
+    }
+
+    pub fn request_body(mut self, body: RuntimeExpressionOrValue) -> Self {
+        self.link.request_body = Some(body);
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<Str>) -> Self {
+        self.link.description = description.into();
+        self
+    }
+
+    pub fn server(mut self, url: impl Into<Str>) -> Self {
+        self.link.server = Some(Server {
+            url: url.into(),
+            ..Server::default()
+        });
+        self
+    }
+
+    /// Returns the link, which no rule of [`Spec::validate`] applies to.
+    pub fn build(self) -> Link {
+        self.link
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v3_0::Rule;
+    use serde_json::json;
+
+    #[test]
+    fn test_build() {
+        let spec = Spec::builder("Pets", "1.0.0")
+            .server("https://pets.example.com")
+            .schema(
+                "Pet",
+                Schema::object()
+                    .required_property("id", Schema::string().format("uuid"))
+                    .property("age", Schema::integer().minimum(0))
+                    .additional_properties(Schema::boolean()),
+            )
+            .path("/pets")
+            .post(
+                Operation::builder()
+                    .operation_id("createPet")
+                    .request_body(
+                        RequestBody::builder()
+                            .required(true)
+                            .json(Schema::component("Pet")),
+                    )
+                    .response(
+                        201,
+                        Response::builder("Created")
+                            .header("Location", Header::builder().schema(Schema::string()))
+                            .link(
+                                "GetPet",
+                                Link::to_operation("getPet").parameter("id", "$response.body#/id"),
+                            ),
+                    ),
+            )
+            .path("/pets/{id}")
+            .parameter_ref("Id")
+            .get(Operation::builder().operation_id("getPet").response(
+                200,
+                Response::builder("A pet").json(Schema::component("Pet")),
+            ))
+            .end()
+            .parameter("Id", Parameter::path("id").schema(Schema::string()))
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&spec).unwrap(),
+            json!({
+              "openapi": "3.0.3",
+              "info": {"title": "Pets", "version": "1.0.0"},
+              "servers": [{"url": "https://pets.example.com"}],
+              "paths": {
+                "/pets": {
+                  "post": {
+                    "operationId": "createPet",
+                    "requestBody": {
+                      "content": {
+                        "application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}
+                      },
+                      "required": true
+                    },
+                    "responses": {
+                      "201": {
+                        "description": "Created",
+                        "headers": {"Location": {"schema": {"type": "string"}}},
+                        "links": {
+                          "GetPet": {
+                            "operationId": "getPet",
+                            "parameters": {"id": "$response.body#/id"}
+                          }
+                        }
+                      }
+                    }
+                  }
+                },
+                "/pets/{id}": {
+                  "get": {
+                    "operationId": "getPet",
+                    "responses": {
+                      "200": {
+                        "description": "A pet",
+                        "content": {
+                          "application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}
+                        }
+                      }
+                    }
+                  },
+                  "parameters": [{"$ref": "#/components/parameters/Id"}]
+                }
+              },
+              "components": {
+                "schemas": {
+                  "Pet": {
+                    "type": "object",
+                    "properties": {
+                      "id": {"type": "string", "format": "uuid"},
+                      "age": {"type": "integer", "minimum": 0}
+                    },
+                    "additionalProperties": {"type": "boolean"},
+                    "required": ["id"]
+                  }
+                },
+                "parameters": {
+                  "Id": {"name": "id", "in": "path", "required": true, "schema": {"type": "string"}}
+                }
+              }
+            })
+        );
+    }
+
+    #[test]
+    fn test_build_errors() {
+        let rules = |violations: Vec<Violation>| -> Vec<(Rule, String)> {
+            violations
+                .into_iter()
+                .map(|v| (v.rule, v.pointer))
+                .collect()
+        };
+
+        let error = Parameter::query("q").build().unwrap_err();
+        assert_eq!(
+            rules(error.clone()),
+            [(Rule::ParameterSchema, String::new())]
+        );
+        assert_eq!(
+            error[0].message,
+            "parameter `q` has neither `schema` nor `content`"
+        );
+
+        let error = Operation::builder()
+            .parameter(Parameter::query("q"))
+            .response(
+                200,
+                Response::builder("Tags").json(
+                    Schema::builder()
+                        .one_of(Schema::array_of(Schema::string()))
+                        .one_of(Schema::object().property("tags", Schema::typed(Type::Array))),
+                ),
+            )
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            rules(error),
+            [
+                (Rule::ParameterSchema, "/parameters/0".to_string()),
+                (
+                    Rule::ArrayItems,
+                    "/responses/200/content/application~1json/schema/oneOf/1/properties/tags"
+                        .to_string()
+                ),
+            ]
+        );
+
+        let error = Spec::builder("Pets", "1.0.0")
+            .path("/pets/{id}")
+            .get(Operation::builder().response(200, Response::builder("A pet")))
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            rules(error),
+            [(Rule::PathParameter, "/paths/~1pets~1{id}/get".to_string())]
+        );
+    }
+}
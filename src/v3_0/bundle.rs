@@ -0,0 +1,631 @@
+//! Bundling of specifications split across several files into a single document.
+
+use crate::{
+    error::{BundleError, BundleErrorKind},
+    json_pointer, load_path,
+    parse::{self, diagnose_leaf, Tree},
+    v3_0::{Spec, COMPONENTS_REF_PREFIX},
+    Result, Str,
+};
+use indexmap::IndexMap;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    mem,
+    path::{Path, PathBuf},
+};
+
+/// Loads the specification stored at `path` along with every file it references, directly or
+/// not, and returns it as a single self-contained document.
+///
+/// Objects referenced from other files are added to the components of the document, and the
+/// references are rewritten to point to them. A hoisted component is named after the last token
+/// of the fragment of its reference, or after the file name if the reference has no fragment.
+/// If the name is already taken, `_2`, `_3`, ... is appended to it, the first reference
+/// encountered in document order getting the first free name. Objects which cannot be
+/// components, such as path items, are inlined instead.
+///
+/// Local references of the root document are left untouched. References to remote documents
+/// are reported as errors.
+pub fn bundle<P: AsRef<Path>>(path: P) -> Result<Spec> {
+    let root = path.as_ref().canonicalize()?;
+    let (mut document, format): (Value, _) = load_path(&root)?;
+
+    let mut taken = HashSet::new();
+    if let Some(Value::Object(components)) = document.get("components") {
+        for (kind, objects) in components {
+            if let Value::Object(objects) = objects {
+                taken.extend(objects.keys().map(|name| (kind.clone(), name.clone())));
+            }
+        }
+    }
+    let mut bundler = Bundler {
+        root: root.clone(),
+        documents: HashMap::new(),
+        names: HashMap::new(),
+        taken,
+        hoisted: IndexMap::new(),
+        inlining: Vec::new(),
+    };
+    bundler.walk(&mut document, Node::Document, &root)?;
+
+    if let (false, Value::Object(document)) = (bundler.hoisted.is_empty(), &mut document) {
+        let components = document
+            .entry("components".to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+        if let Value::Object(components) = components {
+            for (kind, objects) in bundler.hoisted {
+                let entry = components
+                    .entry(kind.to_string())
+                    .or_insert_with(|| Value::Object(Map::new()));
+                if let Value::Object(entry) = entry {
+                    entry.extend(objects);
+                }
+            }
+        }
+    }
+    Ok(parse::from_value(&document, format)?)
+}
+
+/// A JSON value whose objects keep the order of their fields, so that bundled documents do as
+/// well, and that hoisted components are named in document order.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+enum Value {
+    Null,
+    Bool(bool),
+    Number(serde_json::Number),
+    String(String),
+    Array(Vec<Value>),
+    Object(Map),
+}
+
+type Map = IndexMap<String, Value>;
+
+diagnose_leaf!(Value);
+
+impl Value {
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    /// Returns the node at the JSON pointer `pointer`, if there is one.
+    fn pointer(&self, pointer: &str) -> Option<&Value> {
+        let mut value = self;
+        for token in json_pointer::split(pointer)? {
+            value = match value {
+                Value::Object(map) => map.get(&*token)?,
+                Value::Array(items) => items.get(token.parse::<usize>().ok()?)?,
+                _ => return None,
+            };
+        }
+        Some(value)
+    }
+}
+
+impl Tree for Value {
+    fn get(&self, key: &str) -> Option<&Self> {
+        match self {
+            Value::Object(map) => map.get(key),
+            _ => None,
+        }
+    }
+
+    fn entries(&self) -> Vec<(String, &Self)> {
+        match self {
+            Value::Object(map) => map.iter().map(|(k, v)| (k.clone(), v)).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn items(&self) -> &[Self] {
+        match self {
+            Value::Array(items) => items,
+            _ => &[],
+        }
+    }
+
+    fn is_null(&self) -> bool {
+        *self == Value::Null
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        self.as_str()
+    }
+
+    /// Deserializes this sub-tree from its text, as `serde_json::Value` would not keep the order
+    /// of its objects.
+    fn deserialize<T: DeserializeOwned>(&self) -> std::result::Result<T, String> {
+        serde_json::to_string(self)
+            .and_then(|json| serde_json::from_str(&json))
+            .map_err(|err| err.to_string())
+    }
+}
+
+/// Kind of the objects of a document which may be references.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Object {
+    Schema,
+    Response,
+    Parameter,
+    Example,
+    RequestBody,
+    Header,
+    SecurityScheme,
+    Link,
+    Callback,
+    PathItem,
+    Operation,
+    MediaType,
+    Encoding,
+}
+
+/// Objects which can be components, with the name of the map of `Components` holding them.
+const COMPONENTS: [(&str, Object); 9] = [
+    ("schemas", Object::Schema),
+    ("responses", Object::Response),
+    ("parameters", Object::Parameter),
+    ("examples", Object::Example),
+    ("requestBodies", Object::RequestBody),
+    ("headers", Object::Header),
+    ("securitySchemes", Object::SecurityScheme),
+    ("links", Object::Link),
+    ("callbacks", Object::Callback),
+];
+
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+impl Object {
+    /// Name of the map of components holding objects of this kind, if they can be components.
+    fn kind(self) -> Option<&'static str> {
+        COMPONENTS
+            .iter()
+            .find(|(_, object)| *object == self)
+            .map(|(kind, _)| *kind)
+    }
+}
+
+/// Node of a document which may contain references.
+#[derive(Copy, Clone, Debug)]
+enum Node {
+    Document,
+    Components,
+    Object(Object),
+    /// Map of objects, ignoring extensions.
+    Map(Object),
+    List(Object),
+}
+
+impl Node {
+    /// Returns the node of the field `key` of this object node, if it may contain references.
+    fn field(self, key: &str) -> Option<Node> {
+        use self::Object::*;
+
+        let object = match self {
+            Node::Document => {
+                return match key {
+                    "paths" => Some(Node::Map(PathItem)),
+                    "components" => Some(Node::Components),
+                    _ => None,
+                }
+            }
+            Node::Components => {
+                return COMPONENTS
+                    .iter()
+                    .find(|(kind, _)| *kind == key)
+                    .map(|(_, object)| Node::Map(*object))
+            }
+            Node::Object(object) => object,
+            Node::Map(_) | Node::List(_) => return None,
+        };
+        let node = match (object, key) {
+            (Schema, "properties") => Node::Map(Schema),
+            (Schema, "items") | (Schema, "additionalProperties") | (Schema, "not") => {
+                Node::Object(Schema)
+            }
+            (Schema, "allOf") | (Schema, "oneOf") | (Schema, "anyOf") => Node::List(Schema),
+            (Parameter, "schema") | (Header, "schema") | (MediaType, "schema") => {
+                Node::Object(Schema)
+            }
+            (Parameter, "content")
+            | (Header, "content")
+            | (RequestBody, "content")
+            | (Response, "content") => Node::Map(MediaType),
+            (Parameter, "examples") | (Header, "examples") | (MediaType, "examples") => {
+                Node::Map(Example)
+            }
+            (MediaType, "encoding") => Node::Map(Encoding),
+            (Response, "headers") | (Encoding, "headers") => Node::Map(Header),
+            (Response, "links") => Node::Map(Link),
+            (Callback, expression) if !expression.starts_with("x-") => Node::Object(PathItem),
+            (PathItem, "parameters") | (Operation, "parameters") => Node::List(Parameter),
+            (PathItem, method) if METHODS.contains(&method) => Node::Object(Operation),
+            (Operation, "requestBody") => Node::Object(RequestBody),
+            (Operation, "responses") => Node::Map(Response),
+            (Operation, "callbacks") => Node::Map(Callback),
+            _ => return None,
+        };
+        Some(node)
+    }
+}
+
+struct Bundler {
+    /// Canonical path of the root document.
+    root: PathBuf,
+    /// Referenced documents, by canonical path.
+    documents: HashMap<PathBuf, Value>,
+    /// Names of the hoisted components, by kind, document and fragment.
+    names: HashMap<(&'static str, PathBuf, String), Str>,
+    /// Names of components in use, by kind.
+    taken: HashSet<(String, String)>,
+    hoisted: IndexMap<&'static str, Map>,
+    /// Documents and fragments being inlined, to detect cycles.
+    inlining: Vec<(PathBuf, String)>,
+}
+
+impl Bundler {
+    /// Rewrites the references found in `value`, which is a `node` of the document at `base`.
+    fn walk(&mut self, value: &mut Value, node: Node, base: &Path) -> Result<()> {
+        match (node, &mut *value) {
+            (Node::Map(object), Value::Object(map)) => {
+                for (key, child) in map.iter_mut() {
+                    if !key.starts_with("x-") {
+                        self.walk(child, Node::Object(object), base)?;
+                    }
+                }
+                return Ok(());
+            }
+            (Node::List(object), Value::Array(items)) => {
+                for item in items {
+                    self.walk(item, Node::Object(object), base)?;
+                }
+                return Ok(());
+            }
+            _ => {}
+        }
+        if let Node::Object(object) = node {
+            if let Some(reference) = value.get("$ref").and_then(Value::as_str) {
+                let reference = reference.to_string();
+                return self.reference(value, &reference, object, base);
+            }
+        }
+        if let Value::Object(map) = value {
+            for (key, child) in map.iter_mut() {
+                if let Some(node) = node.field(key) {
+                    self.walk(child, node, base)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Rewrites `value`, an `object` holding `reference` in the document at `base`.
+    fn reference(
+        &mut self,
+        value: &mut Value,
+        reference: &str,
+        object: Object,
+        base: &Path,
+    ) -> Result<()> {
+        let error = |kind| BundleError {
+            path: base.to_path_buf(),
+            reference: reference.to_string(),
+            kind,
+        };
+        let (file, fragment) = match reference.find('#') {
+            Some(i) => (&reference[..i], &reference[i + 1..]),
+            None => (reference, ""),
+        };
+        if file.contains("://") {
+            return Err(error(BundleErrorKind::Remote).into());
+        }
+        let path = if file.is_empty() {
+            base.to_path_buf()
+        } else {
+            let dir = base.parent().unwrap_or_else(|| Path::new(""));
+            dir.join(file)
+                .canonicalize()
+                .map_err(|err| error(BundleErrorKind::Load(err.into())))?
+        };
+
+        if path == self.root {
+            if let Value::Object(map) = value {
+                map.insert("$ref".to_string(), Value::String(format!("#{}", fragment)));
+            }
+            return Ok(());
+        }
+        let kind = match object.kind() {
+            Some(kind) => kind,
+            None => return self.inline(value, object, path, fragment, error),
+        };
+        let key = (kind, path, fragment.to_string());
+        let name = match self.names.get(&key) {
+            Some(name) => name.clone(),
+            None => {
+                let path = &key.1;
+                let mut target = self.target(path, fragment).map_err(error)?;
+                let name = self.name(kind, path, fragment);
+                self.names.insert(key.clone(), name.clone());
+                // Reserves the place of the component, so that components are added in the
+                // order they are first referenced.
+                let hoisted = self.hoisted.entry(kind).or_default();
+                hoisted.insert(name.to_string(), Value::Null);
+                self.walk(&mut target, Node::Object(object), path)?;
+                if let Some(hoisted) = self.hoisted.get_mut(kind) {
+                    hoisted.insert(name.to_string(), target);
+                }
+                name
+            }
+        };
+        let reference = format!(
+            "{}{}/{}",
+            COMPONENTS_REF_PREFIX,
+            kind,
+            json_pointer::escape(&name)
+        );
+        *value = Value::Object(Map::from([("$ref".to_string(), Value::String(reference))]));
+        Ok(())
+    }
+
+    /// Replaces `value` with the object it refers to. Other fields of `value` override the
+    /// fields of the referenced object.
+    fn inline(
+        &mut self,
+        value: &mut Value,
+        object: Object,
+        path: PathBuf,
+        fragment: &str,
+        error: impl Fn(BundleErrorKind) -> BundleError,
+    ) -> Result<()> {
+        let key = (path.clone(), fragment.to_string());
+        if self.inlining.contains(&key) {
+            return Err(error(BundleErrorKind::Cycle).into());
+        }
+        let mut target = self.target(&path, fragment).map_err(&error)?;
+        self.inlining.push(key);
+        self.walk(&mut target, Node::Object(object), &path)?;
+        self.inlining.pop();
+
+        if let Value::Object(map) = value {
+            map.shift_remove("$ref");
+        }
+        match (value, target) {
+            (Value::Object(map), Value::Object(mut target)) => {
+                target.extend(mem::take(map));
+                *map = target;
+            }
+            (value, target) => *value = target,
+        }
+        Ok(())
+    }
+
+    /// Returns a copy of the node at `fragment` in the document at `path`.
+    fn target(
+        &mut self,
+        path: &Path,
+        fragment: &str,
+    ) -> std::result::Result<Value, BundleErrorKind> {
+        if !self.documents.contains_key(path) {
+            let (document, _) = load_path(path).map_err(BundleErrorKind::Load)?;
+            self.documents.insert(path.to_path_buf(), document);
+        }
+        self.documents[path]
+            .pointer(fragment)
+            .cloned()
+            .ok_or(BundleErrorKind::Dangling)
+    }
+
+    /// Picks a free name for the component of kind `kind` defined at `fragment` in `path`.
+    fn name(&mut self, kind: &str, path: &Path, fragment: &str) -> Str {
+        let token = json_pointer::split(fragment)
+            .and_then(|tokens| tokens.last().map(|token| token.to_string()))
+            .filter(|token| !token.is_empty());
+        let stem = || {
+            path.file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        };
+        // Component names may only contain these characters.
+        let base: String = token
+            .unwrap_or_else(stem)
+            .chars()
+            .map(|c| match c {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '-' | '_' => c,
+                _ => '_',
+            })
+            .collect();
+        let mut name = base.clone();
+        let mut suffix = 1;
+        while !self.taken.insert((kind.to_string(), name.clone())) {
+            suffix += 1;
+            name = format!("{}_{}", base, suffix);
+        }
+        name.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        error::Error,
+        v3_0::{ComponentOrInlineSchema, ObjectOrReference, Schema},
+    };
+    use std::fs;
+
+    /// Writes `files` to a new temporary directory, returning its path.
+    fn write(test: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("rweb-openapi-{}-{}", test, std::process::id()));
+        for (name, content) in files {
+            let path = dir.join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        dir
+    }
+
+    const ROOT: &str = r##"{
+      "openapi": "3.0.3",
+      "info": {"title": "Pets", "version": "1.0.0"},
+      "paths": {
+        "/pets": {"$ref": "paths/pets.json"}
+      },
+      "components": {
+        "schemas": {
+          "Error": {"type": "string"},
+          "Pet": {"type": "object"}
+        }
+      }
+    }"##;
+
+    const PETS: &str = r##"{
+      "get": {
+        "parameters": [{"$ref": "../common.json#/parameters/limit"}],
+        "responses": {
+          "200": {
+            "description": "Pets",
+            "content": {
+              "application/json": {
+                "schema": {
+                  "type": "array",
+                  "items": {"$ref": "../openapi.json#/components/schemas/Pet"}
+                }
+              }
+            }
+          },
+          "default": {
+            "description": "Error",
+            "content": {
+              "application/json": {"schema": {"$ref": "../common.json#/schemas/Error"}}
+            }
+          }
+        }
+      }
+    }"##;
+
+    const COMMON: &str = r##"{
+      "parameters": {
+        "limit": {
+          "name": "limit",
+          "in": "query",
+          "schema": {"$ref": "#/schemas/Error/properties/code"}
+        }
+      },
+      "schemas": {
+        "Error": {
+          "type": "object",
+          "properties": {
+            "code": {"type": "integer"},
+            "cause": {"$ref": "#/schemas/Error"}
+          }
+        }
+      }
+    }"##;
+
+    #[test]
+    fn test_bundle() {
+        let dir = write(
+            "bundle",
+            &[
+                ("openapi.json", ROOT),
+                ("paths/pets.json", PETS),
+                ("common.json", COMMON),
+            ],
+        );
+        let spec = bundle(dir.join("openapi.json"));
+        fs::remove_dir_all(&dir).unwrap();
+        let spec = spec.unwrap();
+
+        let get = spec.paths["/pets"].get.as_ref().unwrap();
+        assert_eq!(
+            get.parameters[0],
+            ObjectOrReference::Ref {
+                ref_path: "#/components/parameters/limit".into()
+            }
+        );
+        let components = spec.components.as_ref().unwrap();
+        let names: Vec<_> = components.schemas.keys().map(|name| &**name).collect();
+        assert_eq!(names, ["Error", "Pet", "code", "Error_2"]);
+
+        let error = components.get::<Schema>("Error_2").unwrap();
+        assert_eq!(
+            error.properties["cause"],
+            ComponentOrInlineSchema::Component {
+                name: "Error_2".into()
+            }
+        );
+        let response = spec.resolve(&get.responses["default"]).unwrap();
+        assert_eq!(
+            response.content["application/json"].schema,
+            Some(ComponentOrInlineSchema::Component {
+                name: "Error_2".into()
+            })
+        );
+        let response = spec.resolve(&get.responses["200"]).unwrap();
+        let items = response.content["application/json"]
+            .schema
+            .as_ref()
+            .and_then(ComponentOrInlineSchema::unwrap)
+            .and_then(|schema| schema.items.as_deref());
+        assert_eq!(
+            items,
+            Some(&ComponentOrInlineSchema::Component { name: "Pet".into() })
+        );
+    }
+
+    #[test]
+    fn test_bundle_errors() {
+        let dir = write(
+            "bundle-errors",
+            &[
+                (
+                    "dangling.json",
+                    &ROOT.replace("paths/pets.json", "pets.json#/get"),
+                ),
+                (
+                    "cycle.json",
+                    &ROOT.replace("paths/pets.json", "loop.json#/a"),
+                ),
+                (
+                    "remote.json",
+                    &ROOT.replace("paths/pets.json", "https://example.com/pets.json"),
+                ),
+                ("pets.json", "{}"),
+                (
+                    "loop.json",
+                    r##"{"a": {"$ref": "#/b"}, "b": {"$ref": "#/a"}}"##,
+                ),
+            ],
+        );
+        let dangling = bundle(dir.join("dangling.json"));
+        let cycle = bundle(dir.join("cycle.json"));
+        let remote = bundle(dir.join("remote.json"));
+        let missing = bundle(dir.join("missing.json"));
+        fs::remove_dir_all(&dir).unwrap();
+
+        let err = match dangling {
+            Err(Error::Bundle(err)) => err,
+            other => panic!("expected a bundle error, got {:?}", other),
+        };
+        assert!(err.path().ends_with("dangling.json"));
+        assert_eq!(err.reference(), "pets.json#/get");
+        assert!(matches!(err.kind(), BundleErrorKind::Dangling));
+        assert!(err
+            .to_string()
+            .starts_with("cannot follow `pets.json#/get` in "));
+
+        let kind = |result: Result<Spec>| match result {
+            Err(Error::Bundle(err)) => format!("{:?}", err.kind()),
+            other => panic!("expected a bundle error, got {:?}", other),
+        };
+        assert_eq!(kind(cycle), "Cycle");
+        assert_eq!(kind(remote), "Remote");
+        assert!(matches!(missing, Err(Error::Io(_))));
+    }
+}
@@ -0,0 +1,299 @@
+//! Human readable summaries of the changes between two versions of a specification.
+
+use crate::{
+    json_pointer,
+    v3_0::{diff::METHODS, Change, Severity, Spec},
+};
+use indexmap::IndexMap;
+use std::fmt;
+
+/// Part of a [semantic version](https://semver.org) to increment.
+///
+/// Bumps are ordered from the smallest to the largest.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Bump {
+    Patch,
+    Minor,
+    Major,
+}
+
+impl fmt::Display for Bump {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Bump::Patch => "patch",
+            Bump::Minor => "minor",
+            Bump::Major => "major",
+        })
+    }
+}
+
+impl Bump {
+    /// Returns the bump required by `changes`: major if any breaks clients, minor if any
+    /// changes the API without breaking it, and patch otherwise.
+    pub fn for_changes(changes: &[Change]) -> Bump {
+        match changes.iter().map(|change| change.severity).max() {
+            Some(Severity::Breaking) => Bump::Major,
+            Some(Severity::NonBreaking) => Bump::Minor,
+            Some(Severity::Informational) | None => Bump::Patch,
+        }
+    }
+
+    /// Increments `version`, such as `1.4.2`, resetting the smaller parts and dropping any
+    /// pre-release or build metadata.
+    ///
+    /// As in Cargo, the parts shift before `1.0.0`: a major bump of `0.3.1` increments its minor
+    /// version and a minor bump its patch version, while every bump of `0.0.3` increments its
+    /// patch version.
+    ///
+    /// Returns `None` if `version` is not of the form `MAJOR.MINOR.PATCH`, or if the part to
+    /// increment overflows.
+    pub fn apply(self, version: &str) -> Option<String> {
+        let core = version.split(['-', '+']).next()?;
+        let parts = core
+            .split('.')
+            .map(|part| part.parse::<u64>().ok())
+            .collect::<Option<Vec<_>>>()?;
+        let (major, minor, patch) = match parts.as_slice() {
+            [major, minor, patch] => (*major, *minor, *patch),
+            _ => return None,
+        };
+        let bump = match (major, minor, self) {
+            (0, 0, _) => Bump::Patch,
+            (0, _, Bump::Major) => Bump::Minor,
+            (0, _, _) => Bump::Patch,
+            _ => self,
+        };
+        Some(match bump {
+            Bump::Major => format!("{}.0.0", major.checked_add(1)?),
+            Bump::Minor => format!("{}.{}.0", major, minor.checked_add(1)?),
+            Bump::Patch => format!("{}.{}.{}", major, minor, patch.checked_add(1)?),
+        })
+    }
+}
+
+/// The changes between two versions of a specification, with the version bump they require.
+///
+/// Displays as a Markdown document, with the changes grouped by path and operation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Changelog {
+    /// Title of the new version.
+    pub title: String,
+    /// `info.version` of the old version.
+    pub old_version: String,
+    /// `info.version` of the new version.
+    pub new_version: String,
+    pub changes: Vec<Change>,
+    pub bump: Bump,
+}
+
+impl Changelog {
+    /// The old version incremented by [`Changelog::bump`], if it is a semantic version.
+    pub fn recommended_version(&self) -> Option<String> {
+        self.bump.apply(&self.old_version)
+    }
+}
+
+impl Spec {
+    /// Compares this version of the specification with `new`, as [`Spec::diff`] does, and
+    /// summarizes the changes.
+    pub fn changelog(&self, new: &Spec) -> Changelog {
+        let changes = self.diff(new);
+        Changelog {
+            title: new.info.title.to_string(),
+            old_version: self.info.version.to_string(),
+            new_version: new.info.version.to_string(),
+            bump: Bump::for_changes(&changes),
+            changes,
+        }
+    }
+}
+
+/// Heading of the changes to a path, and of those to one of its operations.
+type Groups<'a> = IndexMap<String, IndexMap<Option<String>, Vec<(String, &'a Change)>>>;
+
+/// Splits the pointer of `change` into its path, operation and the rest of the pointer.
+fn group(change: &Change) -> (String, Option<String>, String) {
+    let tokens = json_pointer::split(&change.pointer).unwrap_or_default();
+    let (path, rest) = match tokens.as_slice() {
+        [paths, path, rest @ ..] if paths == "paths" => (path.to_string(), rest),
+        _ => return (String::new(), None, change.pointer.clone()),
+    };
+    let (operation, rest) = match rest {
+        [method, rest @ ..] if METHODS.contains(&&**method) => {
+            let operation = format!("{} {}", method.to_uppercase(), path);
+            (Some(operation), rest)
+        }
+        _ => (None, rest),
+    };
+    let mut pointer = String::new();
+    for token in rest {
+        json_pointer::push(&mut pointer, token);
+    }
+    (path, operation, pointer)
+}
+
+fn severity_label(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Breaking => "Breaking",
+        Severity::NonBreaking => "Non-breaking",
+        Severity::Informational => "Informational",
+    }
+}
+
+impl fmt::Display for Changelog {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "# {} {}", self.title, self.new_version)?;
+        writeln!(f)?;
+        if self.changes.is_empty() {
+            return writeln!(f, "No changes since version `{}`.", self.old_version);
+        }
+        write!(
+            f,
+            "Changes since version `{}`, requiring a {} version bump",
+            self.old_version, self.bump
+        )?;
+        match self.recommended_version() {
+            Some(version) => writeln!(f, ": `{}`.", version)?,
+            None => writeln!(f, ".")?,
+        }
+
+        let mut groups = Groups::new();
+        for change in &self.changes {
+            let (path, operation, pointer) = group(change);
+            groups
+                .entry(path)
+                .or_default()
+                .entry(operation)
+                .or_default()
+                .push((pointer, change));
+        }
+        for (path, operations) in &mut groups {
+            writeln!(f)?;
+            if path.is_empty() {
+                writeln!(f, "## Other changes")?;
+            } else {
+                writeln!(f, "## `{}`", path)?;
+            }
+            // Changes to the path itself come first.
+            operations.sort_by(|a, _, b, _| a.is_some().cmp(&b.is_some()));
+            for (operation, changes) in operations {
+                if let Some(operation) = operation {
+                    writeln!(f)?;
+                    writeln!(f, "### `{}`", operation)?;
+                }
+                writeln!(f)?;
+                // Stable, so the changes of a severity keep their order.
+                changes.sort_by_key(|(_, change)| std::cmp::Reverse(change.severity));
+                for (pointer, change) in changes.iter() {
+                    write!(f, "- **{}**: ", severity_label(change.severity))?;
+                    if !pointer.is_empty() {
+                        write!(f, "`{}`: ", pointer)?;
+                    }
+                    writeln!(f, "{}", change.message)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bump() {
+        let change = |severity| Change {
+            pointer: String::new(),
+            kind: crate::v3_0::ChangeKind::Modified,
+            severity,
+            message: String::new(),
+        };
+        assert_eq!(Bump::for_changes(&[]), Bump::Patch);
+        assert_eq!(
+            Bump::for_changes(&[
+                change(Severity::Informational),
+                change(Severity::NonBreaking)
+            ]),
+            Bump::Minor
+        );
+        assert_eq!(
+            Bump::for_changes(&[change(Severity::Breaking), change(Severity::NonBreaking)]),
+            Bump::Major
+        );
+
+        assert_eq!(Bump::Major.apply("1.4.2").as_deref(), Some("2.0.0"));
+        assert_eq!(Bump::Minor.apply("1.4.2-beta.1").as_deref(), Some("1.5.0"));
+        assert_eq!(Bump::Patch.apply("0.1.0+build").as_deref(), Some("0.1.1"));
+        assert_eq!(Bump::Major.apply("0.3.1").as_deref(), Some("0.4.0"));
+        assert_eq!(Bump::Minor.apply("0.3.1").as_deref(), Some("0.3.2"));
+        assert_eq!(Bump::Major.apply("0.0.3").as_deref(), Some("0.0.4"));
+        assert_eq!(Bump::Minor.apply("1.18446744073709551615.0"), None);
+        assert_eq!(Bump::Patch.apply("1.0"), None);
+        assert_eq!(Bump::Patch.apply("2021-06-01"), None);
+    }
+
+    #[test]
+    fn test_markdown() {
+        let old: Spec = serde_json::from_str(
+            r#"{
+              "openapi": "3.0.3",
+              "info": {"title": "Pets", "version": "1.2.0"},
+              "paths": {
+                "/pets": {
+                  "get": {
+                    "summary": "List pets",
+                    "parameters": [{"name": "kind", "in": "query", "schema": {"type": "string"}}],
+                    "responses": {"200": {"description": "Pets"}}
+                  }
+                },
+                "/stores": {"get": {"responses": {"200": {"description": "Stores"}}}}
+              }
+            }"#,
+        )
+        .unwrap();
+        let mut new = old.clone();
+        new.info.version = "2.0.0".into();
+        new.paths.shift_remove("/stores");
+        let get = new.paths["/pets"].get.as_mut().unwrap();
+        get.summary = "List all pets".into();
+        get.parameters.clear();
+        new.paths.insert("/owners".into(), Default::default());
+        new.paths["/pets"].post = new.paths["/pets"].get.clone();
+
+        let changelog = old.changelog(&new);
+        assert_eq!(changelog.bump, Bump::Major);
+        assert_eq!(changelog.recommended_version().as_deref(), Some("2.0.0"));
+        assert_eq!(
+            changelog.to_string(),
+            "# Pets 2.0.0
+
+Changes since version `1.2.0`, requiring a major version bump: `2.0.0`.
+
+## `/pets`
+
+### `GET /pets`
+
+- **Breaking**: `/parameters/0`: query parameter `kind` removed
+- **Informational**: `/summary`: `summary` changed
+
+### `POST /pets`
+
+- **Non-breaking**: operation added
+
+## `/stores`
+
+- **Breaking**: path removed
+
+## `/owners`
+
+- **Non-breaking**: path added
+"
+        );
+
+        assert_eq!(
+            old.changelog(&old).to_string(),
+            "# Pets 1.2.0\n\nNo changes since version `1.2.0`.\n"
+        );
+    }
+}
@@ -0,0 +1,593 @@
+//! Rust clients for the operations of a specification.
+
+use crate::v3_0::{
+    diff::{operations, METHODS},
+    endpoint::{summary, Argument, Endpoint},
+    models::{docs, Models},
+    operation::Body,
+    Location, Operation, ParameterStyle, PathItem, Spec,
+};
+use std::collections::HashSet;
+
+impl Spec {
+    /// Generates Rust source of an async client with a method per operation, along with the
+    /// [models](Spec::generate_models) of the specification.
+    ///
+    /// Methods are named after the `operationId` of their operation, or after its method and
+    /// path if it has none. They take the path, query, header and cookie parameters, serialized
+    /// according to their `style` and `explode`, followed by the request body, and return an
+    /// enum with a variant per response status code.
+    ///
+    /// The client depends on `reqwest` with its `json` feature, and `form` for form bodies,
+    /// `serde`, `serde_json` and this crate.
+    ///
+    /// Requires the `codegen` feature.
+    pub fn generate_client(&self) -> String {
+        let mut generator = Generator {
+            models: Models::new(self, &["Client", "Error", "ParameterStyle"]),
+            names: HashSet::new(),
+        };
+        generator.models.components();
+        let mut methods = String::new();
+        for (path, item) in &self.paths {
+            for (method, operation) in METHODS.iter().zip(operations(item)) {
+                if let Some(operation) = operation {
+                    methods.push_str(&generator.method(path, item, method, operation));
+                }
+            }
+        }
+        let mut client = format!(
+            "// Client of `{}` {}, generated from its specification.\n\
+             \n\
+             use rweb_openapi::v3_0::ParameterStyle;\n\
+             use serde::{{Deserialize, Serialize}};\n",
+            self.info.title, self.info.version
+        );
+        client.push_str(&generator.models.definitions());
+        client.push('\n');
+        client.push_str(ERROR);
+        client.push_str(&format!(
+            "\n/// Client of `{}` {}.\n",
+            self.info.title, self.info.version
+        ));
+        client.push_str(CLIENT);
+        client.push_str(&methods);
+        client.push_str("}\n");
+        client.push_str(HELPERS);
+        client
+    }
+}
+
+/// The error type of the generated clients.
+const ERROR: &str = r#"/// Errors of the requests of [`Client`].
+#[derive(Debug)]
+pub enum Error {
+    /// The base URL of the client cannot have a path.
+    BaseUrl,
+    /// A parameter could not be serialized.
+    Parameter(serde_json::Error),
+    /// The request could not be sent, or its response could not be read.
+    Request(reqwest::Error),
+    /// The response has a status code which the operation does not define.
+    UnexpectedStatus(u16),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Error::BaseUrl => f.write_str("the base URL cannot have a path"),
+            Error::Parameter(error) => write!(f, "cannot serialize a parameter: {}", error),
+            Error::Request(error) => error.fmt(f),
+            Error::UnexpectedStatus(status) => write!(f, "unexpected status code {}", status),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        Error::Parameter(error)
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(error: reqwest::Error) -> Self {
+        Error::Request(error)
+    }
+}
+"#;
+
+/// The client type of the generated clients, without its closing brace.
+const CLIENT: &str = r#"#[derive(Clone, Debug)]
+pub struct Client {
+    http: reqwest::Client,
+    base_url: reqwest::Url,
+}
+
+impl Client {
+    /// Creates a client of the server at `base_url`.
+    pub fn new(base_url: reqwest::Url) -> Self {
+        Self::with_client(reqwest::Client::new(), base_url)
+    }
+
+    /// Creates a client of the server at `base_url`, sending requests with `http`.
+    pub fn with_client(http: reqwest::Client, base_url: reqwest::Url) -> Self {
+        Client { http, base_url }
+    }
+"#;
+
+/// Serialization of the parameters of the generated clients.
+const HELPERS: &str = r#"
+fn parameter(
+    name: &str,
+    style: ParameterStyle,
+    explode: bool,
+    value: &impl Serialize,
+) -> Result<Vec<(String, String)>, Error> {
+    let value = serde_json::to_value(value)?;
+    Ok(rweb_openapi::v3_0::serialize_parameter(name, style, explode, &value))
+}
+
+fn path_parameter(
+    name: &str,
+    style: ParameterStyle,
+    explode: bool,
+    value: &impl Serialize,
+) -> Result<String, Error> {
+    let pairs = parameter(name, style, explode, value)?;
+    Ok(pairs.into_iter().map(|(_, value)| value).collect())
+}
+"#;
+
+/// Local variables of the generated methods, which parameters must not shadow.
+const LOCALS: [&str; 8] = [
+    "body", "cookies", "query", "request", "response", "segments", "status", "url",
+];
+
+/// Generator of the methods of a client.
+struct Generator<'a> {
+    models: Models<'a>,
+    /// Names of the methods generated so far.
+    names: HashSet<String>,
+}
+
+impl<'a> Generator<'a> {
+    /// Returns the code of the method for `operation`.
+    fn method(
+        &mut self,
+        path: &str,
+        item: &'a PathItem,
+        method: &str,
+        operation: &'a Operation,
+    ) -> String {
+        let endpoint = Endpoint::new(
+            &mut self.models,
+            &mut self.names,
+            &LOCALS,
+            (path, item, method),
+            operation,
+        );
+        let mut arguments: Vec<String> = endpoint
+            .arguments
+            .iter()
+            .map(|argument| format!("{}: {}", argument.name, argument.declared_type()))
+            .collect();
+        if let Some(body) = &endpoint.body {
+            let rust_type = match body.kind {
+                Body::Json | Body::Form => format!("&{}", body.rust_type),
+                Body::Text(_) | Body::Bytes(_) => body.rust_type.clone(),
+            };
+            if body.required {
+                arguments.push(format!("body: {}", rust_type));
+            } else {
+                arguments.push(format!("body: Option<{}>", rust_type));
+            }
+        }
+
+        let name = &endpoint.name;
+        let response = &endpoint.response;
+        let mut code = format!("\n{}", docs(&summary(operation), "    "));
+        if operation.deprecated == Some(true) {
+            code.push_str("    #[deprecated]\n");
+        }
+        let signature = format!(
+            "    pub async fn {}(&self{}) -> Result<{}, Error> {{\n",
+            name,
+            arguments
+                .iter()
+                .map(|argument| format!(", {}", argument))
+                .collect::<String>(),
+            response
+        );
+        if signature.len() <= 101 {
+            code.push_str(&signature);
+        } else {
+            // As rustfmt breaks long signatures.
+            code.push_str(&format!("    pub async fn {}(\n        &self,\n", name));
+            for argument in &arguments {
+                code.push_str(&format!("        {},\n", argument));
+            }
+            code.push_str(&format!("    ) -> Result<{}, Error> {{\n", response));
+        }
+
+        // The URL, with the path and query parameters.
+        code.push_str("        let mut url = self.base_url.clone();\n");
+        code.push_str("        {\n");
+        code.push_str(
+            "            let mut segments = \
+             url.path_segments_mut().map_err(|_| Error::BaseUrl)?;\n",
+        );
+        code.push_str("            segments.pop_if_empty();\n");
+        for segment in path.split('/').filter(|segment| !segment.is_empty()) {
+            code.push_str(&format!(
+                "            segments.push({});\n",
+                path_segment(segment, &endpoint.arguments)
+            ));
+        }
+        code.push_str("        }\n");
+        let query: Vec<_> = located(&endpoint.arguments, Location::Query);
+        if !query.is_empty() {
+            code.push_str("        let mut query = Vec::new();\n");
+            for argument in query {
+                code.push_str(&optional(
+                    &argument.name,
+                    argument.required,
+                    &format!("query.extend({}?);", serialized("parameter", argument)),
+                ));
+            }
+            code.push_str("        if !query.is_empty() {\n");
+            code.push_str("            url.query_pairs_mut().extend_pairs(query);\n");
+            code.push_str("        }\n");
+        }
+
+        let method = method.to_ascii_uppercase();
+        let headers = located(&endpoint.arguments, Location::Header);
+        let cookies = located(&endpoint.arguments, Location::Cookie);
+        let modified = !headers.is_empty() || !cookies.is_empty() || endpoint.body.is_some();
+        code.push_str(&format!(
+            "        let {}request = self.http.request(reqwest::Method::{}, url);\n",
+            if modified { "mut " } else { "" },
+            method
+        ));
+        for argument in headers {
+            code.push_str(&optional(
+                &argument.name,
+                argument.required,
+                &format!(
+                    "for (_, value) in {}? {{\n    request = request.header({:?}, value);\n}}",
+                    serialized("parameter", argument),
+                    argument.parameter.name
+                ),
+            ));
+        }
+        if !cookies.is_empty() {
+            code.push_str("        let mut cookies = Vec::new();\n");
+            for argument in cookies {
+                code.push_str(&optional(
+                    &argument.name,
+                    argument.required,
+                    &format!(
+                        "for (name, value) in {}? {{\n    \
+                         cookies.push(format!(\"{{}}={{}}\", name, value));\n\
+                         }}",
+                        serialized("parameter", argument)
+                    ),
+                ));
+            }
+            code.push_str("        if !cookies.is_empty() {\n");
+            code.push_str(
+                "            request = request.header(\"Cookie\", cookies.join(\"; \"));\n",
+            );
+            code.push_str("        }\n");
+        }
+        if let Some(body) = &endpoint.body {
+            let set = match &body.kind {
+                Body::Json => "request = request.json(body);".to_string(),
+                Body::Form => "request = request.form(body);".to_string(),
+                Body::Text(media_type) | Body::Bytes(media_type) => format!(
+                    "request = request.header(\"Content-Type\", {:?}).body(body);",
+                    media_type
+                ),
+            };
+            code.push_str(&optional("body", body.required, &set));
+        }
+
+        // The response, as a variant of the response enum.
+        code.push_str("        let response = request.send().await?;\n");
+        code.push_str("        let status = response.status().as_u16();\n");
+        let mut variants = String::new();
+        let mut arms = Vec::new();
+        let mut default = None;
+        for variant in &endpoint.variants {
+            let fields = variant.fields();
+            let values: Vec<&str> = (!variant.is_exact())
+                .then_some("status")
+                .into_iter()
+                .chain(variant.content.as_ref().map(|(kind, _)| match kind {
+                    Body::Text(_) => "response.text().await?",
+                    Body::Bytes(_) | Body::Form => "response.bytes().await?.to_vec()",
+                    Body::Json => "response.json().await?",
+                }))
+                .collect();
+            variants.push_str(&docs(variant.description, "    "));
+            if fields.is_empty() {
+                variants.push_str(&format!("    {},\n", variant.name));
+            } else {
+                variants.push_str(&format!("    {}({}),\n", variant.name, fields.join(", ")));
+            }
+            let value = if values.is_empty() {
+                format!("{}::{}", response, variant.name)
+            } else {
+                format!("{}::{}({})", response, variant.name, values.join(", "))
+            };
+            match variant.pattern() {
+                Some(pattern) => arms.push((variant.is_exact(), pattern, value)),
+                None => default = Some(value),
+            }
+        }
+        match (arms.is_empty(), default) {
+            // Only a default response, whatever the status.
+            (true, Some(value)) => code.push_str(&format!("        Ok({})\n    }}\n", value)),
+            (_, default) => {
+                code.push_str("        Ok(match status {\n");
+                // Exact status codes take precedence over ranges.
+                arms.sort_by_key(|(exact, _, _)| !exact);
+                for (_, pattern, value) in arms {
+                    code.push_str(&format!("            {} => {},\n", pattern, value));
+                }
+                match default {
+                    Some(value) => code.push_str(&format!("            _ => {},\n", value)),
+                    None => code.push_str(
+                        "            _ => return Err(Error::UnexpectedStatus(status)),\n",
+                    ),
+                }
+                code.push_str("        })\n    }\n");
+            }
+        }
+
+        let definition = format!(
+            "/// Response of [`Client::{}`].\n#[derive(Debug)]\npub enum {} {{\n{}}}\n",
+            name, response, variants
+        );
+        self.models.insert(endpoint.response, definition);
+        code
+    }
+}
+
+fn located<'p, 'a>(arguments: &'p [Argument<'a>], location: Location) -> Vec<&'p Argument<'a>> {
+    arguments
+        .iter()
+        .filter(|argument| argument.parameter.location == location)
+        .collect()
+}
+
+/// A call of a helper serializing `argument` as its parameter.
+pub(crate) fn serialized(helper: &str, argument: &Argument) -> String {
+    format!(
+        "{}({:?}, ParameterStyle::{}, {}, &{})",
+        helper,
+        argument.parameter.name,
+        style_name(argument.parameter.style_or_default()),
+        argument.parameter.explode_or_default(),
+        argument.name
+    )
+}
+
+fn style_name(style: ParameterStyle) -> &'static str {
+    match style {
+        ParameterStyle::Matrix => "Matrix",
+        ParameterStyle::Label => "Label",
+        ParameterStyle::Form => "Form",
+        ParameterStyle::Simple => "Simple",
+        ParameterStyle::SpaceDelimited => "SpaceDelimited",
+        ParameterStyle::PipeDelimited => "PipeDelimited",
+        ParameterStyle::DeepObject => "DeepObject",
+    }
+}
+
+/// Statements running `statement` with `argument`, if it is given when it is optional.
+fn optional(argument: &str, required: bool, statement: &str) -> String {
+    let indent = if required { "        " } else { "            " };
+    let mut code = String::new();
+    if !required {
+        code.push_str(&format!(
+            "        if let Some({}) = {} {{\n",
+            argument, argument
+        ));
+    }
+    for line in statement.lines() {
+        code.push_str(&format!("{}{}\n", indent, line));
+    }
+    if !required {
+        code.push_str("        }\n");
+    }
+    code
+}
+
+/// An expression of a segment of a path template, with its parameters.
+fn path_segment(segment: &str, arguments: &[Argument]) -> String {
+    let mut format = String::new();
+    let mut values = Vec::new();
+    let mut rest = segment;
+    while let Some(start) = rest.find('{') {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+        let name = &rest[start + 1..end];
+        let argument = arguments.iter().find(|argument| {
+            argument.parameter.location == Location::Path && argument.parameter.name == name
+        });
+        format.push_str(&rest[..start].replace('{', "{{").replace('}', "}}"));
+        match argument {
+            Some(argument) => {
+                format.push_str("{}");
+                values.push(format!("{}?", serialized("path_parameter", argument)));
+            }
+            None => format.push_str(&format!("{{{{{}}}}}", name)),
+        }
+        rest = &rest[end + 1..];
+    }
+    format.push_str(&rest.replace('{', "{{").replace('}', "}}"));
+    if values.is_empty() {
+        format!("{:?}", segment)
+    } else if format == "{}" {
+        format!("&{}", values.join(""))
+    } else {
+        format!("&format!({:?}, {})", format, values.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v3_0::models::{check_generated, GENERATED_SPEC};
+
+    const SPEC: &str = r##"{
+      "openapi": "3.0.3",
+      "info": {"title": "Pets", "version": "1.0.0"},
+      "paths": {
+        "/pets/{petId}": {
+          "parameters": [
+            {
+              "name": "petId",
+              "in": "path",
+              "required": true,
+              "schema": {"type": "integer", "format": "int64"}
+            }
+          ],
+          "get": {
+            "operationId": "getPet",
+            "summary": "Returns a pet.",
+            "parameters": [
+              {
+                "name": "fields",
+                "in": "query",
+                "schema": {"type": "array", "items": {"type": "string"}}
+              },
+              {"name": "X-Request-Id", "in": "header", "required": true, "schema": {}},
+              {"name": "session", "in": "cookie", "schema": {"type": "string"}}
+            ],
+            "responses": {
+              "200": {
+                "description": "The pet.",
+                "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}}
+              },
+              "404": {"description": "No such pet."},
+              "5XX": {
+                "description": "Server error.",
+                "content": {"text/plain": {"schema": {"type": "string"}}}
+              }
+            }
+          },
+          "put": {
+            "deprecated": true,
+            "parameters": [
+              {
+                "name": "tags",
+                "in": "query",
+                "style": "pipeDelimited",
+                "explode": false,
+                "schema": {"type": "array", "items": {"type": "string"}}
+              }
+            ],
+            "requestBody": {
+              "required": true,
+              "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}}
+            },
+            "responses": {
+              "204": {"description": "Updated."},
+              "default": {
+                "description": "An error.",
+                "content": {
+                  "application/json": {
+                    "schema": {"type": "object", "properties": {"message": {"type": "string"}}}
+                  }
+                }
+              }
+            }
+          }
+        },
+        "/pets/{petId}/photo.{format}": {
+          "post": {
+            "operationId": "upload_photo",
+            "parameters": [
+              {"name": "petId", "in": "path", "required": true, "style": "label", "schema": {}},
+              {
+                "name": "format",
+                "in": "path",
+                "required": true,
+                "schema": {"type": "string", "enum": ["png", "jpg"]}
+              }
+            ],
+            "requestBody": {"content": {"image/png": {}}},
+            "responses": {"201": {"description": "Uploaded."}}
+          }
+        }
+      },
+      "components": {
+        "schemas": {
+          "Pet": {
+            "type": "object",
+            "required": ["name"],
+            "properties": {"name": {"type": "string"}}
+          }
+        }
+      }
+    }"##;
+
+    #[test]
+    fn test_generate_client() {
+        let spec: Spec = serde_json::from_str(SPEC).unwrap();
+        let client = spec.generate_client();
+        for expected in &[
+            // Responses.
+            "pub enum GetPetResponse {\n    \
+                 /// The pet.\n    \
+                 Ok(Pet),\n    \
+                 /// No such pet.\n    \
+                 NotFound,\n    \
+                 /// Server error.\n    \
+                 Status5xx(u16, String),\n\
+             }",
+            "            200 => GetPetResponse::Ok(response.json().await?),\n\
+             \x20           404 => GetPetResponse::NotFound,\n\
+             \x20           500..=599 => \
+                 GetPetResponse::Status5xx(status, response.text().await?),\n\
+             \x20           _ => return Err(Error::UnexpectedStatus(status)),\n",
+            "            _ => PutPetsPetIdResponse::Default(status, response.json().await?),\n",
+            "pub struct PutPetsPetIdDefault {",
+            // Parameters, with path parameters overridden by operations.
+            "    /// Returns a pet.\n    \
+                 pub async fn get_pet(\n        \
+                     &self,\n        \
+                     pet_id: i64,\n        \
+                     fields: Option<Vec<String>>,\n        \
+                     x_request_id: serde_json::Value,\n        \
+                     session: Option<String>,\n    \
+                 ) -> Result<GetPetResponse, Error> {",
+            "segments.push(&path_parameter(\"petId\", ParameterStyle::Simple, false, &pet_id)?);",
+            "query.extend(parameter(\"fields\", ParameterStyle::Form, true, &fields)?);",
+            "request = request.header(\"X-Request-Id\", value);",
+            "cookies.push(format!(\"{}={}\", name, value));",
+            "query.extend(parameter(\"tags\", ParameterStyle::PipeDelimited, false, &tags)?);",
+            "segments.push(&path_parameter(\"petId\", ParameterStyle::Label, false, &pet_id)?);",
+            "segments.push(&format!(\"photo.{}\", path_parameter(\"format\", ",
+            // Request bodies.
+            "    #[deprecated]\n    pub async fn put_pets_pet_id(",
+            "        body: &Pet,\n",
+            "        request = request.json(body);\n",
+            "        body: Option<Vec<u8>>,\n",
+            "            request = request.header(\"Content-Type\", \"image/png\").body(body);\n",
+        ] {
+            assert!(client.contains(expected), "{}\n\n{}", expected, client);
+        }
+    }
+
+    #[test]
+    fn test_generated_client() {
+        let spec: Spec = serde_json::from_str(GENERATED_SPEC).unwrap();
+        check_generated("src/client.rs", &spec.generate_client());
+    }
+}
@@ -0,0 +1,1009 @@
+//! Schemas compiled ahead of time, to validate many instances quickly.
+
+use crate::{
+    json_pointer,
+    v3_0::{
+        diff::is_multiple_of,
+        instance::{are_unique, has_type, json_eq, json_type},
+        resolve::component_ref,
+        Component, ComponentOrInlineSchema, Components, InstanceError, RefError, Schema, Type,
+    },
+    Str,
+};
+use indexmap::IndexMap;
+use regex::Regex;
+use serde_json::{Map, Number, Value};
+use std::{collections::HashMap, error, fmt};
+
+/// A schema prepared to validate instances, with its references resolved and its patterns
+/// compiled.
+///
+/// Validates instances exactly like [`Components::validate_instance`], without allocating
+/// anything else than the returned errors.
+///
+/// Requires the `validate` feature.
+#[derive(Clone, Debug)]
+pub struct CompiledSchema {
+    /// Compiled schemas, the validated one first, with indices in place of references.
+    nodes: Vec<Node>,
+}
+
+/// Failure to compile a schema.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CompileError {
+    /// A reference cannot be followed.
+    Ref(RefError),
+    /// A `pattern` is not a valid regular expression.
+    Pattern {
+        /// JSON pointer of the pattern, as in [`InstanceError::schema_pointer`].
+        pointer: String,
+        message: String,
+    },
+}
+
+impl fmt::Display for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CompileError::Ref(err) => err.fmt(f),
+            CompileError::Pattern { pointer, message } => {
+                write!(f, "`{}`: invalid pattern: {}", pointer, message)
+            }
+        }
+    }
+}
+
+impl error::Error for CompileError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            CompileError::Ref(err) => Some(err),
+            CompileError::Pattern { .. } => None,
+        }
+    }
+}
+
+impl From<RefError> for CompileError {
+    fn from(err: RefError) -> Self {
+        CompileError::Ref(err)
+    }
+}
+
+impl CompiledSchema {
+    /// Compiles `schema`, looking up component schemas in `components`.
+    pub fn new(
+        schema: &ComponentOrInlineSchema,
+        components: &Components,
+    ) -> Result<Self, CompileError> {
+        let mut compiler = Compiler {
+            components,
+            nodes: Vec::new(),
+            named: HashMap::new(),
+        };
+        compiler.schema(schema, String::new())?;
+        let mut nodes = compiler.nodes;
+        let tags: Vec<_> = (0..nodes.len()).map(|i| tag(&nodes, i)).collect();
+        for (node, tag) in nodes.iter_mut().zip(tags) {
+            node.tag = tag;
+        }
+        Ok(CompiledSchema { nodes })
+    }
+
+    /// Returns every keyword of the schema which `instance` does not satisfy.
+    pub fn validate(&self, instance: &Value) -> Vec<InstanceError> {
+        let mut errors = Vec::new();
+        self.node(
+            0,
+            instance,
+            Location::Root,
+            None,
+            &mut Sink(Some(&mut errors)),
+        );
+        errors
+    }
+
+    /// Whether `instance` satisfies the schema. Stops at the first unsatisfied keyword.
+    pub fn is_valid(&self, instance: &Value) -> bool {
+        self.node(0, instance, Location::Root, None, &mut Sink(None))
+    }
+}
+
+/// Bit of [`Node::kinds`] for the kind of JSON value of an instance.
+fn kind(value: &Value) -> u8 {
+    match json_type(value) {
+        "null" => 1,
+        "boolean" => 1 << 1,
+        "integer" => 1 << 2,
+        "number" => 1 << 3,
+        "string" => 1 << 4,
+        "array" => 1 << 5,
+        _ => 1 << 6,
+    }
+}
+
+const ALL_KINDS: u8 = (1 << 7) - 1;
+
+fn type_kinds(ty: Type) -> u8 {
+    match ty {
+        Type::Boolean => 1 << 1,
+        Type::Integer => 1 << 2,
+        Type::Number => 1 << 2 | 1 << 3,
+        Type::String | Type::File => 1 << 4,
+        Type::Array => 1 << 5,
+        Type::Object => 1 << 6,
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct Node {
+    /// JSON pointer of the schema, to which the keywords of errors are appended.
+    pointer: Box<str>,
+    /// Whether the node is a component schema, which may be reached again without moving within
+    /// the instance.
+    component: bool,
+    nullable: bool,
+    /// The type, and its name as shown in errors.
+    schema_type: Option<(Type, Box<str>)>,
+    /// Kinds of instances which may satisfy the `type` and `nullable` keywords, to skip
+    /// `oneOf` and `anyOf` branches without validating them.
+    kinds: u8,
+    /// A property required to hold a constant, to skip `oneOf` and `anyOf` branches of tagged
+    /// unions without validating them.
+    tag: Option<(Box<str>, Value)>,
+    enum_values: Vec<Box<str>>,
+    const_value: Option<Value>,
+    /// `minimum`, and whether it is exclusive.
+    minimum: Option<(f64, bool)>,
+    exclusive_minimum: Option<f64>,
+    /// `maximum`, and whether it is exclusive.
+    maximum: Option<(f64, bool)>,
+    exclusive_maximum: Option<f64>,
+    multiple_of: Option<Number>,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+    pattern: Option<(Regex, Box<str>)>,
+    min_items: Option<usize>,
+    max_items: Option<usize>,
+    unique_items: bool,
+    items: Option<usize>,
+    min_properties: Option<usize>,
+    max_properties: Option<usize>,
+    required: Vec<Box<str>>,
+    dependent_required: Vec<(Box<str>, Vec<Box<str>>)>,
+    properties: IndexMap<Box<str>, usize>,
+    additional_properties: Option<usize>,
+    all_of: Vec<usize>,
+    any_of: Vec<usize>,
+    one_of: Vec<usize>,
+}
+
+/// Returns the [`Node::tag`] of `nodes[index]`: the first required property whose schema holds
+/// a constant other than `null`.
+fn tag(nodes: &[Node], index: usize) -> Option<(Box<str>, Value)> {
+    let node = &nodes[index];
+    node.required.iter().find_map(|name| {
+        let property = &nodes[*node.properties.get(name)?];
+        match &property.const_value {
+            Some(value) if !property.nullable => Some((name.clone(), value.clone())),
+            _ => None,
+        }
+    })
+}
+
+struct Compiler<'a> {
+    components: &'a Components,
+    nodes: Vec<Node>,
+    /// Indices of the component schemas compiled so far.
+    named: HashMap<Str, usize>,
+}
+
+impl<'a> Compiler<'a> {
+    /// Compiles `schema`, found at `pointer` unless it is a reference, and returns its index.
+    fn schema(
+        &mut self,
+        schema: &ComponentOrInlineSchema,
+        pointer: String,
+    ) -> Result<usize, CompileError> {
+        let (schema, pointer, name) = match schema {
+            ComponentOrInlineSchema::Inline(schema) => (schema, pointer, None),
+            ComponentOrInlineSchema::Component { name } => {
+                if let Some(&index) = self.named.get(name) {
+                    return Ok(index);
+                }
+                let reference = component_ref::<Schema>(name);
+                let pointer = reference.trim_start_matches('#').to_string();
+                (self.components.get::<Schema>(name)?, pointer, Some(name))
+            }
+            ComponentOrInlineSchema::ExtRef { reference } => {
+                return Err(RefError::Unsupported {
+                    reference: reference.clone(),
+                    expected: Schema::KIND,
+                }
+                .into())
+            }
+        };
+        // Reserves the index first, so that the schema may refer to itself.
+        let index = self.nodes.len();
+        self.nodes.push(Node::default());
+        if let Some(name) = name {
+            self.named.insert(name.clone(), index);
+        }
+        let mut node = self.node(schema, pointer)?;
+        node.component = name.is_some();
+        self.nodes[index] = node;
+        Ok(index)
+    }
+
+    fn child(
+        &mut self,
+        schema: &ComponentOrInlineSchema,
+        pointer: &str,
+        keyword: &[&str],
+    ) -> Result<usize, CompileError> {
+        let mut pointer = pointer.to_string();
+        for keyword in keyword {
+            json_pointer::push(&mut pointer, keyword);
+        }
+        self.schema(schema, pointer)
+    }
+
+    fn children(
+        &mut self,
+        schemas: &[ComponentOrInlineSchema],
+        pointer: &str,
+        keyword: &str,
+    ) -> Result<Vec<usize>, CompileError> {
+        schemas
+            .iter()
+            .enumerate()
+            .map(|(i, schema)| self.child(schema, pointer, &[keyword, &i.to_string()]))
+            .collect()
+    }
+
+    fn node(&mut self, schema: &Schema, pointer: String) -> Result<Node, CompileError> {
+        let bound = |bound: &Option<Value>| bound.as_ref().and_then(Value::as_f64);
+        let exclusive = |flag: &Option<Value>| flag.as_ref().and_then(Value::as_bool) == Some(true);
+        let nullable = schema.nullable == Some(true);
+        let pattern = if schema.pattern.is_empty() {
+            None
+        } else {
+            match Regex::new(&schema.pattern) {
+                Ok(regex) => Some((regex, schema.pattern.as_ref().into())),
+                Err(err) => {
+                    let mut pointer = pointer;
+                    json_pointer::push(&mut pointer, "pattern");
+                    return Err(CompileError::Pattern {
+                        pointer,
+                        message: err.to_string(),
+                    });
+                }
+            }
+        };
+        let items = match &schema.items {
+            Some(items) => Some(self.child(items, &pointer, &["items"])?),
+            None => None,
+        };
+        let mut properties = IndexMap::new();
+        for (name, property) in &schema.properties {
+            let index = self.child(property, &pointer, &["properties", name])?;
+            properties.insert(name.as_ref().into(), index);
+        }
+        let additional_properties = match &schema.additional_properties {
+            Some(additional) => {
+                Some(self.child(additional, &pointer, &["additionalProperties"])?)
+            }
+            None => None,
+        };
+        Ok(Node {
+            component: false,
+            nullable,
+            schema_type: schema
+                .schema_type
+                .map(|ty| (ty, serde_json::json!(ty).to_string().into())),
+            kinds: match schema.schema_type {
+                Some(ty) => type_kinds(ty) | if nullable { 1 } else { 0 },
+                None => ALL_KINDS,
+            },
+            tag: None,
+            enum_values: schema
+                .enum_values
+                .iter()
+                .map(|v| v.as_ref().into())
+                .collect(),
+            const_value: schema.const_value.clone(),
+            minimum: bound(&schema.minimum).map(|m| (m, exclusive(&schema.exclusive_minimum))),
+            exclusive_minimum: bound(&schema.exclusive_minimum),
+            maximum: bound(&schema.maximum).map(|m| (m, exclusive(&schema.exclusive_maximum))),
+            exclusive_maximum: bound(&schema.exclusive_maximum),
+            multiple_of: match &schema.multiple_of {
+                Some(Value::Number(divisor)) => Some(divisor.clone()),
+                _ => None,
+            },
+            min_length: schema.min_length,
+            max_length: schema.max_length,
+            pattern,
+            min_items: schema.min_items,
+            max_items: schema.max_items,
+            unique_items: schema.unique_items == Some(true),
+            items,
+            min_properties: schema.min_properties,
+            max_properties: schema.max_properties,
+            required: schema.required.iter().map(|r| r.as_ref().into()).collect(),
+            dependent_required: schema
+                .dependent_required
+                .iter()
+                .map(|(name, required)| {
+                    let required = required.iter().map(|r| r.as_ref().into()).collect();
+                    (name.as_ref().into(), required)
+                })
+                .collect(),
+            properties,
+            additional_properties,
+            all_of: self.children(&schema.all_of, &pointer, "allOf")?,
+            any_of: self.children(&schema.any_of, &pointer, "anyOf")?,
+            one_of: self.children(&schema.one_of, &pointer, "oneOf")?,
+            pointer: pointer.into(),
+        })
+    }
+}
+
+/// Location of a value within the instance, kept on the stack until an error needs it.
+#[derive(Clone, Copy)]
+enum Location<'a> {
+    Root,
+    Property(&'a Location<'a>, &'a str),
+    Item(&'a Location<'a>, usize),
+}
+
+impl<'a> Location<'a> {
+    fn pointer(&self) -> String {
+        match self {
+            Location::Root => String::new(),
+            Location::Property(parent, name) => {
+                let mut pointer = parent.pointer();
+                json_pointer::push(&mut pointer, name);
+                pointer
+            }
+            Location::Item(parent, i) => {
+                let mut pointer = parent.pointer();
+                json_pointer::push(&mut pointer, &i.to_string());
+                pointer
+            }
+        }
+    }
+}
+
+/// Component schemas being validated at the current location of the instance, innermost first.
+struct Following<'a> {
+    index: usize,
+    outer: Option<&'a Following<'a>>,
+}
+
+impl<'a> Following<'a> {
+    fn contains(following: Option<&Following>, index: usize) -> bool {
+        let mut following = following;
+        while let Some(f) = following {
+            if f.index == index {
+                return true;
+            }
+            following = f.outer;
+        }
+        false
+    }
+}
+
+/// Where errors go: collected, or discarded by stopping at the first one.
+struct Sink<'e>(Option<&'e mut Vec<InstanceError>>);
+
+impl<'e> Sink<'e> {
+    /// Reports an error, returning whether validation goes on.
+    fn fail(
+        &mut self,
+        node: &Node,
+        keyword: &str,
+        location: &Location,
+        message: impl FnOnce() -> String,
+    ) -> bool {
+        match &mut self.0 {
+            Some(errors) => {
+                let mut schema_pointer = node.pointer.to_string();
+                json_pointer::push(&mut schema_pointer, keyword);
+                errors.push(InstanceError {
+                    instance_pointer: location.pointer(),
+                    schema_pointer,
+                    message: message(),
+                });
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn collects(&self) -> bool {
+        self.0.is_some()
+    }
+}
+
+/// Reports an error, and returns `false` unless errors are collected.
+macro_rules! fail {
+    ($valid:ident, $sink:ident, $node:ident, $location:ident, $keyword:literal, $($msg:tt)*) => {{
+        $valid = false;
+        if !$sink.fail($node, $keyword, &$location, || format!($($msg)*)) {
+            return false;
+        }
+    }};
+}
+
+/// Records the result of validating a subschema, and returns `false` unless errors are
+/// collected.
+macro_rules! check {
+    ($valid:ident, $sink:ident, $result:expr) => {{
+        if !$result {
+            $valid = false;
+            if !$sink.collects() {
+                return false;
+            }
+        }
+    }};
+}
+
+impl CompiledSchema {
+    fn node(
+        &self,
+        index: usize,
+        instance: &Value,
+        location: Location,
+        following: Option<&Following>,
+        sink: &mut Sink,
+    ) -> bool {
+        let node = &self.nodes[index];
+        if node.component && Following::contains(following, index) {
+            return true;
+        }
+        let inner = Following {
+            index,
+            outer: following,
+        };
+        let following = if node.component {
+            Some(&inner)
+        } else {
+            following
+        };
+        if instance.is_null() && node.nullable {
+            return true;
+        }
+
+        let mut valid = true;
+        if let Some((ty, name)) = &node.schema_type {
+            if !has_type(instance, *ty) {
+                fail!(
+                    valid,
+                    sink,
+                    node,
+                    location,
+                    "type",
+                    "expected {}, found {}",
+                    name,
+                    json_type(instance)
+                );
+            }
+        }
+        if !node.enum_values.is_empty() {
+            let found = match instance {
+                Value::String(s) => node.enum_values.iter().any(|v| **v == **s),
+                _ => false,
+            };
+            if !found {
+                fail!(
+                    valid,
+                    sink,
+                    node,
+                    location,
+                    "enum",
+                    "{} is not one of the `enum` values",
+                    instance
+                );
+            }
+        }
+        if let Some(value) = &node.const_value {
+            if !json_eq(value, instance) {
+                fail!(
+                    valid,
+                    sink,
+                    node,
+                    location,
+                    "const",
+                    "expected {}, found {}",
+                    value,
+                    instance
+                );
+            }
+        }
+        let result = match instance {
+            Value::Number(number) => self.number(node, number, location, sink),
+            Value::String(string) => self.string(node, string, location, sink),
+            Value::Array(items) => self.array(node, items, location, sink),
+            Value::Object(object) => self.object(node, object, location, sink),
+            Value::Null | Value::Bool(_) => true,
+        };
+        check!(valid, sink, result);
+
+        for &sub in &node.all_of {
+            check!(
+                valid,
+                sink,
+                self.node(sub, instance, location, following, sink)
+            );
+        }
+        if !node.any_of.is_empty() {
+            let matched = node
+                .any_of
+                .iter()
+                .any(|&sub| self.branch(sub, instance, location, following));
+            if !matched {
+                fail!(
+                    valid,
+                    sink,
+                    node,
+                    location,
+                    "anyOf",
+                    "does not match any schema"
+                );
+            }
+        }
+        if !node.one_of.is_empty() {
+            let mut matched = 0;
+            for &sub in &node.one_of {
+                if self.branch(sub, instance, location, following) {
+                    matched += 1;
+                    if matched > 1 && !sink.collects() {
+                        return false;
+                    }
+                }
+            }
+            if matched != 1 {
+                fail!(
+                    valid,
+                    sink,
+                    node,
+                    location,
+                    "oneOf",
+                    "matches {} schemas instead of exactly one",
+                    matched
+                );
+            }
+        }
+        valid
+    }
+
+    /// Whether `instance` satisfies the branch `index` of a `oneOf` or `anyOf`.
+    fn branch(
+        &self,
+        index: usize,
+        instance: &Value,
+        location: Location,
+        following: Option<&Following>,
+    ) -> bool {
+        let node = &self.nodes[index];
+        // Schemas being followed are satisfied without being looked at.
+        if !(node.component && Following::contains(following, index)) {
+            if node.kinds & kind(instance) == 0 {
+                return false;
+            }
+            if let (Some((name, value)), Value::Object(object)) = (&node.tag, instance) {
+                if object.get(&**name) != Some(value) {
+                    return false;
+                }
+            }
+        }
+        self.node(index, instance, location, following, &mut Sink(None))
+    }
+
+    fn number(&self, node: &Node, number: &Number, location: Location, sink: &mut Sink) -> bool {
+        let mut valid = true;
+        let value = number.as_f64().unwrap_or(f64::NAN);
+        if let Some((minimum, exclusive)) = node.minimum {
+            if exclusive && value <= minimum {
+                fail!(
+                    valid,
+                    sink,
+                    node,
+                    location,
+                    "minimum",
+                    "{} is not greater than {}",
+                    number,
+                    minimum
+                );
+            } else if value < minimum {
+                fail!(
+                    valid,
+                    sink,
+                    node,
+                    location,
+                    "minimum",
+                    "{} is less than {}",
+                    number,
+                    minimum
+                );
+            }
+        }
+        if let Some(minimum) = node.exclusive_minimum {
+            if value <= minimum {
+                fail!(
+                    valid,
+                    sink,
+                    node,
+                    location,
+                    "exclusiveMinimum",
+                    "{} is not greater than {}",
+                    number,
+                    minimum
+                );
+            }
+        }
+        if let Some((maximum, exclusive)) = node.maximum {
+            if exclusive && value >= maximum {
+                fail!(
+                    valid,
+                    sink,
+                    node,
+                    location,
+                    "maximum",
+                    "{} is not less than {}",
+                    number,
+                    maximum
+                );
+            } else if value > maximum {
+                fail!(
+                    valid,
+                    sink,
+                    node,
+                    location,
+                    "maximum",
+                    "{} is greater than {}",
+                    number,
+                    maximum
+                );
+            }
+        }
+        if let Some(maximum) = node.exclusive_maximum {
+            if value >= maximum {
+                fail!(
+                    valid,
+                    sink,
+                    node,
+                    location,
+                    "exclusiveMaximum",
+                    "{} is not less than {}",
+                    number,
+                    maximum
+                );
+            }
+        }
+        if let Some(divisor) = &node.multiple_of {
+            if !is_multiple_of(number, divisor) {
+                fail!(
+                    valid,
+                    sink,
+                    node,
+                    location,
+                    "multipleOf",
+                    "{} is not a multiple of {}",
+                    number,
+                    divisor
+                );
+            }
+        }
+        valid
+    }
+
+    fn string(&self, node: &Node, string: &str, location: Location, sink: &mut Sink) -> bool {
+        let mut valid = true;
+        if node.min_length.is_some() || node.max_length.is_some() {
+            let len = string.chars().count();
+            if let Some(min) = node.min_length {
+                if len < min {
+                    fail!(
+                        valid,
+                        sink,
+                        node,
+                        location,
+                        "minLength",
+                        "length {} is less than {}",
+                        len,
+                        min
+                    );
+                }
+            }
+            if let Some(max) = node.max_length {
+                if len > max {
+                    fail!(
+                        valid,
+                        sink,
+                        node,
+                        location,
+                        "maxLength",
+                        "length {} is greater than {}",
+                        len,
+                        max
+                    );
+                }
+            }
+        }
+        if let Some((regex, pattern)) = &node.pattern {
+            if !regex.is_match(string) {
+                fail!(
+                    valid,
+                    sink,
+                    node,
+                    location,
+                    "pattern",
+                    "`{}` does not match `{}`",
+                    string,
+                    pattern
+                );
+            }
+        }
+        valid
+    }
+
+    fn array(&self, node: &Node, items: &[Value], location: Location, sink: &mut Sink) -> bool {
+        let mut valid = true;
+        if let Some(min) = node.min_items {
+            if items.len() < min {
+                fail!(
+                    valid,
+                    sink,
+                    node,
+                    location,
+                    "minItems",
+                    "{} items, expected at least {}",
+                    items.len(),
+                    min
+                );
+            }
+        }
+        if let Some(max) = node.max_items {
+            if items.len() > max {
+                fail!(
+                    valid,
+                    sink,
+                    node,
+                    location,
+                    "maxItems",
+                    "{} items, expected at most {}",
+                    items.len(),
+                    max
+                );
+            }
+        }
+        if node.unique_items && !are_unique(items) {
+            fail!(
+                valid,
+                sink,
+                node,
+                location,
+                "uniqueItems",
+                "items are not unique"
+            );
+        }
+        if let Some(sub) = node.items {
+            for (i, item) in items.iter().enumerate() {
+                let location = Location::Item(&location, i);
+                check!(valid, sink, self.node(sub, item, location, None, sink));
+            }
+        }
+        valid
+    }
+
+    fn object(
+        &self,
+        node: &Node,
+        object: &Map<String, Value>,
+        location: Location,
+        sink: &mut Sink,
+    ) -> bool {
+        let mut valid = true;
+        if let Some(min) = node.min_properties {
+            if object.len() < min {
+                fail!(
+                    valid,
+                    sink,
+                    node,
+                    location,
+                    "minProperties",
+                    "{} properties, expected at least {}",
+                    object.len(),
+                    min
+                );
+            }
+        }
+        if let Some(max) = node.max_properties {
+            if object.len() > max {
+                fail!(
+                    valid,
+                    sink,
+                    node,
+                    location,
+                    "maxProperties",
+                    "{} properties, expected at most {}",
+                    object.len(),
+                    max
+                );
+            }
+        }
+        for name in &node.required {
+            if !object.contains_key(&**name) {
+                fail!(
+                    valid,
+                    sink,
+                    node,
+                    location,
+                    "required",
+                    "missing property `{}`",
+                    name
+                );
+            }
+        }
+        for (name, required) in &node.dependent_required {
+            if !object.contains_key(&**name) {
+                continue;
+            }
+            for dependency in required {
+                if !object.contains_key(&**dependency) {
+                    fail!(
+                        valid,
+                        sink,
+                        node,
+                        location,
+                        "dependentRequired",
+                        "missing property `{}`, required by `{}`",
+                        dependency,
+                        name
+                    );
+                }
+            }
+        }
+        for (name, value) in object {
+            let sub = match node.properties.get(name.as_str()) {
+                Some(&sub) => sub,
+                None => match node.additional_properties {
+                    Some(sub) => sub,
+                    None => continue,
+                },
+            };
+            let location = Location::Property(&location, name);
+            check!(valid, sink, self.node(sub, value, location, None, sink));
+        }
+        valid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const COMPONENTS: &str = r##"{
+      "schemas": {
+        "Shape": {
+          "oneOf": [
+            {"$ref": "#/components/schemas/Circle"},
+            {"$ref": "#/components/schemas/Square"},
+            {"type": "string", "pattern": "^[a-z]+$", "maxLength": 6}
+          ]
+        },
+        "Circle": {
+          "type": "object",
+          "required": ["kind", "radius"],
+          "properties": {
+            "kind": {"const": "circle"},
+            "radius": {"type": "number", "exclusiveMinimum": 0}
+          }
+        },
+        "Square": {
+          "type": "object",
+          "required": ["kind", "side"],
+          "properties": {
+            "kind": {"const": "square"},
+            "side": {"type": "integer", "multipleOf": 2, "maximum": 10, "exclusiveMaximum": true}
+          }
+        },
+        "Drawing": {
+          "type": "object",
+          "nullable": true,
+          "properties": {
+            "shapes": {
+              "type": "array",
+              "uniqueItems": true,
+              "minItems": 1,
+              "items": {"$ref": "#/components/schemas/Shape"}
+            },
+            "parts": {"$ref": "#/components/schemas/Drawing"},
+            "tags": {"type": "string", "enum": ["draft", "final"]}
+          },
+          "additionalProperties": {"anyOf": [{"type": "boolean"}, {"type": "integer"}]},
+          "dependentRequired": {"tags": ["shapes"]},
+          "allOf": [{"$ref": "#/components/schemas/Drawing"}, {"maxProperties": 3}]
+        }
+      }
+    }"##;
+
+    fn compile(name: &str) -> (Components, ComponentOrInlineSchema, CompiledSchema) {
+        let components: Components = serde_json::from_str(COMPONENTS).unwrap();
+        let schema = ComponentOrInlineSchema::Component {
+            name: name.to_string().into(),
+        };
+        let compiled = CompiledSchema::new(&schema, &components).unwrap();
+        (components, schema, compiled)
+    }
+
+    #[test]
+    fn test_same_errors_as_validate_instance() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<CompiledSchema>();
+
+        let (components, schema, compiled) = compile("Drawing");
+        let instances = [
+            json!(null),
+            json!({}),
+            json!({"shapes": [{"kind": "circle", "radius": 1.5}, "dot"], "tags": "draft"}),
+            json!({"shapes": [], "parts": {"parts": null}, "x": 1, "y": 2}),
+            json!({"tags": "sketch", "x": 1.5, "parts": {"shapes": [{"kind": "square", "side": 3}]}}),
+            json!({"shapes": [{"kind": "square", "side": 10}, {"kind": "circle", "radius": 0}]}),
+            json!({"shapes": ["Dot", "circles", {"kind": "circle", "radius": 1}, "dot", "dot"]}),
+            json!({"shapes": [{"kind": "triangle"}]}),
+            json!([{"kind": "circle"}]),
+        ];
+        for instance in &instances {
+            let expected = components.validate_instance(&schema, instance);
+            assert_eq!(compiled.validate(instance), expected, "{}", instance);
+            assert_eq!(
+                compiled.is_valid(instance),
+                expected.is_empty(),
+                "{}",
+                instance
+            );
+        }
+        assert!(compiled.is_valid(&instances[2]));
+        let pointers: Vec<_> = compiled
+            .validate(&instances[6])
+            .into_iter()
+            .map(|e| (e.instance_pointer, e.schema_pointer))
+            .collect();
+        let shape = "/components/schemas/Shape/oneOf".to_string();
+        assert_eq!(
+            pointers,
+            [
+                (
+                    "/shapes".into(),
+                    "/components/schemas/Drawing/properties/shapes/uniqueItems".into()
+                ),
+                ("/shapes/0".into(), shape.clone()),
+                ("/shapes/1".into(), shape),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compile_errors() {
+        let components: Components = serde_json::from_str(COMPONENTS).unwrap();
+        let schema: ComponentOrInlineSchema = serde_json::from_value(json!({
+            "properties": {"shape": {"$ref": "#/components/schemas/Hexagon"}}
+        }))
+        .unwrap();
+        assert_eq!(
+            CompiledSchema::new(&schema, &components).unwrap_err(),
+            CompileError::Ref(RefError::Dangling("#/components/schemas/Hexagon".into()))
+        );
+
+        let schema: ComponentOrInlineSchema = serde_json::from_value(json!({
+            "items": {"type": "string", "pattern": "(unclosed"}
+        }))
+        .unwrap();
+        let err = CompiledSchema::new(&schema, &components).unwrap_err();
+        assert!(
+            matches!(&err, CompileError::Pattern { pointer, .. } if pointer == "/items/pattern"),
+            "{:?}",
+            err
+        );
+    }
+}
@@ -2,19 +2,24 @@ use crate::{
     v3_0::{
         Callback, Example, Header, Link, Parameter, RequestBody, Response, Schema, SecurityScheme,
     },
-    Str,
+    Extensions, Str,
 };
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
+/// Either an object, or a
+/// [Reference Object](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#referenceObject)
+/// pointing to one.
+///
+/// Objects with a `$ref` field are always references, as siblings of `$ref` are ignored.
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 #[serde(untagged)]
 pub enum ObjectOrReference<T> {
-    Object(T),
     Ref {
         #[serde(rename = "$ref")]
         ref_path: Str,
     },
+    Object(T),
 }
 
 /// Holds a set of reusable objects for different aspects of the OAS.
@@ -26,39 +31,42 @@ pub enum ObjectOrReference<T> {
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct Components {
     /// An object to hold reusable Schema Objects.
-    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
     pub schemas: IndexMap<Str, ObjectOrReference<Schema>>,
 
     /// An object to hold reusable Response Objects.
-    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
     pub responses: IndexMap<Str, ObjectOrReference<Response>>,
 
     /// An object to hold reusable Parameter Objects.
-    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
     pub parameters: IndexMap<Str, ObjectOrReference<Parameter>>,
 
     /// An object to hold reusable Example
-    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
     pub examples: IndexMap<Str, ObjectOrReference<Example>>,
 
     /// An object to hold reusable Request Body Objects.
-    #[serde(skip_serializing_if = "IndexMap::is_empty", rename = "requestBodies")]
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty", rename = "requestBodies")]
     pub request_bodies: IndexMap<Str, ObjectOrReference<RequestBody>>,
 
     /// An object to hold reusable Header Objects.
-    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
     pub headers: IndexMap<Str, ObjectOrReference<Header>>,
 
     /// An object to hold reusable Security Scheme Objects.
-    #[serde(skip_serializing_if = "IndexMap::is_empty", rename = "securitySchemes")]
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty", rename = "securitySchemes")]
     pub security_schemes: IndexMap<Str, ObjectOrReference<SecurityScheme>>,
 
     /// An object to hold reusable Link Objects.
-    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
     pub links: IndexMap<Str, ObjectOrReference<Link>>,
 
     /// An object to hold reusable Callback Objects.
-    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
     pub callbacks: IndexMap<Str, ObjectOrReference<Callback>>,
-    // TODO: Add "Specification Extensions" https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions}
+
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
@@ -0,0 +1,496 @@
+//! Replacement of references with the objects they point to.
+
+use crate::{
+    json_pointer,
+    v3_0::{
+        resolve::component_ref, Callback, Component, ComponentOrInlineSchema, Encoding, Header,
+        MediaType, MediaTypeExample, ObjectOrReference, Operation, Parameter, ParameterExamples,
+        ParameterRepresentation, PathItem, RefError, RequestBody, Response, Schema, Spec,
+    },
+    Str,
+};
+
+/// What [`Spec::dereference`] does with a reference to an object which is being inlined, such
+/// as the reference to a tree node schema from its `children` property.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Recursion {
+    /// Keeps the reference at the point where the object would contain itself.
+    Keep,
+    /// Fails with [`RefError::Cycle`].
+    Error,
+}
+
+impl Spec {
+    /// Returns a copy of this specification where every reference to a component, as well as
+    /// every [`PathItem::reference`] to another path, is replaced with the object it refers to.
+    ///
+    /// Components are dereferenced too, and kept so that references preserved with
+    /// [`Recursion::Keep`] stay valid. References to other documents fail with
+    /// [`RefError::Unsupported`], use [`bundle`](crate::v3_0::bundle) to load them first.
+    pub fn dereference(&self, recursion: Recursion) -> Result<Spec, RefError> {
+        let mut deref = Dereferencer {
+            spec: self,
+            recursion,
+            inlining: Vec::new(),
+        };
+        let mut spec = self.clone();
+        for item in spec.paths.values_mut() {
+            deref.path_item(item)?;
+        }
+        if let Some(components) = &mut spec.components {
+            macro_rules! components {
+                ($($field:ident),*) => {
+                    $(for (name, object) in &mut components.$field {
+                        deref.component(name, object)?;
+                    })*
+                };
+            }
+            components!(
+                schemas,
+                responses,
+                parameters,
+                examples,
+                request_bodies,
+                headers,
+                security_schemes,
+                links,
+                callbacks
+            );
+        }
+        Ok(spec)
+    }
+}
+
+struct Dereferencer<'a> {
+    spec: &'a Spec,
+    recursion: Recursion,
+    /// References being inlined, outermost first.
+    inlining: Vec<Str>,
+}
+
+/// Objects which may contain references.
+trait Dereference {
+    fn dereference(&mut self, deref: &mut Dereferencer) -> Result<(), RefError>;
+}
+
+impl<'a> Dereferencer<'a> {
+    /// Calls `f` while `reference` is being inlined.
+    ///
+    /// Returns `Ok(false)` if `reference` is already being inlined and should be kept.
+    fn inlining<F>(&mut self, reference: &str, f: F) -> Result<bool, RefError>
+    where
+        F: FnOnce(&mut Self) -> Result<(), RefError>,
+    {
+        if let Some(start) = self.inlining.iter().position(|r| r == reference) {
+            return match self.recursion {
+                Recursion::Keep => Ok(false),
+                Recursion::Error => {
+                    let mut cycle = self.inlining[start..].to_vec();
+                    cycle.push(reference.to_string().into());
+                    Err(RefError::Cycle(cycle))
+                }
+            };
+        }
+        self.inlining.push(reference.to_string().into());
+        let result = f(self);
+        self.inlining.pop();
+        result.map(|()| true)
+    }
+
+    fn object<T>(&mut self, object: &mut ObjectOrReference<T>) -> Result<(), RefError>
+    where
+        T: Component + Clone + Dereference,
+    {
+        let reference = match object {
+            ObjectOrReference::Object(object) => return object.dereference(self),
+            ObjectOrReference::Ref { ref_path } => ref_path.clone(),
+        };
+        let mut target = self.spec.resolve_ref::<T>(&reference)?.clone();
+        if self.inlining(&reference, |deref| target.dereference(deref))? {
+            *object = ObjectOrReference::Object(target);
+        }
+        Ok(())
+    }
+
+    /// Dereferences the component `name`, which is being inlined while doing so.
+    fn component<T>(
+        &mut self,
+        name: &str,
+        object: &mut ObjectOrReference<T>,
+    ) -> Result<(), RefError>
+    where
+        T: Component + Clone + Dereference,
+    {
+        let reference = component_ref::<T>(name);
+        self.inlining(&reference, |deref| deref.object(object))?;
+        Ok(())
+    }
+
+    fn schema(&mut self, schema: &mut ComponentOrInlineSchema) -> Result<(), RefError> {
+        let name = match schema {
+            ComponentOrInlineSchema::Inline(schema) => return schema.dereference(self),
+            ComponentOrInlineSchema::Component { name } => name.clone(),
+            ComponentOrInlineSchema::ExtRef { reference } => {
+                return Err(RefError::Unsupported {
+                    reference: reference.clone(),
+                    expected: Schema::KIND,
+                })
+            }
+        };
+        let mut reference = ObjectOrReference::Ref {
+            ref_path: component_ref::<Schema>(&name),
+        };
+        self.object(&mut reference)?;
+        if let ObjectOrReference::Object(target) = reference {
+            *schema = ComponentOrInlineSchema::Inline(target);
+        }
+        Ok(())
+    }
+
+    fn path_item(&mut self, item: &mut PathItem) -> Result<(), RefError> {
+        if !item.reference.is_empty() {
+            let reference = item.reference.clone();
+            let mut target = self.path(&reference)?.clone();
+            if !self.inlining(&reference, |deref| deref.path_item(&mut target))? {
+                return item.dereference(self);
+            }
+            merge(item, target);
+        }
+        item.dereference(self)
+    }
+
+    /// Looks up the path item `reference` points to, such as `#/paths/~1pets`.
+    fn path(&self, reference: &str) -> Result<&'a PathItem, RefError> {
+        let tokens = reference
+            .strip_prefix('#')
+            .and_then(json_pointer::split)
+            .unwrap_or_default();
+        match tokens.as_slice() {
+            [paths, path] if paths == "paths" => self
+                .spec
+                .paths
+                .get(&**path)
+                .ok_or_else(|| RefError::Dangling(reference.to_string().into())),
+            _ => Err(RefError::Unsupported {
+                reference: reference.to_string().into(),
+                expected: "paths",
+            }),
+        }
+    }
+}
+
+/// Completes `item` with the fields of the path item it refers to, which it overrides.
+fn merge(item: &mut PathItem, target: PathItem) {
+    let PathItem {
+        reference: _,
+        summary,
+        description,
+        get,
+        put,
+        post,
+        delete,
+        options,
+        head,
+        patch,
+        trace,
+        servers,
+        parameters,
+        extensions,
+    } = target;
+    item.reference = Str::default();
+    if item.summary.is_empty() {
+        item.summary = summary;
+    }
+    if item.description.is_empty() {
+        item.description = description;
+    }
+    item.get = item.get.take().or(get);
+    item.put = item.put.take().or(put);
+    item.post = item.post.take().or(post);
+    item.delete = item.delete.take().or(delete);
+    item.options = item.options.take().or(options);
+    item.head = item.head.take().or(head);
+    item.patch = item.patch.take().or(patch);
+    item.trace = item.trace.take().or(trace);
+    if item.servers.is_empty() {
+        item.servers = servers;
+    }
+    if item.parameters.is_empty() {
+        item.parameters = parameters;
+    }
+    for (key, value) in extensions {
+        if !item.extensions.contains_key(&key) {
+            // Cannot fail, the key comes from other extensions.
+            let _ = item.extensions.insert(key, value);
+        }
+    }
+}
+
+impl Dereference for PathItem {
+    fn dereference(&mut self, deref: &mut Dereferencer) -> Result<(), RefError> {
+        for operation in [
+            &mut self.get,
+            &mut self.put,
+            &mut self.post,
+            &mut self.delete,
+            &mut self.options,
+            &mut self.head,
+            &mut self.patch,
+            &mut self.trace,
+        ]
+        .iter_mut()
+        .filter_map(|operation| operation.as_mut())
+        {
+            operation.dereference(deref)?;
+        }
+        for parameter in &mut self.parameters {
+            deref.object(parameter)?;
+        }
+        Ok(())
+    }
+}
+
+impl Dereference for Operation {
+    fn dereference(&mut self, deref: &mut Dereferencer) -> Result<(), RefError> {
+        for parameter in &mut self.parameters {
+            deref.object(parameter)?;
+        }
+        if let Some(body) = &mut self.request_body {
+            deref.object(body)?;
+        }
+        for response in self.responses.values_mut() {
+            deref.object(response)?;
+        }
+        for callback in self.callbacks.values_mut() {
+            deref.object(callback)?;
+        }
+        Ok(())
+    }
+}
+
+impl Dereference for Callback {
+    fn dereference(&mut self, deref: &mut Dereferencer) -> Result<(), RefError> {
+        for item in self.values_mut() {
+            deref.path_item(item)?;
+        }
+        Ok(())
+    }
+}
+
+impl Dereference for Parameter {
+    fn dereference(&mut self, deref: &mut Dereferencer) -> Result<(), RefError> {
+        match &mut self.representation {
+            Some(ParameterRepresentation::Simple { schema }) => deref.schema(schema)?,
+            Some(ParameterRepresentation::Content { content }) => {
+                for media in content.values_mut() {
+                    media.dereference(deref)?;
+                }
+            }
+            None => {}
+        }
+        if let Some(ParameterExamples::Multiple { examples }) = &mut self.example {
+            for example in examples.values_mut() {
+                deref.object(example)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Dereference for RequestBody {
+    fn dereference(&mut self, deref: &mut Dereferencer) -> Result<(), RefError> {
+        for media in self.content.values_mut() {
+            media.dereference(deref)?;
+        }
+        Ok(())
+    }
+}
+
+impl Dereference for Response {
+    fn dereference(&mut self, deref: &mut Dereferencer) -> Result<(), RefError> {
+        for header in self.headers.values_mut() {
+            deref.object(header)?;
+        }
+        for media in self.content.values_mut() {
+            media.dereference(deref)?;
+        }
+        for link in self.links.values_mut() {
+            deref.object(link)?;
+        }
+        Ok(())
+    }
+}
+
+impl Dereference for Header {
+    fn dereference(&mut self, deref: &mut Dereferencer) -> Result<(), RefError> {
+        match &mut self.schema {
+            Some(schema) => deref.schema(schema),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Dereference for MediaType {
+    fn dereference(&mut self, deref: &mut Dereferencer) -> Result<(), RefError> {
+        if let Some(schema) = &mut self.schema {
+            deref.schema(schema)?;
+        }
+        if let Some(MediaTypeExample::Examples { examples }) = &mut self.examples {
+            for example in examples.values_mut() {
+                deref.object(example)?;
+            }
+        }
+        for encoding in self.encoding.values_mut() {
+            encoding.dereference(deref)?;
+        }
+        Ok(())
+    }
+}
+
+impl Dereference for Encoding {
+    fn dereference(&mut self, deref: &mut Dereferencer) -> Result<(), RefError> {
+        for header in self.headers.values_mut() {
+            deref.object(header)?;
+        }
+        Ok(())
+    }
+}
+
+impl Dereference for Schema {
+    fn dereference(&mut self, deref: &mut Dereferencer) -> Result<(), RefError> {
+        if let Some(items) = &mut self.items {
+            deref.schema(items)?;
+        }
+        for property in self.properties.values_mut() {
+            deref.schema(property)?;
+        }
+        if let Some(additional) = &mut self.additional_properties {
+            deref.schema(additional)?;
+        }
+        for schema in self
+            .all_of
+            .iter_mut()
+            .chain(&mut self.one_of)
+            .chain(&mut self.any_of)
+        {
+            deref.schema(schema)?;
+        }
+        Ok(())
+    }
+}
+
+/// Objects without references.
+macro_rules! dereference_leaf {
+    ($($ty:ty),*) => {
+        $(impl Dereference for $ty {
+            fn dereference(&mut self, _: &mut Dereferencer) -> Result<(), RefError> {
+                Ok(())
+            }
+        })*
+    };
+}
+
+dereference_leaf!(
+    crate::v3_0::Example,
+    crate::v3_0::Link,
+    crate::v3_0::SecurityScheme
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r##"{
+      "openapi": "3.0.3",
+      "info": {"title": "Tree", "version": "1.0.0"},
+      "paths": {
+        "/nodes": {
+          "get": {
+            "parameters": [{"$ref": "#/components/parameters/Depth"}],
+            "responses": {
+              "200": {
+                "description": "A tree",
+                "content": {
+                  "application/json": {"schema": {"$ref": "#/components/schemas/Node"}}
+                }
+              }
+            }
+          }
+        },
+        "/trees": {"$ref": "#/paths/~1nodes", "summary": "Alias"}
+      },
+      "components": {
+        "schemas": {
+          "Name": {"type": "string"},
+          "Node": {
+            "type": "object",
+            "properties": {
+              "name": {"$ref": "#/components/schemas/Name"},
+              "children": {"type": "array", "items": {"$ref": "#/components/schemas/Node"}}
+            }
+          }
+        },
+        "parameters": {
+          "Depth": {"name": "depth", "in": "query", "schema": {"type": "integer"}}
+        }
+      }
+    }"##;
+
+    fn node(spec: &Spec, path: &str) -> Schema {
+        let get = spec.paths[path].get.as_ref().unwrap();
+        let response = match &get.responses["200"] {
+            ObjectOrReference::Object(response) => response,
+            other => panic!("expected a response, got {:?}", other),
+        };
+        match &response.content["application/json"].schema {
+            Some(ComponentOrInlineSchema::Inline(schema)) => schema.clone(),
+            other => panic!("expected an inline schema, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_dereference_keep() {
+        let spec: Spec = serde_json::from_str(SPEC).unwrap();
+        let spec = spec.dereference(Recursion::Keep).unwrap();
+
+        let node = node(&spec, "/nodes");
+        let name = node.properties["name"].unwrap().unwrap();
+        assert_eq!(name.schema_type, Some(crate::v3_0::Type::String));
+        let children = node.properties["children"].unwrap().unwrap();
+        assert_eq!(
+            children.items.as_deref(),
+            Some(&ComponentOrInlineSchema::Component {
+                name: "Node".into()
+            })
+        );
+
+        let trees = &spec.paths["/trees"];
+        assert_eq!(trees.reference, "");
+        assert_eq!(trees.summary, "Alias");
+        let get = trees.get.as_ref().unwrap();
+        assert!(matches!(
+            &get.parameters[0],
+            ObjectOrReference::Object(parameter) if parameter.name == "depth"
+        ));
+
+        // The result still serializes.
+        let json = serde_json::to_string(&spec).unwrap();
+        assert_eq!(serde_json::from_str::<Spec>(&json).unwrap(), spec);
+    }
+
+    #[test]
+    fn test_dereference_errors() {
+        let spec: Spec = serde_json::from_str(SPEC).unwrap();
+        let err = spec.dereference(Recursion::Error).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "cyclic reference: `#/components/schemas/Node` -> `#/components/schemas/Node`"
+        );
+
+        let spec: Spec = serde_json::from_str(&SPEC.replace("~1nodes", "~1missing")).unwrap();
+        assert_eq!(
+            spec.dereference(Recursion::Keep).unwrap_err(),
+            RefError::Dangling("#/paths/~1missing".into())
+        );
+    }
+}
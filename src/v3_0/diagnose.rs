@@ -0,0 +1,389 @@
+//! Locating deserialization failures in OpenAPI 3.0 documents.
+
+use crate::{
+    json_pointer,
+    parse::{
+        child, diagnose_leaf, diagnose_struct, required_str, untagged, Diagnose, Failure, Tree,
+    },
+    v3_0::*,
+    Str,
+};
+use indexmap::IndexMap;
+use url::Url;
+
+diagnose_leaf!(
+    Type,
+    Location,
+    ParameterStyle,
+    RuntimeExpressionOrValue
+);
+
+// Fields of the structs in `schema.rs`, checked against them by `test_diagnosed_fields`.
+diagnose_struct! {
+    Spec {
+        "openapi" => Str,
+        "info" => Info,
+        "servers" => Vec<Server>,
+        "paths" => IndexMap<Str, PathItem>,
+        "components" => Option<Components>,
+        "security" => Vec<SecurityRequirement>,
+        "tags" => Vec<Tag>,
+        "externalDocs" => Option<ExternalDoc>,
+    }
+    Info {
+        "title" => Str,
+        "description" => Str,
+        "termsOfService" => Option<Url>,
+        "version" => Str,
+        "contact" => Option<Contact>,
+        "license" => Option<License>,
+    }
+    Contact {
+        "name" => Str,
+        "url" => Option<Url>,
+        "email" => Str,
+    }
+    License {
+        "name" => Str,
+        "url" => Option<Url>,
+    }
+    Server {
+        "url" => Str,
+        "description" => Str,
+        "variables" => IndexMap<Str, ServerVariable>,
+    }
+    ServerVariable {
+        "default" => Str,
+        "enum" => Vec<Str>,
+        "description" => Str,
+    }
+    PathItem {
+        "$ref" => Str,
+        "summary" => Str,
+        "description" => Str,
+        "get" => Option<Operation>,
+        "put" => Option<Operation>,
+        "post" => Option<Operation>,
+        "delete" => Option<Operation>,
+        "options" => Option<Operation>,
+        "head" => Option<Operation>,
+        "patch" => Option<Operation>,
+        "trace" => Option<Operation>,
+        "servers" => Vec<Server>,
+        "parameters" => Vec<ObjectOrReference<Parameter>>,
+    }
+    Operation {
+        "tags" => Vec<Str>,
+        "summary" => Str,
+        "description" => Str,
+        "externalDocs" => Option<ExternalDoc>,
+        "operationId" => Str,
+        "parameters" => Vec<ObjectOrReference<Parameter>>,
+        "requestBody" => Option<ObjectOrReference<RequestBody>>,
+        "responses" => IndexMap<Str, ObjectOrReference<Response>>,
+        "callbacks" => IndexMap<Str, ObjectOrReference<Callback>>,
+        "deprecated" => Option<bool>,
+        "security" => Option<Vec<SecurityRequirement>>,
+        "servers" => Vec<Server>,
+    }
+    Parameter {
+        "name" => Str,
+        "in" => Location,
+        "description" => Str,
+        "required" => Option<bool>,
+        "deprecated" => Option<bool>,
+        "style" => Option<ParameterStyle>,
+        "explode" => Option<bool>,
+        "allowReserved" => Option<bool>,
+        "schema" => ComponentOrInlineSchema,
+        "content" => IndexMap<Str, MediaType>,
+        "examples" => IndexMap<Str, ObjectOrReference<Example>>,
+    }
+    Schema {
+        "description" => Str,
+        "format" => Str,
+        "items" => Option<Box<ComponentOrInlineSchema>>,
+        "properties" => IndexMap<Str, ComponentOrInlineSchema>,
+        "readOnly" => Option<bool>,
+        "writeOnly" => Option<bool>,
+        "nullable" => Option<bool>,
+        "additionalProperties" => Option<Box<ComponentOrInlineSchema>>,
+        "title" => Str,
+        "allOf" => Vec<ComponentOrInlineSchema>,
+        "oneOf" => Vec<ComponentOrInlineSchema>,
+        "anyOf" => Vec<ComponentOrInlineSchema>,
+        "type" => Option<Type>,
+        "enum" => Vec<Str>,
+        "maxLength" => Option<usize>,
+        "minLength" => Option<usize>,
+        "pattern" => Str,
+        "maxItems" => Option<usize>,
+        "minItems" => Option<usize>,
+        "uniqueItems" => Option<bool>,
+        "maxProperties" => Option<usize>,
+        "minProperties" => Option<usize>,
+        "required" => Vec<Str>,
+        "dependentRequired" => IndexMap<Str, Vec<Str>>,
+    }
+    Response {
+        "description" => Str,
+        "headers" => IndexMap<Str, ObjectOrReference<Header>>,
+        "content" => IndexMap<Str, MediaType>,
+        "links" => IndexMap<Str, ObjectOrReference<Link>>,
+    }
+    Header {
+        "required" => Option<bool>,
+        "schema" => Option<ComponentOrInlineSchema>,
+        "uniqueItems" => Option<bool>,
+        "type" => Option<Type>,
+        "format" => Str,
+        "description" => Str,
+    }
+    RequestBody {
+        "description" => Str,
+        "content" => IndexMap<Str, MediaType>,
+        "required" => Option<bool>,
+    }
+    MediaType {
+        "schema" => Option<ComponentOrInlineSchema>,
+        "examples" => IndexMap<Str, ObjectOrReference<Example>>,
+        "encoding" => IndexMap<Str, Encoding>,
+    }
+    Encoding {
+        "contentType" => Str,
+        "headers" => IndexMap<Str, ObjectOrReference<Header>>,
+        "style" => Str,
+        "explode" => Option<bool>,
+        "allowReserved" => Option<bool>,
+    }
+    Example {
+        "summary" => Str,
+        "description" => Str,
+        "externalValue" => Str,
+    }
+    Flows {
+        "implicit" => Option<ImplicitFlow>,
+        "password" => Option<PasswordFlow>,
+        "clientCredentials" => Option<ClientCredentialsFlow>,
+        "authorizationCode" => Option<AuthorizationCodeFlow>,
+    }
+    ImplicitFlow {
+        "authorizationUrl" => Url,
+        "refreshUrl" => Option<Url>,
+        "scopes" => IndexMap<Str, Str>,
+    }
+    PasswordFlow {
+        "tokenUrl" => Url,
+        "refreshUrl" => Option<Url>,
+        "scopes" => IndexMap<Str, Str>,
+    }
+    ClientCredentialsFlow {
+        "tokenUrl" => Url,
+        "refreshUrl" => Option<Url>,
+        "scopes" => IndexMap<Str, Str>,
+    }
+    AuthorizationCodeFlow {
+        "authorizationUrl" => Url,
+        "tokenUrl" => Url,
+        "refreshUrl" => Option<Url>,
+        "scopes" => IndexMap<Str, Str>,
+    }
+    Tag {
+        "name" => Str,
+        "description" => Str,
+        "externalDocs" => Option<ExternalDoc>,
+    }
+    ExternalDoc {
+        "url" => Url,
+        "description" => Str,
+    }
+    Components {
+        "schemas" => IndexMap<Str, ObjectOrReference<Schema>>,
+        "responses" => IndexMap<Str, ObjectOrReference<Response>>,
+        "parameters" => IndexMap<Str, ObjectOrReference<Parameter>>,
+        "examples" => IndexMap<Str, ObjectOrReference<Example>>,
+        "requestBodies" => IndexMap<Str, ObjectOrReference<RequestBody>>,
+        "headers" => IndexMap<Str, ObjectOrReference<Header>>,
+        "securitySchemes" => IndexMap<Str, ObjectOrReference<SecurityScheme>>,
+        "links" => IndexMap<Str, ObjectOrReference<Link>>,
+        "callbacks" => IndexMap<Str, ObjectOrReference<Callback>>,
+    }
+}
+
+impl<T: Diagnose> Diagnose for ObjectOrReference<T> {
+    fn diagnose<V: Tree>(value: &V, pointer: &mut String) -> Option<Failure> {
+        let message = value.deserialize::<Self>().err()?;
+        let likely = if value.get("$ref").is_some() { 0 } else { 1 };
+        let attempts = vec![
+            ("Ref", required_str(value, "$ref", pointer)),
+            ("Object", T::diagnose(value, pointer)),
+        ];
+        Some(untagged(pointer, message, likely, attempts))
+    }
+}
+
+impl Diagnose for ComponentOrInlineSchema {
+    fn diagnose<V: Tree>(value: &V, pointer: &mut String) -> Option<Failure> {
+        let message = value.deserialize::<Self>().err()?;
+        let likely = if value.get("$ref").is_some() { 0 } else { 2 };
+        let component = required_str(value, "$ref", pointer).or_else(|| {
+            let mut pointer = pointer.clone();
+            json_pointer::push(&mut pointer, "$ref");
+            Some(Failure::new(
+                &pointer,
+                "not a component schema reference path".to_string(),
+            ))
+        });
+        let attempts = vec![
+            ("Component", component),
+            ("ExtRef", required_str(value, "$ref", pointer)),
+            ("Inline", Schema::diagnose(value, pointer)),
+        ];
+        Some(untagged(pointer, message, likely, attempts))
+    }
+}
+
+impl Diagnose for Link {
+    fn diagnose<V: Tree>(value: &V, pointer: &mut String) -> Option<Failure> {
+        let message = value.deserialize::<Self>().err()?;
+        let failure =
+            child::<IndexMap<Str, RuntimeExpressionOrValue>, V>(value, "parameters", pointer)
+                .or_else(|| child::<Str, V>(value, "description", pointer))
+                .or_else(|| child::<Option<Server>, V>(value, "server", pointer));
+        if failure.is_some() {
+            return failure;
+        }
+        let likely = if value.get("operationRef").is_some() {
+            1
+        } else {
+            0
+        };
+        let attempts = vec![
+            ("Id", required_str(value, "operationId", pointer)),
+            ("Ref", required_str(value, "operationRef", pointer)),
+        ];
+        Some(untagged(pointer, message, likely, attempts))
+    }
+}
+
+impl Diagnose for SecurityScheme {
+    fn diagnose<V: Tree>(value: &V, pointer: &mut String) -> Option<Failure> {
+        let message = value.deserialize::<Self>().err()?;
+        if value.get("type").and_then(Tree::as_str) == Some("oauth2") {
+            if let Some(failure) = child::<Flows, V>(value, "flows", pointer) {
+                return Some(failure);
+            }
+        }
+        Some(Failure::new(pointer, message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    /// The keys and types of the fields of each struct in `source`, as serde names them.
+    /// Flattened fields are left out, as well as JSON values, which cannot fail to deserialize.
+    fn fields(source: &str) -> BTreeMap<String, (bool, BTreeMap<String, String>)> {
+        let mut structs = BTreeMap::new();
+        let mut attributes = Vec::new();
+        let mut current: Option<(String, bool, bool, BTreeMap<String, String>)> = None;
+        for line in source.lines().map(str::trim) {
+            if line.starts_with("#[") {
+                attributes.push(line.to_string());
+                continue;
+            }
+            if line.is_empty() || line.starts_with("//") {
+                continue;
+            }
+            let serde = |key: &str| {
+                attributes
+                    .iter()
+                    .filter(|attribute| attribute.starts_with("#[serde("))
+                    .any(|attribute| attribute.contains(key))
+            };
+            match &mut current {
+                None => {
+                    let name = line
+                        .strip_prefix("pub struct ")
+                        .and_then(|rest| rest.strip_suffix(" {"));
+                    if let Some(name) = name {
+                        let camel_case = serde("rename_all = \"camelCase\"");
+                        current = Some((name.to_string(), camel_case, false, BTreeMap::new()));
+                    }
+                }
+                Some((name, _, flattened, fields)) if line == "}" => {
+                    structs.insert(name.clone(), (*flattened, std::mem::take(fields)));
+                    current = None;
+                }
+                Some((_, camel_case, flattened, fields)) => {
+                    let line = line.trim_start_matches("pub(crate) ");
+                    let line = line.trim_start_matches("pub ").trim_end_matches(',');
+                    let (field, ty) = line.split_once(": ").expect(line);
+                    if serde("flatten") {
+                        *flattened |= !ty.ends_with("Extensions");
+                    } else if !ty.contains("serde_json") && !serde("skip,") {
+                        let rename = attributes.iter().find_map(|attribute| {
+                            let rest = attribute.split("rename = \"").nth(1)?;
+                            Some(rest.split('"').next()?.to_string())
+                        });
+                        let key = rename.unwrap_or_else(|| match camel_case {
+                            true => camel(field),
+                            false => field.to_string(),
+                        });
+                        fields.insert(key, ty.replace(' ', ""));
+                    }
+                }
+            }
+            attributes.clear();
+        }
+        structs
+    }
+
+    fn camel(field: &str) -> String {
+        let mut parts = field.split('_');
+        let mut key = parts.next().unwrap_or_default().to_string();
+        for part in parts {
+            let mut chars = part.chars();
+            key.extend(chars.next().map(|c| c.to_ascii_uppercase()));
+            key.extend(chars);
+        }
+        key
+    }
+
+    /// The keys and types listed for each struct by `diagnose_struct!`.
+    fn diagnosed(source: &str) -> BTreeMap<String, BTreeMap<String, String>> {
+        let start = source.find("diagnose_struct! {\n").unwrap();
+        let end = start + source[start..].find("\n}\n").unwrap();
+        let mut structs = BTreeMap::new();
+        let mut current = None;
+        for line in source[start..end].lines().skip(1).map(str::trim) {
+            if let Some(name) = line.strip_suffix(" {") {
+                current = Some(name.to_string());
+            } else if let Some((key, ty)) = line.trim_end_matches(',').split_once(" => ") {
+                let fields: &mut BTreeMap<_, _> =
+                    structs.entry(current.clone().unwrap()).or_default();
+                fields.insert(key.trim_matches('"').to_string(), ty.replace(' ', ""));
+            }
+        }
+        structs
+    }
+
+    #[test]
+    fn test_diagnosed_fields() {
+        let mut structs = fields(include_str!("schema.rs"));
+        structs.extend(fields(include_str!("components.rs")));
+        let diagnosed = diagnosed(include_str!("diagnose.rs"));
+        assert!(diagnosed.len() > 20);
+        for (name, diagnosed) in &diagnosed {
+            let (flattened, fields) = &structs[name];
+            for (key, ty) in fields {
+                assert_eq!(diagnosed.get(key), Some(ty), "{}.{}", name, key);
+            }
+            // The keys of flattened enums are listed as well.
+            if !flattened {
+                assert_eq!(diagnosed.len(), fields.len(), "{}", name);
+            }
+        }
+    }
+}
@@ -0,0 +1,1035 @@
+//! Comparison of two versions of a specification, to find the changes which break clients.
+
+use crate::{
+    json_pointer,
+    v3_0::{
+        ComponentOrInlineSchema, Header, Location, MediaType,
+        ObjectOrReference, Operation, Parameter, ParameterRepresentation, PathItem, RequestBody,
+        Response, Schema, Spec, Type,
+    },
+    Str,
+};
+use indexmap::IndexMap;
+use serde_json::Value;
+use std::{collections::HashSet, fmt};
+
+/// How a [`Change`] affects the clients of an API.
+///
+/// Severities are ordered from the least to the most disruptive.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Documentation only, such as a new description or a deprecation.
+    Informational,
+    /// Existing clients keep working, such as when an optional request property is added.
+    NonBreaking,
+    /// Existing clients may fail, such as when a required request property is added.
+    Breaking,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Informational => "informational",
+            Severity::NonBreaking => "non-breaking",
+            Severity::Breaking => "breaking",
+        })
+    }
+}
+
+/// What happened to the node at [`Change::pointer`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+/// A difference between two versions of a specification.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Change {
+    /// JSON pointer of the changed node, as if every reference was inlined, e.g.
+    /// `/paths/~1pets/get/responses/200/content/application~1json/schema/properties/name`.
+    /// Array indices are those of the new version, unless the item was removed.
+    pub pointer: String,
+    pub kind: ChangeKind,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`{}`: {}", self.pointer, self.message)
+    }
+}
+
+impl Spec {
+    /// Lists the changes from this version of the specification to `new`, following references
+    /// in both so that moving an object into the components is not a change.
+    ///
+    /// Changes are classified by the direction of the data: a new constraint breaks clients
+    /// sending requests, but not clients receiving responses, and the other way around.
+    /// Removing an enum value breaks clients in both directions, as they may still send it or
+    /// rely on receiving it. Paths, operations, parameters, request bodies, responses, headers,
+    /// media types and schemas are compared, callbacks, links, security and servers are not.
+    pub fn diff(&self, new: &Spec) -> Vec<Change> {
+        let mut differ = Differ {
+            old: self,
+            new,
+            pointer: String::new(),
+            direction: Direction::Request,
+            comparing: HashSet::new(),
+            changes: Vec::new(),
+        };
+        differ.at("paths", |d| {
+            d.map(
+                &self.paths,
+                &new.paths,
+                "path",
+                Severity::NonBreaking,
+                |d, old, new| d.path_item(old, new),
+            )
+        });
+        differ.changes
+    }
+}
+
+/// Whether data is sent by clients or received by them.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+enum Direction {
+    Request,
+    Response,
+}
+
+/// Fields of [`PathItem`] holding operations, in order.
+pub(crate) const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+pub(crate) fn operations(item: &PathItem) -> [Option<&Operation>; 8] {
+    [
+        item.get.as_ref(),
+        item.put.as_ref(),
+        item.post.as_ref(),
+        item.delete.as_ref(),
+        item.options.as_ref(),
+        item.head.as_ref(),
+        item.patch.as_ref(),
+        item.trace.as_ref(),
+    ]
+}
+
+/// Whether `value` is a multiple of `divisor`. No value is a multiple of a `divisor` which is
+/// not positive, as `multipleOf` must be.
+pub(crate) fn is_multiple_of(value: &serde_json::Number, divisor: &serde_json::Number) -> bool {
+    if let (Some(value), Some(divisor)) = (value.as_i64(), divisor.as_i64()) {
+        return divisor > 0 && value.checked_rem(divisor) == Some(0);
+    }
+    match (value.as_f64(), divisor.as_f64()) {
+        (Some(value), Some(divisor)) if divisor > 0.0 => {
+            let quotient = value / divisor;
+            (quotient - quotient.round()).abs() < 1e-9
+        }
+        _ => false,
+    }
+}
+
+/// The name of `location` in specifications, such as `query`.
+pub(crate) fn location_name(location: Location) -> String {
+    match serde_json::to_value(location) {
+        Ok(Value::String(name)) => name,
+        _ => format!("{:?}", location),
+    }
+}
+
+fn show<T: fmt::Display>(value: &Option<T>) -> String {
+    match value {
+        Some(value) => format!("`{}`", value),
+        None => "none".to_string(),
+    }
+}
+
+struct Differ<'a> {
+    old: &'a Spec,
+    new: &'a Spec,
+    pointer: String,
+    direction: Direction,
+    /// Pairs of component schemas being compared, so that recursive schemas terminate.
+    comparing: HashSet<(Str, Str, Direction)>,
+    changes: Vec<Change>,
+}
+
+impl<'a> Differ<'a> {
+    fn report(&mut self, kind: ChangeKind, severity: Severity, message: String) {
+        self.changes.push(Change {
+            pointer: self.pointer.clone(),
+            kind,
+            severity,
+            message,
+        });
+    }
+
+    fn at<F: FnOnce(&mut Self)>(&mut self, token: &str, f: F) {
+        let len = self.pointer.len();
+        json_pointer::push(&mut self.pointer, token);
+        f(self);
+        self.pointer.truncate(len);
+    }
+
+    /// Severity of a change which restricts the valid data when `narrowed`, or allows more
+    /// data otherwise.
+    fn severity(&self, narrowed: bool) -> Severity {
+        match (self.direction, narrowed) {
+            (Direction::Request, true) | (Direction::Response, false) => Severity::Breaking,
+            (Direction::Request, false) | (Direction::Response, true) => Severity::NonBreaking,
+        }
+    }
+
+    fn narrowed(&mut self, narrowed: bool, message: String) {
+        let severity = self.severity(narrowed);
+        self.report(ChangeKind::Modified, severity, message);
+    }
+
+    /// Compares the entries of two maps. Removing an entry is breaking, adding one is `added`.
+    fn map<T, F>(
+        &mut self,
+        old: &IndexMap<Str, T>,
+        new: &IndexMap<Str, T>,
+        what: &str,
+        added: Severity,
+        mut f: F,
+    ) where
+        F: FnMut(&mut Self, &T, &T),
+    {
+        for (key, old) in old {
+            self.at(key, |d| match new.get(key) {
+                Some(new) => f(d, old, new),
+                None => d.report(
+                    ChangeKind::Removed,
+                    Severity::Breaking,
+                    format!("{} removed", what),
+                ),
+            });
+        }
+        for key in new.keys().filter(|key| !old.contains_key(*key)) {
+            self.at(key, |d| {
+                d.report(ChangeKind::Added, added, format!("{} added", what))
+            });
+        }
+    }
+
+    fn informational(&mut self, what: &str, old: &str, new: &str) {
+        if old != new {
+            self.at(what, |d| {
+                d.report(
+                    ChangeKind::Modified,
+                    Severity::Informational,
+                    format!("`{}` changed", what),
+                )
+            });
+        }
+    }
+
+    fn deprecated(&mut self, old: Option<bool>, new: Option<bool>) {
+        let (old, new) = (old == Some(true), new == Some(true));
+        if old != new {
+            let message = if new {
+                "deprecated"
+            } else {
+                "no longer deprecated"
+            };
+            self.report(
+                ChangeKind::Modified,
+                Severity::Informational,
+                message.to_string(),
+            );
+        }
+    }
+
+    fn path_item(&mut self, old: &PathItem, new: &PathItem) {
+        self.informational("summary", &old.summary, &new.summary);
+        self.informational("description", &old.description, &new.description);
+        self.at("parameters", |d| {
+            d.parameters(&old.parameters, &new.parameters)
+        });
+        for ((method, old), new) in METHODS.iter().zip(operations(old)).zip(operations(new)) {
+            self.at(method, |d| match (old, new) {
+                (Some(old), Some(new)) => d.operation(old, new),
+                (Some(_), None) => d.report(
+                    ChangeKind::Removed,
+                    Severity::Breaking,
+                    "operation removed".to_string(),
+                ),
+                (None, Some(_)) => d.report(
+                    ChangeKind::Added,
+                    Severity::NonBreaking,
+                    "operation added".to_string(),
+                ),
+                (None, None) => {}
+            });
+        }
+    }
+
+    fn operation(&mut self, old: &Operation, new: &Operation) {
+        self.informational("summary", &old.summary, &new.summary);
+        self.informational("description", &old.description, &new.description);
+        self.informational("operationId", &old.operation_id, &new.operation_id);
+        if old.deprecated != new.deprecated {
+            self.at("deprecated", |d| {
+                d.deprecated(old.deprecated, new.deprecated)
+            });
+        }
+        self.at("parameters", |d| {
+            d.parameters(&old.parameters, &new.parameters)
+        });
+        self.direction = Direction::Request;
+        self.at("requestBody", |d| {
+            let old = old
+                .request_body
+                .as_ref()
+                .and_then(|b| d.old.resolve(b).ok());
+            let new = new
+                .request_body
+                .as_ref()
+                .and_then(|b| d.new.resolve(b).ok());
+            d.request_body(old, new)
+        });
+        self.direction = Direction::Response;
+        self.at("responses", |d| {
+            d.map(
+                &old.responses,
+                &new.responses,
+                "response",
+                Severity::NonBreaking,
+                |d, old, new| {
+                    if let (Ok(old), Ok(new)) = (d.old.resolve(old), d.new.resolve(new)) {
+                        d.response(old, new)
+                    }
+                },
+            )
+        });
+        self.direction = Direction::Request;
+    }
+
+    /// Compares parameters, identified by their location and name.
+    fn parameters(
+        &mut self,
+        old: &[ObjectOrReference<Parameter>],
+        new: &[ObjectOrReference<Parameter>],
+    ) {
+        let old: Vec<_> = old
+            .iter()
+            .filter_map(|p| self.old.resolve(p).ok())
+            .collect();
+        let new: Vec<_> = new
+            .iter()
+            .filter_map(|p| self.new.resolve(p).ok())
+            .collect();
+        fn find(parameters: &[&Parameter], parameter: &Parameter) -> Option<usize> {
+            parameters
+                .iter()
+                .position(|p| p.name == parameter.name && p.location == parameter.location)
+        }
+        self.direction = Direction::Request;
+        for (i, old_parameter) in old.iter().enumerate() {
+            let name = format!(
+                "{} parameter `{}`",
+                location_name(old_parameter.location),
+                old_parameter.name
+            );
+            match find(&new, old_parameter) {
+                Some(j) => self.at(&j.to_string(), |d| {
+                    d.parameter(&name, old_parameter, new[j])
+                }),
+                None => self.at(&i.to_string(), |d| {
+                    d.report(
+                        ChangeKind::Removed,
+                        Severity::Breaking,
+                        format!("{} removed", name),
+                    )
+                }),
+            }
+        }
+        for (j, new_parameter) in new.iter().enumerate() {
+            if find(&old, new_parameter).is_none() {
+                let required = new_parameter.required == Some(true);
+                let (severity, which) = if required {
+                    (Severity::Breaking, "required")
+                } else {
+                    (Severity::NonBreaking, "optional")
+                };
+                let message = format!(
+                    "{} {} parameter `{}` added",
+                    which,
+                    location_name(new_parameter.location),
+                    new_parameter.name
+                );
+                self.at(&j.to_string(), |d| {
+                    d.report(ChangeKind::Added, severity, message)
+                });
+            }
+        }
+    }
+
+    fn parameter(&mut self, name: &str, old: &Parameter, new: &Parameter) {
+        self.informational("description", &old.description, &new.description);
+        self.required(name, old.required, new.required);
+        if old.deprecated != new.deprecated {
+            self.at("deprecated", |d| {
+                d.deprecated(old.deprecated, new.deprecated)
+            });
+        }
+        if old.style != new.style || old.explode != new.explode {
+            self.report(
+                ChangeKind::Modified,
+                Severity::Breaking,
+                format!("serialization of {} changed", name),
+            );
+        }
+        match (&old.representation, &new.representation) {
+            (
+                Some(ParameterRepresentation::Simple { schema: old }),
+                Some(ParameterRepresentation::Simple { schema: new }),
+            ) => self.at("schema", |d| d.schema(old, new)),
+            (
+                Some(ParameterRepresentation::Content { content: old }),
+                Some(ParameterRepresentation::Content { content: new }),
+            ) => self.at("content", |d| d.content(old, new)),
+            (old, new) if old != new => self.report(
+                ChangeKind::Modified,
+                Severity::Breaking,
+                format!("representation of {} changed", name),
+            ),
+            _ => {}
+        }
+    }
+
+    /// Compares the `required` flag of a parameter, header or request body.
+    fn required(&mut self, name: &str, old: Option<bool>, new: Option<bool>) {
+        let (old, new) = (old == Some(true), new == Some(true));
+        if old != new {
+            let message = if new {
+                "became required"
+            } else {
+                "became optional"
+            };
+            self.at("required", |d| {
+                d.narrowed(new, format!("{} {}", name, message))
+            });
+        }
+    }
+
+    fn request_body(&mut self, old: Option<&RequestBody>, new: Option<&RequestBody>) {
+        match (old, new) {
+            (Some(old), Some(new)) => {
+                self.informational("description", &old.description, &new.description);
+                self.required("request body", old.required, new.required);
+                self.at("content", |d| d.content(&old.content, &new.content));
+            }
+            (Some(_), None) => self.report(
+                ChangeKind::Removed,
+                Severity::Breaking,
+                "request body removed".to_string(),
+            ),
+            (None, Some(new)) if new.required == Some(true) => self.report(
+                ChangeKind::Added,
+                Severity::Breaking,
+                "required request body added".to_string(),
+            ),
+            (None, Some(_)) => self.report(
+                ChangeKind::Added,
+                Severity::NonBreaking,
+                "optional request body added".to_string(),
+            ),
+            (None, None) => {}
+        }
+    }
+
+    fn response(&mut self, old: &Response, new: &Response) {
+        self.informational("description", &old.description, &new.description);
+        self.at("headers", |d| {
+            d.map(
+                &old.headers,
+                &new.headers,
+                "header",
+                Severity::NonBreaking,
+                |d, old, new| {
+                    if let (Ok(old), Ok(new)) = (d.old.resolve(old), d.new.resolve(new)) {
+                        d.header(old, new)
+                    }
+                },
+            )
+        });
+        self.at("content", |d| d.content(&old.content, &new.content));
+    }
+
+    fn header(&mut self, old: &Header, new: &Header) {
+        self.informational("description", &old.description, &new.description);
+        self.required("header", old.required, new.required);
+        if let (Some(old), Some(new)) = (&old.schema, &new.schema) {
+            self.at("schema", |d| d.schema(old, new));
+        }
+    }
+
+    fn content(&mut self, old: &IndexMap<Str, MediaType>, new: &IndexMap<Str, MediaType>) {
+        self.map(
+            old,
+            new,
+            "media type",
+            Severity::NonBreaking,
+            |d, old, new| {
+                d.at("schema", |d| match (&old.schema, &new.schema) {
+                    (Some(old), Some(new)) => d.schema(old, new),
+                    (None, Some(_)) => d.narrowed(true, "schema added".to_string()),
+                    (Some(_), None) => d.narrowed(false, "schema removed".to_string()),
+                    (None, None) => {}
+                })
+            },
+        );
+    }
+
+    fn schema(&mut self, old: &ComponentOrInlineSchema, new: &ComponentOrInlineSchema) {
+        let key = match (old, new) {
+            (
+                ComponentOrInlineSchema::Component { name: old },
+                ComponentOrInlineSchema::Component { name: new },
+            ) => Some((old.clone(), new.clone(), self.direction)),
+            _ => None,
+        };
+        if let Some(key) = &key {
+            if !self.comparing.insert(key.clone()) {
+                return;
+            }
+        }
+        match (self.old.resolve_schema(old), self.new.resolve_schema(new)) {
+            (Ok(old), Ok(new)) => self.schema_object(old, new),
+            (Err(err), _) | (_, Err(err)) => {
+                if old != new {
+                    self.report(
+                        ChangeKind::Modified,
+                        Severity::Breaking,
+                        format!("schema changed, and cannot be compared: {}", err),
+                    );
+                }
+            }
+        }
+        if let Some(key) = &key {
+            self.comparing.remove(key);
+        }
+    }
+
+    fn optional_schema(
+        &mut self,
+        keyword: &str,
+        old: &Option<Box<ComponentOrInlineSchema>>,
+        new: &Option<Box<ComponentOrInlineSchema>>,
+    ) {
+        self.at(keyword, |d| match (old, new) {
+            (Some(old), Some(new)) => d.schema(old, new),
+            (None, Some(_)) => d.narrowed(true, format!("`{}` added", keyword)),
+            (Some(_), None) => d.narrowed(false, format!("`{}` removed", keyword)),
+            (None, None) => {}
+        });
+    }
+
+    /// Compares the schemas of `allOf`, `oneOf` or `anyOf` by position. Adding a schema
+    /// narrows the valid data when `narrowing`.
+    fn schemas(
+        &mut self,
+        keyword: &str,
+        old: &[ComponentOrInlineSchema],
+        new: &[ComponentOrInlineSchema],
+        narrowing: bool,
+    ) {
+        self.at(keyword, |d| {
+            for (i, (old, new)) in old.iter().zip(new).enumerate() {
+                d.at(&i.to_string(), |d| d.schema(old, new));
+            }
+            if new.len() > old.len() {
+                d.narrowed(narrowing, format!("schemas added to `{}`", keyword));
+            } else if new.len() < old.len() {
+                d.narrowed(!narrowing, format!("schemas removed from `{}`", keyword));
+            }
+        });
+    }
+
+    /// Compares a keyword which restricts the valid data when added, such as `pattern`.
+    fn constraint<T: PartialEq + fmt::Display>(
+        &mut self,
+        keyword: &str,
+        old: &Option<T>,
+        new: &Option<T>,
+    ) {
+        if old == new {
+            return;
+        }
+        self.at(keyword, |d| {
+            let message = format!("`{}` changed from {} to {}", keyword, show(old), show(new));
+            match (old, new) {
+                (None, Some(_)) => d.narrowed(true, message),
+                (Some(_), None) => d.narrowed(false, message),
+                _ => d.report(ChangeKind::Modified, Severity::Breaking, message),
+            }
+        });
+    }
+
+    /// Compares a bound, which is a `lower` bound such as `minLength` or an upper one.
+    fn bound<T: PartialOrd + fmt::Display>(
+        &mut self,
+        keyword: &str,
+        old: Option<T>,
+        new: Option<T>,
+        lower: bool,
+    ) {
+        let narrowed = match (&old, &new) {
+            (None, Some(_)) => true,
+            (Some(_), None) => false,
+            (Some(old), Some(new)) if old != new => (new > old) == lower,
+            _ => return,
+        };
+        self.at(keyword, |d| {
+            let message = format!(
+                "`{}` changed from {} to {}",
+                keyword,
+                show(&old),
+                show(&new)
+            );
+            d.narrowed(narrowed, message)
+        });
+    }
+
+    /// Compares a flag which restricts the valid data when set, such as `uniqueItems`.
+    fn flag(&mut self, keyword: &str, old: Option<bool>, new: Option<bool>) {
+        let (old, new) = (old == Some(true), new == Some(true));
+        if old != new {
+            self.at(keyword, |d| {
+                let message = format!("`{}` changed from {} to {}", keyword, old, new);
+                d.narrowed(new, message)
+            });
+        }
+    }
+
+    fn schema_object(&mut self, old: &Schema, new: &Schema) {
+        self.informational("description", &old.description, &new.description);
+        if old.schema_type != new.schema_type {
+            let name = |ty: Type| {
+                serde_json::json!(ty)
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string()
+            };
+            let (old_type, new_type) = (old.schema_type.map(name), new.schema_type.map(name));
+            let message = format!(
+                "`type` changed from {} to {}",
+                show(&old_type),
+                show(&new_type)
+            );
+            self.at("type", |d| {
+                match (old_type.as_deref(), new_type.as_deref()) {
+                    (None, Some(_)) | (Some("number"), Some("integer")) => {
+                        d.narrowed(true, message)
+                    }
+                    (Some(_), None) | (Some("integer"), Some("number")) => {
+                        d.narrowed(false, message)
+                    }
+                    _ => d.report(ChangeKind::Modified, Severity::Breaking, message),
+                }
+            });
+        }
+        let non_empty = |s: &Str| Some(s.clone()).filter(|s| !s.is_empty());
+        self.constraint("format", &non_empty(&old.format), &non_empty(&new.format));
+        // `nullable` allows more data when set.
+        let (old_nullable, new_nullable) = (old.nullable == Some(true), new.nullable == Some(true));
+        if old_nullable != new_nullable {
+            self.at("nullable", |d| {
+                let message = format!(
+                    "`nullable` changed from {} to {}",
+                    old_nullable, new_nullable
+                );
+                d.narrowed(old_nullable, message)
+            });
+        }
+        self.enum_values(&old.enum_values, &new.enum_values);
+        self.constraint("const", &old.const_value, &new.const_value);
+
+        let number = |value: &Option<Value>| value.as_ref().and_then(Value::as_f64);
+        let bool_flag = |value: &Option<Value>| value.as_ref().and_then(Value::as_bool);
+        self.bound("minimum", number(&old.minimum), number(&new.minimum), true);
+        self.bound("maximum", number(&old.maximum), number(&new.maximum), false);
+        self.flag(
+            "exclusiveMinimum",
+            bool_flag(&old.exclusive_minimum),
+            bool_flag(&new.exclusive_minimum),
+        );
+        self.flag(
+            "exclusiveMaximum",
+            bool_flag(&old.exclusive_maximum),
+            bool_flag(&new.exclusive_maximum),
+        );
+        if bool_flag(&old.exclusive_minimum).is_none()
+            && bool_flag(&new.exclusive_minimum).is_none()
+        {
+            let (old, new) = (
+                number(&old.exclusive_minimum),
+                number(&new.exclusive_minimum),
+            );
+            self.bound("exclusiveMinimum", old, new, true);
+        }
+        if bool_flag(&old.exclusive_maximum).is_none()
+            && bool_flag(&new.exclusive_maximum).is_none()
+        {
+            let (old, new) = (
+                number(&old.exclusive_maximum),
+                number(&new.exclusive_maximum),
+            );
+            self.bound("exclusiveMaximum", old, new, false);
+        }
+        self.multiple_of(&old.multiple_of, &new.multiple_of);
+        self.bound("minLength", old.min_length, new.min_length, true);
+        self.bound("maxLength", old.max_length, new.max_length, false);
+        self.constraint(
+            "pattern",
+            &non_empty(&old.pattern),
+            &non_empty(&new.pattern),
+        );
+        self.bound("minItems", old.min_items, new.min_items, true);
+        self.bound("maxItems", old.max_items, new.max_items, false);
+        self.flag("uniqueItems", old.unique_items, new.unique_items);
+        self.optional_schema("items", &old.items, &new.items);
+        self.bound(
+            "minProperties",
+            old.min_properties,
+            new.min_properties,
+            true,
+        );
+        self.bound(
+            "maxProperties",
+            old.max_properties,
+            new.max_properties,
+            false,
+        );
+        self.properties(old, new);
+        self.optional_schema(
+            "additionalProperties",
+            &old.additional_properties,
+            &new.additional_properties,
+        );
+        self.dependent_required(old, new);
+        self.schemas("allOf", &old.all_of, &new.all_of, true);
+        self.schemas("oneOf", &old.one_of, &new.one_of, false);
+        self.schemas("anyOf", &old.any_of, &new.any_of, false);
+    }
+
+    fn enum_values(&mut self, old: &[Str], new: &[Str]) {
+        if old == new {
+            return;
+        }
+        self.at("enum", |d| {
+            // A schema without values allows any value.
+            if old.is_empty() || new.is_empty() {
+                let message = if old.is_empty() {
+                    "`enum` added"
+                } else {
+                    "`enum` removed"
+                };
+                return d.narrowed(old.is_empty(), message.to_string());
+            }
+            for value in old.iter().filter(|v| !new.contains(v)) {
+                d.report(
+                    ChangeKind::Removed,
+                    Severity::Breaking,
+                    format!("enum value `{}` removed", value),
+                );
+            }
+            for value in new.iter().filter(|v| !old.contains(v)) {
+                let severity = d.severity(false);
+                d.report(
+                    ChangeKind::Added,
+                    severity,
+                    format!("enum value `{}` added", value),
+                );
+            }
+        });
+    }
+
+    fn multiple_of(&mut self, old: &Option<Value>, new: &Option<Value>) {
+        let number = |value: &Option<Value>| match value {
+            Some(Value::Number(number)) => Some(number.clone()),
+            _ => None,
+        };
+        match (number(old), number(new)) {
+            // A multiple of the old divisor allows a subset of the values.
+            (Some(old), Some(new)) if old != new && is_multiple_of(&new, &old) => {
+                self.at("multipleOf", |d| {
+                    let message = format!("`multipleOf` changed from `{}` to `{}`", old, new);
+                    d.narrowed(true, message)
+                })
+            }
+            (Some(old), Some(new)) if old != new && is_multiple_of(&old, &new) => {
+                self.at("multipleOf", |d| {
+                    let message = format!("`multipleOf` changed from `{}` to `{}`", old, new);
+                    d.narrowed(false, message)
+                })
+            }
+            (old, new) => self.constraint("multipleOf", &old, &new),
+        }
+    }
+
+    fn properties(&mut self, old: &Schema, new: &Schema) {
+        self.at("properties", |d| {
+            for (name, old_property) in &old.properties {
+                d.at(name, |d| match new.properties.get(name) {
+                    Some(new_property) => d.schema(old_property, new_property),
+                    None => d.report(
+                        ChangeKind::Removed,
+                        Severity::Breaking,
+                        format!("property `{}` removed", name),
+                    ),
+                });
+            }
+            for name in new.properties.keys() {
+                if old.properties.contains_key(name) {
+                    continue;
+                }
+                let required = new.required.contains(name);
+                let severity = match d.direction {
+                    Direction::Request if required => Severity::Breaking,
+                    _ => Severity::NonBreaking,
+                };
+                let which = if required { "required" } else { "optional" };
+                d.at(name, |d| {
+                    let message = format!("{} property `{}` added", which, name);
+                    d.report(ChangeKind::Added, severity, message)
+                });
+            }
+        });
+        // Properties added or removed are reported above.
+        let compared =
+            |name: &&Str| old.properties.contains_key(*name) == new.properties.contains_key(*name);
+        self.at("required", |d| {
+            for name in new.required.iter().filter(|n| !old.required.contains(n)) {
+                if compared(&name) {
+                    d.narrowed(true, format!("property `{}` became required", name));
+                }
+            }
+            for name in old.required.iter().filter(|n| !new.required.contains(n)) {
+                if compared(&name) {
+                    d.narrowed(false, format!("property `{}` became optional", name));
+                }
+            }
+        });
+    }
+
+    fn dependent_required(&mut self, old: &Schema, new: &Schema) {
+        fn pairs(schema: &Schema) -> Vec<(&Str, &Str)> {
+            schema
+                .dependent_required
+                .iter()
+                .flat_map(|(name, required)| required.iter().map(move |r| (name, r)))
+                .collect()
+        }
+        let (old, new) = (pairs(old), pairs(new));
+        self.at("dependentRequired", |d| {
+            for (name, required) in new.iter().filter(|p| !old.contains(p)) {
+                let message = format!("property `{}` now requires `{}`", name, required);
+                d.narrowed(true, message);
+            }
+            for (name, required) in old.iter().filter(|p| !new.contains(p)) {
+                let message = format!("property `{}` no longer requires `{}`", name, required);
+                d.narrowed(false, message);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD: &str = r##"{
+      "openapi": "3.0.3",
+      "info": {"title": "Pets", "version": "1.0.0"},
+      "paths": {
+        "/pets": {
+          "get": {
+            "parameters": [
+              {"name": "limit", "in": "query", "schema": {"type": "integer", "maximum": 100}}
+            ],
+            "responses": {
+              "200": {
+                "description": "Pets",
+                "content": {
+                  "application/json": {
+                    "schema": {"type": "array", "items": {"$ref": "#/components/schemas/Pet"}}
+                  }
+                }
+              }
+            }
+          },
+          "post": {
+            "requestBody": {
+              "required": true,
+              "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}}
+            },
+            "responses": {"201": {"description": "Created"}}
+          }
+        },
+        "/stores": {"get": {"responses": {"200": {"description": "Stores"}}}}
+      },
+      "components": {
+        "schemas": {
+          "Pet": {
+            "type": "object",
+            "required": ["name"],
+            "properties": {
+              "name": {"type": "string"},
+              "kind": {"type": "string", "enum": ["cat", "dog", "bird"]},
+              "tag": {"type": "string"},
+              "parent": {"$ref": "#/components/schemas/Pet"}
+            }
+          }
+        }
+      }
+    }"##;
+
+    #[test]
+    fn test_moving_into_components() {
+        let old: Spec = serde_json::from_str(OLD).unwrap();
+        // Same schemas, all inlined but the recursive one.
+        let mut new = old.clone();
+        let pet = new.components.as_mut().unwrap().schemas.shift_remove("Pet");
+        let mut pet = match pet {
+            Some(ObjectOrReference::Object(pet)) => pet,
+            other => panic!("unexpected {:?}", other),
+        };
+        pet.properties["parent"] = ComponentOrInlineSchema::Component {
+            name: "Animal".into(),
+        };
+        new.components
+            .as_mut()
+            .unwrap()
+            .schemas
+            .insert("Animal".into(), ObjectOrReference::Object(pet.clone()));
+        let body = new.paths["/pets"]
+            .post
+            .as_mut()
+            .unwrap()
+            .request_body
+            .as_mut();
+        match body {
+            Some(ObjectOrReference::Object(body)) => {
+                body.content["application/json"].schema =
+                    Some(ComponentOrInlineSchema::Inline(pet.clone()))
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        let response = &mut new.paths["/pets"].get.as_mut().unwrap().responses["200"];
+        match response {
+            ObjectOrReference::Object(response) => {
+                let items = &mut response.content["application/json"].schema;
+                match items {
+                    Some(ComponentOrInlineSchema::Inline(array)) => {
+                        array.items = Some(Box::new(ComponentOrInlineSchema::Component {
+                            name: "Animal".into(),
+                        }))
+                    }
+                    other => panic!("unexpected {:?}", other),
+                }
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert_eq!(old.diff(&new), []);
+    }
+
+    #[test]
+    fn test_diff() {
+        let old: Spec = serde_json::from_str(OLD).unwrap();
+        let new = OLD
+            .replace(
+                r#""/stores": {"get": {"responses": {"200": {"description": "Stores"}}}}"#,
+                r#""/owners": {"get": {"responses": {"200": {"description": "Owners"}}}}"#,
+            )
+            .replace(r#""maximum": 100"#, r#""maximum": 50"#)
+            .replace(r#""required": ["name"]"#, r#""required": ["name", "age"]"#)
+            .replace(
+                r#""tag": {"type": "string"},"#,
+                r#""age": {"type": "integer"}, "nick": {"type": "string"},"#,
+            )
+            .replace(r#"["cat", "dog", "bird"]"#, r#"["cat", "dog", "fish"]"#);
+        let new: Spec = serde_json::from_str(&new).unwrap();
+        let changes: Vec<_> = old
+            .diff(&new)
+            .into_iter()
+            .map(|c| (c.severity, c.to_string()))
+            .collect();
+        let get = "/paths/~1pets/get";
+        let items = "/responses/200/content/application~1json/schema/items";
+        let body = "/paths/~1pets/post/requestBody/content/application~1json/schema";
+        use Severity::*;
+        assert_eq!(
+            changes,
+            [
+                (
+                    Breaking,
+                    format!(
+                        "`{}/parameters/0/schema/maximum`: `maximum` changed from `100` to `50`",
+                        get
+                    )
+                ),
+                (
+                    Breaking,
+                    format!(
+                        "`{}{}/properties/kind/enum`: enum value `bird` removed",
+                        get, items
+                    )
+                ),
+                (
+                    Breaking,
+                    format!(
+                        "`{}{}/properties/kind/enum`: enum value `fish` added",
+                        get, items
+                    )
+                ),
+                (
+                    Breaking,
+                    format!("`{}{}/properties/tag`: property `tag` removed", get, items)
+                ),
+                (
+                    NonBreaking,
+                    format!(
+                        "`{}{}/properties/age`: required property `age` added",
+                        get, items
+                    )
+                ),
+                (
+                    NonBreaking,
+                    format!(
+                        "`{}{}/properties/nick`: optional property `nick` added",
+                        get, items
+                    )
+                ),
+                (
+                    Breaking,
+                    format!("`{}/properties/kind/enum`: enum value `bird` removed", body)
+                ),
+                (
+                    NonBreaking,
+                    format!("`{}/properties/kind/enum`: enum value `fish` added", body)
+                ),
+                (
+                    Breaking,
+                    format!("`{}/properties/tag`: property `tag` removed", body)
+                ),
+                (
+                    Breaking,
+                    format!("`{}/properties/age`: required property `age` added", body)
+                ),
+                (
+                    NonBreaking,
+                    format!("`{}/properties/nick`: optional property `nick` added", body)
+                ),
+                (Breaking, "`/paths/~1stores`: path removed".to_string()),
+                (NonBreaking, "`/paths/~1owners`: path added".to_string()),
+            ]
+        );
+    }
+}
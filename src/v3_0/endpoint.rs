@@ -0,0 +1,233 @@
+//! Analysis of operations shared by the generated clients and servers.
+
+use crate::v3_0::{
+    models::{field_name, type_name, unique, Models},
+    operation::{body, resolved_parameters, Body},
+    Operation, Parameter, ParameterRepresentation, PathItem,
+};
+use std::collections::HashSet;
+
+/// An operation, with the names and Rust types of its parameters, request body and responses.
+pub(crate) struct Endpoint<'a> {
+    /// Name of the function of the operation.
+    pub name: String,
+    pub arguments: Vec<Argument<'a>>,
+    pub body: Option<BodyArgument>,
+    /// Name of the enum of the responses.
+    pub response: String,
+    pub variants: Vec<Variant<'a>>,
+}
+
+/// A parameter of an operation, as an argument of its function.
+pub(crate) struct Argument<'a> {
+    pub parameter: &'a Parameter,
+    pub name: String,
+    /// The type of the value of the parameter, which is optional unless `required`.
+    pub rust_type: String,
+    pub required: bool,
+}
+
+/// The request body of an operation, as an argument of its function.
+pub(crate) struct BodyArgument {
+    pub kind: Body,
+    /// The owned type of the body, which is optional unless `required`.
+    pub rust_type: String,
+    pub required: bool,
+}
+
+/// A response of an operation, as a variant of its response enum.
+pub(crate) struct Variant<'a> {
+    /// The status code, range or `default` of the response.
+    pub status: &'a str,
+    pub name: String,
+    pub description: &'a str,
+    /// The media type of the body, empty if there is none.
+    pub media_type: &'a str,
+    /// How the body is serialized, and its type.
+    pub content: Option<(Body, String)>,
+}
+
+impl<'a> Endpoint<'a> {
+    /// Analyzes `operation`, whose function is named uniquely among `names` and whose arguments
+    /// do not shadow `locals`. The types the operation needs are defined in `models`.
+    pub(crate) fn new(
+        models: &mut Models<'a>,
+        names: &mut HashSet<String>,
+        locals: &[&str],
+        (path, item, method): (&str, &'a PathItem, &str),
+        operation: &'a Operation,
+    ) -> Self {
+        let spec = models.spec();
+        let id = if operation.operation_id.is_empty() {
+            format!("{} {}", method, path)
+        } else {
+            operation.operation_id.to_string()
+        };
+        let name = unique(names, &field_name(&id));
+        let base = type_name(&id);
+
+        let mut locals: HashSet<String> = locals.iter().map(|local| local.to_string()).collect();
+        let mut arguments = Vec::new();
+        for parameter in resolved_parameters(spec, item, operation) {
+            let hint = format!("{}{}", base, type_name(&parameter.name));
+            let rust_type = match &parameter.representation {
+                Some(ParameterRepresentation::Simple { schema }) => models.type_of(schema, &hint),
+                Some(ParameterRepresentation::Content { content }) => {
+                    match content
+                        .values()
+                        .next()
+                        .and_then(|media| media.schema.as_ref())
+                    {
+                        Some(schema) => models.type_of(schema, &hint),
+                        None => "serde_json::Value".to_string(),
+                    }
+                }
+                None => "serde_json::Value".to_string(),
+            };
+            arguments.push(Argument {
+                parameter,
+                name: unique(&mut locals, &field_name(&parameter.name)),
+                rust_type,
+                required: parameter.required == Some(true),
+            });
+        }
+
+        let request_body = operation
+            .request_body
+            .as_ref()
+            .and_then(|body| spec.resolve(body).ok());
+        let body_argument = request_body.and_then(|request_body| {
+            let (_, media, kind) = body(&request_body.content)?;
+            let rust_type = match (&kind, &media.schema) {
+                (Body::Text(_), _) => "String".to_string(),
+                (Body::Bytes(_), _) => "Vec<u8>".to_string(),
+                (_, Some(schema)) => models.type_of(schema, &format!("{}Body", base)),
+                (_, None) => "serde_json::Value".to_string(),
+            };
+            Some(BodyArgument {
+                kind,
+                rust_type,
+                required: request_body.required == Some(true),
+            })
+        });
+
+        let response = models.unique(&format!("{}Response", base));
+        let mut variants = Vec::new();
+        for (status, response_object) in &operation.responses {
+            let response_object = match spec.resolve(response_object) {
+                Ok(response_object) => response_object,
+                Err(_) => continue,
+            };
+            let name = variant_name(status);
+            let content = body(&response_object.content);
+            let media_type = content.as_ref().map_or("", |(media_type, _, _)| media_type);
+            let content = content.map(|(_, media, kind)| {
+                let rust_type = match (&kind, &media.schema) {
+                    (Body::Text(_), _) => "String".to_string(),
+                    (Body::Bytes(_), _) | (Body::Form, _) => "Vec<u8>".to_string(),
+                    (Body::Json, Some(schema)) => {
+                        models.type_of(schema, &format!("{}{}", base, name))
+                    }
+                    (Body::Json, None) => "serde_json::Value".to_string(),
+                };
+                (kind, rust_type)
+            });
+            variants.push(Variant {
+                status,
+                name,
+                description: &response_object.description,
+                media_type,
+                content,
+            });
+        }
+
+        Endpoint {
+            name,
+            arguments,
+            body: body_argument,
+            response,
+            variants,
+        }
+    }
+}
+
+impl Argument<'_> {
+    /// The type of the argument, an `Option` unless the parameter is required.
+    pub(crate) fn declared_type(&self) -> String {
+        if self.required {
+            self.rust_type.clone()
+        } else {
+            format!("Option<{}>", self.rust_type)
+        }
+    }
+}
+
+impl Variant<'_> {
+    /// Whether the variant is for a single status code. Variants of ranges and of the default
+    /// response hold the status code along with the body.
+    pub(crate) fn is_exact(&self) -> bool {
+        self.status.parse::<u16>().is_ok()
+    }
+
+    /// The types of the fields of the variant.
+    pub(crate) fn fields(&self) -> Vec<&str> {
+        (!self.is_exact())
+            .then_some("u16")
+            .into_iter()
+            .chain(self.content.as_ref().map(|(_, rust_type)| &**rust_type))
+            .collect()
+    }
+
+    /// The pattern of the status codes of the variant, or `None` for the default response.
+    pub(crate) fn pattern(&self) -> Option<String> {
+        match self.status.as_bytes() {
+            _ if self.is_exact() => Some(self.status.to_string()),
+            [class @ b'1'..=b'5', b'X' | b'x', b'X' | b'x'] => {
+                let class = char::from(*class);
+                Some(format!("{}00..={}99", class, class))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Name of the variant of a response enum for the status code `status`.
+fn variant_name(status: &str) -> String {
+    let name = match status {
+        "default" => "Default",
+        "200" => "Ok",
+        "201" => "Created",
+        "202" => "Accepted",
+        "204" => "NoContent",
+        "301" => "MovedPermanently",
+        "302" => "Found",
+        "304" => "NotModified",
+        "400" => "BadRequest",
+        "401" => "Unauthorized",
+        "403" => "Forbidden",
+        "404" => "NotFound",
+        "405" => "MethodNotAllowed",
+        "409" => "Conflict",
+        "410" => "Gone",
+        "412" => "PreconditionFailed",
+        "415" => "UnsupportedMediaType",
+        "422" => "UnprocessableEntity",
+        "429" => "TooManyRequests",
+        "500" => "InternalServerError",
+        "501" => "NotImplemented",
+        "502" => "BadGateway",
+        "503" => "ServiceUnavailable",
+        "504" => "GatewayTimeout",
+        status => return format!("Status{}", status.to_ascii_lowercase()),
+    };
+    name.to_string()
+}
+
+/// The summary and description of an operation.
+pub(crate) fn summary(operation: &Operation) -> String {
+    match (operation.summary.trim(), operation.description.trim()) {
+        ("", description) => description.to_string(),
+        (summary, "") => summary.to_string(),
+        (summary, description) => format!("{}\n\n{}", summary, description),
+    }
+}
@@ -0,0 +1,625 @@
+//! Synthesis of example instances of schemas, for specifications without examples.
+
+use crate::{
+    v3_0::{ComponentOrInlineSchema, Components, Schema, Spec, Type},
+    Str,
+};
+use regex::Regex;
+use regex_syntax::hir::{Class, Hir, HirKind};
+use serde_json::{Map, Value};
+use std::{convert::TryFrom, mem};
+
+impl Components {
+    /// Synthesizes an example instance of `schema`, looking up component schemas in `self`.
+    ///
+    /// The `example`, `default` or `const` of a schema is used if it has one, or else its first
+    /// `enum` value. Otherwise the instance has the type of the schema, or the type its keywords
+    /// imply, and satisfies its `format`, its length and range bounds, its `pattern` and its
+    /// `required` properties. Objects have all their properties, and arrays a single item
+    /// unless `minItems` asks for more.
+    ///
+    /// The schemas of `allOf`, and the first ones of `oneOf` and `anyOf`, are merged into the
+    /// schema. Properties and items referring back to a component schema being synthesized are
+    /// left out, or are `null` if they are required, so that recursive schemas terminate.
+    ///
+    /// Requires the `validate` feature.
+    pub fn example_instance(&self, schema: &ComponentOrInlineSchema) -> Value {
+        let mut synthesizer = Synthesizer {
+            components: self,
+            following: Vec::new(),
+        };
+        synthesizer
+            .component_or_inline(schema)
+            .unwrap_or(Value::Null)
+    }
+}
+
+impl Spec {
+    /// Same as [`Components::example_instance`], with the components of this document.
+    pub fn example_instance(&self, schema: &ComponentOrInlineSchema) -> Value {
+        match &self.components {
+            Some(components) => components.example_instance(schema),
+            None => Components::default().example_instance(schema),
+        }
+    }
+}
+
+/// Characters used for character classes, in order of preference.
+const PREFERRED: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789\
+                         -_.~ !\"#$%&'()*+,/:;<=>?@[\\]^`{|}";
+
+/// Appends a string matching `hir` to `out`, with `choose` picking one of the given number of
+/// options, such as the characters of a class, the branches of an alternation or the numbers
+/// of repetitions.
+///
+/// Assertions such as `^` and `\b` are ignored, so the string should be checked against the
+/// pattern.
+pub(crate) fn sample_pattern(hir: &Hir, choose: &mut dyn FnMut(usize) -> usize, out: &mut String) {
+    match hir.kind() {
+        HirKind::Empty | HirKind::Look(_) => {}
+        HirKind::Literal(literal) => out.push_str(&String::from_utf8_lossy(&literal.0)),
+        HirKind::Class(class) => {
+            let contains = |c: char| match class {
+                Class::Unicode(class) => class
+                    .ranges()
+                    .iter()
+                    .any(|range| range.start() <= c && c <= range.end()),
+                Class::Bytes(class) => u8::try_from(c).is_ok_and(|byte| {
+                    class
+                        .ranges()
+                        .iter()
+                        .any(|range| range.start() <= byte && byte <= range.end())
+                }),
+            };
+            let preferred: Vec<char> = PREFERRED.chars().filter(|c| contains(*c)).collect();
+            if !preferred.is_empty() {
+                out.push(preferred[choose(preferred.len())]);
+            } else if let Class::Unicode(class) = class {
+                if !class.ranges().is_empty() {
+                    out.push(class.ranges()[choose(class.ranges().len())].start());
+                }
+            }
+        }
+        HirKind::Repetition(repetition) => {
+            let span = repetition
+                .max
+                .map_or(8, |max| max.saturating_sub(repetition.min))
+                .min(8);
+            let count = repetition.min as usize + choose(span as usize + 1);
+            for _ in 0..count {
+                sample_pattern(&repetition.sub, choose, out);
+            }
+        }
+        HirKind::Capture(capture) => sample_pattern(&capture.sub, choose, out),
+        HirKind::Concat(hirs) => {
+            for hir in hirs {
+                sample_pattern(hir, choose, out);
+            }
+        }
+        HirKind::Alternation(hirs) => sample_pattern(&hirs[choose(hirs.len())], choose, out),
+    }
+}
+
+/// Returns a short string matching `pattern` whose length is within bounds, trying
+/// longer ones until one is.
+fn pattern_example(pattern: &str, min: usize, max: usize) -> Option<String> {
+    let hir = regex_syntax::parse(pattern).ok()?;
+    let regex = Regex::new(pattern).ok()?;
+    (0..16).find_map(|attempt| {
+        let mut string = String::new();
+        sample_pattern(&hir, &mut |options| attempt.min(options - 1), &mut string);
+        let len = string.chars().count();
+        (min <= len && len <= max && regex.is_match(&string)).then_some(string)
+    })
+}
+
+/// An example of a string of the format `format`.
+fn format_example(format: &str) -> &'static str {
+    match format {
+        "date" => "2020-01-01",
+        "date-time" => "2020-01-01T00:00:00Z",
+        "time" => "00:00:00",
+        "duration" => "P1D",
+        "email" => "user@example.com",
+        "hostname" => "example.com",
+        "ipv4" => "192.0.2.1",
+        "ipv6" => "2001:db8::1",
+        "uri" | "url" | "iri" => "https://example.com/",
+        "uri-reference" | "iri-reference" => "/",
+        "uuid" => "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+        "byte" => "ZXhhbXBsZQ==",
+        "password" => "password",
+        _ => "string",
+    }
+}
+
+/// The `example`, `default`, `const` or first `enum` value of `schema`.
+fn explicit(schema: &Schema) -> Option<Value> {
+    schema
+        .example
+        .clone()
+        .or_else(|| schema.default.clone())
+        .or_else(|| schema.const_value.clone())
+        .or_else(|| {
+            let value = schema.enum_values.first()?;
+            Some(Value::String(value.to_string()))
+        })
+}
+
+/// The type the keywords of a schema without `type` imply.
+pub(crate) fn implied_type(schema: &Schema) -> Option<Type> {
+    if !schema.properties.is_empty()
+        || !schema.required.is_empty()
+        || schema.additional_properties.is_some()
+        || schema.min_properties.is_some()
+    {
+        Some(Type::Object)
+    } else if schema.items.is_some() || schema.min_items.is_some() {
+        Some(Type::Array)
+    } else if !schema.pattern.is_empty()
+        || schema.min_length.is_some()
+        || schema.max_length.is_some()
+    {
+        Some(Type::String)
+    } else {
+        match &*schema.format {
+            "int32" | "int64" => Some(Type::Integer),
+            "float" | "double" => Some(Type::Number),
+            "" => None,
+            _ => Some(Type::String),
+        }
+    }
+}
+
+/// A bound of a number, with whether it is exclusive.
+type Bound = Option<(f64, bool)>;
+
+/// The lower and upper bounds of the numbers of `schema`.
+pub(crate) fn bounds(schema: &Schema) -> (Bound, Bound) {
+    let low = bound(&schema.minimum, &schema.exclusive_minimum, 1.0);
+    let high = bound(&schema.maximum, &schema.exclusive_maximum, -1.0);
+    (low, high)
+}
+
+/// A lower bound if `sign` is `1.0`, or an upper bound if it is `-1.0`.
+fn bound(bound: &Option<Value>, exclusive: &Option<Value>, sign: f64) -> Bound {
+    // `exclusiveMinimum` is a flag modifying `minimum` in 3.0, and a bound of its own in later
+    // versions of JSON Schema.
+    let flag = exclusive.as_ref().and_then(Value::as_bool) == Some(true);
+    let inclusive = bound
+        .as_ref()
+        .and_then(Value::as_f64)
+        .map(|bound| (bound, flag));
+    let exclusive = exclusive
+        .as_ref()
+        .and_then(Value::as_f64)
+        .map(|bound| (bound, true));
+    match (inclusive, exclusive) {
+        (Some(inclusive), Some(exclusive)) => Some(stricter(inclusive, exclusive, sign)),
+        (inclusive, exclusive) => inclusive.or(exclusive),
+    }
+}
+
+/// The stricter of two lower bounds if `sign` is `1.0`, or of two upper bounds if it is `-1.0`.
+fn stricter(a: (f64, bool), b: (f64, bool), sign: f64) -> (f64, bool) {
+    if sign * a.0 > sign * b.0 || a.0 == b.0 && a.1 {
+        a
+    } else {
+        b
+    }
+}
+
+/// A number within the bounds of `schema`, and a multiple of its `multipleOf`, preferring `0`
+/// or else the lower bound.
+pub(crate) fn number(schema: &Schema, integer: bool) -> Value {
+    let (low, high) = bounds(schema);
+    let divisor = schema
+        .multiple_of
+        .as_ref()
+        .and_then(Value::as_f64)
+        .filter(|divisor| *divisor > 0.0);
+    let step = divisor.unwrap_or(1.0);
+    let mut value = match (low, high) {
+        (Some((low, _)), _) if low >= 0.0 || high.is_some_and(|(high, _)| high <= 0.0) => low,
+        (_, Some((high, _))) if high <= 0.0 => high,
+        _ => 0.0,
+    };
+    if let Some(divisor) = divisor {
+        value = (value / divisor).round() * divisor;
+    }
+    let within = |value: f64| {
+        let above = low.is_none_or(|(low, exclusive)| value > low || !exclusive && value == low);
+        let below =
+            high.is_none_or(|(high, exclusive)| value < high || !exclusive && value == high);
+        above && below
+    };
+    let valid = |value: f64| {
+        within(value)
+            && (!integer || value.fract() == 0.0)
+            && divisor.is_none_or(|divisor| (value / divisor).fract().abs() < 1e-9)
+    };
+    // Steps up from the lower bound, or down from the upper bound.
+    let direction = if high.is_some() && low.is_none() {
+        -1.0
+    } else {
+        1.0
+    };
+    let candidate = (0..1000)
+        .map(|i| value + direction * step * i as f64)
+        .find(|value| valid(*value));
+    let value = match candidate {
+        Some(value) => value,
+        // Between exclusive bounds closer than a step.
+        None => match (low, high) {
+            (Some((low, _)), Some((high, _))) if !integer => (low + high) / 2.0,
+            _ => value,
+        },
+    };
+    if integer || value.fract() == 0.0 && value.abs() < 1e15 {
+        Value::from(value as i64)
+    } else {
+        serde_json::Number::from_f64(value).map_or(Value::Null, Value::Number)
+    }
+}
+
+/// A string of the format of `schema`, or matching its pattern, within its length bounds.
+pub(crate) fn string(schema: &Schema) -> Value {
+    let min = schema.min_length.unwrap_or(0);
+    let max = schema.max_length.unwrap_or(usize::MAX).max(min);
+    if !schema.pattern.is_empty() {
+        if let Some(string) = pattern_example(&schema.pattern, min, max) {
+            return Value::String(string);
+        }
+    }
+    let example = format_example(&schema.format);
+    let len = example.chars().count();
+    let string = if len < min {
+        example.chars().cycle().take(min).collect()
+    } else {
+        example.chars().take(max).collect()
+    };
+    Value::String(string)
+}
+
+/// Merges the keywords of `source` into those of `target`, so that instances satisfy both.
+fn merge(target: &mut Schema, source: Schema) {
+    fn or<T>(target: &mut Option<T>, source: Option<T>) {
+        if target.is_none() {
+            *target = source;
+        }
+    }
+    fn or_str(target: &mut Str, source: Str) {
+        if target.is_empty() {
+            *target = source;
+        }
+    }
+    fn max(target: &mut Option<usize>, source: Option<usize>) {
+        *target = match (*target, source) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        };
+    }
+    fn min(target: &mut Option<usize>, source: Option<usize>) {
+        *target = match (*target, source) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+    }
+
+    let (low, high) = bounds(target);
+    let (source_low, source_high) = bounds(&source);
+    or(&mut target.schema_type, source.schema_type);
+    or_str(&mut target.format, source.format);
+    or_str(&mut target.pattern, source.pattern);
+    or(&mut target.items, source.items);
+    for (name, property) in source.properties {
+        target.properties.entry(name).or_insert(property);
+    }
+    or(
+        &mut target.additional_properties,
+        source.additional_properties,
+    );
+    or(&mut target.example, source.example);
+    or(&mut target.default, source.default);
+    or(&mut target.const_value, source.const_value);
+    let enum_values = source.enum_values;
+    if target.enum_values.is_empty() {
+        target.enum_values = enum_values;
+    } else if !enum_values.is_empty() {
+        let common: Vec<Str> = target
+            .enum_values
+            .iter()
+            .filter(|value| enum_values.contains(value))
+            .cloned()
+            .collect();
+        if !common.is_empty() {
+            target.enum_values = common;
+        }
+    }
+    or(&mut target.multiple_of, source.multiple_of);
+    if let Some(source_low) = source_low {
+        if low.is_none_or(|low| stricter(source_low, low, 1.0) == source_low) {
+            target.minimum = serde_json::Number::from_f64(source_low.0).map(Value::Number);
+            target.exclusive_minimum = Some(Value::Bool(source_low.1));
+        }
+    }
+    if let Some(source_high) = source_high {
+        if high.is_none_or(|high| stricter(source_high, high, -1.0) == source_high) {
+            target.maximum = serde_json::Number::from_f64(source_high.0).map(Value::Number);
+            target.exclusive_maximum = Some(Value::Bool(source_high.1));
+        }
+    }
+    max(&mut target.min_length, source.min_length);
+    min(&mut target.max_length, source.max_length);
+    max(&mut target.min_items, source.min_items);
+    min(&mut target.max_items, source.max_items);
+    or(&mut target.unique_items, source.unique_items);
+    max(&mut target.min_properties, source.min_properties);
+    min(&mut target.max_properties, source.max_properties);
+    for name in source.required {
+        if !target.required.contains(&name) {
+            target.required.push(name);
+        }
+    }
+    for (name, required) in source.dependent_required {
+        target
+            .dependent_required
+            .entry(name)
+            .or_default()
+            .extend(required);
+    }
+}
+
+/// Returns `schema` with the schemas of its `allOf`, and one of those of its `oneOf` and of its
+/// `anyOf` picked by `choose`, merged into it. Component schemas in `following` are not merged
+/// again.
+pub(crate) fn merged(
+    components: &Components,
+    following: &mut Vec<Str>,
+    schema: &Schema,
+    choose: &mut dyn FnMut(usize) -> usize,
+) -> Schema {
+    let mut schema = schema.clone();
+    let mut parts = mem::take(&mut schema.all_of);
+    for alternatives in [mem::take(&mut schema.one_of), mem::take(&mut schema.any_of)] {
+        if !alternatives.is_empty() {
+            let chosen = choose(alternatives.len());
+            parts.extend(alternatives.into_iter().nth(chosen));
+        }
+    }
+    for part in &parts {
+        let part = match part {
+            ComponentOrInlineSchema::Inline(part) => merged(components, following, part, choose),
+            ComponentOrInlineSchema::Component { name } => {
+                let target = match components.get::<Schema>(name) {
+                    Ok(target) if !following.contains(name) => target,
+                    _ => continue,
+                };
+                following.push(name.clone());
+                let part = merged(components, following, target, choose);
+                following.pop();
+                part
+            }
+            ComponentOrInlineSchema::ExtRef { .. } => continue,
+        };
+        merge(&mut schema, part);
+    }
+    schema
+}
+
+/// Synthesizer of example instances.
+struct Synthesizer<'a> {
+    components: &'a Components,
+    /// Names of the component schemas being synthesized or merged.
+    following: Vec<Str>,
+}
+
+impl Synthesizer<'_> {
+    /// Returns an instance of `schema`, or `None` if it refers to a component schema being
+    /// synthesized.
+    fn component_or_inline(&mut self, schema: &ComponentOrInlineSchema) -> Option<Value> {
+        let name = match schema {
+            ComponentOrInlineSchema::Inline(schema) => return Some(self.schema(schema)),
+            ComponentOrInlineSchema::Component { name } => name,
+            ComponentOrInlineSchema::ExtRef { .. } => return Some(Value::Null),
+        };
+        if self.following.contains(name) {
+            return None;
+        }
+        let target = match self.components.get::<Schema>(name) {
+            Ok(target) => target,
+            Err(_) => return Some(Value::Null),
+        };
+        self.following.push(name.clone());
+        let value = self.schema(target);
+        self.following.pop();
+        Some(value)
+    }
+
+    fn schema(&mut self, schema: &Schema) -> Value {
+        let schema = merged(self.components, &mut self.following, schema, &mut |_| 0);
+        if let Some(value) = explicit(&schema) {
+            return value;
+        }
+        match schema.schema_type.or_else(|| implied_type(&schema)) {
+            Some(Type::Object) => self.object(&schema),
+            Some(Type::Array) => self.array(&schema),
+            Some(Type::String) | Some(Type::File) => string(&schema),
+            Some(Type::Integer) => number(&schema, true),
+            Some(Type::Number) => number(&schema, false),
+            Some(Type::Boolean) => Value::Bool(true),
+            None => Value::Object(Map::new()),
+        }
+    }
+
+    fn array(&mut self, schema: &Schema) -> Value {
+        let count = schema
+            .min_items
+            .unwrap_or(0)
+            .max(1)
+            .min(schema.max_items.unwrap_or(usize::MAX));
+        let item = match &schema.items {
+            Some(items) => self.component_or_inline(items),
+            None => Some(Value::Null),
+        };
+        match item {
+            Some(item) => Value::Array(vec![item; count]),
+            None => Value::Array(Vec::new()),
+        }
+    }
+
+    fn object(&mut self, schema: &Schema) -> Value {
+        let mut object = Map::new();
+        for (name, property) in &schema.properties {
+            match self.component_or_inline(property) {
+                Some(value) => {
+                    object.insert(name.to_string(), value);
+                }
+                None if schema.required.contains(name) => {
+                    object.insert(name.to_string(), Value::Null);
+                }
+                None => {}
+            }
+        }
+        let additional = match &schema.additional_properties {
+            Some(additional) => self.component_or_inline(additional),
+            None => Some(Value::Null),
+        };
+        if schema.properties.is_empty() {
+            if let (Some(value), Some(_)) = (&additional, &schema.additional_properties) {
+                object.insert("key".to_string(), value.clone());
+            }
+        }
+        let mut missing: Vec<&Str> = schema.required.iter().collect();
+        for (name, required) in &schema.dependent_required {
+            if object.contains_key(&**name) {
+                missing.extend(required);
+            }
+        }
+        for name in missing {
+            if !object.contains_key(&**name) {
+                let value = additional.clone().unwrap_or(Value::Null);
+                object.insert(name.to_string(), value);
+            }
+        }
+        let min = schema.min_properties.unwrap_or(0);
+        let mut index = 1;
+        while object.len() < min {
+            let name = format!("property{}", index);
+            if !object.contains_key(&name) {
+                object.insert(name, additional.clone().unwrap_or(Value::Null));
+            }
+            index += 1;
+        }
+        if let Some(max) = schema.max_properties {
+            let optional: Vec<String> = object
+                .keys()
+                .filter(|name| !schema.required.iter().any(|required| required == *name))
+                .cloned()
+                .collect();
+            for name in optional.iter().rev() {
+                if object.len() <= max {
+                    break;
+                }
+                object.remove(name);
+            }
+        }
+        Value::Object(object)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const COMPONENTS: &str = r##"{
+      "schemas": {
+        "Pet": {
+          "type": "object",
+          "required": ["name", "owner"],
+          "properties": {
+            "name": {"type": "string", "minLength": 3, "maxLength": 8, "pattern": "^[a-z]+$"},
+            "kind": {"type": "string", "enum": ["cat", "dog"]},
+            "age": {"type": "integer", "minimum": 3, "exclusiveMinimum": true, "multipleOf": 2},
+            "weight": {"type": "number", "maximum": -1.5, "exclusiveMaximum": true},
+            "born": {"type": "string", "format": "date"},
+            "tags": {"type": "array", "minItems": 2, "items": {"type": "string"}},
+            "parent": {"$ref": "#/components/schemas/Pet"},
+            "children": {"type": "array", "items": {"$ref": "#/components/schemas/Pet"}}
+          },
+          "dependentRequired": {"kind": ["color"]},
+          "additionalProperties": {"type": "string", "example": "red"}
+        },
+        "Owner": {
+          "allOf": [
+            {"$ref": "#/components/schemas/Named"},
+            {"properties": {"id": {"type": "integer", "default": 7}}, "required": ["id"]}
+          ],
+          "oneOf": [{"properties": {"email": {"type": "string", "format": "email"}}}]
+        },
+        "Named": {"type": "object", "properties": {"name": {"const": "Ann"}}}
+      }
+    }"##;
+
+    fn example(schema: &str) -> Value {
+        let components: Components = serde_json::from_str(COMPONENTS).unwrap();
+        let schema = ComponentOrInlineSchema::Component {
+            name: schema.to_string().into(),
+        };
+        let instance = components.example_instance(&schema);
+        assert_eq!(components.validate_instance(&schema, &instance), vec![]);
+        instance
+    }
+
+    #[test]
+    fn test_example_instance() {
+        assert_eq!(
+            example("Pet"),
+            json!({
+                "name": "ccc",
+                "kind": "cat",
+                "age": 4,
+                "weight": -2.5,
+                "born": "2020-01-01",
+                "tags": ["string", "string"],
+                "children": [],
+                "owner": "red",
+                "color": "red"
+            })
+        );
+        assert_eq!(
+            example("Owner"),
+            json!({"name": "Ann", "id": 7, "email": "user@example.com"})
+        );
+
+        // Required properties referring back to the schema being synthesized are `null`.
+        let components: Components = serde_json::from_value(json!({
+            "schemas": {
+                "Node": {
+                    "type": "object",
+                    "required": ["next"],
+                    "properties": {"next": {"$ref": "#/components/schemas/Node"}}
+                }
+            }
+        }))
+        .unwrap();
+        let schema = ComponentOrInlineSchema::Component {
+            name: "Node".into(),
+        };
+        assert_eq!(components.example_instance(&schema), json!({"next": null}));
+    }
+
+    #[test]
+    fn test_pattern_example() {
+        let example = |pattern: &str, min, max| pattern_example(pattern, min, max);
+        assert_eq!(
+            example("^[0-9]{3}-[A-Z]+$", 0, 10),
+            Some("000-A".to_string())
+        );
+        assert_eq!(example("^(cat|dog)s?$", 0, 10), Some("cat".to_string()));
+        assert_eq!(example("^a*$", 3, 5), Some("aaa".to_string()));
+        assert_eq!(example("^\\d+$", 20, 30), None);
+        assert_eq!(example("[", 0, 10), None);
+    }
+}
@@ -0,0 +1,998 @@
+//! Seeded generation of random instances of schemas, for fuzzing and property tests.
+
+use crate::{
+    v3_0::{
+        example::{self, bounds, implied_type, merged, sample_pattern},
+        instance::has_type,
+        ComponentOrInlineSchema, Components, InstanceError, Schema, Spec, Type,
+    },
+    Str,
+};
+use regex::Regex;
+use serde_json::{json, Map, Value};
+use std::borrow::Cow;
+
+/// Number of attempts at generating a value satisfying a schema, before giving up.
+const ATTEMPTS: usize = 16;
+
+/// Depth from which optional properties, additional items and nested values of schemas
+/// without type are no longer generated, so that instances stay small.
+const MAX_DEPTH: usize = 4;
+
+/// Largest length or size bound of a schema that is exceeded to violate it.
+const LIMIT: usize = 10_000;
+
+/// Characters of generated strings without pattern nor format.
+const ALPHANUMERIC: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+/// Generator of random instances of schemas. The instances only depend on the seed of the
+/// generator and the schemas it is given.
+///
+/// See [`Components::instance_generator`].
+#[derive(Clone, Debug)]
+pub struct InstanceGenerator<'a> {
+    components: Cow<'a, Components>,
+    rng: Rng,
+}
+
+/// An instance violating a single keyword of a schema.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InvalidInstance {
+    pub instance: Value,
+    /// The violation, as reported by [`Components::validate_instance`].
+    pub error: InstanceError,
+}
+
+impl InvalidInstance {
+    /// The violated keyword, e.g. `maxLength` or `required`.
+    pub fn keyword(&self) -> &str {
+        keyword(&self.error)
+    }
+}
+
+impl Components {
+    /// Returns a generator of random instances of schemas, looking up component schemas in
+    /// `self`. Generators with the same seed generate the same instances.
+    ///
+    /// Requires the `validate` feature.
+    pub fn instance_generator(&self, seed: u64) -> InstanceGenerator<'_> {
+        InstanceGenerator {
+            components: Cow::Borrowed(self),
+            rng: Rng(seed),
+        }
+    }
+}
+
+impl Spec {
+    /// Same as [`Components::instance_generator`], with the components of this document.
+    pub fn instance_generator(&self, seed: u64) -> InstanceGenerator<'_> {
+        InstanceGenerator {
+            components: match &self.components {
+                Some(components) => Cow::Borrowed(components),
+                None => Cow::Owned(Components::default()),
+            },
+            rng: Rng(seed),
+        }
+    }
+}
+
+impl InstanceGenerator<'_> {
+    /// Generates a random instance of `schema`.
+    ///
+    /// Every keyword checked by [`Components::validate_instance`] is satisfied: values are
+    /// drawn from `enum`, within the range, length and size bounds, multiples of `multipleOf`
+    /// and matching `pattern`. Optional properties are included at random, `null` is drawn for
+    /// `nullable` schemas, one of the schemas of `oneOf` and `anyOf` is picked, and strings
+    /// of a known `format`, such as `date` or `uuid`, are valid for it.
+    ///
+    /// Instances are validated before being returned. If none is valid after a few attempts,
+    /// such as for a `oneOf` whose schemas overlap, the [example
+    /// instance](Components::example_instance) of the schema is returned instead.
+    pub fn valid_instance(&mut self, schema: &ComponentOrInlineSchema) -> Value {
+        let components = &*self.components;
+        let mut generator = Generator {
+            components,
+            rng: &mut self.rng,
+            following: Vec::new(),
+        };
+        for _ in 0..ATTEMPTS {
+            let instance = generator
+                .component_or_inline(schema, 0)
+                .unwrap_or(Value::Null);
+            if components.validate_instance(schema, &instance).is_empty() {
+                return instance;
+            }
+        }
+        components.example_instance(schema)
+    }
+
+    /// Generates `count` different random instances of `schema`, or fewer if it has fewer
+    /// instances or they are seldom generated.
+    pub fn valid_instances(
+        &mut self,
+        schema: &ComponentOrInlineSchema,
+        count: usize,
+    ) -> Vec<Value> {
+        let mut instances = Vec::new();
+        for _ in 0..count.saturating_mul(ATTEMPTS) {
+            if instances.len() == count {
+                break;
+            }
+            let instance = self.valid_instance(schema);
+            if !instances.contains(&instance) {
+                instances.push(instance);
+            }
+        }
+        instances
+    }
+
+    /// Generates instances of `schema` which each violate a single keyword, derived from a
+    /// [valid instance](Self::valid_instance).
+    ///
+    /// There is at most one instance per keyword of each schema along the valid instance, such
+    /// as its `maxLength` or the `type` of one of its properties, and one per property listed
+    /// by `required` and `dependentRequired`. Keywords which cannot be violated on their own,
+    /// such as a `minLength` of `0`, are skipped, as are `oneOf` and `anyOf`.
+    pub fn invalid_instances(&mut self, schema: &ComponentOrInlineSchema) -> Vec<InvalidInstance> {
+        let valid = self.valid_instance(schema);
+        let components = &*self.components;
+        let mut generator = Generator {
+            components,
+            rng: &mut self.rng,
+            following: Vec::new(),
+        };
+        let mut mutations = Vec::new();
+        generator.mutations(schema, &valid, "", &mut Vec::new(), &mut mutations);
+
+        let mut invalid: Vec<InvalidInstance> = Vec::new();
+        for mutation in mutations {
+            for candidate in mutation.candidates {
+                let mut instance = valid.clone();
+                match instance.pointer_mut(&mutation.pointer) {
+                    Some(node) => *node = candidate,
+                    None => continue,
+                }
+                let mut errors = components.validate_instance(schema, &instance);
+                let violates = match &*errors {
+                    [error] => {
+                        error.instance_pointer == mutation.pointer
+                            && keyword(error) == mutation.keyword
+                    }
+                    _ => false,
+                };
+                if violates && invalid.iter().all(|other| other.instance != instance) {
+                    let error = errors.remove(0);
+                    invalid.push(InvalidInstance { instance, error });
+                    break;
+                }
+            }
+        }
+        invalid
+    }
+}
+
+/// The keyword of the schema pointer of `error`.
+fn keyword(error: &InstanceError) -> &str {
+    error.schema_pointer.rsplit('/').next().unwrap_or_default()
+}
+
+/// A SplitMix64 pseudorandom number generator, which is small and good enough for test data.
+#[derive(Clone, Debug)]
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number below `n`, or `0` if `n` is `0`.
+    fn below(&mut self, n: usize) -> usize {
+        if n == 0 {
+            0
+        } else {
+            (self.next() % n as u64) as usize
+        }
+    }
+
+    fn one_in(&mut self, n: usize) -> bool {
+        self.below(n) == 0
+    }
+
+    /// A number between `0` included and `1` excluded.
+    fn unit(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// A string of `len` random characters of `chars`.
+    fn string(&mut self, chars: &[u8], len: usize) -> String {
+        (0..len)
+            .map(|_| char::from(chars[self.below(chars.len())]))
+            .collect()
+    }
+}
+
+/// Replacements of the value at `pointer` of a valid instance, one of which is expected to
+/// violate `keyword` and nothing else.
+struct Mutation {
+    pointer: String,
+    keyword: &'static str,
+    candidates: Vec<Value>,
+}
+
+struct Generator<'a, 'r> {
+    components: &'a Components,
+    rng: &'r mut Rng,
+    /// Names of the component schemas being generated.
+    following: Vec<Str>,
+}
+
+impl Generator<'_, '_> {
+    /// Returns a random instance of `schema`, or `None` if it requires a value of a component
+    /// schema already being generated twice, so that recursive schemas terminate.
+    fn component_or_inline(
+        &mut self,
+        schema: &ComponentOrInlineSchema,
+        depth: usize,
+    ) -> Option<Value> {
+        let name = match schema {
+            ComponentOrInlineSchema::Inline(schema) => return self.schema(schema, depth),
+            ComponentOrInlineSchema::Component { name } => name,
+            ComponentOrInlineSchema::ExtRef { .. } => return Some(Value::Null),
+        };
+        if self.following.iter().filter(|other| *other == name).count() >= 2 {
+            return None;
+        }
+        let target = match self.components.get::<Schema>(name) {
+            Ok(target) => target,
+            Err(_) => return Some(Value::Null),
+        };
+        self.following.push(name.clone());
+        let value = self.schema(target, depth);
+        self.following.pop();
+        value
+    }
+
+    fn schema(&mut self, schema: &Schema, depth: usize) -> Option<Value> {
+        let rng = &mut *self.rng;
+        let schema = merged(self.components, &mut self.following, schema, &mut |n| {
+            rng.below(n)
+        });
+        if schema.nullable == Some(true) && self.rng.one_in(8) {
+            return Some(Value::Null);
+        }
+        if let Some(value) = &schema.const_value {
+            return Some(value.clone());
+        }
+        if !schema.enum_values.is_empty() {
+            let value = &schema.enum_values[self.rng.below(schema.enum_values.len())];
+            return Some(Value::String(value.to_string()));
+        }
+        if self.rng.one_in(8) {
+            if let Some(value) = schema.example.as_ref().or(schema.default.as_ref()) {
+                return Some(value.clone());
+            }
+        }
+        let ty = schema.schema_type.or_else(|| implied_type(&schema));
+        let ty = ty.unwrap_or_else(|| {
+            let types = [
+                Type::Boolean,
+                Type::Integer,
+                Type::Number,
+                Type::String,
+                Type::Array,
+                Type::Object,
+            ];
+            let scalars = if depth < MAX_DEPTH { 6 } else { 4 };
+            types[self.rng.below(scalars)]
+        });
+        let value = match ty {
+            Type::Object => return self.object(&schema, depth),
+            Type::Array => return self.array(&schema, depth),
+            Type::String | Type::File => self.string(&schema),
+            Type::Integer => self.integer(&schema),
+            Type::Number => self.number(&schema),
+            Type::Boolean => Value::Bool(self.rng.one_in(2)),
+        };
+        Some(value)
+    }
+
+    fn integer(&mut self, schema: &Schema) -> Value {
+        let (low, high) = bounds(schema);
+        let low = low.map(|(low, exclusive)| {
+            if exclusive {
+                low.floor() + 1.0
+            } else {
+                low.ceil()
+            }
+        });
+        let high = high.map(|(high, exclusive)| {
+            if exclusive {
+                high.ceil() - 1.0
+            } else {
+                high.floor()
+            }
+        });
+        // The smallest integer which is a multiple of `multipleOf`.
+        let step = match multiple_of(schema) {
+            Some(divisor) => (1..=1000)
+                .map(|i| divisor * i as f64)
+                .find(|step| (step - step.round()).abs() < 1e-9),
+            None => Some(1.0),
+        };
+        match step.and_then(|step| self.multiple(low, high, step.round())) {
+            Some(value) => Value::from(value as i64),
+            None => example::number(schema, true),
+        }
+    }
+
+    fn number(&mut self, schema: &Schema) -> Value {
+        let (low, high) = bounds(schema);
+        let within = |value: f64| {
+            low.is_none_or(|(low, exclusive)| value > low || !exclusive && value == low)
+                && high.is_none_or(|(high, exclusive)| value < high || !exclusive && value == high)
+        };
+        let value = match multiple_of(schema) {
+            Some(divisor) => {
+                let low = low.map(|(low, _)| low);
+                let high = high.map(|(high, _)| high);
+                // Multiples on exclusive bounds are discarded by `within`.
+                (0..ATTEMPTS)
+                    .filter_map(|_| self.multiple(low, high, divisor))
+                    .find(|value| within(*value))
+            }
+            None => {
+                let (low, high) = range(low.map(|(low, _)| low), high.map(|(high, _)| high));
+                let value = low + self.rng.unit() * (high - low);
+                let rounded = (value * 100.0).round() / 100.0;
+                [rounded, value, (low + high) / 2.0]
+                    .iter()
+                    .copied()
+                    .find(|value| within(*value))
+            }
+        };
+        match value.and_then(number_value) {
+            Some(value) => value,
+            None => example::number(schema, false),
+        }
+    }
+
+    /// A random multiple of `step` between `low` and `high`.
+    fn multiple(&mut self, low: Option<f64>, high: Option<f64>, step: f64) -> Option<f64> {
+        let (low, high) = range(low, high);
+        let (first, last) = ((low / step).ceil(), (high / step).floor());
+        if first > last {
+            return None;
+        }
+        let count = ((last - first) as usize).saturating_add(1);
+        Some((first + self.rng.below(count) as f64) * step)
+    }
+
+    fn string(&mut self, schema: &Schema) -> Value {
+        let min = schema.min_length.unwrap_or(0);
+        let max = schema.max_length.unwrap_or(usize::MAX).max(min);
+        let fits = |string: &str| (min..=max).contains(&string.chars().count());
+        if !schema.pattern.is_empty() {
+            let hir = regex_syntax::parse(&schema.pattern);
+            let regex = Regex::new(&schema.pattern);
+            if let (Ok(hir), Ok(regex)) = (hir, regex) {
+                for _ in 0..ATTEMPTS {
+                    let mut string = String::new();
+                    let rng = &mut *self.rng;
+                    sample_pattern(&hir, &mut |n| rng.below(n), &mut string);
+                    if fits(&string) && regex.is_match(&string) {
+                        return Value::String(string);
+                    }
+                }
+            }
+            return example::string(schema);
+        }
+        if let Some(string) = self.format(&schema.format) {
+            if fits(&string) {
+                return Value::String(string);
+            }
+        }
+        let len = min + self.rng.below(max.min(min + 16) - min + 1);
+        Value::String(self.rng.string(ALPHANUMERIC, len))
+    }
+
+    /// A random string of the format `format`, if it is known.
+    fn format(&mut self, format: &str) -> Option<String> {
+        const LOWERCASE: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+        const HEX: &[u8] = b"0123456789abcdef";
+        let rng = &mut *self.rng;
+        let date = |rng: &mut Rng| {
+            let (year, month, day) = (1970 + rng.below(60), 1 + rng.below(12), 1 + rng.below(28));
+            format!("{:04}-{:02}-{:02}", year, month, day)
+        };
+        let time = |rng: &mut Rng| {
+            let (hour, minute, second) = (rng.below(24), rng.below(60), rng.below(60));
+            format!("{:02}:{:02}:{:02}", hour, minute, second)
+        };
+        let word = |rng: &mut Rng| {
+            let len = 1 + rng.below(8);
+            rng.string(LOWERCASE, len)
+        };
+        let string = match format {
+            "date" => date(rng),
+            "date-time" => format!("{}T{}Z", date(rng), time(rng)),
+            "time" => time(rng),
+            "email" => format!("{}@example.com", word(rng)),
+            "hostname" => format!("{}.example.com", word(rng)),
+            "ipv4" => {
+                let octets: Vec<String> = (0..4).map(|_| rng.below(256).to_string()).collect();
+                octets.join(".")
+            }
+            "ipv6" => {
+                let groups: Vec<String> = (0..8).map(|_| rng.string(HEX, 4)).collect();
+                groups.join(":")
+            }
+            "uri" | "url" | "iri" => format!("https://example.com/{}", word(rng)),
+            "uri-reference" | "iri-reference" => format!("/{}", word(rng)),
+            "uuid" => {
+                let hex = rng.string(HEX, 32);
+                format!(
+                    "{}-{}-{}-{}-{}",
+                    &hex[..8],
+                    &hex[8..12],
+                    &hex[12..16],
+                    &hex[16..20],
+                    &hex[20..]
+                )
+            }
+            "byte" => {
+                let len = 4 * (1 + rng.below(4));
+                rng.string(ALPHANUMERIC, len)
+            }
+            _ => return None,
+        };
+        Some(string)
+    }
+
+    fn array(&mut self, schema: &Schema, depth: usize) -> Option<Value> {
+        let min = schema.min_items.unwrap_or(0);
+        let max = schema.max_items.unwrap_or(usize::MAX).max(min);
+        let extra = if depth < MAX_DEPTH { 4 } else { 0 };
+        let count = min + self.rng.below(max.min(min + extra) - min + 1);
+        let any = ComponentOrInlineSchema::Inline(Schema::default());
+        let item_schema = schema.items.as_deref().unwrap_or(&any);
+        let mut items = Vec::new();
+        while items.len() < count {
+            let item = (0..ATTEMPTS)
+                .map(|_| self.component_or_inline(item_schema, depth + 1))
+                .find(|item| match item {
+                    Some(item) => schema.unique_items != Some(true) || !items.contains(item),
+                    None => true,
+                });
+            match item {
+                Some(Some(item)) => items.push(item),
+                _ => break,
+            }
+        }
+        (items.len() >= min).then_some(Value::Array(items))
+    }
+
+    fn object(&mut self, schema: &Schema, depth: usize) -> Option<Value> {
+        let mut object = Map::new();
+        for (name, property) in &schema.properties {
+            let required = schema.required.contains(name);
+            if !required && (depth >= MAX_DEPTH || self.rng.one_in(2)) {
+                continue;
+            }
+            match self.component_or_inline(property, depth + 1) {
+                Some(value) => {
+                    object.insert(name.to_string(), value);
+                }
+                None if required => return None,
+                None => {}
+            }
+        }
+        if let Some(additional) = &schema.additional_properties {
+            let count = if depth < MAX_DEPTH {
+                self.rng.below(3)
+            } else {
+                0
+            };
+            for i in 1..=count {
+                let name = format!("key{}", i);
+                if schema.properties.contains_key(&*name) {
+                    continue;
+                }
+                if let Some(value) = self.component_or_inline(additional, depth + 1) {
+                    object.insert(name, value);
+                }
+            }
+        }
+        // Properties required by others may themselves require more.
+        loop {
+            let missing = schema
+                .required
+                .iter()
+                .chain(
+                    schema
+                        .dependent_required
+                        .iter()
+                        .filter(|(name, _)| object.contains_key(&***name))
+                        .flat_map(|(_, required)| required),
+                )
+                .find(|name| !object.contains_key(&***name));
+            let name = match missing {
+                Some(name) => name,
+                None => break,
+            };
+            let value = self.property(schema, name, depth);
+            object.insert(name.to_string(), value);
+        }
+        let min = schema.min_properties.unwrap_or(0);
+        let mut index = 1;
+        while object.len() < min {
+            let unused = schema
+                .properties
+                .keys()
+                .find(|name| !object.contains_key(&***name));
+            let name = match unused {
+                Some(name) => name.to_string(),
+                None => {
+                    index += 1;
+                    format!("key{}", index - 1)
+                }
+            };
+            if !object.contains_key(&name) {
+                let value = self.property(schema, &name, depth);
+                object.insert(name, value);
+            }
+        }
+        if let Some(max) = schema.max_properties {
+            let needed = |name: &str| {
+                schema.required.iter().any(|required| required == name)
+                    || schema
+                        .dependent_required
+                        .iter()
+                        .filter(|(name, _)| object.contains_key(&***name))
+                        .any(|(_, required)| required.iter().any(|required| required == name))
+            };
+            let optional: Vec<String> = object
+                .keys()
+                .filter(|name| !needed(name))
+                .cloned()
+                .collect();
+            for name in optional.iter().rev() {
+                if object.len() <= max {
+                    break;
+                }
+                object.remove(name);
+            }
+        }
+        Some(Value::Object(object))
+    }
+
+    /// A random value of the property `name` of `schema`.
+    fn property(&mut self, schema: &Schema, name: &str, depth: usize) -> Value {
+        let property = schema
+            .properties
+            .get(name)
+            .or(schema.additional_properties.as_deref());
+        property
+            .and_then(|property| self.component_or_inline(property, depth + 1))
+            .unwrap_or(Value::Null)
+    }
+
+    /// Collects the mutations of `value`, at `pointer` of a valid instance, which may violate
+    /// a keyword of `schema`. `following` holds the component schemas being visited, with
+    /// their pointers.
+    fn mutations(
+        &mut self,
+        schema: &ComponentOrInlineSchema,
+        value: &Value,
+        pointer: &str,
+        following: &mut Vec<(Str, String)>,
+        out: &mut Vec<Mutation>,
+    ) {
+        let name = match schema {
+            ComponentOrInlineSchema::Inline(schema) => {
+                return self.schema_mutations(schema, value, pointer, following, out)
+            }
+            ComponentOrInlineSchema::Component { name } => name,
+            ComponentOrInlineSchema::ExtRef { .. } => return,
+        };
+        let key = (name.clone(), pointer.to_string());
+        let target = match self.components.get::<Schema>(name) {
+            Ok(target) if !following.contains(&key) => target,
+            _ => return,
+        };
+        following.push(key);
+        self.schema_mutations(target, value, pointer, following, out);
+        following.pop();
+    }
+
+    fn schema_mutations(
+        &mut self,
+        schema: &Schema,
+        value: &Value,
+        pointer: &str,
+        following: &mut Vec<(Str, String)>,
+        out: &mut Vec<Mutation>,
+    ) {
+        let mut push = |keyword: &'static str, candidates: Vec<Value>| {
+            if !candidates.is_empty() {
+                out.push(Mutation {
+                    pointer: pointer.to_string(),
+                    keyword,
+                    candidates,
+                });
+            }
+        };
+
+        if let Some(ty) = schema.schema_type {
+            let candidates = [
+                json!(true),
+                json!(0),
+                json!(0.5),
+                json!("string"),
+                json!([]),
+                json!({}),
+            ];
+            push(
+                "type",
+                candidates
+                    .iter()
+                    .filter(|candidate| !has_type(candidate, ty))
+                    .cloned()
+                    .collect(),
+            );
+        }
+        if !schema.enum_values.is_empty() {
+            let mut invalid = "invalid".to_string();
+            while schema.enum_values.iter().any(|value| *value == invalid) {
+                invalid.push('x');
+            }
+            push("enum", vec![Value::String(invalid)]);
+        }
+        if let Some(constant) = &schema.const_value {
+            let candidates = [json!("invalid"), json!(0), json!(false), Value::Null];
+            push(
+                "const",
+                candidates
+                    .iter()
+                    .filter(|candidate| *candidate != constant)
+                    .cloned()
+                    .collect(),
+            );
+        }
+
+        match value {
+            Value::Number(number) => {
+                let number = number.as_f64().unwrap_or_default();
+                let integer = schema.schema_type == Some(Type::Integer);
+                let step = multiple_of(schema).unwrap_or(1.0);
+                let numbers = |values: &[f64]| -> Vec<Value> {
+                    values
+                        .iter()
+                        .filter(|value| !integer || value.fract() == 0.0)
+                        .filter_map(|value| number_value(*value))
+                        .collect()
+                };
+                let exclusive =
+                    |flag: &Option<Value>| flag.as_ref().and_then(Value::as_bool) == Some(true);
+                let bound = |bound: &Option<Value>| bound.as_ref().and_then(Value::as_f64);
+                if let Some(minimum) = bound(&schema.minimum) {
+                    let below = [minimum - step, minimum - 1.0, minimum.floor() - 1.0];
+                    let mut candidates = numbers(&below);
+                    if exclusive(&schema.exclusive_minimum) {
+                        candidates.splice(0..0, numbers(&[minimum]));
+                    }
+                    push("minimum", candidates);
+                }
+                if let Some(minimum) = bound(&schema.exclusive_minimum) {
+                    push(
+                        "exclusiveMinimum",
+                        numbers(&[minimum, minimum - step, minimum.floor() - 1.0]),
+                    );
+                }
+                if let Some(maximum) = bound(&schema.maximum) {
+                    let above = [maximum + step, maximum + 1.0, maximum.ceil() + 1.0];
+                    let mut candidates = numbers(&above);
+                    if exclusive(&schema.exclusive_maximum) {
+                        candidates.splice(0..0, numbers(&[maximum]));
+                    }
+                    push("maximum", candidates);
+                }
+                if let Some(maximum) = bound(&schema.exclusive_maximum) {
+                    push(
+                        "exclusiveMaximum",
+                        numbers(&[maximum, maximum + step, maximum.ceil() + 1.0]),
+                    );
+                }
+                if let Some(divisor) = multiple_of(schema) {
+                    push(
+                        "multipleOf",
+                        numbers(&[number + divisor / 2.0, number + 1.0, number + 0.5]),
+                    );
+                }
+            }
+            Value::String(string) => {
+                let len = string.chars().count();
+                if let Some(min) = schema.min_length.filter(|min| *min > 0) {
+                    let shorter = string.chars().take(min - 1).collect();
+                    push(
+                        "minLength",
+                        vec![Value::String(shorter), Value::String("a".repeat(min - 1))],
+                    );
+                }
+                if let Some(max) = schema.max_length.filter(|max| *max < LIMIT) {
+                    let last = string.chars().last().unwrap_or('a');
+                    let longer = format!(
+                        "{}{}",
+                        string,
+                        last.to_string().repeat(max + 1 - len.min(max))
+                    );
+                    push(
+                        "maxLength",
+                        vec![Value::String(longer), Value::String("a".repeat(max + 1))],
+                    );
+                }
+                if !schema.pattern.is_empty() {
+                    let len = schema.min_length.unwrap_or(0).max(1);
+                    let candidates = ["!", " ", "~", "0", "a", "A"]
+                        .iter()
+                        .map(|c| Value::String(c.repeat(len)))
+                        .chain(Some(Value::String(String::new())))
+                        .chain(Some(Value::String(format!("{} ", string))))
+                        .collect();
+                    push("pattern", candidates);
+                }
+            }
+            Value::Array(items) => {
+                if let Some(min) = schema.min_items.filter(|min| *min > 0) {
+                    let fewer = items.iter().take(min - 1).cloned().collect();
+                    push("minItems", vec![Value::Array(fewer)]);
+                }
+                let any = ComponentOrInlineSchema::Inline(Schema::default());
+                let item_schema = schema.items.as_deref().unwrap_or(&any);
+                if let Some(max) = schema.max_items.filter(|max| *max < LIMIT) {
+                    let mut more = items.clone();
+                    for _ in 0..ATTEMPTS * (max + 1) {
+                        if more.len() > max {
+                            break;
+                        }
+                        if let Some(item) = self.component_or_inline(item_schema, 1) {
+                            if !more.contains(&item) {
+                                more.push(item);
+                            }
+                        }
+                    }
+                    let mut repeated = items.clone();
+                    let last = items.last().cloned().unwrap_or(Value::Null);
+                    repeated.resize(max + 1, last);
+                    push("maxItems", vec![Value::Array(more), Value::Array(repeated)]);
+                }
+                if schema.unique_items == Some(true) {
+                    let mut repeated = items.clone();
+                    match &mut *repeated {
+                        [first, second, ..] => *second = first.clone(),
+                        [first] => {
+                            let first = first.clone();
+                            repeated.push(first);
+                        }
+                        [] => {
+                            let item = self
+                                .component_or_inline(item_schema, 1)
+                                .unwrap_or(Value::Null);
+                            repeated = vec![item.clone(), item];
+                        }
+                    }
+                    push("uniqueItems", vec![Value::Array(repeated)]);
+                }
+            }
+            Value::Object(object) => {
+                for name in &schema.required {
+                    if object.contains_key(&**name) {
+                        let mut without = object.clone();
+                        without.remove(&**name);
+                        push("required", vec![Value::Object(without)]);
+                    }
+                }
+                for (name, required) in &schema.dependent_required {
+                    for dependency in required {
+                        let mut without = object.clone();
+                        if !without.contains_key(&**name) {
+                            let value = self.property(schema, name, 1);
+                            without.insert(name.to_string(), value);
+                        }
+                        without.remove(&**dependency);
+                        push("dependentRequired", vec![Value::Object(without)]);
+                    }
+                }
+                if let Some(min) = schema.min_properties.filter(|min| *min > 0) {
+                    let mut fewer = object.clone();
+                    let optional: Vec<&String> = object
+                        .keys()
+                        .filter(|name| !schema.required.iter().any(|required| required == *name))
+                        .collect();
+                    for name in optional.iter().rev() {
+                        if fewer.len() < min {
+                            break;
+                        }
+                        fewer.remove(*name);
+                    }
+                    if fewer.len() < min {
+                        push("minProperties", vec![Value::Object(fewer)]);
+                    }
+                }
+                if let Some(max) = schema.max_properties.filter(|max| *max < LIMIT) {
+                    let mut more = object.clone();
+                    let mut index = 1;
+                    while more.len() <= max {
+                        let name = format!("key{}", index);
+                        index += 1;
+                        if !more.contains_key(&name) {
+                            let value = self.property(schema, &name, 1);
+                            more.insert(name, value);
+                        }
+                    }
+                    push("maxProperties", vec![Value::Object(more)]);
+                }
+            }
+            Value::Null | Value::Bool(_) => {}
+        }
+
+        for sub in &schema.all_of {
+            self.mutations(sub, value, pointer, following, out);
+        }
+        match value {
+            Value::Array(items) => {
+                if let (Some(item_schema), Some(item)) = (&schema.items, items.first()) {
+                    let pointer = format!("{}/0", pointer);
+                    self.mutations(item_schema, item, &pointer, following, out);
+                }
+            }
+            Value::Object(object) => {
+                for (name, property) in object {
+                    let property_schema = schema
+                        .properties
+                        .get(name.as_str())
+                        .or(schema.additional_properties.as_deref());
+                    if let Some(property_schema) = property_schema {
+                        let mut pointer = pointer.to_string();
+                        crate::json_pointer::push(&mut pointer, name);
+                        self.mutations(property_schema, property, &pointer, following, out);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The `multipleOf` of `schema`, if it is a positive number.
+fn multiple_of(schema: &Schema) -> Option<f64> {
+    schema
+        .multiple_of
+        .as_ref()
+        .and_then(Value::as_f64)
+        .filter(|divisor| *divisor > 0.0)
+}
+
+/// The range of random numbers between the bounds, if any.
+fn range(low: Option<f64>, high: Option<f64>) -> (f64, f64) {
+    const WIDTH: f64 = 1000.0;
+    match (low, high) {
+        (Some(low), Some(high)) => (low, high),
+        (Some(low), None) => (low, low + WIDTH),
+        (None, Some(high)) => (high - WIDTH, high),
+        (None, None) => (-WIDTH, WIDTH),
+    }
+}
+
+/// `value` as a JSON number, an integer if it is one.
+fn number_value(value: f64) -> Option<Value> {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        Some(Value::from(value as i64))
+    } else {
+        serde_json::Number::from_f64(value).map(Value::Number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMPONENTS: &str = r##"{
+      "schemas": {
+        "Pet": {
+          "type": "object",
+          "required": ["name"],
+          "properties": {
+            "name": {"type": "string", "minLength": 2, "maxLength": 8, "pattern": "^[a-z]+$"},
+            "kind": {"type": "string", "enum": ["cat", "dog"]},
+            "age": {"type": "integer", "minimum": 0, "exclusiveMaximum": true, "maximum": 30},
+            "weight": {"type": "number", "multipleOf": 0.5, "nullable": true},
+            "born": {"type": "string", "format": "date-time"},
+            "tags": {
+              "type": "array", "maxItems": 3, "uniqueItems": true, "items": {"type": "string"}
+            },
+            "parent": {"$ref": "#/components/schemas/Pet"}
+          },
+          "dependentRequired": {"kind": ["age"]},
+          "maxProperties": 6
+        },
+        "Id": {
+          "oneOf": [{"type": "integer", "minimum": 1}, {"type": "string", "format": "uuid"}]
+        }
+      }
+    }"##;
+
+    fn schema(name: &str) -> ComponentOrInlineSchema {
+        ComponentOrInlineSchema::Component {
+            name: name.to_string().into(),
+        }
+    }
+
+    #[test]
+    fn test_valid_instances() {
+        let components: Components = serde_json::from_str(COMPONENTS).unwrap();
+        for name in &["Pet", "Id"] {
+            let instances = components
+                .instance_generator(7)
+                .valid_instances(&schema(name), 50);
+            assert_eq!(instances.len(), 50);
+            for instance in &instances {
+                assert_eq!(
+                    components.validate_instance(&schema(name), instance),
+                    vec![]
+                );
+            }
+            let again = components
+                .instance_generator(7)
+                .valid_instances(&schema(name), 50);
+            assert_eq!(instances, again);
+            let other = components
+                .instance_generator(8)
+                .valid_instances(&schema(name), 50);
+            assert_ne!(instances, other);
+        }
+    }
+
+    #[test]
+    fn test_invalid_instances() {
+        let components: Components = serde_json::from_str(COMPONENTS).unwrap();
+        let mut generator = components.instance_generator(1);
+        let invalid = generator.invalid_instances(&schema("Pet"));
+        let mut keywords: Vec<(&str, &str)> = invalid
+            .iter()
+            .map(|invalid| (&*invalid.error.instance_pointer, invalid.keyword()))
+            .collect();
+        keywords.sort_unstable();
+        keywords.dedup();
+        assert_eq!(
+            keywords,
+            vec![
+                ("", "dependentRequired"),
+                ("", "maxProperties"),
+                ("", "required"),
+                ("", "type"),
+                ("/age", "maximum"),
+                ("/age", "minimum"),
+                ("/age", "type"),
+                ("/kind", "enum"),
+                ("/name", "maxLength"),
+                ("/name", "minLength"),
+                ("/name", "pattern"),
+                ("/name", "type"),
+                ("/tags", "maxItems"),
+                ("/tags", "type"),
+                ("/tags", "uniqueItems"),
+                ("/tags/0", "type"),
+                ("/weight", "multipleOf"),
+                ("/weight", "type"),
+            ]
+        );
+        for invalid in &invalid {
+            let errors = components.validate_instance(&schema("Pet"), &invalid.instance);
+            assert_eq!(errors, vec![invalid.error.clone()]);
+        }
+    }
+}
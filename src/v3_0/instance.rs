@@ -0,0 +1,670 @@
+//! Validation of JSON instances, such as request and response payloads, against schemas.
+
+use crate::{
+    json_pointer,
+    v3_0::{
+        diff::is_multiple_of, resolve::component_ref, ComponentOrInlineSchema, Components, Schema,
+        Spec, Type,
+    },
+    Str,
+};
+use regex::Regex;
+use serde_json::{Map, Value};
+use std::{
+    collections::{hash_map::DefaultHasher, HashSet},
+    fmt,
+    hash::{Hash, Hasher},
+    mem,
+};
+
+/// A keyword of a schema which an instance does not satisfy.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InstanceError {
+    /// JSON pointer of the invalid value within the instance.
+    pub instance_pointer: String,
+    /// JSON pointer of the keyword within the validated schema. Once a component reference is
+    /// followed, the pointer is relative to the document instead, e.g.
+    /// `/components/schemas/Pet/required`.
+    pub schema_pointer: String,
+    pub message: String,
+}
+
+impl fmt::Display for InstanceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "`{}`: {} (at `{}` of the schema)",
+            self.instance_pointer, self.message, self.schema_pointer
+        )
+    }
+}
+
+impl Components {
+    /// Validates `instance` against `schema`, looking up component schemas in `self`. Returns
+    /// every unsatisfied keyword.
+    ///
+    /// `format`, `readOnly` and `writeOnly` are not checked, and `null` is a valid value for
+    /// any `nullable` schema.
+    ///
+    /// Requires the `validate` feature.
+    pub fn validate_instance(
+        &self,
+        schema: &ComponentOrInlineSchema,
+        instance: &Value,
+    ) -> Vec<InstanceError> {
+        let mut validator = InstanceValidator {
+            components: self,
+            schema_pointer: String::new(),
+            instance_pointer: String::new(),
+            errors: Vec::new(),
+            following: HashSet::new(),
+        };
+        validator.component_or_inline(schema, instance);
+        validator.errors
+    }
+}
+
+impl Spec {
+    /// Same as [`Components::validate_instance`], with the components of this document.
+    pub fn validate_instance(
+        &self,
+        schema: &ComponentOrInlineSchema,
+        instance: &Value,
+    ) -> Vec<InstanceError> {
+        match &self.components {
+            Some(components) => components.validate_instance(schema, instance),
+            None => Components::default().validate_instance(schema, instance),
+        }
+    }
+}
+
+/// Returns the name of the JSON type of `value`, as used by the `type` keyword.
+pub(crate) fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        Value::Number(n) if n.as_f64().map(|n| n.fract() == 0.0) == Some(true) => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Whether `value` is of type `ty`. Files are transferred as strings.
+pub(crate) fn has_type(value: &Value, ty: Type) -> bool {
+    match (ty, value) {
+        (Type::String, Value::String(_)) | (Type::File, Value::String(_)) => true,
+        (Type::Number, Value::Number(_)) => true,
+        (Type::Integer, value) => json_type(value) == "integer",
+        (Type::Boolean, Value::Bool(_)) => true,
+        (Type::Array, Value::Array(_)) => true,
+        (Type::Object, Value::Object(_)) => true,
+        _ => false,
+    }
+}
+
+/// Arrays with at most this many items are checked for duplicates by comparing every pair.
+const FEW_ITEMS: usize = 16;
+
+/// Whether the items of `items` are all different, as [`json_eq`] compares them.
+pub(crate) fn are_unique(items: &[Value]) -> bool {
+    if items.len() <= FEW_ITEMS {
+        return items
+            .iter()
+            .enumerate()
+            .all(|(i, item)| !items[..i].iter().any(|other| json_eq(item, other)));
+    }
+    let mut seen = HashSet::with_capacity(items.len());
+    items.iter().all(|item| seen.insert(Normalized(item)))
+}
+
+/// Whether `a` and `b` are equal as JSON Schema compares them: numbers are equal if their
+/// values are, such as `1` and `1.0`.
+pub(crate) fn json_eq(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => match (integer(a), integer(b)) {
+            (Some(a), Some(b)) => a == b,
+            _ => a.as_f64() == b.as_f64(),
+        },
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| json_eq(a, b))
+        }
+        (Value::Object(a), Value::Object(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(key, a)| matches!(b.get(key), Some(b) if json_eq(a, b)))
+        }
+        (a, b) => a == b,
+    }
+}
+
+/// The value of `number` if it is an integer, compared exactly rather than as a float.
+fn integer(number: &serde_json::Number) -> Option<i128> {
+    number
+        .as_i64()
+        .map(i128::from)
+        .or_else(|| number.as_u64().map(i128::from))
+        .or_else(|| {
+            let float = number.as_f64()?;
+            // Within the range, integral floats convert exactly.
+            let in_range = float.abs() < 2f64.powi(127);
+            (float.fract() == 0.0 && in_range).then_some(float as i128)
+        })
+}
+
+/// A value hashed and compared as [`json_eq`] does.
+struct Normalized<'a>(&'a Value);
+
+impl PartialEq for Normalized<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        json_eq(self.0, other.0)
+    }
+}
+
+impl Eq for Normalized<'_> {}
+
+impl Hash for Normalized<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self.0).hash(state);
+        match self.0 {
+            Value::Null => {}
+            Value::Bool(b) => b.hash(state),
+            Value::Number(n) => match integer(n) {
+                Some(integer) => integer.hash(state),
+                None => n.as_f64().map(f64::to_bits).hash(state),
+            },
+            Value::String(s) => s.hash(state),
+            Value::Array(items) => {
+                items.len().hash(state);
+                for item in items {
+                    Normalized(item).hash(state);
+                }
+            }
+            Value::Object(object) => {
+                // Independent of the order of the properties.
+                let mut sum = 0u64;
+                for (key, value) in object {
+                    let mut hasher = DefaultHasher::new();
+                    key.hash(&mut hasher);
+                    Normalized(value).hash(&mut hasher);
+                    sum = sum.wrapping_add(hasher.finish());
+                }
+                object.len().hash(state);
+                sum.hash(state);
+            }
+        }
+    }
+}
+
+struct InstanceValidator<'a> {
+    components: &'a Components,
+    schema_pointer: String,
+    instance_pointer: String,
+    errors: Vec<InstanceError>,
+    /// Component schemas being validated, with the instance pointer they are validated at, so
+    /// that schemas such as `A: {allOf: [A]}` terminate.
+    following: HashSet<(Str, String)>,
+}
+
+impl<'a> InstanceValidator<'a> {
+    fn report(&mut self, keyword: &str, message: String) {
+        let mut schema_pointer = self.schema_pointer.clone();
+        json_pointer::push(&mut schema_pointer, keyword);
+        self.errors.push(InstanceError {
+            instance_pointer: self.instance_pointer.clone(),
+            schema_pointer,
+            message,
+        });
+    }
+
+    /// Runs `f` with `keyword` appended to the schema pointer, and `token`, if any, appended to
+    /// the instance pointer.
+    fn at<T>(
+        &mut self,
+        keyword: &[&str],
+        token: Option<&str>,
+        f: impl FnOnce(&mut Self) -> T,
+    ) -> T {
+        let (schema_len, instance_len) = (self.schema_pointer.len(), self.instance_pointer.len());
+        for keyword in keyword {
+            json_pointer::push(&mut self.schema_pointer, keyword);
+        }
+        if let Some(token) = token {
+            json_pointer::push(&mut self.instance_pointer, token);
+        }
+        let out = f(self);
+        self.schema_pointer.truncate(schema_len);
+        self.instance_pointer.truncate(instance_len);
+        out
+    }
+
+    /// Validates `instance` against `schema`, returning the errors instead of reporting them.
+    fn try_schema(
+        &mut self,
+        schema: &ComponentOrInlineSchema,
+        instance: &Value,
+    ) -> Vec<InstanceError> {
+        let errors = mem::take(&mut self.errors);
+        self.component_or_inline(schema, instance);
+        mem::replace(&mut self.errors, errors)
+    }
+
+    fn component_or_inline(&mut self, schema: &ComponentOrInlineSchema, instance: &Value) {
+        let name = match schema {
+            ComponentOrInlineSchema::Inline(schema) => return self.schema(schema, instance),
+            ComponentOrInlineSchema::Component { name } => name,
+            ComponentOrInlineSchema::ExtRef { reference } => {
+                return self.report(
+                    "$ref",
+                    format!("cannot follow `{}`, which is not a component", reference),
+                )
+            }
+        };
+        let target = match self.components.get::<Schema>(name) {
+            Ok(target) => target,
+            Err(err) => return self.report("$ref", err.to_string()),
+        };
+        let key = (name.clone(), self.instance_pointer.clone());
+        if !self.following.insert(key.clone()) {
+            return;
+        }
+        let reference = component_ref::<Schema>(name);
+        let pointer = mem::replace(
+            &mut self.schema_pointer,
+            reference.trim_start_matches('#').to_string(),
+        );
+        self.schema(target, instance);
+        self.schema_pointer = pointer;
+        self.following.remove(&key);
+    }
+
+    fn schema(&mut self, schema: &Schema, instance: &Value) {
+        if instance.is_null() && schema.nullable == Some(true) {
+            return;
+        }
+        if let Some(ty) = schema.schema_type {
+            if !has_type(instance, ty) {
+                self.report(
+                    "type",
+                    format!(
+                        "expected {}, found {}",
+                        serde_json::json!(ty),
+                        json_type(instance)
+                    ),
+                );
+            }
+        }
+        if !schema.enum_values.is_empty() {
+            let found = match instance {
+                Value::String(s) => schema.enum_values.iter().any(|v| v == s),
+                _ => false,
+            };
+            if !found {
+                self.report(
+                    "enum",
+                    format!("{} is not one of the `enum` values", instance),
+                );
+            }
+        }
+        if let Some(value) = &schema.const_value {
+            if !json_eq(value, instance) {
+                self.report("const", format!("expected {}, found {}", value, instance));
+            }
+        }
+        match instance {
+            Value::Number(number) => self.number(schema, number),
+            Value::String(string) => self.string(schema, string),
+            Value::Array(items) => self.array(schema, items),
+            Value::Object(object) => self.object(schema, object),
+            Value::Null | Value::Bool(_) => {}
+        }
+
+        for (i, sub) in schema.all_of.iter().enumerate() {
+            self.at(&["allOf", &i.to_string()], None, |v| {
+                v.component_or_inline(sub, instance)
+            });
+        }
+        if !schema.any_of.is_empty() {
+            let matched = schema.any_of.iter().enumerate().any(|(i, sub)| {
+                self.at(&["anyOf", &i.to_string()], None, |v| {
+                    v.try_schema(sub, instance)
+                })
+                .is_empty()
+            });
+            if !matched {
+                self.report("anyOf", "does not match any schema".to_string());
+            }
+        }
+        if !schema.one_of.is_empty() {
+            let matched: Vec<_> = (0..schema.one_of.len())
+                .filter(|&i| {
+                    let sub = &schema.one_of[i];
+                    self.at(&["oneOf", &i.to_string()], None, |v| {
+                        v.try_schema(sub, instance)
+                    })
+                    .is_empty()
+                })
+                .collect();
+            if matched.len() != 1 {
+                self.report(
+                    "oneOf",
+                    format!("matches {} schemas instead of exactly one", matched.len()),
+                );
+            }
+        }
+    }
+
+    fn number(&mut self, schema: &Schema, number: &serde_json::Number) {
+        let value = number.as_f64().unwrap_or(f64::NAN);
+        let bound = |bound: &Option<Value>| bound.as_ref().and_then(Value::as_f64);
+        // `exclusiveMinimum` is a flag modifying `minimum` in 3.0, and a bound of its own in
+        // later versions of JSON Schema.
+        let exclusive = |flag: &Option<Value>| flag.as_ref().and_then(Value::as_bool) == Some(true);
+        if let Some(minimum) = bound(&schema.minimum) {
+            if exclusive(&schema.exclusive_minimum) && value <= minimum {
+                self.report(
+                    "minimum",
+                    format!("{} is not greater than {}", number, minimum),
+                );
+            } else if value < minimum {
+                self.report("minimum", format!("{} is less than {}", number, minimum));
+            }
+        }
+        if let Some(minimum) = bound(&schema.exclusive_minimum) {
+            if value <= minimum {
+                self.report(
+                    "exclusiveMinimum",
+                    format!("{} is not greater than {}", number, minimum),
+                );
+            }
+        }
+        if let Some(maximum) = bound(&schema.maximum) {
+            if exclusive(&schema.exclusive_maximum) && value >= maximum {
+                self.report(
+                    "maximum",
+                    format!("{} is not less than {}", number, maximum),
+                );
+            } else if value > maximum {
+                self.report("maximum", format!("{} is greater than {}", number, maximum));
+            }
+        }
+        if let Some(maximum) = bound(&schema.exclusive_maximum) {
+            if value >= maximum {
+                self.report(
+                    "exclusiveMaximum",
+                    format!("{} is not less than {}", number, maximum),
+                );
+            }
+        }
+        if let Some(Value::Number(divisor)) = &schema.multiple_of {
+            if !is_multiple_of(number, divisor) {
+                self.report(
+                    "multipleOf",
+                    format!("{} is not a multiple of {}", number, divisor),
+                );
+            }
+        }
+    }
+
+    fn string(&mut self, schema: &Schema, string: &str) {
+        let len = string.chars().count();
+        if let Some(min) = schema.min_length {
+            if len < min {
+                self.report("minLength", format!("length {} is less than {}", len, min));
+            }
+        }
+        if let Some(max) = schema.max_length {
+            if len > max {
+                self.report(
+                    "maxLength",
+                    format!("length {} is greater than {}", len, max),
+                );
+            }
+        }
+        if !schema.pattern.is_empty() {
+            match Regex::new(&schema.pattern) {
+                Ok(pattern) if pattern.is_match(string) => {}
+                Ok(_) => self.report(
+                    "pattern",
+                    format!("`{}` does not match `{}`", string, schema.pattern),
+                ),
+                Err(err) => self.report("pattern", format!("invalid pattern: {}", err)),
+            }
+        }
+    }
+
+    fn array(&mut self, schema: &Schema, items: &[Value]) {
+        if let Some(min) = schema.min_items {
+            if items.len() < min {
+                self.report(
+                    "minItems",
+                    format!("{} items, expected at least {}", items.len(), min),
+                );
+            }
+        }
+        if let Some(max) = schema.max_items {
+            if items.len() > max {
+                self.report(
+                    "maxItems",
+                    format!("{} items, expected at most {}", items.len(), max),
+                );
+            }
+        }
+        if schema.unique_items == Some(true) && !are_unique(items) {
+            self.report("uniqueItems", "items are not unique".to_string());
+        }
+        if let Some(sub) = &schema.items {
+            for (i, item) in items.iter().enumerate() {
+                self.at(&["items"], Some(&i.to_string()), |v| {
+                    v.component_or_inline(sub, item)
+                });
+            }
+        }
+    }
+
+    fn object(&mut self, schema: &Schema, object: &Map<String, Value>) {
+        if let Some(min) = schema.min_properties {
+            if object.len() < min {
+                self.report(
+                    "minProperties",
+                    format!("{} properties, expected at least {}", object.len(), min),
+                );
+            }
+        }
+        if let Some(max) = schema.max_properties {
+            if object.len() > max {
+                self.report(
+                    "maxProperties",
+                    format!("{} properties, expected at most {}", object.len(), max),
+                );
+            }
+        }
+        for name in &schema.required {
+            if !object.contains_key(&**name) {
+                self.report("required", format!("missing property `{}`", name));
+            }
+        }
+        for (name, required) in &schema.dependent_required {
+            if !object.contains_key(&**name) {
+                continue;
+            }
+            for dependency in required {
+                if !object.contains_key(&**dependency) {
+                    self.report(
+                        "dependentRequired",
+                        format!("missing property `{}`, required by `{}`", dependency, name),
+                    );
+                }
+            }
+        }
+        for (name, value) in object {
+            match schema.properties.get(name.as_str()) {
+                Some(sub) => self.at(&["properties", name], Some(name), |v| {
+                    v.component_or_inline(sub, value)
+                }),
+                None => {
+                    if let Some(sub) = &schema.additional_properties {
+                        self.at(&["additionalProperties"], Some(name), |v| {
+                            v.component_or_inline(sub, value)
+                        });
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const COMPONENTS: &str = r##"{
+      "schemas": {
+        "Pet": {
+          "type": "object",
+          "required": ["name"],
+          "properties": {
+            "name": {"type": "string", "minLength": 1, "maxLength": 8, "pattern": "^[a-z]+$"},
+            "kind": {"type": "string", "enum": ["cat", "dog"]},
+            "age": {"type": "integer", "minimum": 0, "exclusiveMaximum": true, "maximum": 30},
+            "weight": {"type": "number", "multipleOf": 0.5, "nullable": true},
+            "tags": {"type": "array", "maxItems": 2, "uniqueItems": true, "items": {"type": "string"}},
+            "parent": {"$ref": "#/components/schemas/Pet"}
+          },
+          "additionalProperties": {"type": "boolean"},
+          "dependentRequired": {"kind": ["age"]}
+        },
+        "Id": {
+          "oneOf": [{"type": "integer"}, {"type": "number", "minimum": 2}],
+          "anyOf": [{"type": "integer"}, {"type": "string"}],
+          "allOf": [{"const": 1}]
+        }
+      }
+    }"##;
+
+    fn errors(schema: &str, instance: Value) -> Vec<(String, String)> {
+        let components: Components = serde_json::from_str(COMPONENTS).unwrap();
+        let schema = ComponentOrInlineSchema::Component {
+            name: schema.to_string().into(),
+        };
+        components
+            .validate_instance(&schema, &instance)
+            .into_iter()
+            .map(|e| (e.instance_pointer, e.schema_pointer))
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_instances() {
+        let pet = json!({
+            "name": "rex",
+            "kind": "dog",
+            "age": 3,
+            "weight": null,
+            "tags": ["a", "b"],
+            "parent": {"name": "max", "weight": 12.5},
+            "vaccinated": true
+        });
+        assert_eq!(errors("Pet", pet), []);
+        assert_eq!(errors("Id", json!(1)), []);
+        assert_eq!(errors("Id", json!(1.0)), []);
+    }
+
+    #[test]
+    fn test_are_unique() {
+        assert!(are_unique(&[json!(1), json!(1.5), json!("1"), json!([1])]));
+        assert!(!are_unique(&[json!(1), json!(1.0)]));
+        assert!(!are_unique(&[
+            json!({"a": [1.0], "b": null}),
+            json!({"b": null, "a": [1]})
+        ]));
+        assert!(are_unique(&[json!(u64::MAX), json!(u64::MAX as f64)]));
+
+        let mut items: Vec<Value> = (0..100).map(|i| json!({ "id": i })).collect();
+        assert!(are_unique(&items));
+        items.push(json!({"id": 42.0}));
+        assert!(!are_unique(&items));
+        items.pop();
+        items.push(json!({"id": 42.5}));
+        assert!(are_unique(&items));
+    }
+
+    #[test]
+    fn test_invalid_instances() {
+        // Fields are sorted, as `serde_json` keeps them.
+        let pet = json!({
+            "kind": "bird",
+            "name": "Rex the dog",
+            "parent": {"age": 30, "weight": "heavy"},
+            "tags": ["a", "a", "b"],
+            "vaccinated": "yes",
+            "weight": 1.2
+        });
+        let pet_pointer = |p: &str| format!("/components/schemas/Pet{}", p);
+        assert_eq!(
+            errors("Pet", pet),
+            [
+                ("".into(), pet_pointer("/dependentRequired")),
+                ("/kind".into(), pet_pointer("/properties/kind/enum")),
+                ("/name".into(), pet_pointer("/properties/name/maxLength")),
+                ("/name".into(), pet_pointer("/properties/name/pattern")),
+                ("/parent".into(), pet_pointer("/required")),
+                ("/parent/age".into(), pet_pointer("/properties/age/maximum")),
+                (
+                    "/parent/weight".into(),
+                    pet_pointer("/properties/weight/type")
+                ),
+                ("/tags".into(), pet_pointer("/properties/tags/maxItems")),
+                ("/tags".into(), pet_pointer("/properties/tags/uniqueItems")),
+                (
+                    "/vaccinated".into(),
+                    pet_pointer("/additionalProperties/type")
+                ),
+                (
+                    "/weight".into(),
+                    pet_pointer("/properties/weight/multipleOf")
+                ),
+            ]
+        );
+
+        let id_pointer = |p: &str| format!("/components/schemas/Id{}", p);
+        assert_eq!(
+            errors("Id", json!("1")),
+            [
+                ("".into(), id_pointer("/allOf/0/const")),
+                ("".into(), id_pointer("/oneOf")),
+            ]
+        );
+        assert_eq!(
+            errors("Id", json!(2)),
+            [
+                ("".into(), id_pointer("/allOf/0/const")),
+                ("".into(), id_pointer("/oneOf")),
+            ]
+        );
+        assert_eq!(
+            errors("Missing", json!(2)),
+            [("".into(), "/$ref".to_string())]
+        );
+
+        let number = |n: Value| serde_json::from_value(n).unwrap();
+        assert!(is_multiple_of(&number(json!(i64::MIN)), &number(json!(2))));
+        assert!(!is_multiple_of(
+            &number(json!(i64::MIN)),
+            &number(json!(-1))
+        ));
+        assert!(!is_multiple_of(&number(json!(4)), &number(json!(0))));
+        assert!(!is_multiple_of(&number(json!(1.0)), &number(json!(-0.5))));
+
+        let components: Components = serde_json::from_str(COMPONENTS).unwrap();
+        let schema = ComponentOrInlineSchema::Component { name: "Pet".into() };
+        let error = &components.validate_instance(&schema, &json!({}))[0];
+        assert_eq!(
+            error.to_string(),
+            "``: missing property `name` (at `/components/schemas/Pet/required` of the schema)"
+        );
+    }
+}
@@ -0,0 +1,818 @@
+//! Merging of the specifications of several services into a single document, such as the one
+//! of a gateway.
+
+use crate::{
+    json_pointer,
+    v3_0::{
+        diff::{operations, METHODS},
+        visit::{self, VisitMut},
+        Callback, Component, ComponentOrInlineSchema, Components, Example, Header, Link,
+        LinkOperation, ObjectOrReference, Operation, Parameter, PathItem, RequestBody, Response,
+        Schema, Spec,
+    },
+    Str,
+};
+use std::{collections::HashMap, fmt};
+
+/// A specification to [`merge`], with how to tell its paths and components from those of the
+/// other specifications.
+#[derive(Clone, Debug)]
+pub struct MergeSource<'a> {
+    pub spec: &'a Spec,
+    /// Prefix of the names of the components, e.g. `billing` for `billing.Invoice`. Names are
+    /// kept as they are if empty.
+    pub namespace: Str,
+    /// Prefix of the paths, e.g. `/billing` for `/billing/invoices`.
+    pub path_prefix: Str,
+}
+
+/// What [`merge`] does when specifications define the same thing differently.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ConflictStrategy {
+    /// Fails with every conflict.
+    Fail,
+    /// Keeps the definition merged first.
+    KeepFirst,
+    /// Keeps the definition merged last.
+    KeepLast,
+}
+
+/// What is defined differently by two specifications.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ConflictKind {
+    /// An operation, i.e. a path and method.
+    Operation,
+    /// The summary, description, servers or parameters common to the operations of a path.
+    PathItem,
+    /// A component other than a security scheme, once namespaced.
+    Component,
+    /// A security scheme, which is not namespaced.
+    SecurityScheme,
+    /// An `operationId` used by two operations.
+    OperationId,
+}
+
+/// A definition conflicting with one merged before.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MergeConflict {
+    /// JSON pointer of the definition in the merged document.
+    pub pointer: String,
+    pub kind: ConflictKind,
+    pub message: String,
+}
+
+impl fmt::Display for MergeConflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`{}`: {}", self.pointer, self.message)
+    }
+}
+
+/// The result of [`merge`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Merged {
+    pub spec: Spec,
+    /// The conflicts resolved with [`ConflictStrategy::KeepFirst`] or
+    /// [`ConflictStrategy::KeepLast`].
+    pub conflicts: Vec<MergeConflict>,
+}
+
+/// Merges the paths and components of `sources` into `base`, which provides the `info` and any
+/// other metadata of the merged document.
+///
+/// Paths are prefixed and components are namespaced as configured by each source, and
+/// references are rewritten to match. Components which are identical once their references
+/// are rewritten are merged, keeping the name of the first one. Security schemes, tags and
+/// servers are merged by name, and the security requirements of a source apply to its
+/// operations without a `security` field of their own. Operations with one, including
+/// public ones with `security: []`, keep theirs.
+///
+/// An `operationId` used by operations of two sources is a conflict, and the operation which
+/// is not kept loses it.
+///
+/// Returns the conflicts instead of the merged document if `strategy` is
+/// [`ConflictStrategy::Fail`] and there are any.
+pub fn merge(
+    base: Spec,
+    sources: &[MergeSource],
+    strategy: ConflictStrategy,
+) -> Result<Merged, Vec<MergeConflict>> {
+    let mut names: Vec<Names> = sources.iter().map(namespaced).collect();
+    while deduplicate(sources, &mut names) {}
+    let mut operation_ids = HashMap::new();
+    for (path, item) in &base.paths {
+        for (method, operation) in METHODS.iter().zip(operations(item)) {
+            if let Some(operation) = operation.filter(|o| !o.operation_id.is_empty()) {
+                let location = (path.clone(), *method);
+                operation_ids
+                    .entry(operation.operation_id.clone())
+                    .or_insert(location);
+            }
+        }
+    }
+    let mut merger = Merger {
+        spec: base,
+        strategy,
+        operation_ids,
+        conflicts: Vec::new(),
+    };
+    for (source, names) in sources.iter().zip(&names) {
+        merger.source(source, names);
+    }
+    match strategy {
+        ConflictStrategy::Fail if !merger.conflicts.is_empty() => Err(merger.conflicts),
+        _ => Ok(Merged {
+            spec: merger.spec,
+            conflicts: merger.conflicts,
+        }),
+    }
+}
+
+/// Merged names of the components of a source, by kind and name.
+type Names = HashMap<(Str, Str), Str>;
+
+fn key<T: Component>(name: &str) -> (Str, Str) {
+    (T::KIND.into(), name.to_string().into())
+}
+
+fn qualify(namespace: &str, name: &str) -> Str {
+    if namespace.is_empty() {
+        name.to_string().into()
+    } else {
+        format!("{}.{}", namespace, name).into()
+    }
+}
+
+fn prefixed(prefix: &str, path: &str) -> Str {
+    let prefix = prefix.trim_end_matches('/');
+    if prefix.is_empty() || prefix.starts_with('/') {
+        format!("{}{}", prefix, path).into()
+    } else {
+        format!("/{}{}", prefix, path).into()
+    }
+}
+
+/// Calls `$f::<T>($args)` for every kind of component but security schemes, which are not
+/// namespaced.
+macro_rules! namespaced_kinds {
+    ($f:ident($($args:expr),*)) => {
+        [
+            $f::<Schema>($($args),*),
+            $f::<Response>($($args),*),
+            $f::<Parameter>($($args),*),
+            $f::<Example>($($args),*),
+            $f::<RequestBody>($($args),*),
+            $f::<Header>($($args),*),
+            $f::<Link>($($args),*),
+            $f::<Callback>($($args),*),
+        ]
+    };
+}
+
+/// Returns the namespaced names of the components of `source`.
+fn namespaced(source: &MergeSource) -> Names {
+    fn kind<T: Component>(source: &MergeSource, names: &mut Names) {
+        if let Some(components) = &source.spec.components {
+            for name in T::components(components).keys() {
+                names.insert(key::<T>(name), qualify(&source.namespace, name));
+            }
+        }
+    }
+
+    let mut names = Names::new();
+    namespaced_kinds!(kind(source, &mut names));
+    names
+}
+
+/// Renames the components which are identical to one met before. Returns whether any was.
+fn deduplicate(sources: &[MergeSource], names: &mut [Names]) -> bool {
+    fn kind<T>(sources: &[MergeSource], names: &mut [Names]) -> bool
+    where
+        T: Component + Clone + PartialEq + Rename,
+    {
+        let mut kept: Vec<(Str, ObjectOrReference<T>)> = Vec::new();
+        let mut renamed = false;
+        for (source, names) in sources.iter().zip(names) {
+            let components = match &source.spec.components {
+                Some(components) => components,
+                None => continue,
+            };
+            for (name, object) in T::components(components) {
+                let key = key::<T>(name);
+                let merged = names[&key].clone();
+                if merged != qualify(&source.namespace, name) {
+                    // Already renamed.
+                    continue;
+                }
+                let mut object = object.clone();
+                object.rename(&mut Renamer {
+                    names,
+                    prefix: &source.path_prefix,
+                });
+                match kept
+                    .iter()
+                    .find(|(name, o)| *o == object && *name != merged)
+                {
+                    Some((name, _)) => {
+                        names.insert(key, name.clone());
+                        renamed = true;
+                    }
+                    None => kept.push((merged, object)),
+                }
+            }
+        }
+        renamed
+    }
+
+    namespaced_kinds!(kind(sources, names)).contains(&true)
+}
+
+struct Merger {
+    spec: Spec,
+    strategy: ConflictStrategy,
+    /// The path and method of the operations of the merged document, by `operationId`.
+    operation_ids: HashMap<Str, (Str, &'static str)>,
+    conflicts: Vec<MergeConflict>,
+}
+
+impl Merger {
+    fn conflict(&mut self, pointer: String, kind: ConflictKind, message: String) {
+        self.conflicts.push(MergeConflict {
+            pointer,
+            kind,
+            message,
+        });
+    }
+
+    fn source(&mut self, source: &MergeSource, names: &Names) {
+        let mut renamer = Renamer {
+            names,
+            prefix: &source.path_prefix,
+        };
+        namespaced_kinds!(component_kind(self, source, &mut renamer));
+        if let Some(components) = &source.spec.components {
+            for (name, scheme) in &components.security_schemes {
+                self.component(name.clone(), scheme.clone());
+            }
+        }
+        for (path, item) in &source.spec.paths {
+            let mut item = item.clone();
+            item.rename(&mut renamer);
+            for method in METHODS.iter() {
+                if let Some(operation) = operation_mut(&mut item, method) {
+                    if operation.security.is_none() && source.spec.security != self.spec.security {
+                        operation.security = Some(source.spec.security.clone());
+                    }
+                }
+            }
+            self.path(prefixed(&source.path_prefix, path), item);
+        }
+        for tag in &source.spec.tags {
+            if !self.spec.tags.iter().any(|t| t.name == tag.name) {
+                self.spec.tags.push(tag.clone());
+            }
+        }
+        for server in &source.spec.servers {
+            if !self.spec.servers.contains(server) {
+                self.spec.servers.push(server.clone());
+            }
+        }
+    }
+
+    /// Adds `object` to the components, unless it is already there.
+    fn component<T: Component + PartialEq>(&mut self, name: Str, object: ObjectOrReference<T>) {
+        let components = self.spec.components.get_or_insert_with(Components::default);
+        let map = T::components_mut(components);
+        let conflict = match map.get(&name) {
+            None => false,
+            Some(existing) if *existing == object => return,
+            Some(_) => true,
+        };
+        if !conflict || self.strategy == ConflictStrategy::KeepLast {
+            map.insert(name.clone(), object);
+        }
+        if conflict {
+            let mut pointer = format!("/components/{}", T::KIND);
+            json_pointer::push(&mut pointer, &name);
+            let (kind, what) = match T::KIND {
+                "securitySchemes" => (ConflictKind::SecurityScheme, "security scheme"),
+                _ => (ConflictKind::Component, "component"),
+            };
+            let message = format!("{} `{}` is defined differently", what, name);
+            self.conflict(pointer, kind, message);
+        }
+    }
+
+    fn path(&mut self, path: Str, mut item: PathItem) {
+        let mut pointer = "/paths".to_string();
+        json_pointer::push(&mut pointer, &path);
+        self.operation_ids(&path, &pointer, &mut item);
+        let existing = match self.spec.paths.get_mut(&path) {
+            Some(existing) => existing,
+            None => {
+                self.spec.paths.insert(path, item);
+                return;
+            }
+        };
+        let mut conflicts = Vec::new();
+        for method in METHODS.iter() {
+            let (existing, new) = (
+                operation_mut(existing, method),
+                operation_mut(&mut item, method),
+            );
+            match (existing.is_some(), new.take()) {
+                (_, None) => {}
+                (false, new) => *existing = new,
+                (true, new) => {
+                    conflicts.push((
+                        format!("{}/{}", pointer, method),
+                        ConflictKind::Operation,
+                        format!("`{} {}` is defined twice", method.to_uppercase(), path),
+                    ));
+                    if self.strategy == ConflictStrategy::KeepLast {
+                        *existing = new;
+                    }
+                }
+            }
+        }
+        // Only the fields common to the operations are left in `item`.
+        let common = without_operations(existing);
+        if item != PathItem::default() && item != common {
+            let keep_last = common == PathItem::default() || {
+                conflicts.push((
+                    pointer,
+                    ConflictKind::PathItem,
+                    format!("path `{}` is described differently", path),
+                ));
+                self.strategy == ConflictStrategy::KeepLast
+            };
+            if keep_last {
+                for method in METHODS.iter() {
+                    *operation_mut(&mut item, method) = operation_mut(existing, method).take();
+                }
+                *existing = item;
+            }
+        }
+        for (pointer, kind, message) in conflicts {
+            self.conflict(pointer, kind, message);
+        }
+    }
+
+    /// Keeps the `operationId`s of the operations of `item` unique, as they are merged at
+    /// `path`.
+    fn operation_ids(&mut self, path: &Str, pointer: &str, item: &mut PathItem) {
+        for method in METHODS.iter() {
+            let operation = match operation_mut(item, method) {
+                Some(operation) if !operation.operation_id.is_empty() => operation,
+                _ => continue,
+            };
+            let replaced = self
+                .spec
+                .paths
+                .get_mut(path)
+                .and_then(|existing| operation_mut(existing, method).as_ref());
+            if replaced.is_some() && self.strategy != ConflictStrategy::KeepLast {
+                // The operation conflicts with one merged before, and is not merged.
+                continue;
+            }
+            if let Some(replaced) = replaced {
+                if self.operation_ids.get(&replaced.operation_id) == Some(&(path.clone(), method)) {
+                    self.operation_ids.remove(&replaced.operation_id);
+                }
+            }
+            let id = operation.operation_id.clone();
+            let (other_path, other_method) = match self.operation_ids.get(&id) {
+                Some(other) => other.clone(),
+                None => {
+                    self.operation_ids.insert(id, (path.clone(), method));
+                    continue;
+                }
+            };
+            let message = format!(
+                "operationId `{}` is also used by `{} {}`",
+                id,
+                other_method.to_uppercase(),
+                other_path
+            );
+            self.conflict(
+                format!("{}/{}/operationId", pointer, method),
+                ConflictKind::OperationId,
+                message,
+            );
+            if self.strategy == ConflictStrategy::KeepLast {
+                let other = &mut self.spec.paths[&other_path];
+                if let Some(other) = operation_mut(other, other_method) {
+                    other.operation_id = Str::default();
+                }
+                self.operation_ids.insert(id, (path.clone(), method));
+            } else {
+                operation.operation_id = Str::default();
+            }
+        }
+    }
+}
+
+fn component_kind<T>(merger: &mut Merger, source: &MergeSource, renamer: &mut Renamer)
+where
+    T: Component + Clone + PartialEq + Rename,
+{
+    let components = match &source.spec.components {
+        Some(components) => components,
+        None => return,
+    };
+    for (name, object) in T::components(components) {
+        let merged = &renamer.names[&key::<T>(name)];
+        if *merged != qualify(&source.namespace, name) {
+            // Identical to a component merged under another name.
+            continue;
+        }
+        let mut object = object.clone();
+        object.rename(renamer);
+        merger.component(merged.clone(), object);
+    }
+}
+
+fn operation_mut<'a>(item: &'a mut PathItem, method: &str) -> &'a mut Option<Operation> {
+    match method {
+        "get" => &mut item.get,
+        "put" => &mut item.put,
+        "post" => &mut item.post,
+        "delete" => &mut item.delete,
+        "options" => &mut item.options,
+        "head" => &mut item.head,
+        "patch" => &mut item.patch,
+        _ => &mut item.trace,
+    }
+}
+
+fn without_operations(item: &PathItem) -> PathItem {
+    let mut item = item.clone();
+    for method in METHODS.iter() {
+        *operation_mut(&mut item, method) = None;
+    }
+    item
+}
+
+/// Rewrites the references of a source to the merged document.
+struct Renamer<'a> {
+    names: &'a Names,
+    prefix: &'a str,
+}
+
+impl<'a> VisitMut for Renamer<'a> {
+    fn visit_component_or_inline_schema_mut(
+        &mut self,
+        pointer: &str,
+        schema: &mut ComponentOrInlineSchema,
+    ) {
+        if let ComponentOrInlineSchema::Component { name } = schema {
+            if let Some(merged) = self.names.get(&key::<Schema>(name)) {
+                *name = merged.clone();
+            }
+        }
+        visit::walk_component_or_inline_schema_mut(self, pointer, schema);
+    }
+
+    fn visit_reference_mut(&mut self, _: &str, reference: &mut Str) {
+        let tokens = reference
+            .strip_prefix('#')
+            .and_then(json_pointer::split)
+            .unwrap_or_default();
+        let renamed = match tokens.as_slice() {
+            [components, kind, name] if components == "components" => self
+                .names
+                .get(&(kind.to_string().into(), name.to_string().into()))
+                .map(|name| format!("#/components/{}/{}", kind, json_pointer::escape(name))),
+            // Paths, or what they contain, such as the operations of `operationRef`.
+            [paths, path, rest @ ..] if paths == "paths" => {
+                let mut pointer = "#/paths".to_string();
+                json_pointer::push(&mut pointer, &prefixed(self.prefix, path));
+                for token in rest {
+                    json_pointer::push(&mut pointer, token);
+                }
+                Some(pointer)
+            }
+            _ => None,
+        };
+        if let Some(renamed) = renamed {
+            *reference = renamed.into();
+        }
+    }
+
+    fn visit_link_mut(&mut self, pointer: &str, link: &mut Link) {
+        if let LinkOperation::Ref { operation_ref } = &mut link.operation {
+            self.visit_reference_mut(pointer, operation_ref);
+        }
+    }
+}
+
+/// Objects which may contain references.
+trait Rename {
+    fn rename(&mut self, renamer: &mut Renamer);
+}
+
+impl<T: Rename> Rename for ObjectOrReference<T> {
+    fn rename(&mut self, renamer: &mut Renamer) {
+        match self {
+            ObjectOrReference::Object(object) => object.rename(renamer),
+            ObjectOrReference::Ref { ref_path } => renamer.visit_reference_mut("", ref_path),
+        }
+    }
+}
+
+macro_rules! rename {
+    ($($ty:ty => $visit:ident),*) => {
+        $(impl Rename for $ty {
+            fn rename(&mut self, renamer: &mut Renamer) {
+                renamer.$visit("", self)
+            }
+        })*
+    };
+}
+
+rename!(
+    Schema => visit_schema_mut,
+    Response => visit_response_mut,
+    Parameter => visit_parameter_mut,
+    Example => visit_example_mut,
+    RequestBody => visit_request_body_mut,
+    Header => visit_header_mut,
+    Link => visit_link_mut,
+    Callback => visit_callback_mut,
+    PathItem => visit_path_item_mut
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(json: &str) -> Spec {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn billing() -> Spec {
+        spec(
+            r##"{
+              "openapi": "3.0.3",
+              "info": {"title": "Billing", "version": "1.0.0"},
+              "servers": [{"url": "https://api.example.com"}],
+              "security": [{"oauth": ["billing"]}],
+              "tags": [{"name": "billing"}],
+              "paths": {
+                "/invoices": {
+                  "get": {
+                    "operationId": "listInvoices",
+                    "tags": ["billing"],
+                    "responses": {
+                      "200": {
+                        "description": "Invoices",
+                        "content": {
+                          "application/json": {"schema": {"$ref": "#/components/schemas/Invoice"}}
+                        },
+                        "links": {"self": {"operationRef": "#/paths/~1invoices/get"}}
+                      }
+                    }
+                  }
+                },
+                "/bills": {"$ref": "#/paths/~1invoices"}
+              },
+              "components": {
+                "schemas": {
+                  "Money": {"type": "object", "properties": {"amount": {"type": "number"}}},
+                  "Invoice": {
+                    "title": "Invoice",
+                    "type": "object",
+                    "properties": {"total": {"$ref": "#/components/schemas/Money"}}
+                  }
+                },
+                "securitySchemes": {
+                  "oauth": {
+                    "type": "oauth2",
+                    "flows": {
+                      "clientCredentials": {
+                        "tokenUrl": "https://auth.example.com/token",
+                        "scopes": {"billing": "Billing", "orders": "Orders"}
+                      }
+                    }
+                  }
+                }
+              }
+            }"##,
+        )
+    }
+
+    fn orders() -> Spec {
+        let json = serde_json::to_string(&billing())
+            .unwrap()
+            .replace("Billing", "Orders")
+            .replace("billing", "orders")
+            .replace("invoices", "orders")
+            .replace("Invoice", "Order")
+            .replace("bills", "purchases");
+        let mut orders = spec(&json);
+        // Both services share the same authorization server.
+        let billing = billing().components.unwrap().security_schemes;
+        orders.components.as_mut().unwrap().security_schemes = billing;
+        orders
+    }
+
+    fn base() -> Spec {
+        spec(
+            r#"{
+              "openapi": "3.0.3",
+              "info": {"title": "Gateway", "version": "1.0.0"},
+              "paths": {}
+            }"#,
+        )
+    }
+
+    #[test]
+    fn test_merge() {
+        let (billing, orders) = (billing(), orders());
+        let sources = [
+            MergeSource {
+                spec: &billing,
+                namespace: "billing".into(),
+                path_prefix: "/billing".into(),
+            },
+            MergeSource {
+                spec: &orders,
+                namespace: "orders".into(),
+                path_prefix: "orders/".into(),
+            },
+        ];
+        let merged = merge(base(), &sources, ConflictStrategy::Fail).unwrap();
+        assert_eq!(merged.conflicts, []);
+        let spec = merged.spec;
+
+        assert_eq!(
+            spec.paths.keys().collect::<Vec<_>>(),
+            [
+                "/billing/invoices",
+                "/billing/bills",
+                "/orders/orders",
+                "/orders/purchases"
+            ]
+        );
+        assert_eq!(
+            spec.paths["/orders/purchases"].reference,
+            "#/paths/~1orders~1orders"
+        );
+        let get = spec.paths["/orders/orders"].get.as_ref().unwrap();
+        assert_eq!(get.security.as_ref(), Some(&orders.security));
+        let response = spec.resolve(&get.responses["200"]).unwrap();
+        assert_eq!(
+            response.links["self"],
+            ObjectOrReference::Object(
+                Link::to_operation_ref("#/paths/~1orders~1orders/get").build()
+            )
+        );
+        assert_eq!(
+            response.content["application/json"].schema,
+            Some(ComponentOrInlineSchema::Component {
+                name: "orders.Order".into()
+            })
+        );
+
+        // Both `Money` schemas are the same, unlike `Invoice` and `Order` which differ by
+        // their title.
+        let components = spec.components.as_ref().unwrap();
+        assert_eq!(
+            components.schemas.keys().collect::<Vec<_>>(),
+            ["billing.Money", "billing.Invoice", "orders.Order"]
+        );
+        let order = spec.resolve_ref::<Schema>("#/components/schemas/orders.Order");
+        assert_eq!(
+            order.unwrap().properties["total"],
+            ComponentOrInlineSchema::Component {
+                name: "billing.Money".into()
+            }
+        );
+        assert_eq!(components.security_schemes.len(), 1);
+        let tags: Vec<_> = spec.tags.iter().map(|t| &t.name).collect();
+        assert_eq!(tags, ["billing", "orders"]);
+        assert_eq!(spec.servers.len(), 1);
+        assert!(spec.security.is_empty());
+    }
+
+    #[test]
+    fn test_merge_public_operations() {
+        let mut billing = billing();
+        let health: PathItem = serde_json::from_str(
+            r#"{
+              "get": {"security": [], "responses": {"204": {"description": "Healthy"}}}
+            }"#,
+        )
+        .unwrap();
+        billing.paths.insert("/health".into(), health);
+        let sources = [MergeSource {
+            spec: &billing,
+            namespace: "billing".into(),
+            path_prefix: "/billing".into(),
+        }];
+        let spec = merge(base(), &sources, ConflictStrategy::Fail)
+            .unwrap()
+            .spec;
+        let get = spec.paths["/billing/health"].get.as_ref().unwrap();
+        assert_eq!(get.security, Some(Vec::new()));
+        let get = spec.paths["/billing/invoices"].get.as_ref().unwrap();
+        assert_eq!(get.security.as_ref(), Some(&billing.security));
+    }
+
+    #[test]
+    fn test_operation_id_conflicts() {
+        let (billing, mut orders) = (billing(), orders());
+        let get = orders.paths["/orders"].get.as_mut().unwrap();
+        get.operation_id = "listInvoices".into();
+        let source = |spec, namespace: &'static str| MergeSource {
+            spec,
+            namespace: namespace.into(),
+            path_prefix: format!("/{}", namespace).into(),
+        };
+        let sources = [source(&billing, "billing"), source(&orders, "orders")];
+
+        let conflicts = merge(base(), &sources, ConflictStrategy::Fail).unwrap_err();
+        let conflicts: Vec<_> = conflicts.iter().map(|c| (c.kind, c.to_string())).collect();
+        assert_eq!(
+            conflicts,
+            [(
+                ConflictKind::OperationId,
+                "`/paths/~1orders~1orders/get/operationId`: operationId `listInvoices` is also \
+                 used by `GET /billing/invoices`"
+                    .to_string()
+            )]
+        );
+
+        let operation_id = |spec: &Spec, path: &str| {
+            let get = spec.paths[path].get.as_ref().unwrap();
+            get.operation_id.clone()
+        };
+        let first = merge(base(), &sources, ConflictStrategy::KeepFirst).unwrap();
+        assert_eq!(first.conflicts.len(), 1);
+        assert_eq!(
+            operation_id(&first.spec, "/billing/invoices"),
+            "listInvoices"
+        );
+        assert_eq!(operation_id(&first.spec, "/orders/orders"), "");
+        let last = merge(base(), &sources, ConflictStrategy::KeepLast).unwrap();
+        assert_eq!(operation_id(&last.spec, "/billing/invoices"), "");
+        assert_eq!(operation_id(&last.spec, "/orders/orders"), "listInvoices");
+    }
+
+    #[test]
+    fn test_conflicts() {
+        let billing = billing();
+        let mut other = billing.clone();
+        let components = other.components.as_mut().unwrap();
+        components.schemas["Money"] = ObjectOrReference::Object(Schema::default());
+        let scheme = components.security_schemes.get_mut("oauth").unwrap();
+        *scheme = ObjectOrReference::Ref {
+            ref_path: "#/components/securitySchemes/other".into(),
+        };
+        other.paths.shift_remove("/bills");
+        let source = |spec| MergeSource {
+            spec,
+            namespace: "billing".into(),
+            path_prefix: "/billing".into(),
+        };
+        let sources = [source(&billing), source(&other)];
+
+        let conflicts = merge(base(), &sources, ConflictStrategy::Fail).unwrap_err();
+        let conflicts: Vec<_> = conflicts.iter().map(|c| (c.kind, c.to_string())).collect();
+        assert_eq!(
+            conflicts,
+            [
+                (
+                    ConflictKind::Component,
+                    "`/components/schemas/billing.Money`: component `billing.Money` is defined \
+                     differently"
+                        .to_string()
+                ),
+                (
+                    ConflictKind::SecurityScheme,
+                    "`/components/securitySchemes/oauth`: security scheme `oauth` is defined \
+                     differently"
+                        .to_string()
+                ),
+                (
+                    ConflictKind::Operation,
+                    "`/paths/~1billing~1invoices/get`: `GET /billing/invoices` is defined twice"
+                        .to_string()
+                ),
+            ]
+        );
+
+        let first = merge(base(), &sources, ConflictStrategy::KeepFirst).unwrap();
+        assert_eq!(first.conflicts.len(), 3);
+        let last = merge(base(), &sources, ConflictStrategy::KeepLast).unwrap();
+        let schemas = &first.spec.components.as_ref().unwrap().schemas;
+        assert_ne!(
+            schemas["billing.Money"],
+            ObjectOrReference::Object(Schema::default())
+        );
+        let schemas = &last.spec.components.as_ref().unwrap().schemas;
+        assert_eq!(
+            schemas["billing.Money"],
+            ObjectOrReference::Object(Schema::default())
+        );
+    }
+}
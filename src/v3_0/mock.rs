@@ -0,0 +1,881 @@
+//! Mock server answering requests with the examples of a specification, for developing clients
+//! without the real server.
+
+use crate::{
+    v3_0::{
+        diff::METHODS,
+        operation::{body, is_json},
+        ExampleValue, Header, MediaType, MediaTypeExample, Operation, RequestParts, Response, Spec,
+    },
+    Str,
+};
+use indexmap::IndexMap;
+use serde_json::{json, Value};
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{Condvar, Mutex, MutexGuard},
+    thread,
+    time::Duration,
+};
+
+/// Largest size of the head of a request, or of a chunk size line, read by the mock server.
+const MAX_HEAD: usize = 64 * 1024;
+
+/// Largest size of the body of a request read by the mock server.
+const MAX_BODY: usize = 16 * 1024 * 1024;
+
+/// Largest number of connections the mock server serves at once.
+const MAX_CONNECTIONS: usize = 64;
+
+/// How long the mock server waits for each read from or write to a connection.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A response of the mock server.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MockResponse {
+    pub status: u16,
+    /// The names and values of the headers, in order, without `Content-Length`.
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl MockResponse {
+    /// Returns the value of the first header named `name`.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| &**value)
+    }
+
+    /// A JSON response listing `errors`.
+    fn errors(status: u16, errors: Vec<String>) -> Self {
+        MockResponse {
+            status,
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: serde_json::to_vec(&json!({ "errors": errors })).unwrap_or_default(),
+        }
+    }
+}
+
+/// Number of connections being served, limited to [`MAX_CONNECTIONS`].
+#[derive(Default)]
+struct Connections {
+    count: Mutex<usize>,
+    released: Condvar,
+}
+
+impl Connections {
+    /// Waits until fewer than [`MAX_CONNECTIONS`] are being served, and counts another one
+    /// until the returned guard is dropped.
+    fn acquire(&self) -> Connection<'_> {
+        let mut count = self
+            .released
+            .wait_while(lock(&self.count), |count| *count >= MAX_CONNECTIONS)
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        *count += 1;
+        Connection(self)
+    }
+}
+
+/// A connection counted by [`Connections`].
+struct Connection<'a>(&'a Connections);
+
+impl Drop for Connection<'_> {
+    fn drop(&mut self) {
+        *lock(&self.0.count) -= 1;
+        self.0.released.notify_one();
+    }
+}
+
+/// Locks `mutex`, even if a thread panicked while holding it.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+impl Spec {
+    /// Answers `request` with one of the responses of the operation it is sent to.
+    ///
+    /// Requests which match no path template are answered with `404 Not Found`, and those with
+    /// another method with `405 Method Not Allowed`. Requests whose parameters or body are
+    /// missing or invalid are answered with `400 Bad Request`. Error bodies are JSON objects
+    /// with a list of `errors`.
+    ///
+    /// The response is the first one with a `2XX` status code, or else the default response.
+    /// A `Prefer: code=404` header selects another response, falling back to ranges such as
+    /// `4XX` and to the default response, and skips the validation of the request. The body is
+    /// the `example` of the media type which the `Accept` header, if any, matches, or the
+    /// example of its `examples` named by a `Prefer: example=name` header, or its first one. It
+    /// is otherwise the [example instance](Spec::example_instance) of its schema. Headers of the
+    /// response with a schema are set to an example instance as well.
+    ///
+    /// `OPTIONS` requests to operations without `options` method are answered as CORS
+    /// preflight requests, and every response allows any origin.
+    ///
+    /// Requires the `mock` feature.
+    pub fn mock_response(&self, request: &RequestParts) -> MockResponse {
+        let mut response = self.mock(request);
+        response
+            .headers
+            .push(("Access-Control-Allow-Origin".to_string(), "*".to_string()));
+        response
+    }
+
+    /// Serves [mock responses](Spec::mock_response) to the HTTP/1.1 requests of the
+    /// connections accepted by `listener`, each on its own thread. Connections are closed after
+    /// a single request, or once a read or write has waited for 30 seconds. At most 64
+    /// connections are served at once, further ones waiting to be accepted.
+    ///
+    /// Returns once accepting a connection fails. Requires the `mock` feature.
+    pub fn serve_mock(&self, listener: &TcpListener) -> io::Result<()> {
+        let connections = Connections::default();
+        thread::scope(|scope| {
+            for stream in listener.incoming() {
+                let stream = stream?;
+                let connection = connections.acquire();
+                scope.spawn(move || {
+                    // The client may be gone, and there is nobody else to tell.
+                    let _ = self.serve_connection(stream);
+                    drop(connection);
+                });
+            }
+            Ok(())
+        })
+    }
+
+    fn serve_connection(&self, stream: TcpStream) -> io::Result<()> {
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        stream.set_write_timeout(Some(TIMEOUT))?;
+        let mut reader = BufReader::new(&stream);
+        let response = match read_http_request(&mut reader, &stream)? {
+            Ok(request) => {
+                let mut response = self.mock_response(&request);
+                if request.method.eq_ignore_ascii_case("head") {
+                    response.body.clear();
+                }
+                response
+            }
+            Err((status, error)) => MockResponse::errors(status, vec![error]),
+        };
+        write_http_response(&mut &stream, &response)
+    }
+
+    fn mock(&self, request: &RequestParts) -> MockResponse {
+        let found = self
+            .find_operation(&request.method, &request.path)
+            .or_else(|| {
+                // `HEAD` requests are answered as `GET` ones, without body.
+                let head = request.method.eq_ignore_ascii_case("head");
+                head.then(|| self.find_operation("get", &request.path))
+                    .flatten()
+            });
+        let found = match found {
+            Some(found) => found,
+            None => {
+                let allowed: Vec<String> = METHODS
+                    .iter()
+                    .filter(|method| self.find_operation(method, &request.path).is_some())
+                    .map(|method| method.to_ascii_uppercase())
+                    .collect();
+                if allowed.is_empty() {
+                    let error = format!("no operation matches `{}`", request.path);
+                    return MockResponse::errors(404, vec![error]);
+                }
+                if request.method.eq_ignore_ascii_case("options") {
+                    return preflight(request, &allowed);
+                }
+                let error = format!(
+                    "`{}` is not allowed, only {}",
+                    request.method,
+                    allowed.join(", ")
+                );
+                let mut response = MockResponse::errors(405, vec![error]);
+                response
+                    .headers
+                    .push(("Allow".to_string(), allowed.join(", ")));
+                return response;
+            }
+        };
+
+        let preferences = preferences(request);
+        let code = preferences
+            .iter()
+            .find(|(name, _)| name == "code")
+            .map(|(_, value)| &**value);
+        if code.is_none() {
+            if let Err(errors) = self.read_request(&found, request) {
+                let errors = errors.iter().map(ToString::to_string).collect();
+                return MockResponse::errors(400, errors);
+            }
+        }
+        let (status, response) = match self.select_response(found.operation, code) {
+            Ok(selected) => selected,
+            Err(error) => return MockResponse::errors(500, vec![error]),
+        };
+
+        let mut headers = Vec::new();
+        for (name, header) in &response.headers {
+            let header = match self.resolve(header) {
+                Ok(header) => header,
+                Err(_) => continue,
+            };
+            if name.eq_ignore_ascii_case("content-type") {
+                continue;
+            }
+            if let Some(value) = self.header_example(header) {
+                headers.push((name.to_string(), value));
+            }
+        }
+        if response.content.is_empty() {
+            return MockResponse {
+                status,
+                headers,
+                body: Vec::new(),
+            };
+        }
+        let accept = request.header("accept").unwrap_or_default();
+        let (media_type, media) = match negotiate(&response.content, accept) {
+            Some(negotiated) => negotiated,
+            None => {
+                let error = format!("none of the media types of the response match `{}`", accept);
+                return MockResponse::errors(406, vec![error]);
+            }
+        };
+        let example = preferences
+            .iter()
+            .find(|(name, _)| name == "example")
+            .map(|(_, value)| &**value);
+        let value = match self.media_example(media, example) {
+            Some(value) => value,
+            None => {
+                return MockResponse {
+                    status,
+                    headers,
+                    body: Vec::new(),
+                }
+            }
+        };
+        // Ranges such as `image/*` are no content type.
+        let content_type = if media_type.contains('*') {
+            "application/octet-stream"
+        } else {
+            media_type
+        };
+        headers.insert(0, ("Content-Type".to_string(), content_type.to_string()));
+        MockResponse {
+            status,
+            headers,
+            body: serialize(media_type, &value),
+        }
+    }
+
+    /// Returns the status code and the response of `operation` to answer with, the one for
+    /// `code` if a status code is preferred.
+    fn select_response<'a>(
+        &'a self,
+        operation: &'a Operation,
+        code: Option<&str>,
+    ) -> Result<(u16, &'a Response), String> {
+        let responses = &operation.responses;
+        let selected = match code {
+            Some(code) => {
+                let status = code
+                    .parse::<u16>()
+                    .ok()
+                    .filter(|status| (100..600).contains(status))
+                    .ok_or_else(|| format!("`{}` is not a status code", code))?;
+                let range = format!("{}XX", &code[..1]);
+                let response = responses
+                    .get(code)
+                    .or_else(|| {
+                        responses
+                            .iter()
+                            .find(|(key, _)| key.eq_ignore_ascii_case(&range))
+                            .map(|(_, response)| response)
+                    })
+                    .or_else(|| responses.get("default"))
+                    .ok_or_else(|| format!("the operation has no `{}` response", code))?;
+                (status, response)
+            }
+            None => {
+                let success = responses.iter().find_map(|(key, response)| {
+                    let status = match key.parse::<u16>() {
+                        Ok(status) => status,
+                        Err(_) if key.eq_ignore_ascii_case("2XX") => 200,
+                        Err(_) => return None,
+                    };
+                    (200..300).contains(&status).then_some((status, response))
+                });
+                success
+                    .or_else(|| responses.get("default").map(|response| (200, response)))
+                    .or_else(|| {
+                        responses
+                            .iter()
+                            .find_map(|(key, response)| Some((key.parse::<u16>().ok()?, response)))
+                    })
+                    .ok_or_else(|| "the operation has no responses".to_string())?
+            }
+        };
+        let (status, response) = selected;
+        let response = self.resolve(response).map_err(|err| err.to_string())?;
+        Ok((status, response))
+    }
+
+    /// The example of `media`, the one named `name` if there are several, or else the example
+    /// instance of its schema.
+    fn media_example(&self, media: &MediaType, name: Option<&str>) -> Option<Value> {
+        let example = match &media.examples {
+            Some(MediaTypeExample::Example { example }) => Some(example.clone()),
+            Some(MediaTypeExample::Examples { examples }) => {
+                let example = name
+                    .and_then(|name| examples.get(name))
+                    .or_else(|| examples.values().next());
+                match example.map(|example| self.resolve(example)) {
+                    Some(Ok(example)) => match &example.value {
+                        Some(ExampleValue::Embedded { value }) => Some(value.clone()),
+                        // External examples are not fetched, to stay offline.
+                        _ => None,
+                    },
+                    _ => None,
+                }
+            }
+            None => None,
+        };
+        example.or_else(|| {
+            let schema = media.schema.as_ref()?;
+            Some(self.example_instance(schema))
+        })
+    }
+
+    /// The value of `header` in an example response.
+    fn header_example(&self, header: &Header) -> Option<String> {
+        let value = self.example_instance(header.schema.as_ref()?);
+        match value {
+            Value::Null => None,
+            Value::String(value) => Some(value),
+            value => Some(value.to_string()),
+        }
+    }
+}
+
+/// The response to a CORS preflight request to a path with the operations `allowed`.
+fn preflight(request: &RequestParts, allowed: &[String]) -> MockResponse {
+    let mut headers = vec![(
+        "Access-Control-Allow-Methods".to_string(),
+        allowed.join(", "),
+    )];
+    if let Some(requested) = request.header("access-control-request-headers") {
+        headers.push((
+            "Access-Control-Allow-Headers".to_string(),
+            requested.to_string(),
+        ));
+    }
+    MockResponse {
+        status: 204,
+        headers,
+        body: Vec::new(),
+    }
+}
+
+/// The preferences of the `Prefer` headers of `request`, such as `code=404`, by name.
+fn preferences(request: &RequestParts) -> Vec<(String, String)> {
+    request
+        .headers
+        .iter()
+        .filter(|(name, _)| name.eq_ignore_ascii_case("prefer"))
+        .flat_map(|(_, value)| value.split([',', ';']))
+        .filter_map(|preference| {
+            let (name, value) = preference.split_once('=')?;
+            let value = value.trim().trim_matches('"');
+            Some((name.trim().to_ascii_lowercase(), value.to_string()))
+        })
+        .collect()
+}
+
+/// Returns the media type of `content` to answer with, the first one the media ranges of
+/// `accept` match, or the JSON one if the request accepts anything.
+fn negotiate<'a>(
+    content: &'a IndexMap<Str, MediaType>,
+    accept: &str,
+) -> Option<(&'a str, &'a MediaType)> {
+    for range in accept.split(',') {
+        let (kind, subtype) = essence(range);
+        if kind.is_empty() || (kind, subtype) == ("*", "*") {
+            continue;
+        }
+        let found = content.iter().find(|(media_type, _)| {
+            let (media_kind, media_subtype) = essence(media_type);
+            kind.eq_ignore_ascii_case(media_kind)
+                && (subtype == "*"
+                    || media_subtype == "*"
+                    || subtype.eq_ignore_ascii_case(media_subtype))
+        });
+        if let Some((media_type, media)) = found {
+            return Some((media_type, media));
+        }
+    }
+    let anything =
+        accept.trim().is_empty() || accept.split(',').any(|range| essence(range) == ("*", "*"));
+    if !anything {
+        return None;
+    }
+    body(content).map(|(media_type, media, _)| (media_type, media))
+}
+
+/// The type and subtype of `media_type`, without parameters.
+fn essence(media_type: &str) -> (&str, &str) {
+    let essence = media_type.split(';').next().unwrap_or_default().trim();
+    essence.split_once('/').unwrap_or((essence, ""))
+}
+
+/// Serializes `value` as a body of the media type `media_type`.
+fn serialize(media_type: &str, value: &Value) -> Vec<u8> {
+    if is_json(media_type) {
+        return serde_json::to_vec_pretty(value).unwrap_or_default();
+    }
+    if media_type.eq_ignore_ascii_case("application/x-www-form-urlencoded") {
+        if let Value::Object(object) = value {
+            let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+            for (name, value) in object {
+                match value {
+                    Value::String(value) => serializer.append_pair(name, value),
+                    value => serializer.append_pair(name, &value.to_string()),
+                };
+            }
+            return serializer.finish().into_bytes();
+        }
+    }
+    match value {
+        Value::String(value) => value.as_bytes().to_vec(),
+        value => value.to_string().into_bytes(),
+    }
+}
+
+/// Reads an HTTP/1.1 request from `reader`. Returns the status code of the error response and
+/// the reason if it is malformed or its body is larger than [`MAX_BODY`], after which the
+/// connection cannot be read further.
+///
+/// `100 Continue` is written to `stream` when the client expects it before sending the body.
+fn read_http_request(
+    reader: &mut impl BufRead,
+    mut stream: impl Write,
+) -> io::Result<Result<RequestParts, (u16, String)>> {
+    let request_line = match read_line(reader)? {
+        Ok(line) => line,
+        Err(error) => return Ok(Err((400, error))),
+    };
+    let mut words = request_line.split(' ');
+    let (method, target) = match (words.next(), words.next(), words.next()) {
+        (Some(method), Some(target), Some(version)) if version.starts_with("HTTP/") => {
+            (method, target)
+        }
+        _ => {
+            let error = format!("malformed request line `{}`", request_line);
+            return Ok(Err((400, error)));
+        }
+    };
+    // Absolute targets, as sent to proxies.
+    let target = match target.split_once("://") {
+        Some((_, rest)) => rest.find('/').map_or("/", |start| &rest[start..]),
+        None => target,
+    };
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let mut request = RequestParts {
+        method: method.to_string(),
+        path: path.to_string(),
+        query: query.to_string(),
+        ..RequestParts::default()
+    };
+
+    let mut size = request_line.len();
+    loop {
+        let line = match read_line(reader)? {
+            Ok(line) => line,
+            Err(error) => return Ok(Err((400, error))),
+        };
+        if line.is_empty() {
+            break;
+        }
+        size += line.len();
+        if size > MAX_HEAD {
+            let error = "the head of the request is too large".to_string();
+            return Ok(Err((400, error)));
+        }
+        match line.split_once(':') {
+            Some((name, value)) => request
+                .headers
+                .push((name.trim().to_string(), value.trim().to_string())),
+            None => return Ok(Err((400, format!("malformed header `{}`", line)))),
+        }
+    }
+
+    let expects_continue = request
+        .header("expect")
+        .is_some_and(|expect| expect.eq_ignore_ascii_case("100-continue"));
+    let chunked = request
+        .header("transfer-encoding")
+        .is_some_and(|encoding| encoding.to_ascii_lowercase().contains("chunked"));
+    let length = match request.header("content-length").map(str::parse::<usize>) {
+        Some(Ok(length)) => length,
+        Some(Err(_)) => {
+            return Ok(Err((400, "malformed `Content-Length` header".to_string())));
+        }
+        None => 0,
+    };
+    if length > MAX_BODY {
+        return Ok(Err((413, too_large())));
+    }
+    if expects_continue && (chunked || length > 0) {
+        stream.write_all(b"HTTP/1.1 100 Continue\r\n\r\n")?;
+    }
+    if chunked {
+        loop {
+            let line = match read_line(reader)? {
+                Ok(line) => line,
+                Err(error) => return Ok(Err((400, error))),
+            };
+            let size = line.split(';').next().unwrap_or_default().trim();
+            let size = match usize::from_str_radix(size, 16) {
+                Ok(size) => size,
+                Err(_) => return Ok(Err((400, format!("malformed chunk size `{}`", line)))),
+            };
+            if size == 0 {
+                // Trailers are ignored.
+                loop {
+                    match read_line(reader)? {
+                        Ok(line) if line.is_empty() => break,
+                        Ok(_) => {}
+                        Err(error) => return Ok(Err((400, error))),
+                    }
+                }
+                break;
+            }
+            let start = request.body.len();
+            let end = match start.checked_add(size) {
+                Some(end) if end <= MAX_BODY => end,
+                _ => return Ok(Err((413, too_large()))),
+            };
+            request.body.resize(end, 0);
+            reader.read_exact(&mut request.body[start..])?;
+            let _ = read_line(reader)?;
+        }
+    } else {
+        request.body.resize(length, 0);
+        reader.read_exact(&mut request.body)?;
+    }
+    Ok(Ok(request))
+}
+
+/// The reason given for requests whose body is larger than [`MAX_BODY`].
+fn too_large() -> String {
+    format!("the body of the request is larger than {} bytes", MAX_BODY)
+}
+
+/// Reads a line ending with `\r\n` or `\n` from `reader`, without its ending.
+fn read_line(reader: &mut impl BufRead) -> io::Result<Result<String, String>> {
+    let mut line = Vec::new();
+    reader.take(MAX_HEAD as u64).read_until(b'\n', &mut line)?;
+    if line.last() != Some(&b'\n') {
+        return Ok(Err("the request is truncated".to_string()));
+    }
+    line.pop();
+    if line.last() == Some(&b'\r') {
+        line.pop();
+    }
+    Ok(String::from_utf8(line).map_err(|_| "the request is not valid UTF-8".to_string()))
+}
+
+/// Writes `response` as an HTTP/1.1 response, announcing that the connection is closed.
+fn write_http_response(stream: &mut impl Write, response: &MockResponse) -> io::Result<()> {
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\n",
+        response.status,
+        reason(response.status)
+    );
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str(&format!(
+        "Content-Length: {}\r\nConnection: close\r\n\r\n",
+        response.body.len()
+    ));
+    stream.write_all(head.as_bytes())?;
+    stream.write_all(&response.body)?;
+    stream.flush()
+}
+
+/// The reason phrase of `status`, empty if it is not a common one.
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        202 => "Accepted",
+        204 => "No Content",
+        301 => "Moved Permanently",
+        302 => "Found",
+        304 => "Not Modified",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        406 => "Not Acceptable",
+        409 => "Conflict",
+        410 => "Gone",
+        412 => "Precondition Failed",
+        413 => "Payload Too Large",
+        415 => "Unsupported Media Type",
+        422 => "Unprocessable Entity",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        501 => "Not Implemented",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Shutdown;
+
+    fn spec() -> Spec {
+        serde_json::from_value(json!({
+            "openapi": "3.0.3",
+            "info": {"title": "Pets", "version": "1.0.0"},
+            "paths": {
+                "/pets/{petId}": {
+                    "parameters": [
+                        {
+                            "name": "petId",
+                            "in": "path",
+                            "required": true,
+                            "schema": {"type": "integer"}
+                        }
+                    ],
+                    "get": {
+                        "responses": {
+                            "200": {
+                                "description": "The pet.",
+                                "headers": {
+                                    "X-Rate-Limit": {"schema": {"type": "integer", "minimum": 10}}
+                                },
+                                "content": {
+                                    "application/json": {
+                                        "examples": {
+                                            "cat": {"value": {"id": 1, "name": "Tom"}},
+                                            "dog": {"$ref": "#/components/examples/Dog"}
+                                        }
+                                    },
+                                    "text/plain": {"example": "Tom"}
+                                }
+                            },
+                            "4XX": {
+                                "description": "An error.",
+                                "content": {
+                                    "application/json": {
+                                        "schema": {"$ref": "#/components/schemas/Error"}
+                                    }
+                                }
+                            }
+                        }
+                    },
+                    "delete": {"responses": {"204": {"description": "Deleted."}}}
+                }
+            },
+            "components": {
+                "schemas": {
+                    "Error": {
+                        "type": "object",
+                        "required": ["code"],
+                        "properties": {"code": {"type": "integer", "minimum": 400}}
+                    }
+                },
+                "examples": {"Dog": {"value": {"id": 2, "name": "Rex"}}}
+            }
+        }))
+        .unwrap()
+    }
+
+    fn request(method: &str, path: &str, headers: &[(&str, &str)]) -> RequestParts {
+        RequestParts {
+            method: method.to_string(),
+            path: path.to_string(),
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            ..RequestParts::default()
+        }
+    }
+
+    fn json_body(response: &MockResponse) -> Value {
+        serde_json::from_slice(&response.body).unwrap()
+    }
+
+    #[test]
+    fn test_mock_response() {
+        let spec = spec();
+
+        let response = spec.mock_response(&request("GET", "/pets/1", &[]));
+        assert_eq!(response.status, 200);
+        assert_eq!(response.header("content-type"), Some("application/json"));
+        assert_eq!(response.header("x-rate-limit"), Some("10"));
+        assert_eq!(response.header("access-control-allow-origin"), Some("*"));
+        assert_eq!(json_body(&response), json!({"id": 1, "name": "Tom"}));
+
+        let prefer = [("Prefer", "example=dog")];
+        let response = spec.mock_response(&request("GET", "/pets/1", &prefer));
+        assert_eq!(json_body(&response), json!({"id": 2, "name": "Rex"}));
+
+        let accept = [("Accept", "text/*;q=0.9, application/xml")];
+        let response = spec.mock_response(&request("GET", "/pets/1", &accept));
+        assert_eq!(response.header("content-type"), Some("text/plain"));
+        assert_eq!(response.body, b"Tom");
+
+        let accept = [("Accept", "application/xml")];
+        let response = spec.mock_response(&request("GET", "/pets/1", &accept));
+        assert_eq!(response.status, 406);
+
+        let prefer = [("Prefer", "code=404")];
+        let response = spec.mock_response(&request("GET", "/pets/x", &prefer));
+        assert_eq!(response.status, 404);
+        assert_eq!(json_body(&response), json!({"code": 400}));
+
+        let prefer = [("Prefer", "code=500")];
+        let response = spec.mock_response(&request("GET", "/pets/1", &prefer));
+        assert_eq!(response.status, 500);
+        assert_eq!(
+            json_body(&response),
+            json!({"errors": ["the operation has no `500` response"]})
+        );
+
+        let response = spec.mock_response(&request("GET", "/pets/x", &[]));
+        assert_eq!(response.status, 400);
+        assert_eq!(
+            json_body(&response),
+            json!({"errors": ["path parameter `petId`: expected \"integer\", found string"]})
+        );
+
+        let response = spec.mock_response(&request("DELETE", "/pets/1", &[]));
+        assert_eq!(response.status, 204);
+        assert_eq!(response.body, b"");
+
+        let response = spec.mock_response(&request("GET", "/owners", &[]));
+        assert_eq!(response.status, 404);
+
+        let response = spec.mock_response(&request("POST", "/pets/1", &[]));
+        assert_eq!(response.status, 405);
+        assert_eq!(response.header("allow"), Some("GET, DELETE"));
+
+        let preflight = [("Access-Control-Request-Headers", "prefer")];
+        let response = spec.mock_response(&request("OPTIONS", "/pets/1", &preflight));
+        assert_eq!(response.status, 204);
+        assert_eq!(
+            response.header("access-control-allow-methods"),
+            Some("GET, DELETE")
+        );
+        assert_eq!(
+            response.header("access-control-allow-headers"),
+            Some("prefer")
+        );
+    }
+
+    #[test]
+    fn test_serve_mock() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || spec().serve_mock(&listener));
+
+        let exchange = |request: &str| {
+            let mut stream = TcpStream::connect(address).unwrap();
+            stream.write_all(request.as_bytes()).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+        let response = exchange("GET /pets/1?verbose HTTP/1.1\r\nHost: localhost\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{}", response);
+        assert!(response.contains("\r\nContent-Type: application/json\r\n"));
+        assert!(response.contains("\r\nConnection: close\r\n"));
+        assert!(response.ends_with("\r\n\r\n{\n  \"id\": 1,\n  \"name\": \"Tom\"\n}"));
+
+        let response = exchange(
+            "DELETE /pets/1 HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n3\r\nabc\r\n0\r\n\r\n",
+        );
+        assert!(
+            response.starts_with("HTTP/1.1 204 No Content\r\n"),
+            "{}",
+            response
+        );
+        assert!(response.contains("\r\nContent-Length: 0\r\n"));
+
+        let response = exchange("HEAD /pets/1 HTTP/1.1\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{}", response);
+        assert!(response.ends_with("Content-Length: 0\r\nConnection: close\r\n\r\n"));
+
+        let response = exchange("nonsense\r\n\r\n");
+        assert!(
+            response.starts_with("HTTP/1.1 400 Bad Request\r\n"),
+            "{}",
+            response
+        );
+
+        let response = exchange("POST /pets HTTP/1.1\r\nContent-Length: 99999999999999\r\n\r\n");
+        assert!(
+            response.starts_with("HTTP/1.1 413 Payload Too Large\r\n"),
+            "{}",
+            response
+        );
+
+        let response = exchange(&format!(
+            "POST /pets HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n{:x}\r\n",
+            usize::MAX
+        ));
+        assert!(
+            response.starts_with("HTTP/1.1 413 Payload Too Large\r\n"),
+            "{}",
+            response
+        );
+
+        // The last chunk without the empty line which ends the trailers.
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream
+            .write_all(b"DELETE /pets/1 HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n0\r\n")
+            .unwrap();
+        stream.shutdown(Shutdown::Write).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(
+            response.starts_with("HTTP/1.1 400 Bad Request\r\n"),
+            "{}",
+            response
+        );
+        assert!(
+            response.contains("the request is truncated"),
+            "{}",
+            response
+        );
+    }
+
+    #[test]
+    fn test_connections() {
+        let connections = Connections::default();
+        let mut served: Vec<_> = (0..MAX_CONNECTIONS)
+            .map(|_| connections.acquire())
+            .collect();
+        thread::scope(|scope| {
+            let waiting = scope.spawn(|| drop(connections.acquire()));
+            thread::sleep(Duration::from_millis(50));
+            assert!(!waiting.is_finished());
+            served.pop();
+            waiting.join().unwrap();
+        });
+        assert_eq!(*lock(&connections.count), MAX_CONNECTIONS - 1);
+    }
+}
@@ -4,7 +4,50 @@
 //! [specification](https://github.com/OAI/OpenAPI-Specification/blob/0dd79f6/versions/3.0.1.md)
 //! for more information.
 
+mod build;
+mod bundle;
+mod changelog;
+#[cfg(feature = "codegen")]
+mod client;
+#[cfg(feature = "validate")]
+mod compiled;
 mod components;
+mod deref;
+mod diagnose;
+mod diff;
+#[cfg(feature = "codegen")]
+mod endpoint;
+#[cfg(feature = "validate")]
+mod example;
+#[cfg(feature = "validate")]
+mod generate;
+#[cfg(feature = "validate")]
+mod instance;
+mod merge;
+#[cfg(feature = "mock")]
+mod mock;
+#[cfg(feature = "codegen")]
+mod models;
+#[cfg(any(feature = "codegen", feature = "validate"))]
+mod operation;
+mod pointer;
+#[cfg(feature = "validate")]
+mod request;
+mod resolve;
 mod schema;
+#[cfg(feature = "codegen")]
+mod server;
+mod style;
+mod to_schema;
+mod validate;
+pub mod visit;
 
-pub use crate::v3_0::{components::*, schema::*};
+pub use crate::v3_0::visit::{Visit, VisitMut};
+pub use crate::v3_0::{
+    build::*, bundle::*, changelog::*, components::*, deref::*, diff::*, merge::*, pointer::*,
+    resolve::*, schema::*, style::*, to_schema::*, validate::*,
+};
+#[cfg(feature = "validate")]
+pub use crate::v3_0::{compiled::*, generate::*, instance::*, request::*};
+#[cfg(feature = "mock")]
+pub use crate::v3_0::mock::*;
@@ -0,0 +1,706 @@
+//! Rust types for the component schemas of a specification.
+
+use crate::v3_0::{resolve, ComponentOrInlineSchema, ObjectOrReference, Schema, Spec, Type};
+use indexmap::IndexMap;
+use std::collections::{BTreeMap, HashSet};
+
+impl Spec {
+    /// Generates Rust source defining a type for each component schema, deriving serde's
+    /// `Serialize` and `Deserialize`.
+    ///
+    /// Objects become structs, string enumerations become enums, `oneOf` and `anyOf` become
+    /// untagged enums and the members of `allOf` become flattened fields. Fields are `Option`s
+    /// unless they are required and not `nullable`, and types referring to themselves are boxed.
+    /// Inline schemas which need a type of their own are named after where they appear.
+    ///
+    /// Types are sorted by name, so that the output only changes with the specification.
+    ///
+    /// Requires the `codegen` feature.
+    pub fn generate_models(&self) -> String {
+        let mut models = Models::new(self, &[]);
+        models.components();
+        format!(
+            "// Models of `{}` {}, generated from the component schemas of its specification.\n\
+             \n\
+             use serde::{{Deserialize, Serialize}};\n\
+             {}",
+            self.info.title,
+            self.info.version,
+            models.definitions()
+        )
+    }
+}
+
+/// Names which generated types must not shadow.
+const RESERVED_TYPES: [&str; 8] = [
+    "Box",
+    "Deserialize",
+    "Option",
+    "Result",
+    "Self",
+    "Serialize",
+    "String",
+    "Vec",
+];
+
+const KEYWORDS: [&str; 51] = [
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while",
+];
+
+/// Generator of Rust types for schemas.
+pub(crate) struct Models<'a> {
+    spec: &'a Spec,
+    /// Type names of the component schemas.
+    components: IndexMap<&'a str, String>,
+    /// Names of the types defined so far, and of those reserved.
+    taken: HashSet<String>,
+    /// Definitions of the types, by name.
+    definitions: BTreeMap<String, String>,
+    /// The component schema whose type is being generated.
+    current: Option<&'a str>,
+}
+
+impl<'a> Models<'a> {
+    /// Creates a generator whose types will not be named `reserved`.
+    pub(crate) fn new(spec: &'a Spec, reserved: &[&str]) -> Self {
+        let mut models = Models {
+            spec,
+            components: IndexMap::new(),
+            taken: RESERVED_TYPES
+                .iter()
+                .chain(reserved)
+                .map(|name| name.to_string())
+                .collect(),
+            definitions: BTreeMap::new(),
+            current: None,
+        };
+        let mut names: Vec<&str> = match &spec.components {
+            Some(components) => components.schemas.keys().map(|name| &**name).collect(),
+            None => Vec::new(),
+        };
+        names.sort_unstable();
+        for name in names {
+            let type_name = models.unique(&type_name(name));
+            models.components.insert(name, type_name);
+        }
+        models
+    }
+
+    /// The specification whose schemas are generated.
+    pub(crate) fn spec(&self) -> &'a Spec {
+        self.spec
+    }
+
+    /// Defines the types of all the component schemas.
+    pub(crate) fn components(&mut self) {
+        let schemas = match &self.spec.components {
+            Some(components) => &components.schemas,
+            None => return,
+        };
+        for (name, type_name) in self.components.clone() {
+            self.current = Some(name);
+            match &schemas[name] {
+                ObjectOrReference::Object(schema) if needs_definition(schema) => {
+                    self.define(schema, &type_name)
+                }
+                ObjectOrReference::Object(schema) => {
+                    let definition = format!(
+                        "{}pub type {} = {};\n",
+                        docs(&schema.description, ""),
+                        type_name,
+                        self.inline_type(schema, &type_name)
+                    );
+                    self.definitions.insert(type_name, definition);
+                }
+                ObjectOrReference::Ref { ref_path } => {
+                    let target = resolve::component_name::<Schema>(ref_path)
+                        .ok()
+                        .and_then(|target| self.components.get(&*target))
+                        .map_or("serde_json::Value", |target| &**target);
+                    let definition = format!("pub type {} = {};\n", type_name, target);
+                    self.definitions.insert(type_name, definition);
+                }
+            }
+        }
+        self.current = None;
+    }
+
+    /// The definitions of the types generated so far, each preceded by an empty line.
+    pub(crate) fn definitions(&self) -> String {
+        self.definitions
+            .values()
+            .map(|definition| format!("\n{}", definition))
+            .collect()
+    }
+
+    /// Returns the Rust type of `schema`, defining a type named after `hint` if it needs one.
+    pub(crate) fn type_of(&mut self, schema: &'a ComponentOrInlineSchema, hint: &str) -> String {
+        self.rust_type(schema, hint, true)
+    }
+
+    /// Adds the definition of a type named by [`Models::unique`].
+    pub(crate) fn insert(&mut self, name: String, definition: String) {
+        self.definitions.insert(name, definition);
+    }
+
+    /// Returns whether `schema`, or the component it refers to, is `nullable`.
+    pub(crate) fn is_nullable(&self, schema: &ComponentOrInlineSchema) -> bool {
+        self.spec
+            .resolve_schema(schema)
+            .is_ok_and(|schema| schema.nullable == Some(true))
+    }
+
+    /// `direct` is whether a value of the type is stored in place, as opposed to in a `Vec` or
+    /// a map, in which case references to the current component must be boxed.
+    fn rust_type(
+        &mut self,
+        schema: &'a ComponentOrInlineSchema,
+        hint: &str,
+        direct: bool,
+    ) -> String {
+        let rust_type = match schema {
+            ComponentOrInlineSchema::Component { name } => {
+                match self.components.get(&**name).cloned() {
+                    Some(type_name) if direct && self.is_recursive(name) => {
+                        format!("Box<{}>", type_name)
+                    }
+                    Some(type_name) => type_name,
+                    None => "serde_json::Value".to_string(),
+                }
+            }
+            ComponentOrInlineSchema::ExtRef { .. } => "serde_json::Value".to_string(),
+            ComponentOrInlineSchema::Inline(schema) => self.inline_type(schema, hint),
+        };
+        if self.is_nullable(schema) {
+            format!("Option<{}>", rust_type)
+        } else {
+            rust_type
+        }
+    }
+
+    /// Returns the Rust type of an inline schema, ignoring `nullable`.
+    fn inline_type(&mut self, schema: &'a Schema, hint: &str) -> String {
+        if needs_definition(schema) {
+            let name = self.unique(hint);
+            self.define(schema, &name);
+            return name;
+        }
+        match schema.schema_type {
+            Some(Type::String) => "String".to_string(),
+            Some(Type::Integer) if schema.format == "int32" => "i32".to_string(),
+            Some(Type::Integer) => "i64".to_string(),
+            Some(Type::Number) if schema.format == "float" => "f32".to_string(),
+            Some(Type::Number) => "f64".to_string(),
+            Some(Type::Boolean) => "bool".to_string(),
+            Some(Type::Array) => match &schema.items {
+                Some(items) => {
+                    format!(
+                        "Vec<{}>",
+                        self.rust_type(items, &format!("{}Item", hint), false)
+                    )
+                }
+                None => "Vec<serde_json::Value>".to_string(),
+            },
+            _ => match &schema.additional_properties {
+                Some(values) => format!(
+                    "std::collections::BTreeMap<String, {}>",
+                    self.rust_type(values, &format!("{}Value", hint), false)
+                ),
+                None if schema.schema_type == Some(Type::Object) => {
+                    "serde_json::Map<String, serde_json::Value>".to_string()
+                }
+                None => "serde_json::Value".to_string(),
+            },
+        }
+    }
+
+    /// Defines the type `name` for a schema which [needs one](needs_definition).
+    fn define(&mut self, schema: &'a Schema, name: &str) {
+        let definition = if !schema.enum_values.is_empty() {
+            self.string_enum(schema, name)
+        } else if !schema.one_of.is_empty() || !schema.any_of.is_empty() {
+            self.untagged_enum(schema, name)
+        } else {
+            self.structure(schema, name)
+        };
+        self.definitions.insert(name.to_string(), definition);
+    }
+
+    fn string_enum(&mut self, schema: &Schema, name: &str) -> String {
+        let mut definition = docs(&schema.description, "");
+        definition.push_str(
+            "#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]\n",
+        );
+        definition.push_str(&format!("pub enum {} {{\n", name));
+        let mut variants = HashSet::new();
+        for value in &schema.enum_values {
+            let variant = unique(&mut variants, &variant_name(value));
+            if variant != *value {
+                definition.push_str(&format!("    #[serde(rename = {:?})]\n", value));
+            }
+            definition.push_str(&format!("    {},\n", variant));
+        }
+        definition.push_str("}\n");
+        definition
+    }
+
+    fn untagged_enum(&mut self, schema: &'a Schema, name: &str) -> String {
+        let mut definition = docs(&schema.description, "");
+        definition.push_str("#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]\n");
+        definition.push_str("#[serde(untagged)]\n");
+        definition.push_str(&format!("pub enum {} {{\n", name));
+        let mut variants = HashSet::new();
+        for member in schema.one_of.iter().chain(&schema.any_of) {
+            let variant = match member {
+                ComponentOrInlineSchema::Component { name } => match self.components.get(&**name) {
+                    Some(type_name) => type_name.clone(),
+                    None => "Value".to_string(),
+                },
+                ComponentOrInlineSchema::ExtRef { .. } => "Value".to_string(),
+                ComponentOrInlineSchema::Inline(member) if !member.title.is_empty() => {
+                    variant_name(&member.title)
+                }
+                ComponentOrInlineSchema::Inline(member) => match member.schema_type {
+                    Some(Type::String) => "String",
+                    Some(Type::Integer) => "Integer",
+                    Some(Type::Number) => "Number",
+                    Some(Type::Boolean) => "Boolean",
+                    Some(Type::Array) => "Array",
+                    Some(Type::Object) => "Object",
+                    Some(Type::File) | None => "Value",
+                }
+                .to_string(),
+            };
+            let variant = unique(&mut variants, &variant);
+            let rust_type = self.rust_type(member, &format!("{}{}", name, variant), true);
+            definition.push_str(&format!("    {}({}),\n", variant, rust_type));
+        }
+        definition.push_str("}\n");
+        definition
+    }
+
+    fn structure(&mut self, schema: &'a Schema, name: &str) -> String {
+        let mut fields = String::new();
+        let mut names = HashSet::new();
+        for (i, member) in schema.all_of.iter().enumerate() {
+            match member {
+                ComponentOrInlineSchema::Inline(member)
+                    if member.enum_values.is_empty()
+                        && member.one_of.is_empty()
+                        && member.any_of.is_empty()
+                        && member.all_of.is_empty() =>
+                {
+                    self.properties(member, name, &mut names, &mut fields)
+                }
+                member => {
+                    let field = match member {
+                        ComponentOrInlineSchema::Component { name } => field_name(name),
+                        _ => format!("all_of_{}", i),
+                    };
+                    let field = unique(&mut names, &field);
+                    let hint = format!("{}{}", name, type_name(&field));
+                    let rust_type = self.rust_type(member, &hint, true);
+                    fields.push_str(&format!(
+                        "    #[serde(flatten)]\n    pub {}: {},\n",
+                        field, rust_type
+                    ));
+                }
+            }
+        }
+        self.properties(schema, name, &mut names, &mut fields);
+        if let Some(values) = &schema.additional_properties {
+            let field = unique(&mut names, "additional_properties");
+            let rust_type = self.rust_type(values, &format!("{}Value", name), false);
+            fields.push_str(&format!(
+                "    #[serde(flatten)]\n    pub {}: std::collections::BTreeMap<String, {}>,\n",
+                field, rust_type
+            ));
+        }
+
+        let mut definition = docs(&schema.description, "");
+        definition.push_str("#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]\n");
+        definition.push_str(&format!("pub struct {} {{\n{}}}\n", name, fields));
+        definition
+    }
+
+    /// Adds a field to the struct `name` for each property of `schema`.
+    fn properties(
+        &mut self,
+        schema: &'a Schema,
+        name: &str,
+        names: &mut HashSet<String>,
+        fields: &mut String,
+    ) {
+        for (property, property_schema) in &schema.properties {
+            let field = unique(names, &field_name(property));
+            let hint = format!("{}{}", name, type_name(property));
+            let mut rust_type = self.rust_type(property_schema, &hint, true);
+            let required = schema.required.iter().any(|required| required == property);
+            let mut attributes = Vec::new();
+            if field.trim_start_matches("r#") != &**property {
+                attributes.push(format!("rename = {:?}", property));
+            }
+            if !required {
+                attributes.push("default".to_string());
+                if !self.is_nullable(property_schema) {
+                    rust_type = format!("Option<{}>", rust_type);
+                }
+                attributes.push("skip_serializing_if = \"Option::is_none\"".to_string());
+            }
+
+            if let ComponentOrInlineSchema::Inline(property_schema) = property_schema {
+                fields.push_str(&docs(&property_schema.description, "    "));
+            }
+            if !attributes.is_empty() {
+                fields.push_str(&format!("    #[serde({})]\n", attributes.join(", ")));
+            }
+            fields.push_str(&format!("    pub {}: {},\n", field, rust_type));
+        }
+    }
+
+    /// Returns whether the component schema `name` contains the current one, other than through
+    /// an array or a map.
+    fn is_recursive(&self, name: &str) -> bool {
+        let current = match self.current {
+            Some(current) => current,
+            None => return false,
+        };
+        let mut stack = vec![name];
+        let mut visited = HashSet::new();
+        while let Some(name) = stack.pop() {
+            if name == current {
+                return true;
+            }
+            if !visited.insert(name) {
+                continue;
+            }
+            if let Some(ObjectOrReference::Object(schema)) = self
+                .spec
+                .components
+                .as_ref()
+                .and_then(|components| components.schemas.get(name))
+            {
+                contained(schema, &mut stack);
+            }
+        }
+        false
+    }
+
+    /// Returns `name`, or `name` followed by a number if it is taken, and takes it.
+    pub(crate) fn unique(&mut self, name: &str) -> String {
+        unique(&mut self.taken, name)
+    }
+}
+
+/// Adds the names of the component schemas that a value of `schema` contains in place.
+fn contained<'a>(schema: &'a Schema, names: &mut Vec<&'a str>) {
+    let members = schema
+        .properties
+        .values()
+        .chain(&schema.all_of)
+        .chain(&schema.one_of)
+        .chain(&schema.any_of);
+    for member in members {
+        match member {
+            ComponentOrInlineSchema::Component { name } => names.push(name),
+            ComponentOrInlineSchema::Inline(member) => contained(member, names),
+            ComponentOrInlineSchema::ExtRef { .. } => {}
+        }
+    }
+}
+
+/// Whether the type of `schema` is a struct or an enum rather than a primitive or a collection.
+fn needs_definition(schema: &Schema) -> bool {
+    !schema.enum_values.is_empty()
+        || !schema.one_of.is_empty()
+        || !schema.any_of.is_empty()
+        || !schema.all_of.is_empty()
+        || !schema.properties.is_empty()
+}
+
+pub(crate) fn unique(taken: &mut HashSet<String>, name: &str) -> String {
+    let mut unique = name.to_string();
+    let mut n = 2;
+    while taken.contains(&unique) {
+        unique = format!("{}{}", name, n);
+        n += 1;
+    }
+    taken.insert(unique.clone());
+    unique
+}
+
+/// Documentation comment of an item, indented with `indent`.
+pub(crate) fn docs(description: &str, indent: &str) -> String {
+    description
+        .trim()
+        .lines()
+        .map(|line| match line.trim_end() {
+            "" => format!("{}///\n", indent),
+            line => format!("{}/// {}\n", indent, line),
+        })
+        .collect()
+}
+
+/// Splits `name` into its words, at separators and at changes of case.
+fn words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let chars: Vec<char> = name.chars().collect();
+    for (i, &ch) in chars.iter().enumerate() {
+        if !ch.is_ascii_alphanumeric() {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            continue;
+        }
+        let previous = if i > 0 { chars[i - 1] } else { ' ' };
+        let next = chars.get(i + 1).copied().unwrap_or(' ');
+        let boundary = ch.is_ascii_uppercase()
+            && (previous.is_ascii_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_ascii_uppercase() && next.is_ascii_lowercase()));
+        if boundary && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        word.push(ch);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+fn pascal_case(name: &str) -> String {
+    words(name)
+        .iter()
+        .map(|word| word[..1].to_ascii_uppercase() + &word[1..].to_ascii_lowercase())
+        .collect()
+}
+
+/// Rust name of the type of a schema named `name`.
+pub(crate) fn type_name(name: &str) -> String {
+    match pascal_case(name) {
+        name if name.is_empty() => "Model".to_string(),
+        name if name.starts_with(|ch: char| ch.is_ascii_digit()) => format!("Model{}", name),
+        name => name,
+    }
+}
+
+fn variant_name(value: &str) -> String {
+    match pascal_case(value) {
+        name if name.is_empty() => "Empty".to_string(),
+        name if name.starts_with(|ch: char| ch.is_ascii_digit()) => format!("Value{}", name),
+        name => name,
+    }
+}
+
+/// Rust name of a field or a function for `name`.
+pub(crate) fn field_name(name: &str) -> String {
+    let snake = words(name)
+        .iter()
+        .map(|word| word.to_ascii_lowercase())
+        .collect::<Vec<_>>()
+        .join("_");
+    if snake.is_empty() {
+        "field".to_string()
+    } else if snake.starts_with(|ch: char| ch.is_ascii_digit()) {
+        format!("_{}", snake)
+    } else if ["crate", "self", "super"].contains(&&*snake) {
+        format!("{}_", snake)
+    } else if KEYWORDS.contains(&&*snake) {
+        format!("r#{}", snake)
+    } else {
+        snake
+    }
+}
+
+/// The specification of the `generated` crate, which builds the code generated from it.
+#[cfg(test)]
+pub(crate) const GENERATED_SPEC: &str = include_str!("../../generated/pets.json");
+
+/// Checks that `source` is the file of the `generated` crate at `path`, or writes it there if
+/// the `UPDATE_GENERATED` environment variable is set.
+#[cfg(test)]
+pub(crate) fn check_generated(path: &str, source: &str) {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("generated")
+        .join(path);
+    if std::env::var_os("UPDATE_GENERATED").is_some() {
+        std::fs::write(&path, source).unwrap();
+    }
+    let generated = std::fs::read_to_string(&path).unwrap_or_default();
+    assert!(
+        generated == source,
+        "`{}` is out of date, run the tests with `UPDATE_GENERATED=1`",
+        path.display()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const SPEC: &str = r##"{
+      "openapi": "3.0.3",
+      "info": {"title": "Pets", "version": "1.0.0"},
+      "paths": {},
+      "components": {
+        "schemas": {
+          "Pet": {
+            "description": "A pet.",
+            "type": "object",
+            "required": ["petName", "kind", "weight"],
+            "properties": {
+              "petName": {"type": "string", "description": "Name of the pet."},
+              "kind": {"$ref": "#/components/schemas/Kind"},
+              "weight": {"type": "number", "format": "float", "nullable": true},
+              "age": {"type": "integer", "format": "int32"},
+              "type": {"type": "string", "enum": ["house", "farm"]},
+              "parent": {"$ref": "#/components/schemas/Pet"},
+              "children": {"type": "array", "items": {"$ref": "#/components/schemas/Pet"}},
+              "labels": {"type": "object", "additionalProperties": {"type": "string"}}
+            }
+          },
+          "Kind": {"type": "string", "enum": ["cat", "dog", "guinea-pig"]},
+          "Id": {
+            "oneOf": [
+              {"type": "integer", "format": "int64"},
+              {"type": "string"},
+              {"type": "object", "properties": {"uuid": {"type": "string"}}}
+            ]
+          },
+          "new-pet": {
+            "allOf": [
+              {"$ref": "#/components/schemas/Pet"},
+              {
+                "type": "object",
+                "required": ["id"],
+                "properties": {"id": {"$ref": "#/components/schemas/Id"}}
+              }
+            ]
+          },
+          "PetAlias": {"$ref": "#/components/schemas/Pet"},
+          "Tags": {"type": "array", "items": {"type": "string"}}
+        }
+      }
+    }"##;
+
+    #[test]
+    fn test_generate_models() {
+        let spec: Spec = serde_json::from_str(SPEC).unwrap();
+        assert_eq!(spec.generate_models(), &MODELS[1..]);
+    }
+
+    #[test]
+    fn test_generated_models() {
+        let spec: Spec = serde_json::from_str(GENERATED_SPEC).unwrap();
+        check_generated("src/models.rs", &spec.generate_models());
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!(type_name("new-pet"), "NewPet");
+        assert_eq!(type_name("HTTPServer_v2"), "HttpServerV2");
+        assert_eq!(type_name("2fa"), "Model2fa");
+        assert_eq!(field_name("petName"), "pet_name");
+        assert_eq!(field_name("X-Rate-Limit"), "x_rate_limit");
+        assert_eq!(field_name("type"), "r#type");
+        assert_eq!(field_name("self"), "self_");
+        assert_eq!(field_name("$"), "field");
+
+        // Types containing each other are boxed in both, and names of std types are not shadowed.
+        let spec: Spec = serde_json::from_str(
+            r##"{
+              "openapi": "3.0.3",
+              "info": {"title": "Tree", "version": "1.0.0"},
+              "paths": {},
+              "components": {
+                "schemas": {
+                  "Node": {"properties": {"leaf": {"$ref": "#/components/schemas/Leaf"}}},
+                  "Leaf": {"properties": {"node": {"$ref": "#/components/schemas/Node"}}},
+                  "String": {"type": "string"}
+                }
+              }
+            }"##,
+        )
+        .unwrap();
+        let models = spec.generate_models();
+        assert!(models.contains("pub node: Option<Box<Node>>"), "{}", models);
+        assert!(models.contains("pub leaf: Option<Box<Leaf>>"), "{}", models);
+        assert!(models.contains("pub type String2 = String;"), "{}", models);
+    }
+
+    const MODELS: &str = r##"
+// Models of `Pets` 1.0.0, generated from the component schemas of its specification.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Id {
+    Integer(i64),
+    String(String),
+    Object(IdObject),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IdObject {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uuid: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Kind {
+    #[serde(rename = "cat")]
+    Cat,
+    #[serde(rename = "dog")]
+    Dog,
+    #[serde(rename = "guinea-pig")]
+    GuineaPig,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NewPet {
+    #[serde(flatten)]
+    pub pet: Pet,
+    pub id: Id,
+}
+
+/// A pet.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Pet {
+    /// Name of the pet.
+    #[serde(rename = "petName")]
+    pub pet_name: String,
+    pub kind: Kind,
+    pub weight: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub age: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<PetType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<Box<Pet>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub children: Option<Vec<Pet>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub labels: Option<std::collections::BTreeMap<String, String>>,
+}
+
+pub type PetAlias = Pet;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PetType {
+    #[serde(rename = "house")]
+    House,
+    #[serde(rename = "farm")]
+    Farm,
+}
+
+pub type Tags = Vec<String>;
+"##;
+}
@@ -0,0 +1,61 @@
+//! Bodies and parameters of operations, shared by the request reader, the mock server and the
+//! code generators.
+
+use crate::v3_0::{Operation, Parameter, PathItem, Spec};
+#[cfg(any(feature = "codegen", feature = "mock"))]
+use crate::{v3_0::MediaType, Str};
+#[cfg(any(feature = "codegen", feature = "mock"))]
+use indexmap::IndexMap;
+
+/// How a request or response body is serialized.
+#[cfg(any(feature = "codegen", feature = "mock"))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Body {
+    Json,
+    Form,
+    Text(String),
+    Bytes(String),
+}
+
+/// Returns the media type to use for a body, and how it is serialized.
+#[cfg(any(feature = "codegen", feature = "mock"))]
+pub(crate) fn body(content: &IndexMap<Str, MediaType>) -> Option<(&str, &MediaType, Body)> {
+    let json = content.iter().find(|(media_type, _)| is_json(media_type));
+    if let Some((media_type, media)) = json {
+        return Some((media_type, media, Body::Json));
+    }
+    let form = "application/x-www-form-urlencoded";
+    if let Some((media_type, media)) = content.get_key_value(form) {
+        return Some((media_type, media, Body::Form));
+    }
+    let (media_type, media) = content.iter().next()?;
+    if media_type.starts_with("text/") {
+        return Some((media_type, media, Body::Text(media_type.to_string())));
+    }
+    Some((media_type, media, Body::Bytes(media_type.to_string())))
+}
+
+/// Whether `media_type` is JSON, such as `application/json` or `application/problem+json`.
+pub(crate) fn is_json(media_type: &str) -> bool {
+    let essence = media_type.split(';').next().unwrap_or_default().trim();
+    essence == "application/json" || essence.ends_with("+json")
+}
+
+/// The parameters of the path item and of the operation, which override them.
+pub(crate) fn resolved_parameters<'a>(
+    spec: &'a Spec,
+    item: &'a PathItem,
+    operation: &'a Operation,
+) -> Vec<&'a Parameter> {
+    let mut parameters: Vec<&Parameter> = Vec::new();
+    for parameter in item.parameters.iter().chain(&operation.parameters) {
+        let parameter = match spec.resolve(parameter) {
+            Ok(parameter) => parameter,
+            Err(_) => continue,
+        };
+        parameters
+            .retain(|other| (&other.name, other.location) != (&parameter.name, parameter.location));
+        parameters.push(parameter);
+    }
+    parameters
+}
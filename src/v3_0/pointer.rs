@@ -0,0 +1,642 @@
+//! Navigation of a specification by [JSON pointer](https://tools.ietf.org/html/rfc6901).
+
+use crate::{
+    json_pointer,
+    v3_0::{
+        diff::METHODS, Callback, ComponentOrInlineSchema, Components, Contact, Encoding, Example,
+        ExternalDoc, Header, Info, License, Link, MediaType, MediaTypeExample, ObjectOrReference,
+        Operation, Parameter, ParameterExamples, ParameterRepresentation, PathItem, RequestBody,
+        Response, Schema, SecurityScheme, Server, ServerVariable, Spec, Tag,
+    },
+    Str,
+};
+use std::borrow::Cow;
+
+/// An object of a specification, or one of its string fields, found by [`Spec::pointer`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Node<'a> {
+    Spec(&'a Spec),
+    Info(&'a Info),
+    Contact(&'a Contact),
+    License(&'a License),
+    Server(&'a Server),
+    ServerVariable(&'a ServerVariable),
+    Tag(&'a Tag),
+    ExternalDoc(&'a ExternalDoc),
+    Components(&'a Components),
+    PathItem(&'a PathItem),
+    Operation(&'a Operation),
+    Parameter(&'a Parameter),
+    RequestBody(&'a RequestBody),
+    Response(&'a Response),
+    Header(&'a Header),
+    MediaType(&'a MediaType),
+    Encoding(&'a Encoding),
+    Callback(&'a Callback),
+    Schema(&'a Schema),
+    Example(&'a Example),
+    Link(&'a Link),
+    SecurityScheme(&'a SecurityScheme),
+    /// The `$ref` of a reference object, or of a [`ComponentOrInlineSchema::ExtRef`].
+    Reference(&'a Str),
+    /// The name of the schema of a [`ComponentOrInlineSchema::Component`]. Its `$ref` child is
+    /// the name as well, which is all that is kept of the reference.
+    ComponentSchema(&'a Str),
+    /// A string field, such as `operationId` or the `$ref` of a [`PathItem`].
+    Str(&'a Str),
+}
+
+/// An object of a specification, or one of its string fields, found by [`Spec::pointer_mut`].
+#[derive(Debug)]
+pub enum NodeMut<'a> {
+    Spec(&'a mut Spec),
+    Info(&'a mut Info),
+    Contact(&'a mut Contact),
+    License(&'a mut License),
+    Server(&'a mut Server),
+    ServerVariable(&'a mut ServerVariable),
+    Tag(&'a mut Tag),
+    ExternalDoc(&'a mut ExternalDoc),
+    Components(&'a mut Components),
+    PathItem(&'a mut PathItem),
+    Operation(&'a mut Operation),
+    Parameter(&'a mut Parameter),
+    RequestBody(&'a mut RequestBody),
+    Response(&'a mut Response),
+    Header(&'a mut Header),
+    MediaType(&'a mut MediaType),
+    Encoding(&'a mut Encoding),
+    Callback(&'a mut Callback),
+    Schema(&'a mut Schema),
+    Example(&'a mut Example),
+    Link(&'a mut Link),
+    SecurityScheme(&'a mut SecurityScheme),
+    /// The `$ref` of a reference object, or of a [`ComponentOrInlineSchema::ExtRef`].
+    Reference(&'a mut Str),
+    /// The name of the schema of a [`ComponentOrInlineSchema::Component`]. Its `$ref` child is
+    /// the name as well, which is all that is kept of the reference.
+    ComponentSchema(&'a mut Str),
+    /// A string field, such as `operationId` or the `$ref` of a [`PathItem`].
+    Str(&'a mut Str),
+}
+
+impl Spec {
+    /// Returns the node at `pointer`, such as `/components/schemas/Pet/properties/name`.
+    ///
+    /// Pointers are those of the serialized document. Objects are nodes, and so are string
+    /// fields such as names, descriptions and `$ref`s, but maps and lists are not:
+    /// `/paths` or `/components/schemas` are not nodes, for example.
+    pub fn pointer(&self, pointer: &str) -> Option<Node<'_>> {
+        let tokens = json_pointer::split(pointer)?;
+        let mut node = Node::Spec(self);
+        let mut rest = tokens.as_slice();
+        while !rest.is_empty() {
+            let (child, used) = find(children(node), rest)?;
+            node = child;
+            rest = &rest[used..];
+        }
+        Some(node)
+    }
+
+    /// Returns the node at `pointer`, as [`Spec::pointer`] does, so that it can be modified.
+    ///
+    /// String fields are nodes even when empty, so that they can be set.
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<NodeMut<'_>> {
+        let tokens = json_pointer::split(pointer)?;
+        let mut node = NodeMut::Spec(self);
+        let mut rest = tokens.as_slice();
+        while !rest.is_empty() {
+            let (child, used) = find(children_mut(node), rest)?;
+            node = child;
+            rest = &rest[used..];
+        }
+        Some(node)
+    }
+
+    /// Returns the pointers of all the nodes of this specification, parents first and in
+    /// document order.
+    ///
+    /// Empty string fields, which are not serialized, are left out.
+    pub fn pointers(&self) -> Vec<String> {
+        fn walk(pointer: String, node: Node, pointers: &mut Vec<String>) {
+            if let Node::Str(value) = node {
+                if value.is_empty() {
+                    return;
+                }
+            }
+            pointers.push(pointer.clone());
+            for ((field, entry), child) in children(node) {
+                let mut pointer = pointer.clone();
+                json_pointer::push(&mut pointer, &field);
+                if let Some(entry) = entry {
+                    json_pointer::push(&mut pointer, &entry);
+                }
+                walk(pointer, child, pointers);
+            }
+        }
+
+        let mut pointers = Vec::new();
+        walk(String::new(), Node::Spec(self), &mut pointers);
+        pointers
+    }
+}
+
+/// The reference tokens from a node to one of its children: a field, and the key or index of
+/// an entry if the field is a map or a list.
+type Tokens<'a> = (Cow<'a, str>, Option<Cow<'a, str>>);
+
+fn field<'a>(name: &'static str) -> Tokens<'a> {
+    (Cow::Borrowed(name), None)
+}
+
+fn entry<'a>(name: &'static str, key: &'a str) -> Tokens<'a> {
+    (Cow::Borrowed(name), Some(Cow::Borrowed(key)))
+}
+
+fn index<'a>(name: &'static str, index: usize) -> Tokens<'a> {
+    (Cow::Borrowed(name), Some(Cow::Owned(index.to_string())))
+}
+
+/// Returns the child reached by the first tokens of `tokens`, with how many were used.
+fn find<'a, N>(children: Vec<(Tokens<'a>, N)>, tokens: &[Cow<str>]) -> Option<(N, usize)> {
+    children
+        .into_iter()
+        .find_map(|((field, entry), child)| match (tokens, entry) {
+            ([first, ..], None) if *first == field => Some((child, 1)),
+            ([first, second, ..], Some(entry)) if *first == field && *second == entry => {
+                Some((child, 2))
+            }
+            _ => None,
+        })
+}
+
+macro_rules! or_ref {
+    ($node:ident, $object:expr, $variant:ident) => {
+        match $object {
+            ObjectOrReference::Object(object) => $node::$variant(object),
+            ObjectOrReference::Ref { ref_path } => $node::Reference(ref_path),
+        }
+    };
+}
+
+macro_rules! or_inline {
+    ($node:ident, $schema:expr) => {
+        match $schema {
+            ComponentOrInlineSchema::Component { name } => $node::ComponentSchema(name),
+            ComponentOrInlineSchema::ExtRef { reference } => $node::Reference(reference),
+            ComponentOrInlineSchema::Inline(schema) => $node::Schema(schema),
+        }
+    };
+}
+
+/// Defines the function returning the children of a node, for `Node` or `NodeMut`.
+macro_rules! children {
+    ($name:ident, $node:ident $(, $mut:tt)?) => {
+        fn $name(node: $node<'_>) -> Vec<(Tokens<'_>, $node<'_>)> {
+            let mut children = Vec::new();
+            match node {
+                $node::Spec(spec) => {
+                    children.push((field("openapi"), $node::Str(&$($mut)? spec.openapi)));
+                    children.push((field("info"), $node::Info(&$($mut)? spec.info)));
+                    for (i, server) in (&$($mut)? spec.servers).into_iter().enumerate() {
+                        children.push((index("servers", i), $node::Server(server)));
+                    }
+                    for (path, item) in &$($mut)? spec.paths {
+                        children.push((entry("paths", path), $node::PathItem(item)));
+                    }
+                    if let Some(components) = &$($mut)? spec.components {
+                        children.push((field("components"), $node::Components(components)));
+                    }
+                    for (i, tag) in (&$($mut)? spec.tags).into_iter().enumerate() {
+                        children.push((index("tags", i), $node::Tag(tag)));
+                    }
+                    if let Some(docs) = &$($mut)? spec.external_docs {
+                        children.push((field("externalDocs"), $node::ExternalDoc(docs)));
+                    }
+                }
+                $node::Info(info) => {
+                    children.push((field("title"), $node::Str(&$($mut)? info.title)));
+                    children.push((field("description"), $node::Str(&$($mut)? info.description)));
+                    children.push((field("version"), $node::Str(&$($mut)? info.version)));
+                    if let Some(contact) = &$($mut)? info.contact {
+                        children.push((field("contact"), $node::Contact(contact)));
+                    }
+                    if let Some(license) = &$($mut)? info.license {
+                        children.push((field("license"), $node::License(license)));
+                    }
+                }
+                $node::Contact(contact) => {
+                    children.push((field("name"), $node::Str(&$($mut)? contact.name)));
+                    children.push((field("email"), $node::Str(&$($mut)? contact.email)));
+                }
+                $node::License(license) => {
+                    children.push((field("name"), $node::Str(&$($mut)? license.name)));
+                }
+                $node::Server(server) => {
+                    children.push((field("url"), $node::Str(&$($mut)? server.url)));
+                    let description = &$($mut)? server.description;
+                    children.push((field("description"), $node::Str(description)));
+                    for (name, variable) in &$($mut)? server.variables {
+                        let node = $node::ServerVariable(variable);
+                        children.push((entry("variables", name), node));
+                    }
+                }
+                $node::ServerVariable(variable) => {
+                    children.push((field("default"), $node::Str(&$($mut)? variable.default)));
+                    let description = &$($mut)? variable.description;
+                    children.push((field("description"), $node::Str(description)));
+                }
+                $node::Tag(tag) => {
+                    children.push((field("name"), $node::Str(&$($mut)? tag.name)));
+                    children.push((field("description"), $node::Str(&$($mut)? tag.description)));
+                    if let Some(docs) = &$($mut)? tag.external_docs {
+                        children.push((field("externalDocs"), $node::ExternalDoc(docs)));
+                    }
+                }
+                $node::ExternalDoc(docs) => {
+                    children.push((field("description"), $node::Str(&$($mut)? docs.description)));
+                }
+                $node::Components(components) => {
+                    for (name, schema) in &$($mut)? components.schemas {
+                        children.push((entry("schemas", name), or_ref!($node, schema, Schema)));
+                    }
+                    for (name, response) in &$($mut)? components.responses {
+                        let node = or_ref!($node, response, Response);
+                        children.push((entry("responses", name), node));
+                    }
+                    for (name, parameter) in &$($mut)? components.parameters {
+                        let node = or_ref!($node, parameter, Parameter);
+                        children.push((entry("parameters", name), node));
+                    }
+                    for (name, example) in &$($mut)? components.examples {
+                        let node = or_ref!($node, example, Example);
+                        children.push((entry("examples", name), node));
+                    }
+                    for (name, body) in &$($mut)? components.request_bodies {
+                        let node = or_ref!($node, body, RequestBody);
+                        children.push((entry("requestBodies", name), node));
+                    }
+                    for (name, header) in &$($mut)? components.headers {
+                        children.push((entry("headers", name), or_ref!($node, header, Header)));
+                    }
+                    for (name, scheme) in &$($mut)? components.security_schemes {
+                        let node = or_ref!($node, scheme, SecurityScheme);
+                        children.push((entry("securitySchemes", name), node));
+                    }
+                    for (name, link) in &$($mut)? components.links {
+                        children.push((entry("links", name), or_ref!($node, link, Link)));
+                    }
+                    for (name, callback) in &$($mut)? components.callbacks {
+                        let node = or_ref!($node, callback, Callback);
+                        children.push((entry("callbacks", name), node));
+                    }
+                }
+                $node::PathItem(item) => {
+                    children.push((field("$ref"), $node::Str(&$($mut)? item.reference)));
+                    children.push((field("summary"), $node::Str(&$($mut)? item.summary)));
+                    children.push((field("description"), $node::Str(&$($mut)? item.description)));
+                    let operations = [
+                        &$($mut)? item.get,
+                        &$($mut)? item.put,
+                        &$($mut)? item.post,
+                        &$($mut)? item.delete,
+                        &$($mut)? item.options,
+                        &$($mut)? item.head,
+                        &$($mut)? item.patch,
+                        &$($mut)? item.trace,
+                    ];
+                    for (method, operation) in METHODS.iter().zip(operations) {
+                        if let Some(operation) = operation {
+                            children.push((field(*method), $node::Operation(operation)));
+                        }
+                    }
+                    for (i, server) in (&$($mut)? item.servers).into_iter().enumerate() {
+                        children.push((index("servers", i), $node::Server(server)));
+                    }
+                    for (i, parameter) in (&$($mut)? item.parameters).into_iter().enumerate() {
+                        let node = or_ref!($node, parameter, Parameter);
+                        children.push((index("parameters", i), node));
+                    }
+                }
+                $node::Operation(operation) => {
+                    children.push((field("summary"), $node::Str(&$($mut)? operation.summary)));
+                    let description = &$($mut)? operation.description;
+                    children.push((field("description"), $node::Str(description)));
+                    if let Some(docs) = &$($mut)? operation.external_docs {
+                        children.push((field("externalDocs"), $node::ExternalDoc(docs)));
+                    }
+                    let id = &$($mut)? operation.operation_id;
+                    children.push((field("operationId"), $node::Str(id)));
+                    let parameters = &$($mut)? operation.parameters;
+                    for (i, parameter) in parameters.into_iter().enumerate() {
+                        let node = or_ref!($node, parameter, Parameter);
+                        children.push((index("parameters", i), node));
+                    }
+                    if let Some(body) = &$($mut)? operation.request_body {
+                        children.push((field("requestBody"), or_ref!($node, body, RequestBody)));
+                    }
+                    for (status, response) in &$($mut)? operation.responses {
+                        let node = or_ref!($node, response, Response);
+                        children.push((entry("responses", status), node));
+                    }
+                    for (name, callback) in &$($mut)? operation.callbacks {
+                        let node = or_ref!($node, callback, Callback);
+                        children.push((entry("callbacks", name), node));
+                    }
+                    for (i, server) in (&$($mut)? operation.servers).into_iter().enumerate() {
+                        children.push((index("servers", i), $node::Server(server)));
+                    }
+                }
+                $node::Parameter(parameter) => {
+                    children.push((field("name"), $node::Str(&$($mut)? parameter.name)));
+                    let description = &$($mut)? parameter.description;
+                    children.push((field("description"), $node::Str(description)));
+                    match &$($mut)? parameter.representation {
+                        Some(ParameterRepresentation::Simple { schema }) => {
+                            children.push((field("schema"), or_inline!($node, schema)));
+                        }
+                        Some(ParameterRepresentation::Content { content }) => {
+                            for (name, media) in content {
+                                children.push((entry("content", name), $node::MediaType(media)));
+                            }
+                        }
+                        None => {}
+                    }
+                    if let Some(ParameterExamples::Multiple { examples }) =
+                        &$($mut)? parameter.example
+                    {
+                        for (name, example) in examples {
+                            let node = or_ref!($node, example, Example);
+                            children.push((entry("examples", name), node));
+                        }
+                    }
+                }
+                $node::RequestBody(body) => {
+                    children.push((field("description"), $node::Str(&$($mut)? body.description)));
+                    for (name, media) in &$($mut)? body.content {
+                        children.push((entry("content", name), $node::MediaType(media)));
+                    }
+                }
+                $node::Response(response) => {
+                    let description = &$($mut)? response.description;
+                    children.push((field("description"), $node::Str(description)));
+                    for (name, header) in &$($mut)? response.headers {
+                        children.push((entry("headers", name), or_ref!($node, header, Header)));
+                    }
+                    for (name, media) in &$($mut)? response.content {
+                        children.push((entry("content", name), $node::MediaType(media)));
+                    }
+                    for (name, link) in &$($mut)? response.links {
+                        children.push((entry("links", name), or_ref!($node, link, Link)));
+                    }
+                }
+                $node::Header(header) => {
+                    let description = &$($mut)? header.description;
+                    children.push((field("description"), $node::Str(description)));
+                    if let Some(schema) = &$($mut)? header.schema {
+                        children.push((field("schema"), or_inline!($node, schema)));
+                    }
+                }
+                $node::MediaType(media) => {
+                    if let Some(schema) = &$($mut)? media.schema {
+                        children.push((field("schema"), or_inline!($node, schema)));
+                    }
+                    if let Some(MediaTypeExample::Examples { examples }) =
+                        &$($mut)? media.examples
+                    {
+                        for (name, example) in examples {
+                            let node = or_ref!($node, example, Example);
+                            children.push((entry("examples", name), node));
+                        }
+                    }
+                    for (name, encoding) in &$($mut)? media.encoding {
+                        children.push((entry("encoding", name), $node::Encoding(encoding)));
+                    }
+                }
+                $node::Encoding(encoding) => {
+                    for (name, header) in &$($mut)? encoding.headers {
+                        children.push((entry("headers", name), or_ref!($node, header, Header)));
+                    }
+                }
+                $node::Callback(callback) => {
+                    for (expression, item) in callback {
+                        let tokens = (Cow::Borrowed(&**expression), None);
+                        children.push((tokens, $node::PathItem(item)));
+                    }
+                }
+                $node::Schema(schema) => {
+                    children.push((field("title"), $node::Str(&$($mut)? schema.title)));
+                    let description = &$($mut)? schema.description;
+                    children.push((field("description"), $node::Str(description)));
+                    children.push((field("format"), $node::Str(&$($mut)? schema.format)));
+                    children.push((field("pattern"), $node::Str(&$($mut)? schema.pattern)));
+                    if let Some(items) = &$($mut)? schema.items {
+                        children.push((field("items"), or_inline!($node, &$($mut)? **items)));
+                    }
+                    for (name, property) in &$($mut)? schema.properties {
+                        children.push((entry("properties", name), or_inline!($node, property)));
+                    }
+                    if let Some(additional) = &$($mut)? schema.additional_properties {
+                        let node = or_inline!($node, &$($mut)? **additional);
+                        children.push((field("additionalProperties"), node));
+                    }
+                    for (name, schemas) in [
+                        ("allOf", &$($mut)? schema.all_of),
+                        ("oneOf", &$($mut)? schema.one_of),
+                        ("anyOf", &$($mut)? schema.any_of),
+                    ] {
+                        for (i, schema) in schemas.into_iter().enumerate() {
+                            children.push((index(name, i), or_inline!($node, schema)));
+                        }
+                    }
+                }
+                $node::Example(example) => {
+                    children.push((field("summary"), $node::Str(&$($mut)? example.summary)));
+                    let description = &$($mut)? example.description;
+                    children.push((field("description"), $node::Str(description)));
+                }
+                $node::Link(link) => {
+                    children.push((field("description"), $node::Str(&$($mut)? link.description)));
+                    if let Some(server) = &$($mut)? link.server {
+                        children.push((field("server"), $node::Server(server)));
+                    }
+                }
+                $node::Reference(reference) | $node::ComponentSchema(reference) => {
+                    children.push((field("$ref"), $node::Str(reference)));
+                }
+                $node::SecurityScheme(_) | $node::Str(_) => {}
+            }
+            children
+        }
+    };
+}
+
+children!(children, Node);
+children!(children_mut, NodeMut, mut);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec() -> Spec {
+        serde_json::from_str(
+            r##"{
+              "openapi": "3.0.3",
+              "info": {
+                "title": "Pets",
+                "version": "1.0.0",
+                "contact": {"name": "Ann"},
+                "license": {"name": "MIT"}
+              },
+              "servers": [
+                {"url": "https://{region}.example.com", "variables": {"region": {"default": "eu"}}}
+              ],
+              "paths": {
+                "/pets/{id}": {
+                  "parameters": [{"$ref": "#/components/parameters/Id"}],
+                  "put": {
+                    "operationId": "updatePet",
+                    "requestBody": {
+                      "content": {
+                        "application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}
+                      }
+                    },
+                    "responses": {"204": {"description": "Updated"}}
+                  }
+                }
+              },
+              "components": {
+                "parameters": {
+                  "Id": {"name": "id", "in": "path", "required": true, "schema": {"type": "string"}}
+                },
+                "schemas": {
+                  "Pet": {
+                    "type": "object",
+                    "properties": {
+                      "name": {"type": "string"},
+                      "a/b~c": {"type": "array", "items": {"$ref": "other.yaml#/Tag"}}
+                    }
+                  }
+                }
+              },
+              "tags": [{"name": "pets", "externalDocs": {"url": "https://example.com/pets"}}]
+            }"##,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_pointer() {
+        let spec = spec();
+        let put = "/paths/~1pets~1{id}/put";
+        assert_eq!(
+            spec.pointer(&format!("{}/operationId", put)),
+            Some(Node::Str(&"updatePet".into()))
+        );
+        assert!(matches!(
+            spec.pointer(&format!("{}/requestBody/content/application~1json/schema", put)),
+            Some(Node::ComponentSchema(name)) if name == "Pet"
+        ));
+        assert!(matches!(
+            spec.pointer("/paths/~1pets~1{id}/parameters/0/$ref"),
+            Some(Node::Str(reference)) if reference == "#/components/parameters/Id"
+        ));
+        assert!(matches!(
+            spec.pointer("/components/schemas/Pet/properties/a~1b~0c/items"),
+            Some(Node::Reference(reference)) if reference == "other.yaml#/Tag"
+        ));
+        assert!(matches!(
+            spec.pointer(&format!("{}/requestBody/content/application~1json/schema/$ref", put)),
+            Some(Node::Str(name)) if name == "Pet"
+        ));
+        assert!(matches!(
+            spec.pointer("/servers/0/variables/region/default"),
+            Some(Node::Str(default)) if default == "eu"
+        ));
+        assert!(matches!(
+            spec.pointer("/tags/0/externalDocs"),
+            Some(Node::ExternalDoc(docs)) if docs.url.as_str() == "https://example.com/pets"
+        ));
+        assert!(matches!(
+            spec.pointer("/info/license/name"),
+            Some(Node::Str(name)) if name == "MIT"
+        ));
+        assert!(matches!(spec.pointer(""), Some(Node::Spec(_))));
+        assert!(matches!(
+            spec.pointer("/components/parameters/Id"),
+            Some(Node::Parameter(parameter)) if parameter.name == "id"
+        ));
+        assert_eq!(spec.pointer("/components/schemas"), None);
+        assert_eq!(spec.pointer("/paths/~1pets~1{id}/get"), None);
+        assert_eq!(spec.pointer("paths"), None);
+    }
+
+    #[test]
+    fn test_pointer_mut() {
+        let mut spec = spec();
+        let put = "/paths/~1pets~1{id}/put";
+        match spec.pointer_mut(&format!("{}/summary", put)) {
+            Some(NodeMut::Str(summary)) => *summary = "Update a pet".into(),
+            other => panic!("unexpected {:?}", other),
+        }
+        match spec.pointer_mut("/components/schemas/Pet/properties/name") {
+            Some(NodeMut::Schema(schema)) => schema.min_length = Some(1),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert_eq!(
+            spec.paths["/pets/{id}"].put.as_ref().unwrap().summary,
+            "Update a pet"
+        );
+        assert!(matches!(
+            spec.pointer("/components/schemas/Pet/properties/name"),
+            Some(Node::Schema(schema)) if schema.min_length == Some(1)
+        ));
+
+        let pointers = spec.pointers();
+        assert_eq!(
+            pointers,
+            [
+                "",
+                "/openapi",
+                "/info",
+                "/info/title",
+                "/info/version",
+                "/info/contact",
+                "/info/contact/name",
+                "/info/license",
+                "/info/license/name",
+                "/servers/0",
+                "/servers/0/url",
+                "/servers/0/variables/region",
+                "/servers/0/variables/region/default",
+                "/paths/~1pets~1{id}",
+                "/paths/~1pets~1{id}/put",
+                "/paths/~1pets~1{id}/put/summary",
+                "/paths/~1pets~1{id}/put/operationId",
+                "/paths/~1pets~1{id}/put/requestBody",
+                "/paths/~1pets~1{id}/put/requestBody/content/application~1json",
+                "/paths/~1pets~1{id}/put/requestBody/content/application~1json/schema",
+                "/paths/~1pets~1{id}/put/requestBody/content/application~1json/schema/$ref",
+                "/paths/~1pets~1{id}/put/responses/204",
+                "/paths/~1pets~1{id}/put/responses/204/description",
+                "/paths/~1pets~1{id}/parameters/0",
+                "/paths/~1pets~1{id}/parameters/0/$ref",
+                "/components",
+                "/components/schemas/Pet",
+                "/components/schemas/Pet/properties/name",
+                "/components/schemas/Pet/properties/a~1b~0c",
+                "/components/schemas/Pet/properties/a~1b~0c/items",
+                "/components/schemas/Pet/properties/a~1b~0c/items/$ref",
+                "/components/parameters/Id",
+                "/components/parameters/Id/name",
+                "/components/parameters/Id/schema",
+                "/tags/0",
+                "/tags/0/name",
+                "/tags/0/externalDocs",
+            ]
+        );
+        for pointer in &pointers {
+            assert!(spec.pointer(pointer).is_some(), "{}", pointer);
+        }
+    }
+}
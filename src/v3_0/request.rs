@@ -0,0 +1,648 @@
+//! Matching of requests to the operations of a specification, and reading of their parameters
+//! and bodies.
+
+use crate::{
+    v3_0::{
+        diff::{location_name, operations, METHODS},
+        operation::{is_json, resolved_parameters},
+        InstanceError, Location, MediaType, Operation, Parameter, ParameterRepresentation,
+        ParameterStyle, PathItem, Spec,
+    },
+    Str,
+};
+use indexmap::IndexMap;
+use serde_json::{Map, Value};
+use std::{error, fmt};
+
+/// A path template, such as `/pets/{petId}/photo.{format}`, which request paths are matched
+/// against.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PathTemplate {
+    segments: Vec<Vec<Part>>,
+}
+
+/// A part of a segment of a path template.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Part {
+    Literal(String),
+    Parameter(String),
+}
+
+impl PathTemplate {
+    /// Parses `template`. Braces which are not closed are taken literally.
+    pub fn parse(template: &str) -> Self {
+        PathTemplate {
+            segments: template.split('/').map(parts).collect(),
+        }
+    }
+
+    /// Returns the names of the parameters of the template, in order.
+    pub fn parameters(&self) -> Vec<&str> {
+        self.segments
+            .iter()
+            .flatten()
+            .filter_map(|part| match part {
+                Part::Parameter(name) => Some(&**name),
+                Part::Literal(_) => None,
+            })
+            .collect()
+    }
+
+    /// Matches `path`, without its query, against the template. Returns the percent-decoded
+    /// values of the parameters, or `None` if the path does not match.
+    ///
+    /// Parameters match non-empty parts of a single segment. When a segment has several
+    /// parameters, such as `{name}.{extension}`, the first ones match as little as they can.
+    pub fn matches(&self, path: &str) -> Option<Vec<(String, String)>> {
+        let segments: Vec<&str> = path.split('/').collect();
+        if segments.len() != self.segments.len() {
+            return None;
+        }
+        let mut values = Vec::new();
+        for (parts, segment) in self.segments.iter().zip(segments) {
+            if !match_parts(parts, segment, &mut values) {
+                return None;
+            }
+        }
+        Some(values)
+    }
+}
+
+impl fmt::Display for PathTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (index, parts) in self.segments.iter().enumerate() {
+            if index > 0 {
+                f.write_str("/")?;
+            }
+            for part in parts {
+                match part {
+                    Part::Literal(literal) => f.write_str(literal)?,
+                    Part::Parameter(name) => write!(f, "{{{}}}", name)?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Splits a segment of a path template into literals and parameters.
+fn parts(segment: &str) -> Vec<Part> {
+    let mut parts = Vec::new();
+    let mut rest = segment;
+    while let Some(start) = rest.find('{') {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+        if start > 0 {
+            parts.push(Part::Literal(rest[..start].to_string()));
+        }
+        parts.push(Part::Parameter(rest[start + 1..end].to_string()));
+        rest = &rest[end + 1..];
+    }
+    if !rest.is_empty() || parts.is_empty() {
+        parts.push(Part::Literal(rest.to_string()));
+    }
+    parts
+}
+
+/// Matches `segment` against `parts`, adding the values of their parameters to `values`.
+fn match_parts(parts: &[Part], segment: &str, values: &mut Vec<(String, String)>) -> bool {
+    match parts.split_first() {
+        None => segment.is_empty(),
+        Some((Part::Literal(literal), rest)) => match segment.strip_prefix(&**literal) {
+            Some(segment) => match_parts(rest, segment, values),
+            None => false,
+        },
+        Some((Part::Parameter(name), rest)) => {
+            for end in (1..=segment.len()).filter(|end| segment.is_char_boundary(*end)) {
+                values.push((name.clone(), percent_decode(&segment[..end])));
+                if match_parts(rest, &segment[end..], values) {
+                    return true;
+                }
+                values.pop();
+            }
+            false
+        }
+    }
+}
+
+/// Decodes the `%XX` escapes of `value`, replacing invalid UTF-8.
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let hex = |index: usize| {
+        bytes
+            .get(index)
+            .and_then(|byte| char::from(*byte).to_digit(16))
+    };
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        match (bytes[index], hex(index + 1), hex(index + 2)) {
+            (b'%', Some(high), Some(low)) => {
+                decoded.push((high * 16 + low) as u8);
+                index += 3;
+            }
+            (byte, _, _) => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// The parts of an HTTP request which [`Spec::read_request`] reads.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RequestParts {
+    /// The method, such as `GET`, in any case.
+    pub method: String,
+    /// The percent-encoded path, without the query.
+    pub path: String,
+    /// The query, without its leading `?`.
+    pub query: String,
+    /// The names and values of the headers, in order. Names are compared case-insensitively.
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl RequestParts {
+    /// Returns the value of the first header named `name`.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| &**value)
+    }
+
+    fn headers_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> {
+        self.headers
+            .iter()
+            .filter(move |(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| &**value)
+    }
+}
+
+/// An operation whose path template and method a request matches, as found by
+/// [`Spec::find_operation`].
+#[derive(Clone, Debug)]
+pub struct OperationMatch<'a> {
+    /// The path template of the operation.
+    pub path: &'a str,
+    /// The method of the operation, in lowercase.
+    pub method: &'static str,
+    pub item: &'a PathItem,
+    pub operation: &'a Operation,
+    /// The percent-decoded values of the parameters of the path template.
+    pub parameters: Vec<(String, String)>,
+}
+
+/// The values of the parameters and body of a request, as read by [`Spec::read_request`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RequestValues {
+    /// The values of the path parameters which are present, by name.
+    pub path: Map<String, Value>,
+    /// The values of the query parameters which are present, by name.
+    pub query: Map<String, Value>,
+    /// The values of the header parameters which are present, by the names of the parameters.
+    pub header: Map<String, Value>,
+    /// The values of the cookie parameters which are present, by name.
+    pub cookie: Map<String, Value>,
+    /// The body, if there is one and it is JSON, a form or text. Other bodies are left in
+    /// [`RequestParts::body`].
+    pub body: Option<Value>,
+}
+
+/// A parameter or request body which does not satisfy its operation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RequestError {
+    /// The location of the parameter, or `None` for the request body.
+    pub location: Option<Location>,
+    /// The name of the parameter, empty for the request body.
+    pub name: String,
+    pub message: String,
+}
+
+impl RequestError {
+    fn new(parameter: Option<&Parameter>, message: String) -> Self {
+        RequestError {
+            location: parameter.map(|parameter| parameter.location),
+            name: parameter.map_or_else(String::new, |parameter| parameter.name.to_string()),
+            message,
+        }
+    }
+}
+
+impl fmt::Display for RequestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.location {
+            Some(location) => write!(
+                f,
+                "{} parameter `{}`: {}",
+                location_name(location),
+                self.name,
+                self.message
+            ),
+            None => write!(f, "request body: {}", self.message),
+        }
+    }
+}
+
+impl error::Error for RequestError {}
+
+impl Spec {
+    /// Finds the operation which serves requests with `method` to `path`, ignoring a query.
+    ///
+    /// When several path templates match, the one with the fewest parameters is chosen, so that
+    /// `/pets/mine` takes precedence over `/pets/{id}`.
+    pub fn find_operation(&self, method: &str, path: &str) -> Option<OperationMatch<'_>> {
+        let path = path.split('?').next().unwrap_or_default();
+        let index = METHODS
+            .iter()
+            .position(|known| known.eq_ignore_ascii_case(method))?;
+        let mut found: Option<OperationMatch> = None;
+        for (template, item) in &self.paths {
+            let operation = match operations(item)[index] {
+                Some(operation) => operation,
+                None => continue,
+            };
+            let parameters = match PathTemplate::parse(template).matches(path) {
+                Some(parameters) => parameters,
+                None => continue,
+            };
+            if found
+                .as_ref()
+                .is_none_or(|found| parameters.len() < found.parameters.len())
+            {
+                found = Some(OperationMatch {
+                    path: template,
+                    method: METHODS[index],
+                    item,
+                    operation,
+                    parameters,
+                });
+            }
+        }
+        found
+    }
+
+    /// Reads the parameters and body of `request` to the operation `found`, and validates them
+    /// against their schemas. Returns every missing or invalid value otherwise.
+    ///
+    /// Parameters are parsed according to their `style` and `explode`, and the body according to
+    /// its `Content-Type` header.
+    ///
+    /// Requires the `validate` feature.
+    pub fn read_request(
+        &self,
+        found: &OperationMatch,
+        request: &RequestParts,
+    ) -> Result<RequestValues, Vec<RequestError>> {
+        let mut values = RequestValues::default();
+        let mut errors = Vec::new();
+        let query: Vec<(String, String)> = url::form_urlencoded::parse(request.query.as_bytes())
+            .into_owned()
+            .collect();
+        let cookies: Vec<(String, String)> = request
+            .headers_named("cookie")
+            .flat_map(|cookies| cookies.split(';'))
+            .filter_map(|cookie| {
+                let (name, value) = cookie.trim().split_once('=')?;
+                Some((name.to_string(), percent_decode(value)))
+            })
+            .collect();
+        for parameter in resolved_parameters(self, found.item, found.operation) {
+            let header;
+            let (pairs, read) = match parameter.location {
+                Location::Path => (&found.parameters, &mut values.path),
+                Location::Query => (&query, &mut values.query),
+                Location::Header => {
+                    let joined: Vec<&str> = request.headers_named(&parameter.name).collect();
+                    header = if joined.is_empty() {
+                        Vec::new()
+                    } else {
+                        vec![(parameter.name.to_string(), joined.join(","))]
+                    };
+                    (&header, &mut values.header)
+                }
+                Location::Cookie => (&cookies, &mut values.cookie),
+                // Not a location of parameters in OpenAPI 3.
+                Location::FormData => continue,
+            };
+            let (value, schema) = match &parameter.representation {
+                Some(ParameterRepresentation::Content { content }) => {
+                    let value = pairs.iter().find(|(name, _)| *name == parameter.name);
+                    let value = value.map(|(_, value)| {
+                        serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.clone()))
+                    });
+                    let schema = content
+                        .values()
+                        .next()
+                        .and_then(|media| media.schema.as_ref());
+                    (value, schema)
+                }
+                Some(ParameterRepresentation::Simple { schema }) => {
+                    (parameter.deserialize(self, pairs), Some(schema))
+                }
+                None => (parameter.deserialize(self, pairs), None),
+            };
+            let value = match value {
+                Some(value) => value,
+                None if parameter.required == Some(true) => {
+                    errors.push(RequestError::new(
+                        Some(parameter),
+                        "is required".to_string(),
+                    ));
+                    continue;
+                }
+                None => continue,
+            };
+            if let Some(schema) = schema {
+                errors.extend(
+                    self.validate_instance(schema, &value)
+                        .into_iter()
+                        .map(|error| RequestError::new(Some(parameter), message(error))),
+                );
+            }
+            read.insert(parameter.name.to_string(), value);
+        }
+
+        let request_body = found
+            .operation
+            .request_body
+            .as_ref()
+            .and_then(|request_body| self.resolve(request_body).ok());
+        if let Some(request_body) = request_body {
+            let content_type = request.header("content-type");
+            if request.body.is_empty() && content_type.is_none() {
+                if request_body.required == Some(true) {
+                    errors.push(RequestError::new(None, "is required".to_string()));
+                }
+            } else {
+                let content_type = content_type.unwrap_or("application/octet-stream");
+                match media_type(&request_body.content, content_type) {
+                    Some(media) => match self.read_body(media, content_type, &request.body) {
+                        Ok(body) => {
+                            if let (Some(body), Some(schema)) = (&body, &media.schema) {
+                                errors.extend(
+                                    self.validate_instance(schema, body)
+                                        .into_iter()
+                                        .map(|error| RequestError::new(None, message(error))),
+                                );
+                            }
+                            values.body = body;
+                        }
+                        Err(message) => errors.push(RequestError::new(None, message)),
+                    },
+                    None => errors.push(RequestError::new(
+                        None,
+                        format!("the media type `{}` is not supported", content_type),
+                    )),
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(values)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Parses a body of the type `content_type`, described by `media`.
+    fn read_body(
+        &self,
+        media: &MediaType,
+        content_type: &str,
+        body: &[u8],
+    ) -> Result<Option<Value>, String> {
+        let essence = content_type.split(';').next().unwrap_or_default().trim();
+        if is_json(essence) {
+            match serde_json::from_slice(body) {
+                Ok(body) => Ok(Some(body)),
+                Err(error) => Err(format!("is not valid JSON: {}", error)),
+            }
+        } else if essence.eq_ignore_ascii_case("application/x-www-form-urlencoded") {
+            let pairs: Vec<(String, String)> =
+                url::form_urlencoded::parse(body).into_owned().collect();
+            let schema = media
+                .schema
+                .as_ref()
+                .and_then(|schema| self.resolve_schema(schema).ok());
+            let mut form = Map::new();
+            for (name, _) in &pairs {
+                if form.contains_key(name) {
+                    continue;
+                }
+                // Fields are read as exploded query parameters of the types of the properties.
+                let property = schema.and_then(|schema| schema.properties.get(&**name));
+                let field = Parameter {
+                    name: name.clone().into(),
+                    style: Some(ParameterStyle::Form),
+                    explode: Some(true),
+                    representation: property.map(|schema| ParameterRepresentation::Simple {
+                        schema: schema.clone(),
+                    }),
+                    ..Parameter::default()
+                };
+                if let Some(value) = field.deserialize(self, &pairs) {
+                    form.insert(name.clone(), value);
+                }
+            }
+            Ok(Some(Value::Object(form)))
+        } else if essence.len() > 5 && essence[..5].eq_ignore_ascii_case("text/") {
+            Ok(Some(Value::String(
+                String::from_utf8_lossy(body).into_owned(),
+            )))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// The message of an error of a value, with the pointer to the invalid part of the value.
+fn message(error: InstanceError) -> String {
+    if error.instance_pointer.is_empty() {
+        error.message
+    } else {
+        format!("`{}`: {}", error.instance_pointer, error.message)
+    }
+}
+
+/// Returns the media type of `content` which `content_type` matches, preferring media types
+/// over ranges such as `image/*`.
+fn media_type<'a>(
+    content: &'a IndexMap<Str, MediaType>,
+    content_type: &str,
+) -> Option<&'a MediaType> {
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    let range = match essence.split_once('/') {
+        Some((kind, _)) => format!("{}/*", kind),
+        None => "*/*".to_string(),
+    };
+    let find = |wanted: &str| {
+        content.iter().find_map(|(media_type, media)| {
+            let media_essence = media_type.split(';').next().unwrap_or_default().trim();
+            media_essence.eq_ignore_ascii_case(wanted).then_some(media)
+        })
+    };
+    find(&essence)
+        .or_else(|| find(&range))
+        .or_else(|| find("*/*"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_path_template() {
+        let template = PathTemplate::parse("/pets/{petId}/photo.{format}");
+        assert_eq!(template.parameters(), ["petId", "format"]);
+        assert_eq!(template.to_string(), "/pets/{petId}/photo.{format}");
+        assert_eq!(
+            template.matches("/pets/a%20b/photo.tar.gz"),
+            Some(vec![
+                ("petId".to_string(), "a b".to_string()),
+                ("format".to_string(), "tar.gz".to_string())
+            ])
+        );
+        assert_eq!(template.matches("/pets//photo.png"), None);
+        assert_eq!(template.matches("/pets/1/photo."), None);
+        assert_eq!(template.matches("/pets/1/photo.png/more"), None);
+
+        let template = PathTemplate::parse("/files/{name}.{extension}");
+        assert_eq!(
+            template.matches("/files/a.b.c"),
+            Some(vec![
+                ("name".to_string(), "a".to_string()),
+                ("extension".to_string(), "b.c".to_string())
+            ])
+        );
+        assert_eq!(PathTemplate::parse("/").matches("/"), Some(Vec::new()));
+        assert_eq!(
+            PathTemplate::parse("/{open").matches("/{open"),
+            Some(Vec::new())
+        );
+    }
+
+    #[test]
+    fn test_read_request() {
+        let spec: Spec = serde_json::from_value(json!({
+            "openapi": "3.0.3",
+            "info": {"title": "Pets", "version": "1.0.0"},
+            "paths": {
+                "/pets/{petId}": {
+                    "parameters": [
+                        {
+                            "name": "petId",
+                            "in": "path",
+                            "required": true,
+                            "schema": {"type": "integer"}
+                        }
+                    ],
+                    "put": {
+                        "parameters": [
+                            {
+                                "name": "tags",
+                                "in": "query",
+                                "schema": {
+                                    "type": "array",
+                                    "items": {"type": "string"},
+                                    "maxItems": 2
+                                }
+                            },
+                            {"name": "X-Dry-Run", "in": "header", "schema": {"type": "boolean"}},
+                            {"name": "session", "in": "cookie", "required": true, "schema": {}}
+                        ],
+                        "requestBody": {
+                            "required": true,
+                            "content": {
+                                "application/json": {
+                                    "schema": {"$ref": "#/components/schemas/Pet"}
+                                },
+                                "application/x-www-form-urlencoded": {
+                                    "schema": {"$ref": "#/components/schemas/Pet"}
+                                }
+                            }
+                        },
+                        "responses": {"204": {"description": "Updated."}}
+                    }
+                },
+                "/pets/mine": {"put": {"responses": {"204": {"description": "Updated."}}}}
+            },
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "required": ["name"],
+                        "properties": {"name": {"type": "string"}, "age": {"type": "integer"}}
+                    }
+                }
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            spec.find_operation("PUT", "/pets/mine").unwrap().path,
+            "/pets/mine"
+        );
+        assert!(spec.find_operation("GET", "/pets/1").is_none());
+        assert!(spec.find_operation("PUT", "/pets").is_none());
+
+        let found = spec.find_operation("PUT", "/pets/1?tags=a").unwrap();
+        assert_eq!(found.path, "/pets/{petId}");
+        assert_eq!(found.method, "put");
+        let mut request = RequestParts {
+            method: "PUT".to_string(),
+            path: "/pets/1".to_string(),
+            query: "tags=a&tags=b%20c".to_string(),
+            headers: vec![
+                ("x-dry-run".to_string(), "true".to_string()),
+                ("Cookie".to_string(), "theme=dark; session=abc".to_string()),
+                ("Content-Type".to_string(), "application/json".to_string()),
+            ],
+            body: br#"{"name": "Rex", "age": 3}"#.to_vec(),
+        };
+        let values = spec.read_request(&found, &request).unwrap();
+        assert_eq!(Value::Object(values.path), json!({"petId": 1}));
+        assert_eq!(Value::Object(values.query), json!({"tags": ["a", "b c"]}));
+        assert_eq!(Value::Object(values.header), json!({"X-Dry-Run": true}));
+        assert_eq!(Value::Object(values.cookie), json!({"session": "abc"}));
+        assert_eq!(values.body, Some(json!({"name": "Rex", "age": 3})));
+
+        request.headers[2].1 = "application/x-www-form-urlencoded".to_string();
+        request.body = b"name=Rex&age=3".to_vec();
+        let values = spec.read_request(&found, &request).unwrap();
+        assert_eq!(values.body, Some(json!({"name": "Rex", "age": 3})));
+
+        let found = spec.find_operation("put", "/pets/one").unwrap();
+        let request = RequestParts {
+            method: "PUT".to_string(),
+            path: "/pets/one".to_string(),
+            query: "tags=a&tags=b&tags=c".to_string(),
+            headers: vec![("Content-Type".to_string(), "text/plain".to_string())],
+            body: b"Rex".to_vec(),
+        };
+        let errors: Vec<String> = spec
+            .read_request(&found, &request)
+            .unwrap_err()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            errors,
+            [
+                "path parameter `petId`: expected \"integer\", found string",
+                "query parameter `tags`: 3 items, expected at most 2",
+                "cookie parameter `session`: is required",
+                "request body: the media type `text/plain` is not supported",
+            ]
+        );
+    }
+}
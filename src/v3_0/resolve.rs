@@ -0,0 +1,321 @@
+//! Resolution of local references to components, such as `#/components/schemas/Pet`.
+
+use crate::{
+    json_pointer,
+    v3_0::{
+        Callback, ComponentOrInlineSchema, Components, Example, Header, Link, ObjectOrReference,
+        Parameter, RequestBody, Response, Schema, SecurityScheme, Spec,
+    },
+    Str,
+};
+use indexmap::IndexMap;
+use std::{error, fmt};
+
+/// Prefix of references to objects in [`Components`].
+pub const COMPONENTS_REF_PREFIX: &str = "#/components/";
+
+/// Object that can be defined in [`Components`] and referenced with a
+/// [`ObjectOrReference::Ref`].
+pub trait Component: Sized {
+    /// Name of the map holding components of this kind, e.g. `schemas`.
+    const KIND: &'static str;
+
+    fn components(components: &Components) -> &IndexMap<Str, ObjectOrReference<Self>>;
+
+    fn components_mut(components: &mut Components) -> &mut IndexMap<Str, ObjectOrReference<Self>>;
+}
+
+macro_rules! component {
+    ($($ty:ty => $kind:literal, $field:ident;)*) => {
+        $(impl Component for $ty {
+            const KIND: &'static str = $kind;
+
+            fn components(components: &Components) -> &IndexMap<Str, ObjectOrReference<Self>> {
+                &components.$field
+            }
+
+            fn components_mut(
+                components: &mut Components,
+            ) -> &mut IndexMap<Str, ObjectOrReference<Self>> {
+                &mut components.$field
+            }
+        })*
+    };
+}
+
+component! {
+    Schema => "schemas", schemas;
+    Response => "responses", responses;
+    Parameter => "parameters", parameters;
+    Example => "examples", examples;
+    RequestBody => "requestBodies", request_bodies;
+    Header => "headers", headers;
+    SecurityScheme => "securitySchemes", security_schemes;
+    Link => "links", links;
+    Callback => "callbacks", callbacks;
+}
+
+/// Failure to resolve a reference.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RefError {
+    /// The reference does not point to a component of the expected kind in this document, e.g.
+    /// it points to another file.
+    Unsupported {
+        reference: Str,
+        /// The kind of component expected, such as `schemas`.
+        expected: &'static str,
+    },
+    /// No component is defined at the reference.
+    Dangling(Str),
+    /// The reference leads back to itself. Holds the references followed, starting and ending
+    /// with the same one.
+    Cycle(Vec<Str>),
+}
+
+impl fmt::Display for RefError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RefError::Unsupported {
+                reference,
+                expected,
+            } => write!(
+                f,
+                "`{}` is not a reference to `{}{}`",
+                reference, COMPONENTS_REF_PREFIX, expected
+            ),
+            RefError::Dangling(reference) => write!(f, "`{}` is not defined", reference),
+            RefError::Cycle(references) => {
+                f.write_str("cyclic reference: ")?;
+                for (i, reference) in references.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(" -> ")?;
+                    }
+                    write!(f, "`{}`", reference)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl error::Error for RefError {}
+
+/// Returns the name of the component of kind `T` that `reference` points to.
+pub(crate) fn component_name<T: Component>(reference: &str) -> Result<Str, RefError> {
+    let unsupported = || RefError::Unsupported {
+        reference: reference.to_string().into(),
+        expected: T::KIND,
+    };
+    let tokens = json_pointer::split(reference.strip_prefix('#').ok_or_else(unsupported)?)
+        .ok_or_else(unsupported)?;
+    match tokens.as_slice() {
+        [components, kind, name] if components == "components" && kind == T::KIND => {
+            Ok(name.to_string().into())
+        }
+        _ => Err(unsupported()),
+    }
+}
+
+/// Returns the reference to the component of kind `T` named `name`.
+pub(crate) fn component_ref<T: Component>(name: &str) -> Str {
+    format!(
+        "{}{}/{}",
+        COMPONENTS_REF_PREFIX,
+        T::KIND,
+        json_pointer::escape(name)
+    )
+    .into()
+}
+
+impl Components {
+    /// Looks up a component by name, following chained references.
+    pub fn get<T: Component>(&self, name: &str) -> Result<&T, RefError> {
+        let mut followed: Vec<Str> = vec![component_ref::<T>(name)];
+        let mut name: Str = name.to_string().into();
+        loop {
+            let component = match T::components(self).get(&*name) {
+                Some(component) => component,
+                None => return Err(RefError::Dangling(followed.pop().unwrap_or_default())),
+            };
+            match component {
+                ObjectOrReference::Object(object) => return Ok(object),
+                ObjectOrReference::Ref { ref_path } => {
+                    name = component_name::<T>(ref_path)?;
+                    if let Some(start) = followed.iter().position(|r| r == ref_path) {
+                        let mut cycle = followed.split_off(start);
+                        cycle.push(ref_path.clone());
+                        return Err(RefError::Cycle(cycle));
+                    }
+                    followed.push(ref_path.clone());
+                }
+            }
+        }
+    }
+
+    /// Resolves a reference such as `#/components/schemas/Pet`, following chained references.
+    pub fn resolve_ref<T: Component>(&self, reference: &str) -> Result<&T, RefError> {
+        self.get(&component_name::<T>(reference)?)
+    }
+
+    /// Returns the object itself, or the component it refers to.
+    pub fn resolve<'a, T: Component>(
+        &'a self,
+        object: &'a ObjectOrReference<T>,
+    ) -> Result<&'a T, RefError> {
+        match object {
+            ObjectOrReference::Object(object) => Ok(object),
+            ObjectOrReference::Ref { ref_path } => self.resolve_ref(ref_path),
+        }
+    }
+
+    /// Returns the inline schema, or the component schema it refers to.
+    pub fn resolve_schema<'a>(
+        &'a self,
+        schema: &'a ComponentOrInlineSchema,
+    ) -> Result<&'a Schema, RefError> {
+        match schema {
+            ComponentOrInlineSchema::Inline(schema) => Ok(schema),
+            ComponentOrInlineSchema::Component { name } => self.get(name),
+            ComponentOrInlineSchema::ExtRef { reference } => Err(RefError::Unsupported {
+                reference: reference.clone(),
+                expected: Schema::KIND,
+            }),
+        }
+    }
+}
+
+impl Spec {
+    /// Same as [`Components::resolve_ref`], failing if there are no components.
+    pub fn resolve_ref<T: Component>(&self, reference: &str) -> Result<&T, RefError> {
+        match &self.components {
+            Some(components) => components.resolve_ref(reference),
+            None => {
+                component_name::<T>(reference)?;
+                Err(RefError::Dangling(reference.to_string().into()))
+            }
+        }
+    }
+
+    /// Same as [`Components::resolve`], failing on references if there are no components.
+    pub fn resolve<'a, T: Component>(
+        &'a self,
+        object: &'a ObjectOrReference<T>,
+    ) -> Result<&'a T, RefError> {
+        match object {
+            ObjectOrReference::Object(object) => Ok(object),
+            ObjectOrReference::Ref { ref_path } => self.resolve_ref(ref_path),
+        }
+    }
+
+    /// Same as [`Components::resolve_schema`], failing on references if there are no
+    /// components.
+    pub fn resolve_schema<'a>(
+        &'a self,
+        schema: &'a ComponentOrInlineSchema,
+    ) -> Result<&'a Schema, RefError> {
+        match (schema, &self.components) {
+            (ComponentOrInlineSchema::Inline(schema), _) => Ok(schema),
+            (_, Some(components)) => components.resolve_schema(schema),
+            (ComponentOrInlineSchema::Component { name }, None) => {
+                Err(RefError::Dangling(component_ref::<Schema>(name)))
+            }
+            (ComponentOrInlineSchema::ExtRef { reference }, None) => Err(RefError::Unsupported {
+                reference: reference.clone(),
+                expected: Schema::KIND,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r##"{
+      "openapi": "3.0.3",
+      "info": {"title": "Pets", "version": "1.0.0"},
+      "paths": {
+        "/pets": {
+          "get": {
+            "parameters": [{"$ref": "#/components/parameters/Limit"}],
+            "responses": {
+              "200": {"$ref": "#/components/responses/Pets"},
+              "404": {"$ref": "#/components/responses/Missing"}
+            }
+          }
+        }
+      },
+      "components": {
+        "schemas": {
+          "Pet": {"type": "object"},
+          "Animal": {"$ref": "#/components/schemas/Pet"},
+          "A": {"$ref": "#/components/schemas/B"},
+          "B": {"$ref": "#/components/schemas/A"}
+        },
+        "responses": {
+          "Pets": {"$ref": "#/components/responses/PetList"},
+          "PetList": {"description": "A list of pets"}
+        },
+        "parameters": {
+          "Limit": {"name": "limit", "in": "query"}
+        },
+        "callbacks": {
+          "Event": {"{$request.body#/url}": {"post": {"responses": {}}}},
+          "Alias": {"$ref": "#/components/callbacks/Event"}
+        }
+      }
+    }"##;
+
+    #[test]
+    fn test_resolve_chains() {
+        let spec: Spec = serde_json::from_str(SPEC).unwrap();
+        let get = spec.paths["/pets"].get.as_ref().unwrap();
+
+        let parameter = spec.resolve(&get.parameters[0]).unwrap();
+        assert_eq!(parameter.name, "limit");
+        let response = spec.resolve(&get.responses["200"]).unwrap();
+        assert_eq!(response.description, "A list of pets");
+
+        let animal = ComponentOrInlineSchema::Component {
+            name: "Animal".into(),
+        };
+        let schema = spec.resolve_schema(&animal).unwrap();
+        assert_eq!(schema.schema_type, Some(crate::v3_0::Type::Object));
+
+        let callback: &Callback = spec.resolve_ref("#/components/callbacks/Alias").unwrap();
+        assert!(callback.contains_key("{$request.body#/url}"));
+    }
+
+    #[test]
+    fn test_resolve_errors() {
+        let spec: Spec = serde_json::from_str(SPEC).unwrap();
+        let get = spec.paths["/pets"].get.as_ref().unwrap();
+
+        let err = spec.resolve(&get.responses["404"]).unwrap_err();
+        assert_eq!(
+            err,
+            RefError::Dangling("#/components/responses/Missing".into())
+        );
+
+        let err = spec
+            .resolve_ref::<Schema>("#/components/schemas/A")
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "cyclic reference: `#/components/schemas/A` -> `#/components/schemas/B` -> \
+             `#/components/schemas/A`"
+        );
+
+        let err = spec
+            .resolve_ref::<Response>("#/components/schemas/Pet")
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`#/components/schemas/Pet` is not a reference to `#/components/responses`"
+        );
+        assert!(matches!(
+            spec.resolve_ref::<Schema>("common.json#/components/schemas/Pet"),
+            Err(RefError::Unsupported { .. })
+        ));
+    }
+}
@@ -1,8 +1,9 @@
 //! Schema specification for [OpenAPI 3.0.0](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.0.md)
 
 use crate::{
+    json_pointer,
     v3_0::components::{Components, ObjectOrReference},
-    Str,
+    Extensions, Str,
 };
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
@@ -30,7 +31,7 @@ pub struct Spec {
     /// with a
     /// [url](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#serverUrl)
     /// value of `/`.
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub servers: Vec<Server>,
 
     /// Holds the relative paths to the individual endpoints and their operations. The path is
@@ -48,7 +49,7 @@ pub struct Spec {
     /// The list of  values includes alternative security requirement objects that can be used.
     /// Only one of the security requirement objects need to be satisfied to authorize a request.
     /// Individual operations can override this definition.
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub security: Vec<SecurityRequirement>,
 
     /// A list of tags used by the specification with additional metadata.
@@ -57,13 +58,16 @@ pub struct Spec {
     /// [Operation Object](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#operationObject)
     /// must be declared. The tags that are not declared MAY be organized randomly or
     /// based on the tools' logic. Each tag name in the list MUST be unique.
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<Tag>,
 
     /// Additional external documentation.
     #[serde(skip_serializing_if = "Option::is_none", rename = "externalDocs")]
     pub external_docs: Option<ExternalDoc>,
-    // TODO: Add "Specification Extensions" https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions}
+
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// General information about the API.
@@ -76,7 +80,7 @@ pub struct Info {
     /// The title of the application.
     pub title: Str,
     /// A short description of the application. CommonMark syntax MAY be used for rich text representation.
-    #[serde(skip_serializing_if = "str::is_empty")]
+    #[serde(default, skip_serializing_if = "str::is_empty")]
     pub description: Str,
     /// A URL to the Terms of Service for the API. MUST be in the format of a URL.
     #[serde(rename = "termsOfService", skip_serializing_if = "Option::is_none")]
@@ -91,6 +95,9 @@ pub struct Info {
     /// The license information for the exposed API.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub license: Option<License>,
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// Contact information for the exposed API.
@@ -98,16 +105,19 @@ pub struct Info {
 /// See <https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#contactObject>.
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct Contact {
-    #[serde(skip_serializing_if = "str::is_empty")]
+    #[serde(default, skip_serializing_if = "str::is_empty")]
     pub name: Str,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<Url>,
 
     // TODO: Make sure the email is a valid email
-    #[serde(skip_serializing_if = "str::is_empty")]
+    #[serde(default, skip_serializing_if = "str::is_empty")]
     pub email: Str,
-    // TODO: Add "Specification Extensions" https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions
+
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// License information for the exposed API.
@@ -120,7 +130,9 @@ pub struct License {
     /// A URL to the license used for the API.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<Url>,
-    // TODO: Add "Specification Extensions" https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions}
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// An object representing a Server.
@@ -134,12 +146,15 @@ pub struct Server {
     /// in {brackets}.
     pub url: Str,
     /// An optional string describing the host designated by the URL. CommonMark syntax MAY be used for rich text representation.
-    #[serde(skip_serializing_if = "str::is_empty")]
+    #[serde(default, skip_serializing_if = "str::is_empty")]
     pub description: Str,
     /// A map between a variable name and its value. The value is used for substitution in
     /// the server's URL template.
-    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
     pub variables: IndexMap<Str, ServerVariable>,
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// An object representing a Server Variable for server URL template substitution.
@@ -152,14 +167,17 @@ pub struct ServerVariable {
     pub default: Str,
     /// An enumeration of string values to be used if the substitution options are from a limited
     /// set.
-    #[serde(rename = "enum", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, rename = "enum", skip_serializing_if = "Vec::is_empty")]
     pub substitutions_enum: Vec<Str>,
     /// An optional description for the server variable. [CommonMark] syntax MAY be used for rich
     /// text representation.
     ///
     /// [CommonMark]: https://spec.commonmark.org/
-    #[serde(skip_serializing_if = "str::is_empty")]
+    #[serde(default, skip_serializing_if = "str::is_empty")]
     pub description: Str,
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// Describes the operations available on a single path.
@@ -175,15 +193,15 @@ pub struct PathItem {
     /// [Path Item Object](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#pathItemObject).
     /// If there are conflicts between the referenced definition and this Path Item's definition,
     /// the behavior is undefined.
-    #[serde(skip_serializing_if = "str::is_empty", rename = "$ref")]
+    #[serde(default, skip_serializing_if = "str::is_empty", rename = "$ref")]
     pub reference: Str,
 
     /// An optional, string summary, intended to apply to all operations in this path.
-    #[serde(skip_serializing_if = "str::is_empty")]
+    #[serde(default, skip_serializing_if = "str::is_empty")]
     pub summary: Str,
     /// An optional, string description, intended to apply to all operations in this path.
     /// [CommonMark syntax](http://spec.commonmark.org/) MAY be used for rich text representation.
-    #[serde(skip_serializing_if = "str::is_empty")]
+    #[serde(default, skip_serializing_if = "str::is_empty")]
     pub description: Str,
 
     /// A definition of a GET operation on this path.
@@ -212,7 +230,7 @@ pub struct PathItem {
     pub trace: Option<Operation>,
 
     /// An alternative `server` array to service all operations in this path.
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub servers: Vec<Server>,
 
     /// A list of parameters that are applicable for all the operations described under this
@@ -226,9 +244,12 @@ pub struct PathItem {
     /// [Reference Object](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#referenceObject)
     /// to link to parameters that are defined at the
     /// [OpenAPI Object's components/parameters](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#componentsParameters).
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub parameters: Vec<ObjectOrReference<Parameter>>,
-    // TODO: Add "Specification Extensions" https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions}
+
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// Describes a single API operation on a path.
@@ -239,14 +260,14 @@ pub struct PathItem {
 pub struct Operation {
     /// A list of tags for API documentation control. Tags can be used for logical grouping of
     /// operations by resources or any other qualifier.
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<Str>,
     /// A short summary of what the operation does.
-    #[serde(skip_serializing_if = "str::is_empty")]
+    #[serde(default, skip_serializing_if = "str::is_empty")]
     pub summary: Str,
     /// A verbose explanation of the operation behavior.
     /// [CommonMark syntax](http://spec.commonmark.org/) MAY be used for rich text representation.
-    #[serde(skip_serializing_if = "str::is_empty")]
+    #[serde(default, skip_serializing_if = "str::is_empty")]
     pub description: Str,
     /// Additional external documentation for this operation.
     #[serde(skip_serializing_if = "Option::is_none", rename = "externalDocs")]
@@ -254,7 +275,7 @@ pub struct Operation {
     /// Unique string used to identify the operation. The id MUST be unique among all operations
     /// described in the API. Tools and libraries MAY use the operationId to uniquely identify an
     /// operation, therefore, it is RECOMMENDED to follow common programming naming conventions.
-    #[serde(skip_serializing_if = "str::is_empty", rename = "operationId")]
+    #[serde(default, skip_serializing_if = "str::is_empty", rename = "operationId")]
     pub operation_id: Str,
 
     /// A list of parameters that are applicable for this operation. If a parameter is already
@@ -269,7 +290,7 @@ pub struct Operation {
     /// [Reference Object](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#referenceObject)
     /// to link to parameters that are defined at the
     /// [OpenAPI Object's components/parameters](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#componentsParameters).
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub parameters: Vec<ObjectOrReference<Parameter>>,
 
     /// The request body applicable for this operation. The requestBody is only supported in HTTP methods where the HTTP 1.1 specification RFC7231 has explicitly defined semantics for request bodies. In other cases where the HTTP spec is vague, requestBody SHALL be ignored by consumers.
@@ -292,7 +313,7 @@ pub struct Operation {
     /// response for a successful operation call.
     ///
     /// See <https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#responsesObject>.
-    pub responses: IndexMap<Str, Response>,
+    pub responses: IndexMap<Str, ObjectOrReference<Response>>,
 
     /// A map of possible out-of band callbacks related to the parent operation. The key is
     /// a unique identifier for the Callback Object. Each value in the map is a
@@ -301,8 +322,8 @@ pub struct Operation {
     /// expected responses. The key value used to identify the callback object is
     /// an expression, evaluated at runtime, that identifies a URL to use for the
     /// callback operation.
-    #[serde(skip_serializing_if = "IndexMap::is_empty")]
-    pub callbacks: IndexMap<Str, Callback>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub callbacks: IndexMap<Str, ObjectOrReference<Callback>>,
 
     /// Declares this operation to be deprecated. Consumers SHOULD refrain from usage
     /// of the declared operation. Default value is `false`.
@@ -315,14 +336,18 @@ pub struct Operation {
     /// This definition overrides any declared top-level
     /// [`security`](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#oasSecurity).
     /// To remove a top-level security declaration, an empty array can be used.
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    pub security: Vec<SecurityRequirement>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub security: Option<Vec<SecurityRequirement>>,
 
     /// An alternative `server` array to service this operation. If an alternative `server`
     /// object is specified at the Path Item Object or Root level, it will be overridden by
     /// this value.
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub servers: Vec<Server>,
+
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// Describes a single operation parameter.
@@ -343,7 +368,7 @@ pub struct Parameter {
 
     /// A brief description of the parameter. This could contain examples of use. 
     /// [CommonMark syntax](https://spec.commonmark.org/) MAY be used for rich text representation.
-    #[serde(skip_serializing_if = "str::is_empty")]
+    #[serde(default, skip_serializing_if = "str::is_empty")]
     pub description: Str,
 
     /// Determines whether this parameter is mandatory.
@@ -391,9 +416,14 @@ pub struct Parameter {
     /// Example(s) of the parameter's potential value
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
     pub example: Option<ParameterExamples>,
+
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// The schema defining the type used for the parameter or a map containing the representations for the parameter.
+#[allow(clippy::large_enum_variant)]
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 #[serde(untagged)]
 pub enum ParameterRepresentation {
@@ -453,6 +483,8 @@ pub enum Location {
     Header,
     #[serde(rename = "path")]
     Path,
+    #[serde(rename = "cookie")]
+    Cookie,
     #[serde(rename = "formData")]
     FormData,
 }
@@ -483,21 +515,22 @@ mod component_ser_as_ref {
     const PATH_REF_PREFIX: &str = "#/components/schemas/"; 
 
     pub fn serialize<S: Serializer>(component: &Str, ser: S) -> Result<S::Ok, S::Error> {
-        ser.serialize_str(&(PATH_REF_PREFIX.to_string() + component))
+        ser.serialize_str(&(PATH_REF_PREFIX.to_string() + &json_pointer::escape(component)))
     }
 
     pub fn deserialize<'de, D: Deserializer<'de>>(deser: D) -> Result<Str, D::Error> {
         let s = String::deserialize(deser)?;
         if let Some(s) = s.strip_prefix(PATH_REF_PREFIX) {
-            Ok(Str::Owned(s.to_string()))
+            Ok(Str::Owned(json_pointer::unescape(s).into_owned()))
         } else {
             Err(de::Error::custom("not a component schema reference path"))
         }
     }
 }
 
-/// Either a reference to a component schema
+/// Either a reference to a component schema, a reference to a schema defined elsewhere,
 /// or an \[inline\] schema itself.
+#[allow(clippy::large_enum_variant)]
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 #[serde(untagged)]
 pub enum ComponentOrInlineSchema {
@@ -509,15 +542,25 @@ pub enum ComponentOrInlineSchema {
         #[serde(rename = "$ref", serialize_with = "component_ser_as_ref::serialize", deserialize_with = "component_ser_as_ref::deserialize")]
         name: Str
     },
+    /// Reference to a schema which is not a component of this document, such as
+    /// `common.yaml#/components/schemas/Error`.
+    ///
+    /// Use [`bundle`](crate::v3_0::bundle) to load such references into the components of a
+    /// single document.
+    ExtRef {
+        #[serde(rename = "$ref")]
+        reference: Str,
+    },
     Inline(Schema),
-    // Add "ExtRef" variant if support for externally referenced schemas (neither inline nor components) is needed
 }
 impl ComponentOrInlineSchema {
     /// Unwrap inlined
+    ///
+    /// Use [`Components::resolve_schema`] to also look up component schemas.
     pub fn unwrap(&self) -> Option<&Schema> {
         match self {
             Self::Inline(s) => Some(s),
-            Self::Component{..} => None,
+            Self::Component{..} | Self::ExtRef{..} => None,
         }
     }
 }
@@ -567,16 +610,16 @@ pub struct Schema {
     // - format - See [Data Type Formats](#dataTypeFormat) for further details. While relying on JSON Schema's defined formats, the OAS offers a few additional predefined formats.
     // - default - The default value represents what would be assumed by the consumer of the input as the value of the schema if one is not provided. Unlike JSON Schema, the value MUST conform to the defined type for the Schema Object defined at the same level. For example, if `type` is `string`, then `default` can be `"foo"` but cannot be `1`.
 
-    #[serde(skip_serializing_if = "str::is_empty")]
+    #[serde(default, skip_serializing_if = "str::is_empty")]
     pub description: Str,
 
-    #[serde(skip_serializing_if = "str::is_empty")]
+    #[serde(default, skip_serializing_if = "str::is_empty")]
     pub format: Str,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub items: Option<Box<ComponentOrInlineSchema>>,
 
-    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
     pub properties: IndexMap<Str, ComponentOrInlineSchema>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -606,7 +649,7 @@ pub struct Schema {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub example: Option<serde_json::value::Value>,
 
-    #[serde(skip_serializing_if = "str::is_empty")]
+    #[serde(default, skip_serializing_if = "str::is_empty")]
     pub title: Str,
 
     /// The default value represents what would be assumed by the consumer of the input as the value
@@ -618,17 +661,17 @@ pub struct Schema {
 
     /// Inline or referenced schema MUST be of a [Schema Object](#schemaObject) and not a standard
     /// JSON Schema.
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub all_of: Vec<ComponentOrInlineSchema>,
 
     /// Inline or referenced schema MUST be of a [Schema Object](#schemaObject) and not a standard
     /// JSON Schema.
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub one_of: Vec<ComponentOrInlineSchema>,
 
     /// Inline or referenced schema MUST be of a [Schema Object](#schemaObject) and not a standard
     /// JSON Schema.
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub any_of: Vec<ComponentOrInlineSchema>,
 
 
@@ -639,7 +682,7 @@ pub struct Schema {
     
     #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
     pub schema_type: Option<Type>,
-    #[serde(rename = "enum", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, rename = "enum", skip_serializing_if = "Vec::is_empty")]
     pub enum_values: Vec<Str>,
     #[serde(rename = "const", skip_serializing_if = "Option::is_none")]
     pub const_value: Option<serde_json::Value>,
@@ -663,7 +706,7 @@ pub struct Schema {
     pub max_length: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub min_length: Option<usize>,
-    #[serde(skip_serializing_if = "str::is_empty")]
+    #[serde(default, skip_serializing_if = "str::is_empty")]
     pub pattern: Str,
 
     // Arrays
@@ -681,10 +724,14 @@ pub struct Schema {
     pub max_properties: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub min_properties: Option<usize>,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub required: Vec<Str>,
-    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
     pub dependent_required: IndexMap<Str, Vec<Str>>,
+
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// Describes a single response from an API Operation, including design-time, static `links`
@@ -701,22 +748,25 @@ pub struct Response {
     /// [RFC7230](https://tools.ietf.org/html/rfc7230#page-22) states header names are case
     /// insensitive. If a response header is defined with the name `"Content-Type"`, it SHALL
     /// be ignored.
-    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
     pub headers: IndexMap<Str, ObjectOrReference<Header>>,
 
     /// A map containing descriptions of potential response payloads. The key is a media type
     /// or [media type range](https://tools.ietf.org/html/rfc7231#appendix-D) and the value
     /// describes it. For responses that match multiple keys, only the most specific key is
     /// applicable. e.g. text/plain overrides text/*
-    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
     pub content: IndexMap<Str, MediaType>,
 
     /// A map of operations links that can be followed from the response. The key of the map
     /// is a short name for the link, following the naming constraints of the names for
     /// [Component Objects](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#componentsObject).
-    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
     pub links: IndexMap<Str, ObjectOrReference<Link>>,
-    // TODO: Add "Specification Extensions" https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions}
+
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// The Header Object follows the structure of the
@@ -740,11 +790,11 @@ pub struct Header {
     /// string, number, boolean, integer, array, file ( only for formData )
     #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
     pub param_type: Option<Type>,
-    #[serde(skip_serializing_if = "str::is_empty")]
+    #[serde(default, skip_serializing_if = "str::is_empty")]
     pub format: Str,
     /// A brief description of the parameter. This could contain examples
     /// of use.  GitHub Flavored Markdown is allowed.
-    #[serde(skip_serializing_if = "str::is_empty")]
+    #[serde(default, skip_serializing_if = "str::is_empty")]
     pub description: Str,
     // collectionFormat: ???
     // default: ???
@@ -760,6 +810,9 @@ pub struct Header {
     // enum ??
     // multipleOf ??
     // allowEmptyValue ( for query / body params )
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// Describes a single request body.
@@ -769,7 +822,7 @@ pub struct Header {
 pub struct RequestBody {
     /// A brief description of the request body. This could contain examples of use.
     /// [CommonMark syntax](http://spec.commonmark.org/) MAY be used for rich text representation.
-    #[serde(skip_serializing_if = "str::is_empty")]
+    #[serde(default, skip_serializing_if = "str::is_empty")]
     pub description: Str,
 
     /// The content of the request body. The key is a media type or
@@ -780,6 +833,10 @@ pub struct RequestBody {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub required: Option<bool>,
+
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// The Link object represents a possible design-time link for a response.
@@ -800,7 +857,7 @@ pub struct RequestBody {
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub struct Link {
     #[serde(flatten)]
-    operation: LinkOperation,
+    pub(crate) operation: LinkOperation,
 
     /// A map representing parameters to pass to an operation as specified with `operationId`
     /// or identified via `operationRef`. The key is the parameter name to be used, whereas
@@ -809,24 +866,27 @@ pub struct Link {
     /// [parameter location](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#parameterIn)
     /// `[{in}.]{name}` for operations that use the same parameter name in different
     /// locations (e.g. path.id).
-    #[serde(skip_serializing_if = "IndexMap::is_empty")]
-    parameters: IndexMap<Str, RuntimeExpressionOrValue>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub(crate) parameters: IndexMap<Str, RuntimeExpressionOrValue>,
 
     /// A literal value or
     /// [{expression}](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#runtimeExpression)
     /// to use as a request body when calling the target operation.
     #[serde(rename = "requestBody", skip_serializing_if = "Option::is_none")]
-    request_body: Option<RuntimeExpressionOrValue>,
+    pub(crate) request_body: Option<RuntimeExpressionOrValue>,
 
     /// A description of the link. [CommonMark syntax](http://spec.commonmark.org/) MAY be
     /// used for rich text representation.
-    #[serde(skip_serializing_if = "str::is_empty")]
-    description: Str,
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub(crate) description: Str,
 
     /// A server object to be used by the target operation.
     #[serde(skip_serializing_if = "Option::is_none")]
-    server: Option<Server>,
-    // TODO: Add "Specification Extensions" https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtension
+    pub(crate) server: Option<Server>,
+
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// Runtime expression or literal value. Used for Link `parameters` and `request_body`.
@@ -876,8 +936,12 @@ pub struct MediaType {
     /// property name, MUST exist in the schema as a property. The encoding object SHALL
     /// only apply to `requestBody` objects when the media type is `multipart`
     /// or `application/x-www-form-urlencoded`.
-    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
     pub encoding: IndexMap<Str, Encoding>,
+
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
@@ -906,14 +970,14 @@ pub struct Encoding {
     /// for `array` – the default is defined based on the inner type. The value can be a
     /// specific media type (e.g. `application/json`), a wildcard media type
     /// (e.g. `image/*`), or a comma-separated list of the two types.
-    #[serde(skip_serializing_if = "str::is_empty", rename = "contentType")]
+    #[serde(default, skip_serializing_if = "str::is_empty", rename = "contentType")]
     pub content_type: Str,
 
     /// A map allowing additional information to be provided as headers, for example
     /// `Content-Disposition`.  `Content-Type` is described separately and SHALL be
     /// ignored in this section. This property SHALL be ignored if the request body
     /// media type is not a `multipart`.
-    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
     pub headers: IndexMap<Str, ObjectOrReference<Header>>,
 
     /// Describes how a specific property value will be serialized depending on its type.
@@ -923,7 +987,7 @@ pub struct Encoding {
     /// property. The behavior follows the same values as `query` parameters, including
     /// default values. This property SHALL be ignored if the request body media type
     /// is not `application/x-www-form-urlencoded`.
-    #[serde(skip_serializing_if = "str::is_empty")]
+    #[serde(default, skip_serializing_if = "str::is_empty")]
     pub style: Str,
 
     /// When this is true, property values of type `array` or `object` generate
@@ -943,24 +1007,30 @@ pub struct Encoding {
     /// not `application/x-www-form-urlencoded`.
     #[serde(skip_serializing_if = "Option::is_none", rename = "allowReserved")]
     pub allow_reserved: Option<bool>,
+
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// See <https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#exampleObject>.
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct Example {
     /// Short description for the example.
-    #[serde(skip_serializing_if = "str::is_empty")]
+    #[serde(default, skip_serializing_if = "str::is_empty")]
     pub summary: Str,
 
     /// Long description for the example.
     /// [CommonMark syntax](http://spec.commonmark.org/) MAY be used for rich text representation.
-    #[serde(skip_serializing_if = "str::is_empty")]
+    #[serde(default, skip_serializing_if = "str::is_empty")]
     pub description: Str,
     /// Embedded literal example or a URL that points to the literal example.
     #[serde(skip_serializing_if = "Option::is_none", flatten)]
     pub value: Option<ExampleValue>,
 
-    // TODO: Add "Specification Extensions" https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions}
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// Embedded literal example or a URL that points to the literal example.
@@ -989,6 +1059,7 @@ pub enum ExampleValue {
 /// [OpenID Connect Discovery](https://tools.ietf.org/html/draft-ietf-oauth-discovery-06).
 ///
 /// See <https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#securitySchemeObject>.
+#[allow(clippy::large_enum_variant)]
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 #[serde(tag = "type")]
 pub enum SecurityScheme {
@@ -1001,7 +1072,7 @@ pub enum SecurityScheme {
     #[serde(rename = "http")]
     Http {
         scheme: Str,
-        #[serde(rename = "bearerFormat")]
+        #[serde(default, rename = "bearerFormat", skip_serializing_if = "str::is_empty")]
         bearer_format: Str,
     },
     #[serde(rename = "oauth2")]
@@ -1027,6 +1098,9 @@ pub struct Flows {
     pub client_credentials: Option<ClientCredentialsFlow>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub authorization_code: Option<AuthorizationCodeFlow>,
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// Configuration details for a implicit OAuth Flow
@@ -1039,6 +1113,9 @@ pub struct ImplicitFlow {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub refresh_url: Option<Url>,
     pub scopes: IndexMap<Str, Str>,
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// Configuration details for a password OAuth Flow
@@ -1047,10 +1124,13 @@ pub struct ImplicitFlow {
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct PasswordFlow {
-    token_url: Url,
+    pub token_url: Url,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub refresh_url: Option<Url>,
     pub scopes: IndexMap<Str, Str>,
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// Configuration details for a client credentials OAuth Flow
@@ -1059,10 +1139,13 @@ pub struct PasswordFlow {
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct ClientCredentialsFlow {
-    token_url: Url,
+    pub token_url: Url,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub refresh_url: Option<Url>,
     pub scopes: IndexMap<Str, Str>,
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// Configuration details for a authorization code OAuth Flow
@@ -1072,13 +1155,15 @@ pub struct ClientCredentialsFlow {
 #[serde(rename_all = "camelCase")]
 pub struct AuthorizationCodeFlow {
     pub authorization_url: Url,
-    token_url: Url,
+    pub token_url: Url,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub refresh_url: Option<Url>,
     pub scopes: IndexMap<Str, Str>,
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
-// TODO: Implement
 /// A map of possible out-of band callbacks related to the parent operation. Each value in
 /// the map is a Path Item Object that describes a set of requests that may be initiated by
 /// the API provider and the expected responses. The key value used to identify the callback
@@ -1086,11 +1171,7 @@ pub struct AuthorizationCodeFlow {
 /// callback operation.
 ///
 /// See <https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#callbackObject>.
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
-pub struct Callback(
-    /// A Path Item Object used to define a callback request and expected responses.
-    serde_json::Value, // TODO: Add "Specification Extensions" https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions}
-);
+pub type Callback = IndexMap<Str, PathItem>;
 
 /// # [Security Requirement Object](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.1.0.md#securityRequirementObject)
 /// Lists the required security schemes to execute this operation.
@@ -1114,13 +1195,16 @@ pub struct Tag {
 
     /// A short description for the tag.
     /// [CommonMark syntax](http://spec.commonmark.org/) MAY be used for rich text representation.
-    #[serde(skip_serializing_if = "str::is_empty")]
+    #[serde(default, skip_serializing_if = "str::is_empty")]
     pub description: Str,
-    // /// Additional external documentation for this tag.
-    // #[serde(skip_serializing_if = "Option::is_none")]
-    // pub external_docs: Option<Vec<ExternalDoc>>,
 
-    // TODO: Add "Specification Extensions" https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions}
+    /// Additional external documentation for this tag.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "externalDocs")]
+    pub external_docs: Option<ExternalDoc>,
+
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// Allows referencing an external resource for extended documentation.
@@ -1133,9 +1217,12 @@ pub struct ExternalDoc {
 
     /// A short description of the target documentation.
     /// [CommonMark syntax](http://spec.commonmark.org/) MAY be used for rich text representation.
-    #[serde(skip_serializing_if = "str::is_empty")]
+    #[serde(default, skip_serializing_if = "str::is_empty")]
     pub description: Str,
-    // TODO: Add "Specification Extensions" https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions}
+
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 #[cfg(test)]
@@ -1164,7 +1251,7 @@ mod tests {
             }
           }
         }"#;
-        let obj: SecurityScheme = serde_json::from_str(&IMPLICIT_OAUTH2_SAMPLE).unwrap();
+        let obj: SecurityScheme = serde_json::from_str(IMPLICIT_OAUTH2_SAMPLE).unwrap();
         match obj {
             SecurityScheme::OAuth2 { flows } => {
                 assert!(flows.implicit.is_some());
@@ -1189,7 +1276,31 @@ mod tests {
                 assert!(implicit.scopes.contains_key("write:pets"));
                 assert!(implicit.scopes.contains_key("read:pets"));
             }
-            _ => assert!(false, "wrong security scheme type"),
+            _ => panic!("wrong security scheme type"),
         }
     }
+
+    #[test]
+    fn test_extensions_round_trip() {
+        const INFO: &str = r#"{"title":"pets","description":"","version":"1.0","x-logo":{"url":"logo.png"},"x-audience":"internal"}"#;
+        let info: Info = serde_json::from_str(INFO).unwrap();
+        assert_eq!(info.extensions.len(), 2);
+        assert_eq!(
+            info.extensions.get::<String>("x-audience").unwrap().as_deref(),
+            Some("internal")
+        );
+        assert_eq!(
+            serde_json::to_string(&info).unwrap(),
+            r#"{"title":"pets","version":"1.0","x-logo":{"url":"logo.png"},"x-audience":"internal"}"#
+        );
+
+        const TAG: &str = r#"{"name":"pets","description":"","x-displayName":"Pets","x-internal":true}"#;
+        let mut tag: Tag = serde_json::from_str(TAG).unwrap();
+        assert_eq!(tag.extensions.get::<bool>("x-internal").unwrap(), Some(true));
+        tag.extensions.insert("x-order", 2).unwrap();
+        assert_eq!(
+            serde_json::to_string(&tag).unwrap(),
+            r#"{"name":"pets","x-displayName":"Pets","x-internal":true,"x-order":2}"#
+        );
+    }
 }
@@ -0,0 +1,654 @@
+//! Rust server stubs for the operations of a specification.
+
+use crate::v3_0::{
+    diff::{operations, METHODS},
+    endpoint::{summary, Endpoint},
+    models::{docs, field_name, type_name, unique, Models},
+    operation::Body,
+    Location, Operation, PathItem, Spec,
+};
+use indexmap::IndexMap;
+use std::collections::HashSet;
+
+impl Spec {
+    /// Generates Rust source of a `warp` server, with a trait per tag whose implementations
+    /// serve the operations, along with the [models](Spec::generate_models) of the
+    /// specification.
+    ///
+    /// Operations are grouped by their first tag, in traits named after it such as `PetApi`, or
+    /// in `DefaultApi` if they have no tags. Their methods take the path, query, header and
+    /// cookie parameters followed by the request body, and return an enum with a variant per
+    /// response status code, which implements `warp::Reply`.
+    ///
+    /// For each trait, a function such as `pet_api` returns the filter serving its operations
+    /// with an implementation. The filter of each operation matches the methods of requests and
+    /// the rest of their paths against its path template, so that it can be mounted under a
+    /// prefix, and [reads and validates](Spec::read_request) their parameters and bodies
+    /// against the specification, which is embedded in the server. Invalid requests are
+    /// answered with a `400 Bad Request` listing the errors, and bodies longer than the
+    /// `body_limit` of the trait, 1 MiB unless overridden, with a `413 Payload Too Large`,
+    /// without calling the trait.
+    ///
+    /// The server depends on `warp` 0.3, `futures-util` 0.3, `serde`, `serde_json` and this
+    /// crate with its `validate` feature.
+    ///
+    /// Requires the `codegen` feature.
+    pub fn generate_server(&self) -> String {
+        // Operations by the name of their trait, along with their tag.
+        let mut tags: IndexMap<String, (Option<&str>, Operations)> = IndexMap::new();
+        for (path, item) in &self.paths {
+            for (method, operation) in METHODS.iter().zip(operations(item)) {
+                if let Some(operation) = operation {
+                    let tag = operation.tags.first().map(|tag| &**tag);
+                    let name = format!("{}Api", type_name(tag.unwrap_or("default")));
+                    tags.entry(name)
+                        .or_insert_with(|| (tag, Vec::new()))
+                        .1
+                        .push((&**path, item, *method, operation));
+                }
+            }
+        }
+
+        let mut reserved: Vec<&str> = tags.keys().map(|name| &**name).collect();
+        reserved.extend(&["Arc", "Filter", "Reply", "Request"]);
+        let mut generator = Generator {
+            models: Models::new(self, &reserved),
+            names: HELPERS_FUNCTIONS
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+        };
+        generator.models.components();
+        let mut apis = String::new();
+        for (api, (tag, operations)) in &tags {
+            apis.push_str(&generator.api(api, *tag, operations));
+        }
+
+        let mut server = format!(
+            "// Server of `{}` {}, generated from its specification.\n\
+             \n\
+             use serde::{{Deserialize, Serialize}};\n\
+             use std::sync::Arc;\n\
+             use warp::{{Filter, Reply}};\n",
+            self.info.title, self.info.version
+        );
+        server.push_str(&generator.models.definitions());
+        server.push_str(&apis);
+        server.push_str(HELPERS);
+        let json = serde_json::to_string_pretty(self).unwrap_or_default();
+        let hashes = "#".repeat(
+            (1..)
+                .find(|count| !json.contains(&format!("\"{}", "#".repeat(*count))))
+                .unwrap_or(1),
+        );
+        server.push_str(&format!(
+            "\n/// The specification of the server.\nconst SPECIFICATION: &str = r{}\"{}\"{};\n",
+            hashes, json, hashes
+        ));
+        server
+    }
+}
+
+/// Operations, with their paths, path items and methods.
+type Operations<'a> = Vec<(&'a str, &'a PathItem, &'a str, &'a Operation)>;
+
+/// Functions and trait methods of the generated servers, which operations must not be named
+/// after.
+const HELPERS_FUNCTIONS: [&str; 8] = [
+    "body_limit",
+    "invalid",
+    "json_body",
+    "parameter",
+    "read_body",
+    "reply",
+    "route",
+    "spec",
+];
+
+/// Local variables of the generated handlers, which parameters must not shadow.
+const LOCALS: [&str; 5] = ["body", "json_body", "parameter", "request", "service"];
+
+/// The request type and functions of the generated servers.
+const HELPERS: &str = r#"
+/// The parameters and body of a request to an operation, read and validated.
+struct Request {
+    values: rweb_openapi::v3_0::RequestValues,
+    body: Vec<u8>,
+}
+
+/// The filter serving the operation at `template` with `method` by calling `handler` with its
+/// requests, once they are read and validated.
+///
+/// `template` is matched against the rest of the path of requests. Only when it matches is the
+/// operation of the specification for the path looked up, in case a more specific template
+/// matches it as well.
+fn route<H, F>(
+    method: &'static str,
+    template: &'static str,
+    body_limit: u64,
+    handler: H,
+) -> impl Filter<Extract = (warp::reply::Response,), Error = warp::Rejection> + Clone
+where
+    H: Fn(Request) -> F + Clone + Send + Sync + 'static,
+    F: std::future::Future<Output = Result<warp::reply::Response, warp::reply::Response>> + Send,
+{
+    let parsed = rweb_openapi::v3_0::PathTemplate::parse(template);
+    warp::method()
+        .and(warp::path::tail())
+        .and_then(move |request_method: warp::http::Method, tail: warp::path::Tail| {
+            let path = format!("/{}", tail.as_str());
+            let matches = request_method.as_str().eq_ignore_ascii_case(method)
+                && parsed.matches(&path).is_some();
+            async move {
+                if !matches {
+                    return Err(warp::reject::not_found());
+                }
+                match spec().find_operation(method, &path) {
+                    Some(found) if found.path == template => Ok((found, path)),
+                    _ => Err(warp::reject::not_found()),
+                }
+            }
+        })
+        .untuple_one()
+        .and(warp::query::raw().or(warp::any().map(String::new)).unify())
+        .and(warp::header::headers_cloned())
+        .and(warp::body::stream())
+        .and_then(
+            move |found: rweb_openapi::v3_0::OperationMatch<'static>,
+                  path: String,
+                  query: String,
+                  headers: warp::http::HeaderMap,
+                  body| {
+                let handler = handler.clone();
+                async move {
+                    let body = match read_body(body, body_limit).await {
+                        Ok(body) => body,
+                        Err(response) => return Ok(response),
+                    };
+                    let parts = rweb_openapi::v3_0::RequestParts {
+                        method: found.method.to_ascii_uppercase(),
+                        path,
+                        query,
+                        headers: headers
+                            .iter()
+                            .filter_map(|(name, value)| {
+                                Some((name.to_string(), value.to_str().ok()?.to_string()))
+                            })
+                            .collect(),
+                        body,
+                    };
+                    let response = match spec().read_request(&found, &parts) {
+                        Ok(values) => {
+                            let request = Request {
+                                values,
+                                body: parts.body,
+                            };
+                            handler(request).await.unwrap_or_else(|response| response)
+                        }
+                        Err(errors) => invalid(errors.iter().map(ToString::to_string).collect()),
+                    };
+                    Ok::<_, warp::Rejection>(response)
+                }
+            },
+        )
+}
+
+/// Reads `body`, unless it is longer than `limit` bytes.
+#[allow(clippy::result_large_err)]
+async fn read_body<B: warp::Buf>(
+    body: impl futures_util::Stream<Item = Result<B, warp::Error>>,
+    limit: u64,
+) -> Result<Vec<u8>, warp::reply::Response> {
+    let mut body = std::pin::pin!(body);
+    let mut read = Vec::new();
+    while let Some(chunk) = futures_util::StreamExt::next(&mut body).await {
+        let mut chunk = chunk.map_err(|error| invalid(vec![format!("request body: {}", error)]))?;
+        if (read.len() + chunk.remaining()) as u64 > limit {
+            return Err(reply(413, "", Vec::new()));
+        }
+        while chunk.has_remaining() {
+            let bytes = chunk.chunk();
+            read.extend_from_slice(bytes);
+            let length = bytes.len();
+            chunk.advance(length);
+        }
+    }
+    Ok(read)
+}
+
+/// Deserializes the parameter `name` from the values of its location.
+#[allow(clippy::result_large_err)]
+fn parameter<T: serde::de::DeserializeOwned>(
+    values: &serde_json::Map<String, serde_json::Value>,
+    name: &str,
+) -> Result<T, warp::reply::Response> {
+    let value = values.get(name).cloned().unwrap_or_default();
+    serde_json::from_value(value)
+        .map_err(|error| invalid(vec![format!("parameter `{}`: {}", name, error)]))
+}
+
+/// Deserializes a JSON or form body.
+#[allow(clippy::result_large_err)]
+fn json_body<T: serde::de::DeserializeOwned>(
+    body: Option<serde_json::Value>,
+) -> Result<T, warp::reply::Response> {
+    serde_json::from_value(body.unwrap_or_default())
+        .map_err(|error| invalid(vec![format!("request body: {}", error)]))
+}
+
+fn reply(status: u16, content_type: &'static str, body: Vec<u8>) -> warp::reply::Response {
+    let mut response = warp::reply::Response::new(body.into());
+    *response.status_mut() = warp::http::StatusCode::from_u16(status)
+        .unwrap_or(warp::http::StatusCode::INTERNAL_SERVER_ERROR);
+    if !content_type.is_empty() {
+        response.headers_mut().insert(
+            warp::http::header::CONTENT_TYPE,
+            warp::http::HeaderValue::from_static(content_type),
+        );
+    }
+    response
+}
+
+/// The response to an invalid request, listing its `errors`.
+fn invalid(errors: Vec<String>) -> warp::reply::Response {
+    let body = serde_json::json!({ "errors": errors });
+    reply(400, "application/json", serde_json::to_vec(&body).unwrap_or_default())
+}
+
+/// The specification of the server, parsed once.
+fn spec() -> &'static rweb_openapi::v3_0::Spec {
+    static SPEC: std::sync::OnceLock<rweb_openapi::v3_0::Spec> = std::sync::OnceLock::new();
+    SPEC.get_or_init(|| {
+        serde_json::from_str(SPECIFICATION).expect("the specification of the server is valid")
+    })
+}
+"#;
+
+/// The type of the filters of the generated servers.
+const FILTER: &str =
+    "impl Filter<Extract = (warp::reply::Response,), Error = warp::Rejection> + Clone";
+
+/// Generator of the traits and filters of a server.
+struct Generator<'a> {
+    models: Models<'a>,
+    /// Names of the functions generated so far, and of the helpers.
+    names: HashSet<String>,
+}
+
+impl<'a> Generator<'a> {
+    /// Returns the code of the trait `api` for the operations of `tag`, of the function
+    /// returning the filter serving them, and of the filters of the operations.
+    fn api(&mut self, api: &str, tag: Option<&str>, operations: &Operations<'a>) -> String {
+        let spec = self.models.spec();
+        let description = tag.map_or("Operations without tags.".to_string(), |tag| {
+            match spec.tags.iter().find(|known| known.name == tag) {
+                Some(known) if !known.description.trim().is_empty() => {
+                    known.description.to_string()
+                }
+                _ => format!("Operations tagged `{}`.", tag),
+            }
+        });
+        let mut methods = String::new();
+        let mut filters = String::new();
+        let mut names = Vec::new();
+        for (path, item, method, operation) in operations {
+            let endpoint = Endpoint::new(
+                &mut self.models,
+                &mut self.names,
+                &LOCALS,
+                (path, item, method),
+                operation,
+            );
+            methods.push_str(&self.method(api, &endpoint, operation));
+            filters.push_str(&filter(api, &endpoint, operation, path, method));
+            names.push(endpoint.name);
+        }
+        methods.push_str(
+            "\n    /// The maximum length of the bodies of requests, in bytes. Longer requests are \
+             answered\n    \
+             /// with a `413 Payload Too Large`, without calling the trait.\n    \
+             fn body_limit(&self) -> u64 {\n        \
+                 1024 * 1024\n    \
+             }\n",
+        );
+        let function = unique(&mut self.names, &field_name(api));
+
+        let mut code = format!(
+            "\n{}pub trait {}: Send + Sync + 'static {{{}}}\n",
+            docs(&description, ""),
+            api,
+            methods
+        );
+        code.push_str(&format!(
+            "\n/// Serves the operations of [`{}`] with `service`.\n\
+             pub fn {}<S: {}>(\n    service: Arc<S>,\n) -> {} {{\n",
+            api, function, api, FILTER
+        ));
+        let last = names.len() - 1;
+        for (index, name) in names.iter().enumerate() {
+            let service = if index == last {
+                "service"
+            } else {
+                "service.clone()"
+            };
+            match index {
+                0 => code.push_str(&format!("    {}({})\n", name, service)),
+                _ => code.push_str(&format!(
+                    "        .or({}({}))\n        .unify()\n",
+                    name, service
+                )),
+            }
+        }
+        code.push_str("}\n");
+        code.push_str(&filters);
+        code
+    }
+
+    /// Returns the code of the method of `endpoint` in the trait `api`, and defines its
+    /// response enum.
+    fn method(&mut self, api: &str, endpoint: &Endpoint, operation: &Operation) -> String {
+        let mut arguments: Vec<String> = endpoint
+            .arguments
+            .iter()
+            .map(|argument| format!("{}: {}", argument.name, argument.declared_type()))
+            .collect();
+        if let Some(body) = &endpoint.body {
+            if body.required {
+                arguments.push(format!("body: {}", body.rust_type));
+            } else {
+                arguments.push(format!("body: Option<{}>", body.rust_type));
+            }
+        }
+        let output = format!(
+            ") -> impl std::future::Future<Output = {}> + Send;\n",
+            endpoint.response
+        );
+
+        let mut code = format!("\n{}", docs(&summary(operation), "    "));
+        if operation.deprecated == Some(true) {
+            code.push_str("    #[deprecated]\n");
+        }
+        let signature = format!(
+            "    fn {}(&self{}{}",
+            endpoint.name,
+            arguments
+                .iter()
+                .map(|argument| format!(", {}", argument))
+                .collect::<String>(),
+            output
+        );
+        if signature.len() <= 101 {
+            code.push_str(&signature);
+        } else {
+            // As rustfmt breaks long signatures.
+            code.push_str(&format!("    fn {}(\n        &self,\n", endpoint.name));
+            for argument in &arguments {
+                code.push_str(&format!("        {},\n", argument));
+            }
+            code.push_str(&format!("    {}", output));
+        }
+
+        let mut variants = String::new();
+        let mut arms = String::new();
+        for variant in &endpoint.variants {
+            let fields = variant.fields();
+            variants.push_str(&docs(variant.description, "    "));
+            let (pattern, status) = match (variant.is_exact(), &variant.content) {
+                (true, None) => (String::new(), variant.status),
+                (true, Some(_)) => ("(body)".to_string(), variant.status),
+                (false, None) => ("(status)".to_string(), "status"),
+                (false, Some(_)) => ("(status, body)".to_string(), "status"),
+            };
+            if fields.is_empty() {
+                variants.push_str(&format!("    {},\n", variant.name));
+            } else {
+                variants.push_str(&format!("    {}({}),\n", variant.name, fields.join(", ")));
+            }
+            let body = match &variant.content {
+                None => "Vec::new()",
+                Some((Body::Json, _)) => "serde_json::to_vec(&body).unwrap_or_default()",
+                Some((Body::Text(_), _)) => "body.into_bytes()",
+                Some((Body::Form, _)) | Some((Body::Bytes(_), _)) => "body",
+            };
+            let arm = format!(
+                "            {}::{}{} => ",
+                endpoint.response, variant.name, pattern
+            );
+            let value = format!("reply({}, {:?}, {})", status, variant.media_type, body);
+            if arm.len() + value.len() < 100 {
+                arms.push_str(&format!("{}{},\n", arm, value));
+            } else {
+                // As rustfmt breaks long arms.
+                arms.push_str(&format!(
+                    "{}{{\n                {}\n            }}\n",
+                    arm, value
+                ));
+            }
+        }
+        let definition = format!(
+            "/// Response of [`{}::{}`].\n\
+             #[derive(Debug)]\n\
+             pub enum {} {{\n{}}}\n\
+             \n\
+             impl warp::Reply for {} {{\n    \
+                 fn into_response(self) -> warp::reply::Response {{\n        \
+                     match self {{\n{}        }}\n    \
+                 }}\n\
+             }}\n",
+            api, endpoint.name, endpoint.response, variants, endpoint.response, arms
+        );
+        self.models.insert(endpoint.response.clone(), definition);
+        code
+    }
+}
+
+/// Returns the code of the function returning the filter of `endpoint`, which serves
+/// `operation`.
+fn filter(
+    api: &str,
+    endpoint: &Endpoint,
+    operation: &Operation,
+    path: &str,
+    method: &str,
+) -> String {
+    let request = if endpoint.arguments.is_empty() && endpoint.body.is_none() {
+        "_request"
+    } else {
+        "request"
+    };
+    let mut code = format!(
+        "\n/// Serves [`{}::{}`] with `service`.\n",
+        api, endpoint.name
+    );
+    if operation.deprecated == Some(true) {
+        // The filter calls the deprecated method.
+        code.push_str("#[allow(deprecated)]\n");
+    }
+    code.push_str(&format!(
+        "pub fn {}<S: {}>(\n    service: Arc<S>,\n) -> {} {{\n    \
+             let body_limit = service.body_limit();\n    \
+             route({:?}, {:?}, body_limit, move |{}: Request| {{\n        \
+                 let service = service.clone();\n        \
+                 async move {{\n",
+        endpoint.name, api, FILTER, method, path, request
+    ));
+    let mut values = Vec::new();
+    for argument in &endpoint.arguments {
+        let location = match argument.parameter.location {
+            Location::Path => "path",
+            Location::Query => "query",
+            Location::Header => "header",
+            Location::Cookie => "cookie",
+            // Not a location of parameters in OpenAPI 3, so never read.
+            Location::FormData => {
+                values.push("None".to_string());
+                continue;
+            }
+        };
+        code.push_str(&format!(
+            "            let {} = parameter(&request.values.{}, {:?})?;\n",
+            argument.name, location, argument.parameter.name
+        ));
+        values.push(argument.name.clone());
+    }
+    if let Some(body) = &endpoint.body {
+        let value = match (&body.kind, body.required) {
+            (Body::Json, _) | (Body::Form, _) => "json_body(request.values.body)?",
+            (Body::Text(_), true) => "String::from_utf8_lossy(&request.body).into_owned()",
+            (Body::Text(_), false) => {
+                "(!request.body.is_empty())\n                \
+                 .then(|| String::from_utf8_lossy(&request.body).into_owned())"
+            }
+            (Body::Bytes(_), true) => "request.body",
+            (Body::Bytes(_), false) => "(!request.body.is_empty()).then_some(request.body)",
+        };
+        code.push_str(&format!("            let body = {};\n", value));
+        values.push("body".to_string());
+    }
+    code.push_str(&format!(
+        "            Ok(service.{}({}).await.into_response())\n        \
+             }}\n    \
+         }})\n\
+         }}\n",
+        endpoint.name,
+        values.join(", ")
+    ));
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v3_0::models::{check_generated, GENERATED_SPEC};
+
+    const SPEC: &str = r##"{
+      "openapi": "3.0.3",
+      "info": {"title": "Pets", "version": "1.0.0"},
+      "tags": [{"name": "pets", "description": "Everything about pets."}],
+      "paths": {
+        "/pets/{petId}": {
+          "parameters": [
+            {
+              "name": "petId",
+              "in": "path",
+              "required": true,
+              "schema": {"type": "integer", "format": "int64"}
+            }
+          ],
+          "get": {
+            "operationId": "getPet",
+            "tags": ["pets"],
+            "summary": "Returns a pet.",
+            "parameters": [
+              {
+                "name": "fields",
+                "in": "query",
+                "schema": {"type": "array", "items": {"type": "string"}}
+              },
+              {"name": "X-Request-Id", "in": "header", "required": true, "schema": {}}
+            ],
+            "responses": {
+              "200": {
+                "description": "The pet.",
+                "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}}
+              },
+              "404": {"description": "No such pet."},
+              "5XX": {
+                "description": "Server error.",
+                "content": {"text/plain": {"schema": {"type": "string"}}}
+              }
+            }
+          },
+          "put": {
+            "operationId": "updatePet",
+            "tags": ["pets", "admin"],
+            "requestBody": {
+              "required": true,
+              "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}}
+            },
+            "responses": {"204": {"description": "Updated."}}
+          }
+        },
+        "/health": {
+          "get": {
+            "responses": {
+              "default": {"description": "The health.", "content": {"text/plain": {}}}
+            }
+          }
+        }
+      },
+      "components": {
+        "schemas": {
+          "Pet": {
+            "type": "object",
+            "required": ["name"],
+            "properties": {"name": {"type": "string"}}
+          }
+        }
+      }
+    }"##;
+
+    #[test]
+    fn test_generate_server() {
+        let spec: Spec = serde_json::from_str(SPEC).unwrap();
+        let server = spec.generate_server();
+        for expected in &[
+            // Traits, by tag.
+            "/// Everything about pets.\n\
+             pub trait PetsApi: Send + Sync + 'static {\n    \
+                 /// Returns a pet.\n    \
+                 fn get_pet(\n        \
+                     &self,\n        \
+                     pet_id: i64,\n        \
+                     fields: Option<Vec<String>>,\n        \
+                     x_request_id: serde_json::Value,\n    \
+                 ) -> impl std::future::Future<Output = GetPetResponse> + Send;\n",
+            "    fn update_pet(\n        \
+                     &self,\n        \
+                     pet_id: i64,\n        \
+                     body: Pet,\n    \
+                 ) -> impl std::future::Future<Output = UpdatePetResponse> + Send;\n",
+            "/// Operations without tags.\npub trait DefaultApi: Send + Sync + 'static {",
+            // Filters.
+            "pub fn pets_api<S: PetsApi>(\n    \
+                 service: Arc<S>,\n\
+             ) -> impl Filter<Extract = (warp::reply::Response,), Error = warp::Rejection> \
+                 + Clone {\n    \
+                 get_pet(service.clone())\n        \
+                     .or(update_pet(service))\n        \
+                     .unify()\n\
+             }\n",
+            "    let body_limit = service.body_limit();\n    \
+                 route(\"get\", \"/pets/{petId}\", body_limit, move |request: Request| {\n        \
+                 let service = service.clone();\n        \
+                 async move {\n            \
+                     let pet_id = parameter(&request.values.path, \"petId\")?;\n            \
+                     let fields = parameter(&request.values.query, \"fields\")?;\n            \
+                     let x_request_id = \
+                         parameter(&request.values.header, \"X-Request-Id\")?;\n            \
+                     Ok(service.get_pet(pet_id, fields, x_request_id).await.into_response())\n",
+            "            let body = json_body(request.values.body)?;\n",
+            // Responses.
+            "            GetPetResponse::Ok(body) => {\n                \
+                 reply(200, \"application/json\", \
+                     serde_json::to_vec(&body).unwrap_or_default())\n            \
+             }\n\
+             \x20           GetPetResponse::NotFound => reply(404, \"\", Vec::new()),\n\
+             \x20           GetPetResponse::Status5xx(status, body) => {\n",
+            "            GetHealthResponse::Default(status, body) => {\n                \
+                 reply(status, \"text/plain\", body.into_bytes())\n            \
+             }\n",
+            // The embedded specification.
+            // References contain `"#`, hence the two hashes.
+            "const SPECIFICATION: &str = r##\"{\n  \"openapi\": \"3.0.3\",",
+        ] {
+            assert!(server.contains(expected), "{}\n\n{}", expected, server);
+        }
+    }
+
+    #[test]
+    fn test_generated_server() {
+        let spec: Spec = serde_json::from_str(GENERATED_SPEC).unwrap();
+        check_generated("src/server.rs", &spec.generate_server());
+    }
+}
@@ -0,0 +1,516 @@
+//! Serialization of parameter values according to their `style` and `explode`.
+
+use crate::v3_0::{
+    ComponentOrInlineSchema, Location, Parameter, ParameterRepresentation, ParameterStyle, Schema,
+    Spec, Type,
+};
+use serde_json::{Map, Value};
+
+impl Parameter {
+    /// The `style` of the parameter, or the default for its location: `form` for query and
+    /// cookie parameters and `simple` for the others.
+    pub fn style_or_default(&self) -> ParameterStyle {
+        match (self.style, self.location) {
+            (Some(style), _) => style,
+            (None, Location::Query) | (None, Location::Cookie) | (None, Location::FormData) => {
+                ParameterStyle::Form
+            }
+            (None, Location::Path) | (None, Location::Header) => ParameterStyle::Simple,
+        }
+    }
+
+    /// The `explode` of the parameter, or the default for its style: `true` for `form` and
+    /// `false` for the others.
+    pub fn explode_or_default(&self) -> bool {
+        self.explode
+            .unwrap_or(self.style_or_default() == ParameterStyle::Form)
+    }
+
+    /// Serializes `value` as this parameter, with [`serialize_parameter`].
+    pub fn serialize(&self, value: &Value) -> Vec<(String, String)> {
+        serialize_parameter(
+            &self.name,
+            self.style_or_default(),
+            self.explode_or_default(),
+            value,
+        )
+    }
+
+    /// Parses the value of this parameter from name and value pairs, the inverse of
+    /// [`Parameter::serialize`]. Returns `None` if the parameter is absent.
+    ///
+    /// Its schema, whose references are resolved in `spec`, tells arrays and objects apart from
+    /// single values, and numbers and booleans apart from strings. Values which do not parse as
+    /// their type are kept as strings, for validation to report them.
+    pub fn deserialize(&self, spec: &Spec, pairs: &[(String, String)]) -> Option<Value> {
+        let schema = match &self.representation {
+            Some(ParameterRepresentation::Simple { schema }) => spec.resolve_schema(schema).ok(),
+            _ => None,
+        };
+        let explode = self.explode_or_default();
+        let shape = Shape::of(schema);
+        let value = |name: &str| {
+            pairs
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str())
+        };
+        let raw = match self.style_or_default() {
+            ParameterStyle::Simple => shape.split(value(&self.name)?, ',', explode),
+            ParameterStyle::Label => {
+                let value = value(&self.name)?;
+                let value = value.strip_prefix('.').unwrap_or(value);
+                shape.split(value, if explode { '.' } else { ',' }, explode)
+            }
+            ParameterStyle::Matrix => {
+                let value = value(&self.name)?;
+                let value = value.strip_prefix(';').unwrap_or(value);
+                let assigned = |value: &'_ str| {
+                    let prefix = format!("{}=", self.name);
+                    value.strip_prefix(&*prefix).unwrap_or(value).to_string()
+                };
+                match shape {
+                    Shape::Array if explode => Raw::Items(value.split(';').map(assigned).collect()),
+                    Shape::Object if explode => shape.split(value, ';', true),
+                    _ => shape.split(&assigned(value), ',', false),
+                }
+            }
+            ParameterStyle::Form
+            | ParameterStyle::SpaceDelimited
+            | ParameterStyle::PipeDelimited
+                if explode =>
+            {
+                match shape {
+                    Shape::Array => {
+                        let items: Vec<String> = pairs
+                            .iter()
+                            .filter(|(key, _)| *key == self.name)
+                            .map(|(_, value)| value.clone())
+                            .collect();
+                        if items.is_empty() {
+                            return None;
+                        }
+                        Raw::Items(items)
+                    }
+                    Shape::Object => {
+                        let properties = schema.map(|schema| &schema.properties);
+                        let entries: Vec<(String, String)> = pairs
+                            .iter()
+                            .filter(|(key, _)| {
+                                properties.is_none_or(|properties| {
+                                    properties.is_empty() || properties.contains_key(&**key)
+                                })
+                            })
+                            .cloned()
+                            .collect();
+                        if entries.is_empty() {
+                            return None;
+                        }
+                        Raw::Entries(entries)
+                    }
+                    Shape::Scalar => Raw::Scalar(value(&self.name)?.to_string()),
+                }
+            }
+            ParameterStyle::Form => shape.split(value(&self.name)?, ',', false),
+            ParameterStyle::SpaceDelimited => shape.split(value(&self.name)?, ' ', false),
+            ParameterStyle::PipeDelimited => shape.split(value(&self.name)?, '|', false),
+            ParameterStyle::DeepObject => {
+                let prefix = format!("{}[", self.name);
+                let entries: Vec<(String, String)> = pairs
+                    .iter()
+                    .filter_map(|(key, value)| {
+                        let key = key.strip_prefix(&*prefix)?.strip_suffix(']')?;
+                        Some((key.to_string(), value.clone()))
+                    })
+                    .collect();
+                if entries.is_empty() {
+                    return None;
+                }
+                Raw::Entries(entries)
+            }
+        };
+        Some(raw.typed(schema, spec))
+    }
+}
+
+/// Whether a parameter holds an array, an object or a single value.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Shape {
+    Scalar,
+    Array,
+    Object,
+}
+
+impl Shape {
+    fn of(schema: Option<&Schema>) -> Shape {
+        match schema {
+            Some(schema) if schema.schema_type == Some(Type::Array) => Shape::Array,
+            Some(schema)
+                if schema.schema_type == Some(Type::Object) || !schema.properties.is_empty() =>
+            {
+                Shape::Object
+            }
+            _ => Shape::Scalar,
+        }
+    }
+
+    /// Splits a serialized value at `separator`. The keys and values of objects alternate, or
+    /// are joined with `=` if `assigned`.
+    fn split(self, value: &str, separator: char, assigned: bool) -> Raw {
+        let items = || -> Vec<String> {
+            if value.is_empty() {
+                Vec::new()
+            } else {
+                value.split(separator).map(str::to_string).collect()
+            }
+        };
+        match self {
+            Shape::Scalar => Raw::Scalar(value.to_string()),
+            Shape::Array => Raw::Items(items()),
+            Shape::Object if assigned => Raw::Entries(
+                items()
+                    .iter()
+                    .map(|item| match item.split_once('=') {
+                        Some((key, value)) => (key.to_string(), value.to_string()),
+                        None => (item.clone(), String::new()),
+                    })
+                    .collect(),
+            ),
+            Shape::Object => Raw::Entries(
+                items()
+                    .chunks(2)
+                    .map(|entry| (entry[0].clone(), entry.get(1).cloned().unwrap_or_default()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// A parameter value split according to its style, before its strings are parsed.
+enum Raw {
+    Scalar(String),
+    Items(Vec<String>),
+    Entries(Vec<(String, String)>),
+}
+
+impl Raw {
+    fn typed<'a>(self, schema: Option<&'a Schema>, spec: &'a Spec) -> Value {
+        let resolve =
+            |schema: Option<&'a ComponentOrInlineSchema>| spec.resolve_schema(schema?).ok();
+        match self {
+            Raw::Scalar(value) => typed(value, schema),
+            Raw::Items(items) => {
+                let items_schema = resolve(schema.and_then(|schema| schema.items.as_deref()));
+                Value::Array(
+                    items
+                        .into_iter()
+                        .map(|item| typed(item, items_schema))
+                        .collect(),
+                )
+            }
+            Raw::Entries(entries) => Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| {
+                        let property = resolve(schema.and_then(|schema| {
+                            schema
+                                .properties
+                                .get(&*key)
+                                .or(schema.additional_properties.as_deref())
+                        }));
+                        let value = typed(value, property);
+                        (key, value)
+                    })
+                    .collect::<Map<String, Value>>(),
+            ),
+        }
+    }
+}
+
+/// Parses `value` as the type of `schema`, or keeps it as a string.
+fn typed(value: String, schema: Option<&Schema>) -> Value {
+    let parsed = match schema.and_then(|schema| schema.schema_type) {
+        Some(Type::Integer) => value
+            .parse::<i64>()
+            .map(Value::from)
+            .or_else(|_| value.parse::<u64>().map(Value::from))
+            .ok(),
+        Some(Type::Number) => value.parse::<i64>().map(Value::from).ok().or_else(|| {
+            let number = serde_json::Number::from_f64(value.parse().ok()?)?;
+            Some(Value::Number(number))
+        }),
+        Some(Type::Boolean) => value.parse::<bool>().ok().map(Value::Bool),
+        _ => None,
+    };
+    parsed.unwrap_or(Value::String(value))
+}
+
+/// Serializes the value of the parameter `name` as `style` does, returning name and value pairs
+/// which are not percent-encoded.
+///
+/// The `form`, `spaceDelimited`, `pipeDelimited` and `deepObject` styles return the query pairs
+/// or cookies of the value. The `simple`, `label` and `matrix` styles return a single pair of
+/// `name` and the serialized value, such as `;id=3,4` for a `matrix` array.
+///
+/// Values nested in arrays or objects are serialized as JSON.
+pub fn serialize_parameter(
+    name: &str,
+    style: ParameterStyle,
+    explode: bool,
+    value: &Value,
+) -> Vec<(String, String)> {
+    let single = |value: String| vec![(name.to_string(), value)];
+    match style {
+        ParameterStyle::Simple => match value {
+            Value::Object(object) if explode => single(pairs(object, "=", ",")),
+            value => single(items(value).join(",")),
+        },
+        ParameterStyle::Label => match value {
+            Value::Object(object) if explode => single(format!(".{}", pairs(object, "=", "."))),
+            Value::Array(_) if explode => single(format!(".{}", items(value).join("."))),
+            value => single(format!(".{}", items(value).join(","))),
+        },
+        ParameterStyle::Matrix => match value {
+            Value::Object(object) if explode => single(
+                object
+                    .iter()
+                    .map(|(key, value)| format!(";{}={}", key, scalar(value)))
+                    .collect(),
+            ),
+            Value::Array(array) if explode => single(
+                array
+                    .iter()
+                    .map(|value| format!(";{}={}", name, scalar(value)))
+                    .collect(),
+            ),
+            Value::Array(array) if array.is_empty() => single(format!(";{}", name)),
+            value => single(format!(";{}={}", name, items(value).join(","))),
+        },
+        ParameterStyle::Form | ParameterStyle::SpaceDelimited | ParameterStyle::PipeDelimited
+            if explode =>
+        {
+            match value {
+                Value::Object(object) => object
+                    .iter()
+                    .map(|(key, value)| (key.clone(), scalar(value)))
+                    .collect(),
+                Value::Array(array) => array
+                    .iter()
+                    .map(|value| (name.to_string(), scalar(value)))
+                    .collect(),
+                value => single(scalar(value)),
+            }
+        }
+        ParameterStyle::Form => single(items(value).join(",")),
+        ParameterStyle::SpaceDelimited => single(items(value).join(" ")),
+        ParameterStyle::PipeDelimited => single(items(value).join("|")),
+        ParameterStyle::DeepObject => match value {
+            Value::Object(object) => object
+                .iter()
+                .map(|(key, value)| (format!("{}[{}]", name, key), scalar(value)))
+                .collect(),
+            value => single(scalar(value)),
+        },
+    }
+}
+
+/// The items of an array, the keys and values of an object, or a single scalar.
+fn items(value: &Value) -> Vec<String> {
+    match value {
+        Value::Array(array) => array.iter().map(scalar).collect(),
+        Value::Object(object) => object
+            .iter()
+            .flat_map(|(key, value)| vec![key.clone(), scalar(value)])
+            .collect(),
+        value => vec![scalar(value)],
+    }
+}
+
+fn pairs(object: &serde_json::Map<String, Value>, assign: &str, separator: &str) -> String {
+    object
+        .iter()
+        .map(|(key, value)| format!("{}{}{}", key, assign, scalar(value)))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(string) => string.clone(),
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn serialized(style: ParameterStyle, explode: bool, value: Value) -> Vec<String> {
+        serialize_parameter("color", style, explode, &value)
+            .into_iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect()
+    }
+
+    #[test]
+    fn test_serialize_parameter() {
+        use ParameterStyle::*;
+
+        // The examples of the specification, with the fields of the object sorted as
+        // `serde_json` keeps them.
+        let string = || json!("blue");
+        let array = || json!(["blue", "black", "brown"]);
+        let object = || json!({"B": 150, "G": 200, "R": 100});
+        for (style, explode, value, expected) in vec![
+            (Matrix, false, string(), vec![";color=blue"]),
+            (Matrix, false, array(), vec![";color=blue,black,brown"]),
+            (
+                Matrix,
+                true,
+                array(),
+                vec![";color=blue;color=black;color=brown"],
+            ),
+            (Matrix, false, object(), vec![";color=B,150,G,200,R,100"]),
+            (Matrix, true, object(), vec![";B=150;G=200;R=100"]),
+            (Label, false, string(), vec![".blue"]),
+            (Label, false, array(), vec![".blue,black,brown"]),
+            (Label, true, array(), vec![".blue.black.brown"]),
+            (Label, false, object(), vec![".B,150,G,200,R,100"]),
+            (Label, true, object(), vec![".B=150.G=200.R=100"]),
+            (Form, false, string(), vec!["blue"]),
+            (Form, false, array(), vec!["blue,black,brown"]),
+            (Form, true, array(), vec!["blue", "black", "brown"]),
+            (Form, false, object(), vec!["B,150,G,200,R,100"]),
+            (Simple, false, array(), vec!["blue,black,brown"]),
+            (Simple, true, object(), vec!["B=150,G=200,R=100"]),
+            (SpaceDelimited, false, array(), vec!["blue black brown"]),
+            (PipeDelimited, false, array(), vec!["blue|black|brown"]),
+        ] {
+            let expected: Vec<String> = expected
+                .into_iter()
+                .map(|value| format!("color={}", value))
+                .collect();
+            assert_eq!(
+                serialized(style, explode, value.clone()),
+                expected,
+                "{:?} {} {}",
+                style,
+                explode,
+                value
+            );
+        }
+        assert_eq!(
+            serialized(Form, true, object()),
+            vec!["B=150", "G=200", "R=100"]
+        );
+        assert_eq!(
+            serialized(DeepObject, true, object()),
+            vec!["color[B]=150", "color[G]=200", "color[R]=100"]
+        );
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let spec = Spec::default();
+        let schemas = [
+            json!({"type": "integer"}),
+            json!({"type": "array", "items": {"type": "integer"}}),
+            json!({
+                "type": "object",
+                "properties": {"R": {"type": "integer"}, "on": {"type": "boolean"}}
+            }),
+            json!({"type": "string"}),
+        ];
+        let values = [
+            json!(5),
+            json!([3, 4]),
+            json!({"R": 100, "on": true}),
+            json!("007"),
+        ];
+        for style in &[
+            ParameterStyle::Matrix,
+            ParameterStyle::Label,
+            ParameterStyle::Form,
+            ParameterStyle::Simple,
+            ParameterStyle::SpaceDelimited,
+            ParameterStyle::PipeDelimited,
+            ParameterStyle::DeepObject,
+        ] {
+            for explode in &[false, true] {
+                for (schema, value) in schemas.iter().zip(&values) {
+                    let deep = *style == ParameterStyle::DeepObject;
+                    let delimited = matches!(
+                        style,
+                        ParameterStyle::SpaceDelimited | ParameterStyle::PipeDelimited
+                    );
+                    if deep && !value.is_object() || delimited && !value.is_array() {
+                        continue;
+                    }
+                    let parameter = Parameter {
+                        name: "color".into(),
+                        style: Some(*style),
+                        explode: Some(*explode),
+                        representation: Some(ParameterRepresentation::Simple {
+                            schema: serde_json::from_value(schema.clone()).unwrap(),
+                        }),
+                        ..Parameter::default()
+                    };
+                    let pairs = parameter.serialize(value);
+                    assert_eq!(
+                        parameter.deserialize(&spec, &pairs).as_ref(),
+                        Some(value),
+                        "{:?} {} {:?}",
+                        style,
+                        explode,
+                        pairs
+                    );
+                }
+            }
+        }
+
+        let parameter = Parameter {
+            name: "limit".into(),
+            ..Parameter::default()
+        };
+        assert_eq!(parameter.deserialize(&spec, &[]), None);
+        let pairs = [("limit".to_string(), "ten".to_string())];
+        assert_eq!(parameter.deserialize(&spec, &pairs), Some(json!("ten")));
+        let parameter = Parameter {
+            representation: Some(ParameterRepresentation::Simple {
+                schema: serde_json::from_value(json!({"type": "number"})).unwrap(),
+            }),
+            ..parameter
+        };
+        let pairs = [("limit".to_string(), "2.5".to_string())];
+        assert_eq!(parameter.deserialize(&spec, &pairs), Some(json!(2.5)));
+    }
+
+    #[test]
+    fn test_defaults() {
+        let parameter = Parameter {
+            name: "id".into(),
+            location: Location::Query,
+            ..Parameter::default()
+        };
+        assert_eq!(parameter.style_or_default(), ParameterStyle::Form);
+        assert!(parameter.explode_or_default());
+        assert_eq!(
+            parameter.serialize(&json!([1, 2])),
+            vec![("id".into(), "1".into()), ("id".into(), "2".into())]
+        );
+
+        let parameter = Parameter {
+            name: "id".into(),
+            location: Location::Path,
+            ..Parameter::default()
+        };
+        assert_eq!(parameter.style_or_default(), ParameterStyle::Simple);
+        assert!(!parameter.explode_or_default());
+        assert_eq!(
+            parameter.serialize(&json!([1, 2])),
+            vec![("id".to_string(), "1,2".to_string())]
+        );
+    }
+}
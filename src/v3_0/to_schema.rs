@@ -0,0 +1,834 @@
+//! Schemas of Rust types.
+
+use crate::{
+    v3_0::{ComponentOrInlineSchema, Components, ObjectOrReference, Schema, Type},
+    Str,
+};
+#[cfg(feature = "derive")]
+pub use rweb_openapi_derive::ToSchema;
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+    rc::Rc,
+    sync::Arc,
+};
+
+/// A type with a schema describing its serialized form.
+///
+/// Types with a [`component_name`](ToSchema::component_name) are registered into the
+/// components and referred to by name, others are inlined. With the `derive` feature, the trait
+/// can be derived for types implementing `Serialize`, honouring their `#[serde]` attributes.
+pub trait ToSchema {
+    /// Name of the component schema of the type, or `None` to inline its schema.
+    fn component_name() -> Option<Str> {
+        None
+    }
+
+    /// Returns the schema of the type, registering the components it refers to.
+    fn schema(components: &mut Components) -> Schema;
+
+    /// Whether a field of this type may be left out of an object.
+    fn is_optional() -> bool {
+        false
+    }
+
+    /// Returns the schema whose properties a `#[serde(flatten)]` field of the type adds to the
+    /// object containing it. Options flatten the type they wrap.
+    fn flattened_schema(components: &mut Components) -> Schema {
+        Self::schema(components)
+    }
+
+    /// Returns the schema to use where the type appears: a reference to its component,
+    /// registered first if needed, or its schema.
+    ///
+    /// A component of the same name with a different schema belongs to another type, so `_2`,
+    /// `_3`, ... is appended to the name of this one.
+    fn to_schema(components: &mut Components) -> ComponentOrInlineSchema {
+        let base = match Self::component_name() {
+            Some(name) => name,
+            None => return ComponentOrInlineSchema::Inline(Self::schema(components)),
+        };
+        let placeholder = || ObjectOrReference::Object(Schema::default());
+        let mut name = base.clone();
+        for suffix in 2.. {
+            let existing = match components.schemas.get_mut(&name) {
+                Some(existing) => existing,
+                None => {
+                    // Registered before the schema is built, so that recursive types refer to it.
+                    components.schemas.insert(name.clone(), placeholder());
+                    let schema = ObjectOrReference::Object(Self::schema(components));
+                    components.schemas.insert(name.clone(), schema);
+                    break;
+                }
+            };
+            // Still being built, by this type as it refers to itself.
+            if *existing == placeholder() {
+                break;
+            }
+            let existing = std::mem::replace(existing, placeholder());
+            let same = ObjectOrReference::Object(Self::schema(components)) == existing;
+            components.schemas.insert(name.clone(), existing);
+            if same {
+                break;
+            }
+            name = format!("{}_{}", base, suffix).into();
+        }
+        ComponentOrInlineSchema::Component { name }
+    }
+}
+
+impl Components {
+    /// Registers the schema of `T` and those it refers to, returning the schema to use for `T`.
+    pub fn register<T: ToSchema + ?Sized>(&mut self) -> ComponentOrInlineSchema {
+        T::to_schema(self)
+    }
+}
+
+fn typed(schema_type: Type, format: &'static str) -> Schema {
+    Schema {
+        schema_type: Some(schema_type),
+        format: format.into(),
+        ..Schema::default()
+    }
+}
+
+/// Implements [`ToSchema`] for integers, with their bounds other than those of the format.
+macro_rules! integers {
+    ($($ty:ty => $format:expr, $minimum:expr, $maximum:expr);*) => {
+        $(
+            impl ToSchema for $ty {
+                fn schema(_: &mut Components) -> Schema {
+                    Schema {
+                        minimum: $minimum,
+                        maximum: $maximum,
+                        ..typed(Type::Integer, $format)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+integers!(
+    i8 => "int32", Some(i8::MIN.into()), Some(i8::MAX.into());
+    i16 => "int32", Some(i16::MIN.into()), Some(i16::MAX.into());
+    i32 => "int32", None, None;
+    i64 => "int64", None, None;
+    isize => "int64", None, None;
+    u8 => "int32", Some(0.into()), Some(u8::MAX.into());
+    u16 => "int32", Some(0.into()), Some(u16::MAX.into());
+    u32 => "int64", Some(0.into()), Some(u32::MAX.into());
+    u64 => "int64", Some(0.into()), None;
+    usize => "int64", Some(0.into()), None
+);
+
+impl ToSchema for f32 {
+    fn schema(_: &mut Components) -> Schema {
+        typed(Type::Number, "float")
+    }
+}
+
+impl ToSchema for f64 {
+    fn schema(_: &mut Components) -> Schema {
+        typed(Type::Number, "double")
+    }
+}
+
+impl ToSchema for bool {
+    fn schema(_: &mut Components) -> Schema {
+        typed(Type::Boolean, "")
+    }
+}
+
+impl ToSchema for char {
+    fn schema(_: &mut Components) -> Schema {
+        Schema {
+            min_length: Some(1),
+            max_length: Some(1),
+            ..typed(Type::String, "")
+        }
+    }
+}
+
+impl ToSchema for str {
+    fn schema(_: &mut Components) -> Schema {
+        typed(Type::String, "")
+    }
+}
+
+impl ToSchema for String {
+    fn schema(_: &mut Components) -> Schema {
+        typed(Type::String, "")
+    }
+}
+
+impl ToSchema for url::Url {
+    fn schema(_: &mut Components) -> Schema {
+        typed(Type::String, "uri")
+    }
+}
+
+/// Any value.
+impl ToSchema for serde_json::Value {
+    fn schema(_: &mut Components) -> Schema {
+        Schema::default()
+    }
+}
+
+impl<T: ToSchema> ToSchema for Option<T> {
+    fn schema(components: &mut Components) -> Schema {
+        match T::to_schema(components) {
+            ComponentOrInlineSchema::Inline(schema) => Schema {
+                nullable: Some(true),
+                ..schema
+            },
+            // Siblings of `$ref` are ignored.
+            schema => Schema {
+                nullable: Some(true),
+                all_of: vec![schema],
+                ..Schema::default()
+            },
+        }
+    }
+
+    fn is_optional() -> bool {
+        true
+    }
+
+    fn flattened_schema(components: &mut Components) -> Schema {
+        T::flattened_schema(components)
+    }
+}
+
+/// Implements [`ToSchema`] for pointers, as the type they point to.
+macro_rules! pointers {
+    ($($ty:ty),*) => {
+        $(
+            impl<T: ToSchema + ?Sized> ToSchema for $ty {
+                fn component_name() -> Option<Str> {
+                    T::component_name()
+                }
+
+                fn schema(components: &mut Components) -> Schema {
+                    T::schema(components)
+                }
+
+                fn is_optional() -> bool {
+                    T::is_optional()
+                }
+
+                fn flattened_schema(components: &mut Components) -> Schema {
+                    T::flattened_schema(components)
+                }
+            }
+        )*
+    };
+}
+
+pointers!(&T, Box<T>, Rc<T>, Arc<T>);
+
+impl<T: ToSchema + ToOwned + ?Sized> ToSchema for Cow<'_, T> {
+    fn component_name() -> Option<Str> {
+        T::component_name()
+    }
+
+    fn schema(components: &mut Components) -> Schema {
+        T::schema(components)
+    }
+}
+
+fn array<T: ToSchema>(components: &mut Components, unique: bool) -> Schema {
+    Schema {
+        items: Some(Box::new(T::to_schema(components))),
+        unique_items: if unique { Some(true) } else { None },
+        ..typed(Type::Array, "")
+    }
+}
+
+impl<T: ToSchema> ToSchema for [T] {
+    fn schema(components: &mut Components) -> Schema {
+        array::<T>(components, false)
+    }
+}
+
+impl<T: ToSchema, const N: usize> ToSchema for [T; N] {
+    fn schema(components: &mut Components) -> Schema {
+        Schema {
+            min_items: Some(N),
+            max_items: Some(N),
+            ..array::<T>(components, false)
+        }
+    }
+}
+
+impl<T: ToSchema> ToSchema for Vec<T> {
+    fn schema(components: &mut Components) -> Schema {
+        array::<T>(components, false)
+    }
+}
+
+impl<T: ToSchema> ToSchema for VecDeque<T> {
+    fn schema(components: &mut Components) -> Schema {
+        array::<T>(components, false)
+    }
+}
+
+impl<T: ToSchema, S> ToSchema for HashSet<T, S> {
+    fn schema(components: &mut Components) -> Schema {
+        array::<T>(components, true)
+    }
+}
+
+impl<T: ToSchema> ToSchema for BTreeSet<T> {
+    fn schema(components: &mut Components) -> Schema {
+        array::<T>(components, true)
+    }
+}
+
+impl<T: ToSchema, S> ToSchema for indexmap::IndexSet<T, S> {
+    fn schema(components: &mut Components) -> Schema {
+        array::<T>(components, true)
+    }
+}
+
+/// Maps serialize as objects, whose keys are strings.
+fn map<V: ToSchema>(components: &mut Components) -> Schema {
+    Schema {
+        additional_properties: Some(Box::new(V::to_schema(components))),
+        ..typed(Type::Object, "")
+    }
+}
+
+impl<K, V: ToSchema, S> ToSchema for HashMap<K, V, S> {
+    fn schema(components: &mut Components) -> Schema {
+        map::<V>(components)
+    }
+}
+
+impl<K, V: ToSchema> ToSchema for BTreeMap<K, V> {
+    fn schema(components: &mut Components) -> Schema {
+        map::<V>(components)
+    }
+}
+
+impl<K, V: ToSchema, S> ToSchema for indexmap::IndexMap<K, V, S> {
+    fn schema(components: &mut Components) -> Schema {
+        map::<V>(components)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz: chrono::TimeZone> ToSchema for chrono::DateTime<Tz> {
+    fn schema(_: &mut Components) -> Schema {
+        typed(Type::String, "date-time")
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl ToSchema for chrono::NaiveDateTime {
+    fn schema(_: &mut Components) -> Schema {
+        typed(Type::String, "date-time")
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl ToSchema for chrono::NaiveDate {
+    fn schema(_: &mut Components) -> Schema {
+        typed(Type::String, "date")
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl ToSchema for chrono::NaiveTime {
+    fn schema(_: &mut Components) -> Schema {
+        typed(Type::String, "time")
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl ToSchema for uuid::Uuid {
+    fn schema(_: &mut Components) -> Schema {
+        typed(Type::String, "uuid")
+    }
+}
+
+/// Helpers of the code generated by `#[derive(ToSchema)]`.
+#[doc(hidden)]
+pub mod __derive {
+    use super::*;
+
+    pub fn object() -> Schema {
+        typed(Type::Object, "")
+    }
+
+    /// Schema of `null`, such as a unit struct.
+    pub fn null() -> Schema {
+        Schema {
+            nullable: Some(true),
+            ..Schema::default()
+        }
+    }
+
+    pub fn describe(schema: &mut Schema, description: &'static str) {
+        if schema.description.is_empty() {
+            schema.description = description.into();
+        }
+    }
+
+    /// Adds the property `name` of type `T` to an object schema.
+    pub fn property<T: ToSchema + ?Sized>(
+        schema: &mut Schema,
+        components: &mut Components,
+        name: &'static str,
+        required: bool,
+        description: &'static str,
+    ) {
+        let mut property = T::to_schema(components);
+        if let ComponentOrInlineSchema::Inline(property) = &mut property {
+            describe(property, description);
+        }
+        schema.properties.insert(name.into(), property);
+        if required && !T::is_optional() {
+            schema.required.push(name.into());
+        }
+    }
+
+    /// Adds the properties of `T` to an object schema, as `#[serde(flatten)]` does. They are
+    /// optional if `T` is.
+    pub fn flatten<T: ToSchema + ?Sized>(schema: &mut Schema, components: &mut Components) {
+        let flattened = T::flattened_schema(components);
+        let optional = T::is_optional();
+        for (name, property) in flattened.properties {
+            schema.properties.insert(name, property);
+        }
+        if !optional {
+            schema.required.extend(flattened.required);
+        }
+        if flattened.additional_properties.is_some() {
+            schema.additional_properties = flattened.additional_properties;
+        }
+        // Such as flattened enums.
+        if !flattened.one_of.is_empty() {
+            schema.all_of.push(
+                Schema {
+                    one_of: flattened.one_of,
+                    ..Schema::default()
+                }
+                .into(),
+            );
+        }
+        if !flattened.any_of.is_empty() {
+            schema.all_of.push(
+                Schema {
+                    any_of: flattened.any_of,
+                    ..Schema::default()
+                }
+                .into(),
+            );
+        }
+    }
+
+    /// Schema of a newtype, which serializes as the type it wraps.
+    pub fn newtype(schema: ComponentOrInlineSchema) -> Schema {
+        match schema {
+            ComponentOrInlineSchema::Inline(schema) => schema,
+            schema => Schema {
+                all_of: vec![schema],
+                ..Schema::default()
+            },
+        }
+    }
+
+    /// Schema of a tuple, which serializes as an array.
+    pub fn tuple(items: Vec<ComponentOrInlineSchema>) -> Schema {
+        let len = items.len();
+        let mut distinct: Vec<ComponentOrInlineSchema> = Vec::new();
+        for item in items {
+            if !distinct.contains(&item) {
+                distinct.push(item);
+            }
+        }
+        let items = if distinct.len() == 1 {
+            distinct.remove(0)
+        } else {
+            ComponentOrInlineSchema::Inline(Schema {
+                one_of: distinct,
+                ..Schema::default()
+            })
+        };
+        Schema {
+            items: Some(Box::new(items)),
+            min_items: Some(len),
+            max_items: Some(len),
+            ..typed(Type::Array, "")
+        }
+    }
+
+    /// Schema of a string among `values`, such as the unit variants of an enum.
+    pub fn string_enum(values: &[&'static str]) -> Schema {
+        Schema {
+            enum_values: values.iter().map(|value| Str::from(*value)).collect(),
+            ..typed(Type::String, "")
+        }
+    }
+
+    pub fn one_of(schemas: Vec<ComponentOrInlineSchema>) -> Schema {
+        Schema {
+            one_of: schemas,
+            ..Schema::default()
+        }
+    }
+
+    /// Schema of an externally tagged variant: an object with the single property `variant`.
+    pub fn external(variant: &'static str, schema: ComponentOrInlineSchema) -> Schema {
+        let mut object = object();
+        object.properties.insert(variant.into(), schema);
+        object.required.push(variant.into());
+        object
+    }
+
+    /// Schema of an internally tagged variant: its own schema with the property `tag`.
+    pub fn internal(
+        tag: &'static str,
+        variant: &'static str,
+        schema: ComponentOrInlineSchema,
+    ) -> Schema {
+        let mut tagged = object();
+        tagged
+            .properties
+            .insert(tag.into(), string_enum(&[variant]).into());
+        tagged.required.push(tag.into());
+        match schema {
+            ComponentOrInlineSchema::Inline(mut schema) if schema.all_of.is_empty() => {
+                schema
+                    .properties
+                    .insert(tag.into(), string_enum(&[variant]).into());
+                schema.required.insert(0, tag.into());
+                schema.schema_type = Some(Type::Object);
+                schema
+            }
+            schema => Schema {
+                all_of: vec![tagged.into(), schema],
+                ..Schema::default()
+            },
+        }
+    }
+
+    /// Schema of an adjacently tagged variant: an object with the properties `tag` and, unless
+    /// the variant is a unit, `content`.
+    pub fn adjacent(
+        tag: &'static str,
+        content: &'static str,
+        variant: &'static str,
+        schema: Option<ComponentOrInlineSchema>,
+    ) -> Schema {
+        let mut object = object();
+        object
+            .properties
+            .insert(tag.into(), string_enum(&[variant]).into());
+        object.required.push(tag.into());
+        if let Some(schema) = schema {
+            object.properties.insert(content.into(), schema);
+            object.required.push(content.into());
+        }
+        object
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rweb_openapi_derive::ToSchema;
+    use serde::Serialize;
+    use serde_json::{json, Value};
+
+    fn register<T: ToSchema + ?Sized>() -> (Value, Components) {
+        let mut components = Components::default();
+        let schema = components.register::<T>();
+        (serde_json::to_value(schema).unwrap(), components)
+    }
+
+    #[test]
+    fn test_std() {
+        assert_eq!(
+            register::<u8>().0,
+            json!({"type": "integer", "format": "int32", "minimum": 0, "maximum": 255})
+        );
+        assert_eq!(
+            register::<Option<Vec<i64>>>().0,
+            json!({
+                "type": "array",
+                "items": {"type": "integer", "format": "int64"},
+                "nullable": true
+            })
+        );
+        assert_eq!(
+            register::<HashMap<String, [f32; 2]>>().0,
+            json!({
+                "type": "object",
+                "additionalProperties": {
+                    "type": "array",
+                    "items": {"type": "number", "format": "float"},
+                    "maxItems": 2,
+                    "minItems": 2
+                }
+            })
+        );
+        assert_eq!(
+            register::<BTreeSet<Box<str>>>().0,
+            json!({"type": "array", "items": {"type": "string"}, "uniqueItems": true})
+        );
+    }
+
+    /// A pet.
+    #[derive(Serialize, ToSchema)]
+    #[serde(rename_all = "camelCase")]
+    struct Pet {
+        /// Name of the pet.
+        pet_name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        age: Option<u32>,
+        #[serde(skip)]
+        #[allow(dead_code)]
+        secret: bool,
+        #[serde(flatten)]
+        owner: Owner,
+        kind: Kind,
+        parent: Option<Box<Pet>>,
+    }
+
+    #[derive(Serialize, ToSchema)]
+    struct Owner {
+        #[serde(rename = "owner")]
+        name: String,
+    }
+
+    #[derive(Serialize, ToSchema)]
+    #[serde(tag = "type", rename_all = "lowercase")]
+    enum Kind {
+        Cat,
+        Dog { good: bool },
+        Other(Owner),
+    }
+
+    #[derive(Serialize, ToSchema)]
+    struct Listing {
+        id: u64,
+        #[serde(flatten)]
+        owner: Option<Owner>,
+    }
+
+    /// Either or both of a name and a number.
+    #[derive(Serialize)]
+    struct Label {
+        name: Option<String>,
+        number: Option<u64>,
+    }
+
+    impl ToSchema for Label {
+        fn schema(_: &mut Components) -> Schema {
+            Schema {
+                any_of: vec![
+                    ComponentOrInlineSchema::Inline(Schema {
+                        required: vec!["name".into()],
+                        ..Schema::default()
+                    }),
+                    ComponentOrInlineSchema::Inline(Schema {
+                        required: vec!["number".into()],
+                        ..Schema::default()
+                    }),
+                ],
+                ..typed(Type::Object, "")
+            }
+        }
+    }
+
+    #[derive(Serialize, ToSchema)]
+    struct Labelled {
+        #[serde(flatten)]
+        label: Label,
+    }
+
+    #[derive(Serialize, ToSchema)]
+    #[serde(untagged)]
+    enum Id {
+        Number(u64),
+        Name(String),
+    }
+
+    #[test]
+    fn test_derive() {
+        let (pet, components) = register::<Pet>();
+        assert_eq!(pet, json!({"$ref": "#/components/schemas/Pet"}));
+        assert_eq!(
+            serde_json::to_value(&components.schemas).unwrap(),
+            json!({
+                "Pet": {
+                    "description": "A pet.",
+                    "type": "object",
+                    "properties": {
+                        "petName": {"description": "Name of the pet.", "type": "string"},
+                        "age": {
+                            "type": "integer",
+                            "format": "int64",
+                            "nullable": true,
+                            "maximum": 4294967295u32,
+                            "minimum": 0
+                        },
+                        "owner": {"type": "string"},
+                        "kind": {"$ref": "#/components/schemas/Kind"},
+                        "parent": {
+                            "nullable": true,
+                            "allOf": [{"$ref": "#/components/schemas/Pet"}]
+                        }
+                    },
+                    "required": ["petName", "owner", "kind"]
+                },
+                "Owner": {
+                    "type": "object",
+                    "properties": {"owner": {"type": "string"}},
+                    "required": ["owner"]
+                },
+                "Kind": {
+                    "oneOf": [
+                        {
+                            "type": "object",
+                            "properties": {"type": {"type": "string", "enum": ["cat"]}},
+                            "required": ["type"]
+                        },
+                        {
+                            "type": "object",
+                            "properties": {
+                                "good": {"type": "boolean"},
+                                "type": {"type": "string", "enum": ["dog"]}
+                            },
+                            "required": ["type", "good"]
+                        },
+                        {
+                            "allOf": [
+                                {
+                                    "type": "object",
+                                    "properties": {"type": {"type": "string", "enum": ["other"]}},
+                                    "required": ["type"]
+                                },
+                                {"$ref": "#/components/schemas/Owner"}
+                            ]
+                        }
+                    ]
+                }
+            })
+        );
+
+        let pet = Pet {
+            pet_name: "Tom".into(),
+            age: None,
+            secret: true,
+            owner: Owner { name: "Jo".into() },
+            kind: Kind::Dog { good: true },
+            parent: None,
+        };
+        let schema = Components::default().register::<Pet>();
+        let instance = serde_json::to_value(&pet).unwrap();
+        assert!(components.validate_instance(&schema, &instance).is_empty());
+        let schema = Components::default().register::<Kind>();
+        for kind in &[Kind::Cat, Kind::Other(Owner { name: "Jo".into() })] {
+            let instance = serde_json::to_value(kind).unwrap();
+            assert!(components.validate_instance(&schema, &instance).is_empty());
+        }
+
+        let (_, components) = register::<Listing>();
+        assert_eq!(
+            serde_json::to_value(&components.schemas).unwrap(),
+            json!({
+                "Listing": {
+                    "type": "object",
+                    "properties": {
+                        "id": {"type": "integer", "format": "int64", "minimum": 0},
+                        "owner": {"type": "string"}
+                    },
+                    "required": ["id"]
+                }
+            })
+        );
+        let schema = Components::default().register::<Listing>();
+        for owner in [None, Some(Owner { name: "Jo".into() })] {
+            let instance = serde_json::to_value(Listing { id: 1, owner }).unwrap();
+            assert!(components.validate_instance(&schema, &instance).is_empty());
+        }
+
+        let (_, components) = register::<Labelled>();
+        assert_eq!(
+            serde_json::to_value(&components.schemas["Labelled"]).unwrap()["allOf"],
+            json!([{"anyOf": [{"required": ["name"]}, {"required": ["number"]}]}])
+        );
+
+        let (id, components) = register::<Id>();
+        assert_eq!(id, json!({"$ref": "#/components/schemas/Id"}));
+        assert_eq!(
+            serde_json::to_value(&components.schemas["Id"]).unwrap(),
+            json!({
+                "oneOf": [
+                    {"type": "integer", "format": "int64", "minimum": 0},
+                    {"type": "string"}
+                ]
+            })
+        );
+        let schema = Components::default().register::<Id>();
+        for id in &[Id::Number(1), Id::Name("one".into())] {
+            let instance = serde_json::to_value(id).unwrap();
+            assert!(components.validate_instance(&schema, &instance).is_empty());
+        }
+    }
+
+    mod other {
+        use super::*;
+
+        #[derive(Serialize, ToSchema)]
+        pub struct Owner {
+            pub id: u64,
+            pub next: Option<Box<Owner>>,
+        }
+    }
+
+    #[derive(Serialize, ToSchema)]
+    struct Owners {
+        first: Owner,
+        second: other::Owner,
+        third: Owner,
+    }
+
+    #[test]
+    fn test_name_collisions() {
+        let (_, mut components) = register::<Owners>();
+        assert_eq!(
+            serde_json::to_value(&components.schemas["Owners"]).unwrap(),
+            json!({
+                "type": "object",
+                "properties": {
+                    "first": {"$ref": "#/components/schemas/Owner"},
+                    "second": {"$ref": "#/components/schemas/Owner_2"},
+                    "third": {"$ref": "#/components/schemas/Owner"}
+                },
+                "required": ["first", "second", "third"]
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(&components.schemas["Owner_2"]).unwrap(),
+            json!({
+                "type": "object",
+                "properties": {
+                    "id": {"type": "integer", "format": "int64", "minimum": 0},
+                    "next": {"nullable": true, "allOf": [{"$ref": "#/components/schemas/Owner_2"}]}
+                },
+                "required": ["id"]
+            })
+        );
+        assert_eq!(
+            components.register::<other::Owner>(),
+            ComponentOrInlineSchema::Component {
+                name: "Owner_2".into()
+            }
+        );
+        assert_eq!(components.schemas.len(), 3);
+    }
+}
@@ -0,0 +1,629 @@
+//! Validation of the rules of the specification which deserialization does not enforce.
+
+use crate::{
+    json_pointer,
+    v3_0::{
+        Callback, ComponentOrInlineSchema, Header, Location, MediaType, ObjectOrReference,
+        Operation, Parameter, ParameterRepresentation, PathItem, RequestBody, Response, Schema,
+        SecurityRequirement, SecurityScheme, Server, Spec, Type,
+    },
+    Str,
+};
+use indexmap::IndexMap;
+use std::{collections::HashMap, fmt};
+
+/// A rule checked by [`Spec::validate`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Rule {
+    /// Response keys are HTTP status codes, ranges such as `4XX`, or `default`.
+    ResponseKey,
+    /// Every parameter of a path template is defined as a required path parameter.
+    PathParameter,
+    /// Parameters of a list are unique by name and location.
+    DuplicateParameter,
+    /// Operation ids are unique within the document.
+    DuplicateOperationId,
+    /// Array schemas define their `items`.
+    ArrayItems,
+    /// Security requirements name security schemes declared in the components.
+    SecurityScheme,
+    /// Security requirements only list scopes declared by their scheme.
+    SecurityScope,
+    /// The default value of a server variable is one of its `enum` values.
+    ServerVariable,
+    /// The `content` of a parameter has exactly one media type.
+    ParameterContent,
+    /// Parameters define either a `schema` or a `content`.
+    ParameterSchema,
+}
+
+/// A violation of a [`Rule`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Violation {
+    /// JSON pointer of the offending node.
+    pub pointer: String,
+    pub rule: Rule,
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`{}`: {}", self.pointer, self.message)
+    }
+}
+
+impl Spec {
+    /// Checks the rules of the specification listed in [`Rule`], returning every violation in
+    /// document order.
+    ///
+    /// References which cannot be resolved are not reported, and the objects they point to are
+    /// not checked.
+    pub fn validate(&self) -> Vec<Violation> {
+        validate_with(self, Validator::spec)
+    }
+}
+
+/// Checks an object outside of a specification, with `f`, resolving references against `spec`.
+pub(crate) fn validate_with<'a>(
+    spec: &'a Spec,
+    f: impl FnOnce(&mut Validator<'a>),
+) -> Vec<Violation> {
+    let mut validator = Validator {
+        spec,
+        pointer: String::new(),
+        violations: Vec::new(),
+        operation_ids: HashMap::new(),
+    };
+    f(&mut validator);
+    validator.violations
+}
+
+/// Returns the names of the parameters of a path template, such as `id` for `/pets/{id}`.
+pub(crate) fn template_parameters(path: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = path;
+    while let Some(start) = rest.find('{') {
+        rest = &rest[start + 1..];
+        match rest.find('}') {
+            Some(end) => {
+                names.push(&rest[..end]);
+                rest = &rest[end + 1..];
+            }
+            None => break,
+        }
+    }
+    names
+}
+
+/// Whether `key` is a valid key of the responses of an operation.
+fn is_response_key(key: &str) -> bool {
+    let bytes = key.as_bytes();
+    match bytes {
+        [b'1'..=b'5', b'X', b'X'] => true,
+        [b'1'..=b'5', tens, units] => tens.is_ascii_digit() && units.is_ascii_digit(),
+        _ => key == "default",
+    }
+}
+
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+pub(crate) struct Validator<'a> {
+    spec: &'a Spec,
+    pointer: String,
+    violations: Vec<Violation>,
+    /// Pointers of the operations, by id.
+    operation_ids: HashMap<&'a str, String>,
+}
+
+impl<'a> Validator<'a> {
+    fn report(&mut self, rule: Rule, message: String) {
+        self.violations.push(Violation {
+            pointer: self.pointer.clone(),
+            rule,
+            message,
+        });
+    }
+
+    /// Runs `f` with `token` appended to the current pointer.
+    fn at<T>(&mut self, token: &str, f: impl FnOnce(&mut Self) -> T) -> T {
+        let len = self.pointer.len();
+        json_pointer::push(&mut self.pointer, token);
+        let out = f(self);
+        self.pointer.truncate(len);
+        out
+    }
+
+    fn map<T>(
+        &mut self,
+        key: &str,
+        map: &'a IndexMap<Str, T>,
+        mut f: impl FnMut(&mut Self, &'a T),
+    ) {
+        self.at(key, |v| {
+            for (name, value) in map {
+                v.at(name, |v| f(v, value));
+            }
+        })
+    }
+
+    fn list<T>(&mut self, key: &str, list: &'a [T], mut f: impl FnMut(&mut Self, &'a T)) {
+        self.at(key, |v| {
+            for (i, value) in list.iter().enumerate() {
+                v.at(&i.to_string(), |v| f(v, value));
+            }
+        })
+    }
+
+    /// Calls `f` with the object, unless it is a reference. Components are checked where they
+    /// are defined.
+    fn object<T>(&mut self, object: &'a ObjectOrReference<T>, f: impl FnOnce(&mut Self, &'a T)) {
+        if let ObjectOrReference::Object(object) = object {
+            f(self, object);
+        }
+    }
+
+    fn spec(&mut self) {
+        let spec = self.spec;
+        self.list("servers", &spec.servers, Self::server);
+        self.at("paths", |v| {
+            for (path, item) in &spec.paths {
+                v.at(path, |v| v.path_item(Some(path), item));
+            }
+        });
+        if let Some(components) = &spec.components {
+            self.at("components", |v| {
+                v.map("schemas", &components.schemas, |v, schema| {
+                    v.object(schema, Self::schema)
+                });
+                v.map("responses", &components.responses, |v, response| {
+                    v.object(response, Self::response)
+                });
+                v.map("parameters", &components.parameters, |v, parameter| {
+                    v.object(parameter, Self::parameter)
+                });
+                v.map("requestBodies", &components.request_bodies, |v, body| {
+                    v.object(body, Self::request_body)
+                });
+                v.map("headers", &components.headers, |v, header| {
+                    v.object(header, Self::header)
+                });
+                v.map("callbacks", &components.callbacks, |v, callback| {
+                    v.object(callback, Self::callback)
+                });
+            });
+        }
+        self.list("security", &spec.security, Self::security_requirement);
+    }
+
+    fn server(&mut self, server: &'a Server) {
+        self.map("variables", &server.variables, |v, variable| {
+            let values = &variable.substitutions_enum;
+            if !values.is_empty() && !values.contains(&variable.default) {
+                v.at("default", |v| {
+                    v.report(
+                        Rule::ServerVariable,
+                        format!(
+                            "default `{}` is not one of the `enum` values",
+                            variable.default
+                        ),
+                    )
+                });
+            }
+        });
+    }
+
+    /// Checks a path item, with the template of its path unless it is part of a callback.
+    fn path_item(&mut self, path: Option<&str>, item: &'a PathItem) {
+        self.list("servers", &item.servers, Self::server);
+        self.parameters(&item.parameters);
+        let operations = [
+            &item.get,
+            &item.put,
+            &item.post,
+            &item.delete,
+            &item.options,
+            &item.head,
+            &item.patch,
+            &item.trace,
+        ];
+        for (method, operation) in METHODS.iter().zip(operations.iter()) {
+            if let Some(operation) = operation {
+                self.at(method, |v| v.operation(path, item, operation));
+            }
+        }
+    }
+
+    fn callback(&mut self, callback: &'a Callback) {
+        for (expression, item) in callback {
+            self.at(expression, |v| v.path_item(None, item));
+        }
+    }
+
+    pub(crate) fn operation(
+        &mut self,
+        path: Option<&str>,
+        item: &'a PathItem,
+        operation: &'a Operation,
+    ) {
+        if !operation.operation_id.is_empty() {
+            let id = &*operation.operation_id;
+            match self.operation_ids.get(id) {
+                Some(first) => {
+                    let message = format!("operation id `{}` is already used by `{}`", id, first);
+                    self.at("operationId", |v| {
+                        v.report(Rule::DuplicateOperationId, message)
+                    });
+                }
+                None => {
+                    self.operation_ids.insert(id, self.pointer.clone());
+                }
+            }
+        }
+        if let Some(path) = path {
+            self.path_parameters(path, item, operation);
+        }
+        self.parameters(&operation.parameters);
+        if let Some(body) = &operation.request_body {
+            self.at("requestBody", |v| v.object(body, Self::request_body));
+        }
+        self.at("responses", |v| {
+            for (key, response) in &operation.responses {
+                v.at(key, |v| {
+                    if !is_response_key(key) {
+                        v.report(
+                            Rule::ResponseKey,
+                            format!("`{}` is not a status code, a range or `default`", key),
+                        );
+                    }
+                    v.object(response, Self::response);
+                });
+            }
+        });
+        self.map("callbacks", &operation.callbacks, |v, callback| {
+            v.object(callback, Self::callback)
+        });
+        if let Some(security) = &operation.security {
+            self.list("security", security, Self::security_requirement);
+        }
+        self.list("servers", &operation.servers, Self::server);
+    }
+
+    /// Checks that the parameters of the template of `path` are defined for an operation.
+    fn path_parameters(&mut self, path: &str, item: &'a PathItem, operation: &'a Operation) {
+        let parameters: Vec<&Parameter> = operation
+            .parameters
+            .iter()
+            .chain(&item.parameters)
+            .filter_map(|parameter| self.spec.resolve(parameter).ok())
+            .collect();
+        for name in template_parameters(path) {
+            let parameter = parameters
+                .iter()
+                .find(|p| p.location == Location::Path && p.name == name);
+            match parameter {
+                Some(parameter) if parameter.required == Some(true) => {}
+                Some(_) => self.report(
+                    Rule::PathParameter,
+                    format!("path parameter `{}` must be required", name),
+                ),
+                None => self.report(
+                    Rule::PathParameter,
+                    format!("path parameter `{}` is not defined", name),
+                ),
+            }
+        }
+    }
+
+    fn parameters(&mut self, parameters: &'a [ObjectOrReference<Parameter>]) {
+        let mut seen: Vec<(&Str, Location)> = Vec::new();
+        self.list("parameters", parameters, |v, parameter| {
+            if let Ok(resolved) = v.spec.resolve(parameter) {
+                let key = (&resolved.name, resolved.location);
+                if seen.contains(&key) {
+                    v.report(
+                        Rule::DuplicateParameter,
+                        format!(
+                            "parameter `{}` in {:?} is already defined",
+                            resolved.name, resolved.location
+                        ),
+                    );
+                } else {
+                    seen.push(key);
+                }
+            }
+            v.object(parameter, Self::parameter);
+        });
+    }
+
+    pub(crate) fn parameter(&mut self, parameter: &'a Parameter) {
+        match &parameter.representation {
+            Some(ParameterRepresentation::Simple { schema }) => {
+                self.at("schema", |v| v.component_or_inline(schema))
+            }
+            Some(ParameterRepresentation::Content { content }) => {
+                if content.len() != 1 {
+                    self.at("content", |v| {
+                        v.report(
+                            Rule::ParameterContent,
+                            format!(
+                                "`content` must have exactly one media type, found {}",
+                                content.len()
+                            ),
+                        )
+                    });
+                }
+                self.map("content", content, Self::media_type);
+            }
+            None => self.report(
+                Rule::ParameterSchema,
+                format!(
+                    "parameter `{}` has neither `schema` nor `content`",
+                    parameter.name
+                ),
+            ),
+        }
+    }
+
+    pub(crate) fn request_body(&mut self, body: &'a RequestBody) {
+        self.map("content", &body.content, Self::media_type);
+    }
+
+    pub(crate) fn response(&mut self, response: &'a Response) {
+        self.map("headers", &response.headers, |v, header| {
+            v.object(header, Self::header)
+        });
+        self.map("content", &response.content, Self::media_type);
+    }
+
+    pub(crate) fn header(&mut self, header: &'a Header) {
+        if let Some(schema) = &header.schema {
+            self.at("schema", |v| v.component_or_inline(schema));
+        }
+    }
+
+    pub(crate) fn media_type(&mut self, media: &'a MediaType) {
+        if let Some(schema) = &media.schema {
+            self.at("schema", |v| v.component_or_inline(schema));
+        }
+        self.map("encoding", &media.encoding, |v, encoding| {
+            v.map("headers", &encoding.headers, |v, header| {
+                v.object(header, Self::header)
+            })
+        });
+    }
+
+    fn component_or_inline(&mut self, schema: &'a ComponentOrInlineSchema) {
+        if let ComponentOrInlineSchema::Inline(schema) = schema {
+            self.schema(schema);
+        }
+    }
+
+    pub(crate) fn schema(&mut self, schema: &'a Schema) {
+        if schema.schema_type == Some(Type::Array) && schema.items.is_none() {
+            self.report(
+                Rule::ArrayItems,
+                "`items` must be defined for arrays".to_string(),
+            );
+        }
+        if let Some(items) = &schema.items {
+            self.at("items", |v| v.component_or_inline(items));
+        }
+        self.map("properties", &schema.properties, Self::component_or_inline);
+        if let Some(additional) = &schema.additional_properties {
+            self.at("additionalProperties", |v| {
+                v.component_or_inline(additional)
+            });
+        }
+        self.list("allOf", &schema.all_of, Self::component_or_inline);
+        self.list("oneOf", &schema.one_of, Self::component_or_inline);
+        self.list("anyOf", &schema.any_of, Self::component_or_inline);
+    }
+
+    fn security_requirement(&mut self, requirement: &'a SecurityRequirement) {
+        for (name, scopes) in requirement {
+            let scheme = self
+                .spec
+                .components
+                .as_ref()
+                .and_then(|components| components.security_schemes.get(name))
+                .map(|scheme| self.spec.resolve(scheme));
+            let scheme = match scheme {
+                Some(Ok(scheme)) => scheme,
+                // Reported elsewhere, if at all.
+                Some(Err(_)) => continue,
+                None => {
+                    self.at(name, |v| {
+                        v.report(
+                            Rule::SecurityScheme,
+                            format!("security scheme `{}` is not declared", name),
+                        )
+                    });
+                    continue;
+                }
+            };
+            let declared = |scope: &Str| match scheme {
+                SecurityScheme::OAuth2 { flows } => [
+                    flows.implicit.as_ref().map(|f| &f.scopes),
+                    flows.password.as_ref().map(|f| &f.scopes),
+                    flows.client_credentials.as_ref().map(|f| &f.scopes),
+                    flows.authorization_code.as_ref().map(|f| &f.scopes),
+                ]
+                .iter()
+                .flatten()
+                .any(|scopes| scopes.contains_key(scope)),
+                // Scopes are defined by the OpenID provider.
+                SecurityScheme::OpenIdConnect { .. } => true,
+                // The list of scopes must be empty for other schemes.
+                SecurityScheme::ApiKey { .. } | SecurityScheme::Http { .. } => false,
+            };
+            self.list(name, scopes, |v, scope| {
+                if !declared(scope) {
+                    v.report(
+                        Rule::SecurityScope,
+                        format!("scope `{}` is not declared by `{}`", scope, name),
+                    );
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r##"{
+      "openapi": "3.0.3",
+      "info": {"title": "Pets", "version": "1.0.0"},
+      "servers": [
+        {"url": "https://{env}.example.com", "variables": {"env": {"default": "dev", "enum": ["prod"]}}}
+      ],
+      "paths": {
+        "/pets/{id}/{tag}": {
+          "parameters": [{"$ref": "#/components/parameters/Id"}],
+          "get": {
+            "operationId": "getPet",
+            "parameters": [
+              {"name": "tag", "in": "path", "schema": {"type": "string"}},
+              {"name": "q", "in": "query", "content": {"text/plain": {}, "application/json": {}}},
+              {"name": "q", "in": "query", "schema": {"type": "string"}}
+            ],
+            "responses": {
+              "200": {"description": "A pet"},
+              "2XX": {"description": "Other success"},
+              "ok": {"description": "Invalid"}
+            },
+            "security": [{"oauth": ["read", "admin"]}, {"key": ["read"]}, {"missing": []}]
+          },
+          "put": {
+            "operationId": "getPet",
+            "callbacks": {
+              "onEvent": {
+                "{$request.body#/url}": {"post": {"responses": {"200": {"description": "ok"}}}}
+              }
+            },
+            "responses": {
+              "default": {
+                "description": "A pet",
+                "content": {
+                  "application/json": {
+                    "schema": {"type": "object", "properties": {"tags": {"type": "array"}}}
+                  }
+                }
+              }
+            }
+          }
+        }
+      },
+      "components": {
+        "schemas": {
+          "Tags": {"type": "array"}
+        },
+        "parameters": {
+          "Id": {"name": "id", "in": "path", "required": true}
+        },
+        "securitySchemes": {
+          "key": {"type": "apiKey", "name": "key", "in": "header"},
+          "oauth": {
+            "type": "oauth2",
+            "flows": {
+              "implicit": {
+                "authorizationUrl": "https://example.com/auth",
+                "scopes": {"read": "Read pets"}
+              }
+            }
+          }
+        }
+      }
+    }"##;
+
+    #[test]
+    fn test_validate() {
+        let spec: Spec = serde_json::from_str(SPEC).unwrap();
+        let violations: Vec<_> = spec
+            .validate()
+            .into_iter()
+            .map(|v| (v.rule, v.pointer))
+            .collect();
+        let path = "/paths/~1pets~1{id}~1{tag}";
+        assert_eq!(
+            violations,
+            [
+                (
+                    Rule::ServerVariable,
+                    "/servers/0/variables/env/default".to_string()
+                ),
+                (Rule::PathParameter, format!("{}/get", path)),
+                (
+                    Rule::ParameterContent,
+                    format!("{}/get/parameters/1/content", path)
+                ),
+                (
+                    Rule::DuplicateParameter,
+                    format!("{}/get/parameters/2", path)
+                ),
+                (Rule::ResponseKey, format!("{}/get/responses/ok", path)),
+                (
+                    Rule::SecurityScope,
+                    format!("{}/get/security/0/oauth/1", path)
+                ),
+                (
+                    Rule::SecurityScope,
+                    format!("{}/get/security/1/key/0", path)
+                ),
+                (
+                    Rule::SecurityScheme,
+                    format!("{}/get/security/2/missing", path)
+                ),
+                (
+                    Rule::DuplicateOperationId,
+                    format!("{}/put/operationId", path)
+                ),
+                (Rule::PathParameter, format!("{}/put", path)),
+                (
+                    Rule::ArrayItems,
+                    format!(
+                        "{}/put/responses/default/content/application~1json/schema/properties/tags",
+                        path
+                    )
+                ),
+                (Rule::ArrayItems, "/components/schemas/Tags".to_string()),
+                (
+                    Rule::ParameterSchema,
+                    "/components/parameters/Id".to_string()
+                ),
+            ]
+        );
+
+        let messages: Vec<_> = spec.validate().iter().map(ToString::to_string).collect();
+        assert_eq!(
+            messages[1],
+            format!("`{}/get`: path parameter `tag` must be required", path)
+        );
+        assert_eq!(
+            messages[8],
+            format!(
+                "`{}/put/operationId`: operation id `getPet` is already used by `{}/get`",
+                path, path
+            )
+        );
+        assert_eq!(
+            messages[9],
+            format!("`{}/put`: path parameter `tag` is not defined", path)
+        );
+    }
+
+    #[test]
+    fn test_helpers() {
+        assert_eq!(template_parameters("/pets/{id}/tags/{tag}"), ["id", "tag"]);
+        assert_eq!(template_parameters("/pets/{id"), Vec::<&str>::new());
+        assert!(is_response_key("200"));
+        assert!(is_response_key("4XX"));
+        assert!(is_response_key("default"));
+        assert!(!is_response_key("600"));
+        assert!(!is_response_key("2xx"));
+        assert!(!is_response_key("20"));
+    }
+}
@@ -0,0 +1,967 @@
+//! Traversal of the tree of objects of a specification.
+//!
+//! [`Visit`] and [`VisitMut`] have one method per type of object, called with the JSON pointer of
+//! the object within the document. By default, each method calls the `walk` function of the
+//! same name, which visits the children of the object. Overriding methods can call it too, to
+//! keep recursing:
+//!
+//! ```
+//! use rweb_openapi::v3_0::{visit::{self, Visit}, Operation, Spec};
+//!
+//! #[derive(Default)]
+//! struct OperationIds<'a>(Vec<&'a str>);
+//!
+//! impl<'a> Visit<'a> for OperationIds<'a> {
+//!     fn visit_operation(&mut self, pointer: &str, operation: &'a Operation) {
+//!         self.0.push(&operation.operation_id);
+//!         visit::walk_operation(self, pointer, operation);
+//!     }
+//! }
+//!
+//! let spec = Spec::default();
+//! let mut ids = OperationIds::default();
+//! spec.visit(&mut ids);
+//! assert!(ids.0.is_empty());
+//! ```
+//!
+//! References are not followed, and components are visited once from [`Spec`].
+
+use crate::{
+    json_pointer,
+    v3_0::{
+        diff::METHODS, Callback, ComponentOrInlineSchema, Components, Encoding, Example, Header,
+        Link, MediaType, MediaTypeExample, ObjectOrReference, Operation, Parameter,
+        ParameterExamples, ParameterRepresentation, PathItem, RequestBody, Response, Schema,
+        SecurityScheme, Spec,
+    },
+    Str,
+};
+use indexmap::IndexMap;
+
+impl Spec {
+    /// Visits this specification and all of its objects.
+    pub fn visit<'a, V: Visit<'a> + ?Sized>(&'a self, visitor: &mut V) {
+        visitor.visit_spec("", self);
+    }
+
+    /// Visits this specification and all of its objects, which may be modified.
+    pub fn visit_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+        visitor.visit_spec_mut("", self);
+    }
+}
+
+/// Visitor of the objects of a specification, borrowed for `'a`.
+pub trait Visit<'a> {
+    fn visit_spec(&mut self, pointer: &str, spec: &'a Spec) {
+        walk_spec(self, pointer, spec)
+    }
+
+    fn visit_components(&mut self, pointer: &str, components: &'a Components) {
+        walk_components(self, pointer, components)
+    }
+
+    fn visit_path_item(&mut self, pointer: &str, item: &'a PathItem) {
+        walk_path_item(self, pointer, item)
+    }
+
+    fn visit_operation(&mut self, pointer: &str, operation: &'a Operation) {
+        walk_operation(self, pointer, operation)
+    }
+
+    fn visit_parameter(&mut self, pointer: &str, parameter: &'a Parameter) {
+        walk_parameter(self, pointer, parameter)
+    }
+
+    fn visit_request_body(&mut self, pointer: &str, body: &'a RequestBody) {
+        walk_request_body(self, pointer, body)
+    }
+
+    fn visit_response(&mut self, pointer: &str, response: &'a Response) {
+        walk_response(self, pointer, response)
+    }
+
+    fn visit_header(&mut self, pointer: &str, header: &'a Header) {
+        walk_header(self, pointer, header)
+    }
+
+    fn visit_media_type(&mut self, pointer: &str, media: &'a MediaType) {
+        walk_media_type(self, pointer, media)
+    }
+
+    fn visit_encoding(&mut self, pointer: &str, encoding: &'a Encoding) {
+        walk_encoding(self, pointer, encoding)
+    }
+
+    fn visit_callback(&mut self, pointer: &str, callback: &'a Callback) {
+        walk_callback(self, pointer, callback)
+    }
+
+    /// Visits a schema, or a reference to a component schema.
+    fn visit_component_or_inline_schema(
+        &mut self,
+        pointer: &str,
+        schema: &'a ComponentOrInlineSchema,
+    ) {
+        walk_component_or_inline_schema(self, pointer, schema)
+    }
+
+    fn visit_schema(&mut self, pointer: &str, schema: &'a Schema) {
+        walk_schema(self, pointer, schema)
+    }
+
+    fn visit_example(&mut self, _pointer: &str, _example: &'a Example) {}
+
+    fn visit_link(&mut self, _pointer: &str, _link: &'a Link) {}
+
+    fn visit_security_scheme(&mut self, _pointer: &str, _scheme: &'a SecurityScheme) {}
+
+    /// Visits a reference, held by an [`ObjectOrReference::Ref`], a
+    /// [`ComponentOrInlineSchema::ExtRef`] or a [`PathItem::reference`].
+    ///
+    /// References to component schemas are only visited as a
+    /// [`ComponentOrInlineSchema::Component`].
+    fn visit_reference(&mut self, _pointer: &str, _reference: &'a Str) {}
+}
+
+/// Visitor of the objects of a specification, which may modify them.
+pub trait VisitMut {
+    fn visit_spec_mut(&mut self, pointer: &str, spec: &mut Spec) {
+        walk_spec_mut(self, pointer, spec)
+    }
+
+    fn visit_components_mut(&mut self, pointer: &str, components: &mut Components) {
+        walk_components_mut(self, pointer, components)
+    }
+
+    fn visit_path_item_mut(&mut self, pointer: &str, item: &mut PathItem) {
+        walk_path_item_mut(self, pointer, item)
+    }
+
+    fn visit_operation_mut(&mut self, pointer: &str, operation: &mut Operation) {
+        walk_operation_mut(self, pointer, operation)
+    }
+
+    fn visit_parameter_mut(&mut self, pointer: &str, parameter: &mut Parameter) {
+        walk_parameter_mut(self, pointer, parameter)
+    }
+
+    fn visit_request_body_mut(&mut self, pointer: &str, body: &mut RequestBody) {
+        walk_request_body_mut(self, pointer, body)
+    }
+
+    fn visit_response_mut(&mut self, pointer: &str, response: &mut Response) {
+        walk_response_mut(self, pointer, response)
+    }
+
+    fn visit_header_mut(&mut self, pointer: &str, header: &mut Header) {
+        walk_header_mut(self, pointer, header)
+    }
+
+    fn visit_media_type_mut(&mut self, pointer: &str, media: &mut MediaType) {
+        walk_media_type_mut(self, pointer, media)
+    }
+
+    fn visit_encoding_mut(&mut self, pointer: &str, encoding: &mut Encoding) {
+        walk_encoding_mut(self, pointer, encoding)
+    }
+
+    fn visit_callback_mut(&mut self, pointer: &str, callback: &mut Callback) {
+        walk_callback_mut(self, pointer, callback)
+    }
+
+    /// Visits a schema, or a reference to a component schema.
+    fn visit_component_or_inline_schema_mut(
+        &mut self,
+        pointer: &str,
+        schema: &mut ComponentOrInlineSchema,
+    ) {
+        walk_component_or_inline_schema_mut(self, pointer, schema)
+    }
+
+    fn visit_schema_mut(&mut self, pointer: &str, schema: &mut Schema) {
+        walk_schema_mut(self, pointer, schema)
+    }
+
+    fn visit_example_mut(&mut self, _pointer: &str, _example: &mut Example) {}
+
+    fn visit_link_mut(&mut self, _pointer: &str, _link: &mut Link) {}
+
+    fn visit_security_scheme_mut(&mut self, _pointer: &str, _scheme: &mut SecurityScheme) {}
+
+    /// Visits a reference, as [`Visit::visit_reference`] does.
+    fn visit_reference_mut(&mut self, _pointer: &str, _reference: &mut Str) {}
+}
+
+fn child(pointer: &str, token: &str) -> String {
+    let mut pointer = pointer.to_string();
+    json_pointer::push(&mut pointer, token);
+    pointer
+}
+
+fn object_or_reference<'a, V, T>(
+    visitor: &mut V,
+    pointer: &str,
+    object: &'a ObjectOrReference<T>,
+    visit: fn(&mut V, &str, &'a T),
+) where
+    V: Visit<'a> + ?Sized,
+{
+    match object {
+        ObjectOrReference::Object(object) => visit(visitor, pointer, object),
+        ObjectOrReference::Ref { ref_path } => visitor.visit_reference(pointer, ref_path),
+    }
+}
+
+fn map<'a, V, T>(
+    visitor: &mut V,
+    pointer: &str,
+    key: &str,
+    objects: &'a IndexMap<Str, ObjectOrReference<T>>,
+    visit: fn(&mut V, &str, &'a T),
+) where
+    V: Visit<'a> + ?Sized,
+{
+    let pointer = child(pointer, key);
+    for (name, object) in objects {
+        object_or_reference(visitor, &child(&pointer, name), object, visit);
+    }
+}
+
+fn list<'a, V, T>(
+    visitor: &mut V,
+    pointer: &str,
+    key: &str,
+    objects: &'a [ObjectOrReference<T>],
+    visit: fn(&mut V, &str, &'a T),
+) where
+    V: Visit<'a> + ?Sized,
+{
+    let pointer = child(pointer, key);
+    for (i, object) in objects.iter().enumerate() {
+        object_or_reference(visitor, &child(&pointer, &i.to_string()), object, visit);
+    }
+}
+
+fn content<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    pointer: &str,
+    content: &'a IndexMap<Str, MediaType>,
+) {
+    let pointer = child(pointer, "content");
+    for (name, media) in content {
+        visitor.visit_media_type(&child(&pointer, name), media);
+    }
+}
+
+pub fn walk_spec<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, pointer: &str, spec: &'a Spec) {
+    let paths = child(pointer, "paths");
+    for (path, item) in &spec.paths {
+        visitor.visit_path_item(&child(&paths, path), item);
+    }
+    if let Some(components) = &spec.components {
+        visitor.visit_components(&child(pointer, "components"), components);
+    }
+}
+
+pub fn walk_components<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    pointer: &str,
+    components: &'a Components,
+) {
+    map(
+        visitor,
+        pointer,
+        "schemas",
+        &components.schemas,
+        V::visit_schema,
+    );
+    map(
+        visitor,
+        pointer,
+        "responses",
+        &components.responses,
+        V::visit_response,
+    );
+    map(
+        visitor,
+        pointer,
+        "parameters",
+        &components.parameters,
+        V::visit_parameter,
+    );
+    map(
+        visitor,
+        pointer,
+        "examples",
+        &components.examples,
+        V::visit_example,
+    );
+    let bodies = &components.request_bodies;
+    map(
+        visitor,
+        pointer,
+        "requestBodies",
+        bodies,
+        V::visit_request_body,
+    );
+    map(
+        visitor,
+        pointer,
+        "headers",
+        &components.headers,
+        V::visit_header,
+    );
+    let schemes = &components.security_schemes;
+    map(
+        visitor,
+        pointer,
+        "securitySchemes",
+        schemes,
+        V::visit_security_scheme,
+    );
+    map(visitor, pointer, "links", &components.links, V::visit_link);
+    map(
+        visitor,
+        pointer,
+        "callbacks",
+        &components.callbacks,
+        V::visit_callback,
+    );
+}
+
+pub fn walk_path_item<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    pointer: &str,
+    item: &'a PathItem,
+) {
+    if !item.reference.is_empty() {
+        visitor.visit_reference(&child(pointer, "$ref"), &item.reference);
+    }
+    let operations = [
+        &item.get,
+        &item.put,
+        &item.post,
+        &item.delete,
+        &item.options,
+        &item.head,
+        &item.patch,
+        &item.trace,
+    ];
+    for (method, operation) in METHODS.iter().zip(operations.iter()) {
+        if let Some(operation) = operation {
+            visitor.visit_operation(&child(pointer, method), operation);
+        }
+    }
+    list(
+        visitor,
+        pointer,
+        "parameters",
+        &item.parameters,
+        V::visit_parameter,
+    );
+}
+
+pub fn walk_operation<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    pointer: &str,
+    operation: &'a Operation,
+) {
+    list(
+        visitor,
+        pointer,
+        "parameters",
+        &operation.parameters,
+        V::visit_parameter,
+    );
+    if let Some(body) = &operation.request_body {
+        let pointer = child(pointer, "requestBody");
+        object_or_reference(visitor, &pointer, body, V::visit_request_body);
+    }
+    map(
+        visitor,
+        pointer,
+        "responses",
+        &operation.responses,
+        V::visit_response,
+    );
+    map(
+        visitor,
+        pointer,
+        "callbacks",
+        &operation.callbacks,
+        V::visit_callback,
+    );
+}
+
+pub fn walk_parameter<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    pointer: &str,
+    parameter: &'a Parameter,
+) {
+    match &parameter.representation {
+        Some(ParameterRepresentation::Simple { schema }) => {
+            visitor.visit_component_or_inline_schema(&child(pointer, "schema"), schema)
+        }
+        Some(ParameterRepresentation::Content { content: media }) => {
+            content(visitor, pointer, media)
+        }
+        None => {}
+    }
+    if let Some(ParameterExamples::Multiple { examples }) = &parameter.example {
+        map(visitor, pointer, "examples", examples, V::visit_example);
+    }
+}
+
+pub fn walk_request_body<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    pointer: &str,
+    body: &'a RequestBody,
+) {
+    content(visitor, pointer, &body.content);
+}
+
+pub fn walk_response<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    pointer: &str,
+    response: &'a Response,
+) {
+    map(
+        visitor,
+        pointer,
+        "headers",
+        &response.headers,
+        V::visit_header,
+    );
+    content(visitor, pointer, &response.content);
+    map(visitor, pointer, "links", &response.links, V::visit_link);
+}
+
+pub fn walk_header<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, pointer: &str, header: &'a Header) {
+    if let Some(schema) = &header.schema {
+        visitor.visit_component_or_inline_schema(&child(pointer, "schema"), schema);
+    }
+}
+
+pub fn walk_media_type<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    pointer: &str,
+    media: &'a MediaType,
+) {
+    if let Some(schema) = &media.schema {
+        visitor.visit_component_or_inline_schema(&child(pointer, "schema"), schema);
+    }
+    if let Some(MediaTypeExample::Examples { examples }) = &media.examples {
+        map(visitor, pointer, "examples", examples, V::visit_example);
+    }
+    let encodings = child(pointer, "encoding");
+    for (name, encoding) in &media.encoding {
+        visitor.visit_encoding(&child(&encodings, name), encoding);
+    }
+}
+
+pub fn walk_encoding<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    pointer: &str,
+    encoding: &'a Encoding,
+) {
+    map(
+        visitor,
+        pointer,
+        "headers",
+        &encoding.headers,
+        V::visit_header,
+    );
+}
+
+pub fn walk_callback<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    pointer: &str,
+    callback: &'a Callback,
+) {
+    for (expression, item) in callback {
+        visitor.visit_path_item(&child(pointer, expression), item);
+    }
+}
+
+pub fn walk_component_or_inline_schema<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    pointer: &str,
+    schema: &'a ComponentOrInlineSchema,
+) {
+    match schema {
+        ComponentOrInlineSchema::Component { .. } => {}
+        ComponentOrInlineSchema::ExtRef { reference } => {
+            visitor.visit_reference(&child(pointer, "$ref"), reference)
+        }
+        ComponentOrInlineSchema::Inline(schema) => visitor.visit_schema(pointer, schema),
+    }
+}
+
+pub fn walk_schema<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, pointer: &str, schema: &'a Schema) {
+    if let Some(items) = &schema.items {
+        visitor.visit_component_or_inline_schema(&child(pointer, "items"), items);
+    }
+    let properties = child(pointer, "properties");
+    for (name, property) in &schema.properties {
+        visitor.visit_component_or_inline_schema(&child(&properties, name), property);
+    }
+    if let Some(additional) = &schema.additional_properties {
+        let pointer = child(pointer, "additionalProperties");
+        visitor.visit_component_or_inline_schema(&pointer, additional);
+    }
+    for (key, schemas) in [
+        ("allOf", &schema.all_of),
+        ("oneOf", &schema.one_of),
+        ("anyOf", &schema.any_of),
+    ]
+    .iter()
+    {
+        let pointer = child(pointer, key);
+        for (i, schema) in schemas.iter().enumerate() {
+            visitor.visit_component_or_inline_schema(&child(&pointer, &i.to_string()), schema);
+        }
+    }
+}
+
+fn object_or_reference_mut<V, T>(
+    visitor: &mut V,
+    pointer: &str,
+    object: &mut ObjectOrReference<T>,
+    visit: fn(&mut V, &str, &mut T),
+) where
+    V: VisitMut + ?Sized,
+{
+    match object {
+        ObjectOrReference::Object(object) => visit(visitor, pointer, object),
+        ObjectOrReference::Ref { ref_path } => visitor.visit_reference_mut(pointer, ref_path),
+    }
+}
+
+fn map_mut<V, T>(
+    visitor: &mut V,
+    pointer: &str,
+    key: &str,
+    objects: &mut IndexMap<Str, ObjectOrReference<T>>,
+    visit: fn(&mut V, &str, &mut T),
+) where
+    V: VisitMut + ?Sized,
+{
+    let pointer = child(pointer, key);
+    for (name, object) in objects {
+        object_or_reference_mut(visitor, &child(&pointer, name), object, visit);
+    }
+}
+
+fn list_mut<V, T>(
+    visitor: &mut V,
+    pointer: &str,
+    key: &str,
+    objects: &mut [ObjectOrReference<T>],
+    visit: fn(&mut V, &str, &mut T),
+) where
+    V: VisitMut + ?Sized,
+{
+    let pointer = child(pointer, key);
+    for (i, object) in objects.iter_mut().enumerate() {
+        object_or_reference_mut(visitor, &child(&pointer, &i.to_string()), object, visit);
+    }
+}
+
+fn content_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    pointer: &str,
+    content: &mut IndexMap<Str, MediaType>,
+) {
+    let pointer = child(pointer, "content");
+    for (name, media) in content {
+        visitor.visit_media_type_mut(&child(&pointer, name), media);
+    }
+}
+
+pub fn walk_spec_mut<V: VisitMut + ?Sized>(visitor: &mut V, pointer: &str, spec: &mut Spec) {
+    let paths = child(pointer, "paths");
+    for (path, item) in &mut spec.paths {
+        visitor.visit_path_item_mut(&child(&paths, path), item);
+    }
+    if let Some(components) = &mut spec.components {
+        visitor.visit_components_mut(&child(pointer, "components"), components);
+    }
+}
+
+pub fn walk_components_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    pointer: &str,
+    components: &mut Components,
+) {
+    let schemas = &mut components.schemas;
+    map_mut(visitor, pointer, "schemas", schemas, V::visit_schema_mut);
+    let responses = &mut components.responses;
+    map_mut(
+        visitor,
+        pointer,
+        "responses",
+        responses,
+        V::visit_response_mut,
+    );
+    let parameters = &mut components.parameters;
+    map_mut(
+        visitor,
+        pointer,
+        "parameters",
+        parameters,
+        V::visit_parameter_mut,
+    );
+    let examples = &mut components.examples;
+    map_mut(visitor, pointer, "examples", examples, V::visit_example_mut);
+    let bodies = &mut components.request_bodies;
+    map_mut(
+        visitor,
+        pointer,
+        "requestBodies",
+        bodies,
+        V::visit_request_body_mut,
+    );
+    let headers = &mut components.headers;
+    map_mut(visitor, pointer, "headers", headers, V::visit_header_mut);
+    let schemes = &mut components.security_schemes;
+    map_mut(
+        visitor,
+        pointer,
+        "securitySchemes",
+        schemes,
+        V::visit_security_scheme_mut,
+    );
+    map_mut(
+        visitor,
+        pointer,
+        "links",
+        &mut components.links,
+        V::visit_link_mut,
+    );
+    let callbacks = &mut components.callbacks;
+    map_mut(
+        visitor,
+        pointer,
+        "callbacks",
+        callbacks,
+        V::visit_callback_mut,
+    );
+}
+
+pub fn walk_path_item_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    pointer: &str,
+    item: &mut PathItem,
+) {
+    if !item.reference.is_empty() {
+        visitor.visit_reference_mut(&child(pointer, "$ref"), &mut item.reference);
+    }
+    let operations = [
+        &mut item.get,
+        &mut item.put,
+        &mut item.post,
+        &mut item.delete,
+        &mut item.options,
+        &mut item.head,
+        &mut item.patch,
+        &mut item.trace,
+    ];
+    for (method, operation) in METHODS.iter().zip(operations) {
+        if let Some(operation) = operation {
+            visitor.visit_operation_mut(&child(pointer, method), operation);
+        }
+    }
+    let parameters = &mut item.parameters;
+    list_mut(
+        visitor,
+        pointer,
+        "parameters",
+        parameters,
+        V::visit_parameter_mut,
+    );
+}
+
+pub fn walk_operation_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    pointer: &str,
+    operation: &mut Operation,
+) {
+    let parameters = &mut operation.parameters;
+    list_mut(
+        visitor,
+        pointer,
+        "parameters",
+        parameters,
+        V::visit_parameter_mut,
+    );
+    if let Some(body) = &mut operation.request_body {
+        let pointer = child(pointer, "requestBody");
+        object_or_reference_mut(visitor, &pointer, body, V::visit_request_body_mut);
+    }
+    let responses = &mut operation.responses;
+    map_mut(
+        visitor,
+        pointer,
+        "responses",
+        responses,
+        V::visit_response_mut,
+    );
+    let callbacks = &mut operation.callbacks;
+    map_mut(
+        visitor,
+        pointer,
+        "callbacks",
+        callbacks,
+        V::visit_callback_mut,
+    );
+}
+
+pub fn walk_parameter_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    pointer: &str,
+    parameter: &mut Parameter,
+) {
+    match &mut parameter.representation {
+        Some(ParameterRepresentation::Simple { schema }) => {
+            visitor.visit_component_or_inline_schema_mut(&child(pointer, "schema"), schema)
+        }
+        Some(ParameterRepresentation::Content { content }) => {
+            content_mut(visitor, pointer, content)
+        }
+        None => {}
+    }
+    if let Some(ParameterExamples::Multiple { examples }) = &mut parameter.example {
+        map_mut(visitor, pointer, "examples", examples, V::visit_example_mut);
+    }
+}
+
+pub fn walk_request_body_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    pointer: &str,
+    body: &mut RequestBody,
+) {
+    content_mut(visitor, pointer, &mut body.content);
+}
+
+pub fn walk_response_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    pointer: &str,
+    response: &mut Response,
+) {
+    let headers = &mut response.headers;
+    map_mut(visitor, pointer, "headers", headers, V::visit_header_mut);
+    content_mut(visitor, pointer, &mut response.content);
+    map_mut(
+        visitor,
+        pointer,
+        "links",
+        &mut response.links,
+        V::visit_link_mut,
+    );
+}
+
+pub fn walk_header_mut<V: VisitMut + ?Sized>(visitor: &mut V, pointer: &str, header: &mut Header) {
+    if let Some(schema) = &mut header.schema {
+        visitor.visit_component_or_inline_schema_mut(&child(pointer, "schema"), schema);
+    }
+}
+
+pub fn walk_media_type_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    pointer: &str,
+    media: &mut MediaType,
+) {
+    if let Some(schema) = &mut media.schema {
+        visitor.visit_component_or_inline_schema_mut(&child(pointer, "schema"), schema);
+    }
+    if let Some(MediaTypeExample::Examples { examples }) = &mut media.examples {
+        map_mut(visitor, pointer, "examples", examples, V::visit_example_mut);
+    }
+    let encodings = child(pointer, "encoding");
+    for (name, encoding) in &mut media.encoding {
+        visitor.visit_encoding_mut(&child(&encodings, name), encoding);
+    }
+}
+
+pub fn walk_encoding_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    pointer: &str,
+    encoding: &mut Encoding,
+) {
+    let headers = &mut encoding.headers;
+    map_mut(visitor, pointer, "headers", headers, V::visit_header_mut);
+}
+
+pub fn walk_callback_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    pointer: &str,
+    callback: &mut Callback,
+) {
+    for (expression, item) in callback {
+        visitor.visit_path_item_mut(&child(pointer, expression), item);
+    }
+}
+
+pub fn walk_component_or_inline_schema_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    pointer: &str,
+    schema: &mut ComponentOrInlineSchema,
+) {
+    match schema {
+        ComponentOrInlineSchema::Component { .. } => {}
+        ComponentOrInlineSchema::ExtRef { reference } => {
+            visitor.visit_reference_mut(&child(pointer, "$ref"), reference)
+        }
+        ComponentOrInlineSchema::Inline(schema) => visitor.visit_schema_mut(pointer, schema),
+    }
+}
+
+pub fn walk_schema_mut<V: VisitMut + ?Sized>(visitor: &mut V, pointer: &str, schema: &mut Schema) {
+    if let Some(items) = &mut schema.items {
+        visitor.visit_component_or_inline_schema_mut(&child(pointer, "items"), items);
+    }
+    let properties = child(pointer, "properties");
+    for (name, property) in &mut schema.properties {
+        visitor.visit_component_or_inline_schema_mut(&child(&properties, name), property);
+    }
+    if let Some(additional) = &mut schema.additional_properties {
+        let pointer = child(pointer, "additionalProperties");
+        visitor.visit_component_or_inline_schema_mut(&pointer, additional);
+    }
+    for (key, schemas) in [
+        ("allOf", &mut schema.all_of),
+        ("oneOf", &mut schema.one_of),
+        ("anyOf", &mut schema.any_of),
+    ] {
+        let pointer = child(pointer, key);
+        for (i, schema) in schemas.iter_mut().enumerate() {
+            let pointer = child(&pointer, &i.to_string());
+            visitor.visit_component_or_inline_schema_mut(&pointer, schema);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r##"{
+      "openapi": "3.0.3",
+      "info": {"title": "Pets", "version": "1.0.0"},
+      "paths": {
+        "/pets/{id}": {
+          "parameters": [{"$ref": "#/components/parameters/Id"}],
+          "get": {
+            "responses": {
+              "200": {
+                "description": "A pet",
+                "headers": {"X-Rate": {"schema": {"type": "integer"}}},
+                "content": {
+                  "application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}
+                }
+              }
+            }
+          }
+        }
+      },
+      "components": {
+        "parameters": {
+          "Id": {"name": "id", "in": "path", "required": true, "schema": {"type": "string"}}
+        },
+        "schemas": {
+          "Pet": {
+            "type": "object",
+            "properties": {
+              "tags": {"type": "array", "items": {"type": "string"}},
+              "owner": {"oneOf": [{"$ref": "other.yaml#/Owner"}, {"type": "string"}]}
+            },
+            "additionalProperties": {"$ref": "#/components/schemas/Pet"}
+          }
+        }
+      }
+    }"##;
+
+    #[derive(Default)]
+    struct Collect<'a> {
+        visited: Vec<String>,
+        references: Vec<(String, &'a str)>,
+    }
+
+    impl<'a> Visit<'a> for Collect<'a> {
+        fn visit_component_or_inline_schema(
+            &mut self,
+            pointer: &str,
+            schema: &'a ComponentOrInlineSchema,
+        ) {
+            self.visited.push(pointer.to_string());
+            walk_component_or_inline_schema(self, pointer, schema);
+        }
+
+        fn visit_reference(&mut self, pointer: &str, reference: &'a Str) {
+            self.references.push((pointer.to_string(), reference));
+        }
+    }
+
+    #[test]
+    fn test_visit() {
+        let spec: Spec = serde_json::from_str(SPEC).unwrap();
+        let mut collect = Collect::default();
+        spec.visit(&mut collect);
+        let pet = "/components/schemas/Pet";
+        assert_eq!(
+            collect.visited,
+            [
+                "/paths/~1pets~1{id}/get/responses/200/headers/X-Rate/schema".to_string(),
+                "/paths/~1pets~1{id}/get/responses/200/content/application~1json/schema".into(),
+                format!("{}/properties/tags", pet),
+                format!("{}/properties/tags/items", pet),
+                format!("{}/properties/owner", pet),
+                format!("{}/properties/owner/oneOf/0", pet),
+                format!("{}/properties/owner/oneOf/1", pet),
+                format!("{}/additionalProperties", pet),
+                "/components/parameters/Id/schema".into(),
+            ]
+        );
+        assert_eq!(
+            collect.references,
+            [
+                (
+                    "/paths/~1pets~1{id}/parameters/0".to_string(),
+                    "#/components/parameters/Id"
+                ),
+                (
+                    format!("{}/properties/owner/oneOf/0/$ref", pet),
+                    "other.yaml#/Owner"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_visit_mut() {
+        /// Makes every string schema a UUID.
+        struct Uuids(usize);
+
+        impl VisitMut for Uuids {
+            fn visit_schema_mut(&mut self, pointer: &str, schema: &mut Schema) {
+                if schema.schema_type == Some(crate::v3_0::Type::String) {
+                    schema.format = "uuid".into();
+                    self.0 += 1;
+                }
+                walk_schema_mut(self, pointer, schema);
+            }
+        }
+
+        let mut spec: Spec = serde_json::from_str(SPEC).unwrap();
+        let mut uuids = Uuids(0);
+        spec.visit_mut(&mut uuids);
+        assert_eq!(uuids.0, 3);
+        let parameter = spec.resolve_ref::<Parameter>("#/components/parameters/Id");
+        match &parameter.unwrap().representation {
+            Some(ParameterRepresentation::Simple { schema }) => {
+                assert_eq!(schema.unwrap().unwrap().format, "uuid")
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+}
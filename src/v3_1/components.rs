@@ -0,0 +1,93 @@
+use crate::{
+    v3_1::{
+        Callback, Example, Header, Link, Parameter, PathItem, RequestBody, Response, Schema,
+        SecurityScheme,
+    },
+    Extensions, Str,
+};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+/// Either an object, or a
+/// [Reference Object](https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.1.0.md#referenceObject)
+/// pointing to one.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum ObjectOrReference<T> {
+    Ref {
+        #[serde(rename = "$ref")]
+        ref_path: Str,
+        /// A short summary which by default SHOULD override that of the referenced component.
+        #[serde(default, skip_serializing_if = "str::is_empty")]
+        summary: Str,
+        /// A description which by default SHOULD override that of the referenced component.
+        #[serde(default, skip_serializing_if = "str::is_empty")]
+        description: Str,
+    },
+    Object(T),
+}
+
+/// Holds a set of reusable objects for different aspects of the OAS.
+///
+/// All objects defined within the components object will have no effect on the API unless
+/// they are explicitly referenced from properties outside the components object.
+///
+/// See <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.1.0.md#componentsObject>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Components {
+    /// An object to hold reusable Schema Objects.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub schemas: IndexMap<Str, Schema>,
+
+    /// An object to hold reusable Response Objects.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub responses: IndexMap<Str, ObjectOrReference<Response>>,
+
+    /// An object to hold reusable Parameter Objects.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub parameters: IndexMap<Str, ObjectOrReference<Parameter>>,
+
+    /// An object to hold reusable Example Objects.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub examples: IndexMap<Str, ObjectOrReference<Example>>,
+
+    /// An object to hold reusable Request Body Objects.
+    #[serde(
+        default,
+        skip_serializing_if = "IndexMap::is_empty",
+        rename = "requestBodies"
+    )]
+    pub request_bodies: IndexMap<Str, ObjectOrReference<RequestBody>>,
+
+    /// An object to hold reusable Header Objects.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub headers: IndexMap<Str, ObjectOrReference<Header>>,
+
+    /// An object to hold reusable Security Scheme Objects.
+    #[serde(
+        default,
+        skip_serializing_if = "IndexMap::is_empty",
+        rename = "securitySchemes"
+    )]
+    pub security_schemes: IndexMap<Str, ObjectOrReference<SecurityScheme>>,
+
+    /// An object to hold reusable Link Objects.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub links: IndexMap<Str, ObjectOrReference<Link>>,
+
+    /// An object to hold reusable Callback Objects.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub callbacks: IndexMap<Str, ObjectOrReference<Callback>>,
+
+    /// An object to hold reusable Path Item Objects.
+    #[serde(
+        default,
+        skip_serializing_if = "IndexMap::is_empty",
+        rename = "pathItems"
+    )]
+    pub path_items: IndexMap<Str, ObjectOrReference<PathItem>>,
+
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
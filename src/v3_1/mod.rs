@@ -0,0 +1,18 @@
+//! Support for OpenApi version 3.1.0 specification.
+//!
+//! See the
+//! [specification](https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.1.0.md)
+//! for more information.
+//!
+//! Objects that did not change since 3.0 are re-exported from [`v3_0`](crate::v3_0).
+
+mod components;
+mod schema;
+mod upgrade;
+
+pub use crate::v3_0::{
+    AuthorizationCodeFlow, ClientCredentialsFlow, Contact, Example, ExampleValue, ExternalDoc,
+    Flows, ImplicitFlow, Link, LinkOperation, ParameterStyle, PasswordFlow,
+    RuntimeExpressionOrValue, SecurityRequirement, Server, ServerVariable,
+};
+pub use crate::v3_1::{components::*, schema::*, upgrade::*};
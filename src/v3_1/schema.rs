@@ -0,0 +1,959 @@
+//! Schema specification for [OpenAPI 3.1.0](https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.1.0.md)
+
+use crate::{
+    v3_1::{
+        components::{Components, ObjectOrReference},
+        Contact, Example, ExternalDoc, Flows, Link, ParameterStyle, SecurityRequirement, Server,
+    },
+    Extensions, Str,
+};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// The default value of
+/// [`jsonSchemaDialect`](https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.1.0.md#oasJsonSchemaDialect).
+pub const OAS_SCHEMA_DIALECT: &str = "https://spec.openapis.org/oas/3.1/dialect/base";
+
+/// top level document
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Spec {
+    /// This string MUST be the version number of the OpenAPI Specification that the OpenAPI
+    /// document uses. The `openapi` field SHOULD be used by tooling to interpret the OpenAPI
+    /// document. This is not related to the API
+    /// [`info.version`](https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.1.0.md#infoVersion)
+    /// string.
+    pub openapi: Str,
+
+    /// Provides metadata about the API. The metadata MAY be used by tooling as required.
+    pub info: Info,
+
+    /// The default value for the `$schema` keyword within
+    /// [Schema Objects](https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.1.0.md#schemaObject)
+    /// contained within this OAS document. This MUST be in the form of a URI.
+    #[serde(
+        default,
+        skip_serializing_if = "str::is_empty",
+        rename = "jsonSchemaDialect"
+    )]
+    pub json_schema_dialect: Str,
+
+    /// An array of Server Objects, which provide connectivity information to a target server.
+    /// If the `servers` property is not provided, or is an empty array, the default value would
+    /// be a Server Object with a url value of `/`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub servers: Vec<Server>,
+
+    /// The available paths and operations for the API. Unlike 3.0, the paths MAY be omitted
+    /// when the document only describes webhooks or components.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub paths: IndexMap<Str, PathItem>,
+
+    /// The incoming webhooks that MAY be received as part of this API and that the API consumer
+    /// MAY choose to implement. The key name is a unique string to refer to each webhook, while
+    /// the (optionally referenced) Path Item Object describes a request that may be initiated
+    /// by the API provider and the expected responses.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub webhooks: IndexMap<Str, ObjectOrReference<PathItem>>,
+
+    /// An element to hold various schemas for the specification.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components: Option<Components>,
+
+    /// A declaration of which security mechanisms can be used across the API.
+    /// The list of values includes alternative security requirement objects that can be used.
+    /// Only one of the security requirement objects need to be satisfied to authorize a request.
+    /// Individual operations can override this definition.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub security: Vec<SecurityRequirement>,
+
+    /// A list of tags used by the document with additional metadata. Each tag name in the list
+    /// MUST be unique.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<Tag>,
+
+    /// Additional external documentation.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "externalDocs")]
+    pub external_docs: Option<ExternalDoc>,
+
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// General information about the API.
+///
+/// See <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.1.0.md#infoObject>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Info {
+    /// The title of the API.
+    pub title: Str,
+    /// A short summary of the API.
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub summary: Str,
+    /// A description of the API. CommonMark syntax MAY be used for rich text representation.
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub description: Str,
+    /// A URL to the Terms of Service for the API. This MUST be in the form of a URL.
+    #[serde(rename = "termsOfService", skip_serializing_if = "Option::is_none")]
+    pub terms_of_service: Option<Url>,
+    /// The version of the OpenAPI document (which is distinct from the OpenAPI Specification
+    /// version or the API implementation version).
+    pub version: Str,
+    /// The contact information for the exposed API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact: Option<Contact>,
+    /// The license information for the exposed API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<License>,
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// License information for the exposed API.
+///
+/// See <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.1.0.md#licenseObject>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct License {
+    /// The license name used for the API.
+    pub name: Str,
+    /// An [SPDX](https://spdx.org/spdx-specification-21-web-version#h.jxpfx0ykyb60) license
+    /// expression for the API. The `identifier` field is mutually exclusive of the `url` field.
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub identifier: Str,
+    /// A URL to the license used for the API. The `url` field is mutually exclusive of the
+    /// `identifier` field.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<Url>,
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// Describes the operations available on a single path.
+///
+/// See <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.1.0.md#pathItemObject>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct PathItem {
+    /// Allows for a referenced definition of this path item. The referenced structure MUST be in
+    /// the form of a Path Item Object. In case a Path Item Object field appears both in the
+    /// defined object and the referenced object, the behavior is undefined.
+    #[serde(default, skip_serializing_if = "str::is_empty", rename = "$ref")]
+    pub reference: Str,
+
+    /// An optional, string summary, intended to apply to all operations in this path.
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub summary: Str,
+    /// An optional, string description, intended to apply to all operations in this path.
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub description: Str,
+
+    /// A definition of a GET operation on this path.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub get: Option<Operation>,
+    /// A definition of a PUT operation on this path.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub put: Option<Operation>,
+    /// A definition of a POST operation on this path.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post: Option<Operation>,
+    /// A definition of a DELETE operation on this path.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delete: Option<Operation>,
+    /// A definition of a OPTIONS operation on this path.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<Operation>,
+    /// A definition of a HEAD operation on this path.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub head: Option<Operation>,
+    /// A definition of a PATCH operation on this path.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub patch: Option<Operation>,
+    /// A definition of a TRACE operation on this path.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace: Option<Operation>,
+
+    /// An alternative `server` array to service all operations in this path.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub servers: Vec<Server>,
+
+    /// A list of parameters that are applicable for all the operations described under this
+    /// path. These parameters can be overridden at the operation level, but cannot be removed
+    /// there. The list MUST NOT include duplicated parameters.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<ObjectOrReference<Parameter>>,
+
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// Describes a single API operation on a path.
+///
+/// See <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.1.0.md#operationObject>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Operation {
+    /// A list of tags for API documentation control.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<Str>,
+    /// A short summary of what the operation does.
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub summary: Str,
+    /// A verbose explanation of the operation behavior.
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub description: Str,
+    /// Additional external documentation for this operation.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "externalDocs")]
+    pub external_docs: Option<ExternalDoc>,
+    /// Unique string used to identify the operation. The id MUST be unique among all operations
+    /// described in the API.
+    #[serde(default, skip_serializing_if = "str::is_empty", rename = "operationId")]
+    pub operation_id: Str,
+
+    /// A list of parameters that are applicable for this operation. If a parameter is already
+    /// defined at the Path Item, the new definition will override it but can never remove it.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<ObjectOrReference<Parameter>>,
+
+    /// The request body applicable for this operation.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "requestBody")]
+    pub request_body: Option<ObjectOrReference<RequestBody>>,
+
+    /// The list of possible responses as they are returned from executing this operation. The
+    /// key is a HTTP status code, a range such as `2XX`, or `default`.
+    ///
+    /// See <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.1.0.md#responsesObject>.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub responses: IndexMap<Str, ObjectOrReference<Response>>,
+
+    /// A map of possible out-of band callbacks related to the parent operation.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub callbacks: IndexMap<Str, ObjectOrReference<Callback>>,
+
+    /// Declares this operation to be deprecated. Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    /// A declaration of which security mechanisms can be used for this operation. This
+    /// definition overrides any declared top-level `security`. To remove a top-level security
+    /// declaration, an empty array can be used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub security: Option<Vec<SecurityRequirement>>,
+
+    /// An alternative `server` array to service this operation.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub servers: Vec<Server>,
+
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// A map of possible out-of band callbacks related to the parent operation. Each value in the
+/// map is a Path Item Object that describes a set of requests that may be initiated by the API
+/// provider and the expected responses. The key is a runtime expression that identifies a URL
+/// to use for the callback operation.
+///
+/// See <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.1.0.md#callbackObject>.
+pub type Callback = IndexMap<Str, PathItem>;
+
+/// The location of a parameter.
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Location {
+    Query,
+    Header,
+    Path,
+    Cookie,
+}
+
+/// Just for convenience.
+impl Default for Location {
+    fn default() -> Self {
+        Location::Query
+    }
+}
+
+/// Describes a single operation parameter.
+/// A unique parameter is defined by a combination of a `name` and a location (`in`).
+///
+/// See <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.1.0.md#parameterObject>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Parameter {
+    /// The name of the parameter. Parameter names are case sensitive.
+    pub name: Str,
+
+    /// The location of the parameter.
+    #[serde(rename = "in")]
+    pub location: Location,
+
+    /// A brief description of the parameter.
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub description: Str,
+
+    /// Determines whether this parameter is mandatory. MUST be `true` for path parameters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+
+    /// Specifies that a parameter is deprecated and SHOULD be transitioned out of usage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    /// Sets the ability to pass empty-valued `query` parameters. Use of this property is NOT
+    /// RECOMMENDED, as it is likely to be removed in a later revision.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_empty_value: Option<bool>,
+
+    /// Describes how the parameter value will be serialized depending on the type of the
+    /// parameter value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<ParameterStyle>,
+
+    /// When this is true, parameter values of type `array` or `object` generate separate
+    /// parameters for each value of the array or key-value pair of the map.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explode: Option<bool>,
+
+    /// Determines whether the parameter value SHOULD allow reserved characters, as defined by
+    /// [RFC3986](https://tools.ietf.org/html/rfc3986#section-2.2), to be included without
+    /// percent-encoding. Only applies to `query` parameters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_reserved: Option<bool>,
+
+    /// The schema defining the type used for the parameter or a map containing the
+    /// representations for the parameter.
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub representation: Option<ParameterRepresentation>,
+
+    /// Example(s) of the parameter's potential value.
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub example: Option<Examples>,
+
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// The schema defining the type used for a parameter or header, or a map containing its
+/// representations.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(untagged)]
+#[allow(clippy::large_enum_variant)]
+pub enum ParameterRepresentation {
+    Simple {
+        /// The schema defining the type used for the parameter.
+        schema: Schema,
+    },
+    Content {
+        /// A map containing the representations for the parameter. The key is the media type
+        /// and the value describes it. The map MUST only contain one entry.
+        content: IndexMap<Str, MediaType>,
+    },
+}
+
+/// Example(s) of a parameter, header or media type. The `example` field is mutually exclusive
+/// of the `examples` field.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum Examples {
+    One {
+        /// Example of the potential value.
+        example: serde_json::Value,
+    },
+    Multiple {
+        /// Examples of the potential value.
+        examples: IndexMap<Str, ObjectOrReference<Example>>,
+    },
+}
+
+/// The Header Object follows the structure of the Parameter Object, without `name` and `in`.
+///
+/// See <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.1.0.md#headerObject>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Header {
+    /// A brief description of the header.
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub description: Str,
+    /// Determines whether this header is mandatory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+    /// Specifies that the header is deprecated and SHOULD be transitioned out of usage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+    /// Describes how the header value will be serialized. Only `simple` is allowed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<ParameterStyle>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explode: Option<bool>,
+    /// The schema defining the type used for the header or a map containing its
+    /// representations.
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub representation: Option<ParameterRepresentation>,
+    /// Example(s) of the header's potential value.
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub example: Option<Examples>,
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// Describes a single request body.
+///
+/// See <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.1.0.md#requestBodyObject>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct RequestBody {
+    /// A brief description of the request body.
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub description: Str,
+
+    /// The content of the request body. The key is a media type or media type range and the
+    /// value describes it.
+    pub content: IndexMap<Str, MediaType>,
+
+    /// Determines if the request body is required in the request. Defaults to `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// Describes a single response from an API Operation.
+///
+/// See <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.1.0.md#responseObject>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Response {
+    /// A description of the response.
+    pub description: Str,
+
+    /// Maps a header name to its definition. Header names are case insensitive.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub headers: IndexMap<Str, ObjectOrReference<Header>>,
+
+    /// A map containing descriptions of potential response payloads. The key is a media type
+    /// or media type range and the value describes it.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub content: IndexMap<Str, MediaType>,
+
+    /// A map of operations links that can be followed from the response.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub links: IndexMap<Str, ObjectOrReference<Link>>,
+
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// Each Media Type Object provides schema and examples for the media type identified by its key.
+///
+/// See <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.1.0.md#mediaTypeObject>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct MediaType {
+    /// The schema defining the content of the request, response, or parameter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<Schema>,
+
+    /// Example(s) of the media type.
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub examples: Option<Examples>,
+
+    /// A map between a property name and its encoding information.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub encoding: IndexMap<Str, Encoding>,
+
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// A single encoding definition applied to a single schema property.
+///
+/// See <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.1.0.md#encodingObject>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Encoding {
+    /// The Content-Type for encoding a specific property.
+    #[serde(default, skip_serializing_if = "str::is_empty", rename = "contentType")]
+    pub content_type: Str,
+
+    /// A map allowing additional information to be provided as headers.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub headers: IndexMap<Str, ObjectOrReference<Header>>,
+
+    /// Describes how a specific property value will be serialized depending on its type.
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub style: Str,
+
+    /// When this is true, property values of type `array` or `object` generate separate
+    /// parameters for each value of the array, or key-value-pair of the map.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explode: Option<bool>,
+
+    /// Determines whether the parameter value SHOULD allow reserved characters.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "allowReserved")]
+    pub allow_reserved: Option<bool>,
+
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// Defines a security scheme that can be used by the operations.
+///
+/// See <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.1.0.md#securitySchemeObject>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(tag = "type")]
+#[allow(clippy::large_enum_variant)]
+pub enum SecurityScheme {
+    #[serde(rename = "apiKey")]
+    ApiKey {
+        name: Str,
+        #[serde(rename = "in")]
+        location: Str,
+        #[serde(default, skip_serializing_if = "str::is_empty")]
+        description: Str,
+    },
+    #[serde(rename = "http")]
+    Http {
+        scheme: Str,
+        #[serde(
+            default,
+            rename = "bearerFormat",
+            skip_serializing_if = "str::is_empty"
+        )]
+        bearer_format: Str,
+        #[serde(default, skip_serializing_if = "str::is_empty")]
+        description: Str,
+    },
+    #[serde(rename = "mutualTLS")]
+    MutualTls {
+        #[serde(default, skip_serializing_if = "str::is_empty")]
+        description: Str,
+    },
+    #[serde(rename = "oauth2")]
+    OAuth2 {
+        flows: Flows,
+        #[serde(default, skip_serializing_if = "str::is_empty")]
+        description: Str,
+    },
+    #[serde(rename = "openIdConnect")]
+    OpenIdConnect {
+        #[serde(rename = "openIdConnectUrl")]
+        open_id_connect_url: Str,
+        #[serde(default, skip_serializing_if = "str::is_empty")]
+        description: Str,
+    },
+}
+
+/// Adds metadata to a single tag that is used by the Operation Object.
+///
+/// See <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.1.0.md#tagObject>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Tag {
+    /// The name of the tag.
+    pub name: Str,
+
+    /// A description for the tag.
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub description: Str,
+
+    /// Additional external documentation for this tag.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "externalDocs")]
+    pub external_docs: Option<ExternalDoc>,
+
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// Primitive types of [JSON Schema](https://json-schema.org/draft/2020-12/json-schema-validation.html#rfc.section.6.1.1).
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Type {
+    Null,
+    Boolean,
+    Object,
+    Array,
+    Number,
+    String,
+    Integer,
+}
+
+/// Value of the `type` keyword: a single type, or an array of unique types.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum SchemaType {
+    Single(Type),
+    Multiple(Vec<Type>),
+}
+
+impl SchemaType {
+    pub fn types(&self) -> &[Type] {
+        match self {
+            SchemaType::Single(ty) => std::slice::from_ref(ty),
+            SchemaType::Multiple(types) => types,
+        }
+    }
+
+    pub fn contains(&self, ty: Type) -> bool {
+        self.types().contains(&ty)
+    }
+}
+
+impl From<Type> for SchemaType {
+    fn from(ty: Type) -> Self {
+        SchemaType::Single(ty)
+    }
+}
+
+impl From<Vec<Type>> for SchemaType {
+    /// Collapses a single type into [`SchemaType::Single`].
+    fn from(mut types: Vec<Type>) -> Self {
+        if types.len() == 1 {
+            SchemaType::Single(types.remove(0))
+        } else {
+            SchemaType::Multiple(types)
+        }
+    }
+}
+
+/// A JSON Schema, which is either a boolean or a [`Schema`].
+///
+/// `true` accepts any instance and `false` rejects all of them.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum BoolOrSchema {
+    Bool(bool),
+    Schema(Box<Schema>),
+}
+
+impl From<Schema> for BoolOrSchema {
+    fn from(schema: Schema) -> Self {
+        BoolOrSchema::Schema(Box::new(schema))
+    }
+}
+
+/// The Schema Object allows the definition of input and output data types. It is a superset of
+/// [JSON Schema Specification Draft 2020-12](https://tools.ietf.org/html/draft-bhutton-json-schema-00):
+/// all of its keywords are allowed, and it adds `discriminator`, `xml`, `externalDocs` and the
+/// deprecated `example`.
+///
+/// Unlike OpenAPI 3.0, references (`$ref`) are part of the schema itself and may have siblings,
+/// and nullability is expressed with the `null` type instead of `nullable`.
+///
+/// See <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.1.0.md#schemaObject>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Schema {
+    // Core vocabulary
+    /// The dialect of this schema and its sub-schemas.
+    #[serde(default, rename = "$schema", skip_serializing_if = "str::is_empty")]
+    pub schema: Str,
+    /// Identifies the schema resource with its canonical URI.
+    #[serde(default, rename = "$id", skip_serializing_if = "str::is_empty")]
+    pub id: Str,
+    /// Reference to another schema, applied in place alongside the other keywords.
+    #[serde(default, rename = "$ref", skip_serializing_if = "str::is_empty")]
+    pub reference: Str,
+    /// Plain name fragment identifying this schema within its resource.
+    #[serde(default, rename = "$anchor", skip_serializing_if = "str::is_empty")]
+    pub anchor: Str,
+    #[serde(default, rename = "$dynamicRef", skip_serializing_if = "str::is_empty")]
+    pub dynamic_ref: Str,
+    #[serde(
+        default,
+        rename = "$dynamicAnchor",
+        skip_serializing_if = "str::is_empty"
+    )]
+    pub dynamic_anchor: Str,
+    /// Vocabularies available to schemas using this schema as their meta-schema.
+    #[serde(
+        default,
+        rename = "$vocabulary",
+        skip_serializing_if = "IndexMap::is_empty"
+    )]
+    pub vocabulary: IndexMap<Str, bool>,
+    #[serde(default, rename = "$comment", skip_serializing_if = "str::is_empty")]
+    pub comment: Str,
+    /// Re-usable schemas, to be referenced from within this schema.
+    #[serde(default, rename = "$defs", skip_serializing_if = "IndexMap::is_empty")]
+    pub defs: IndexMap<Str, BoolOrSchema>,
+
+    // Meta-data vocabulary
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub title: Str,
+    /// [CommonMark syntax](http://spec.commonmark.org/) MAY be used for rich text representation.
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub description: Str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub write_only: Option<bool>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<serde_json::Value>,
+
+    // Applicator vocabulary
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub all_of: Vec<BoolOrSchema>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub any_of: Vec<BoolOrSchema>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub one_of: Vec<BoolOrSchema>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub not: Option<BoolOrSchema>,
+    #[serde(rename = "if", skip_serializing_if = "Option::is_none")]
+    pub if_schema: Option<BoolOrSchema>,
+    #[serde(rename = "then", skip_serializing_if = "Option::is_none")]
+    pub then_schema: Option<BoolOrSchema>,
+    #[serde(rename = "else", skip_serializing_if = "Option::is_none")]
+    pub else_schema: Option<BoolOrSchema>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub dependent_schemas: IndexMap<Str, BoolOrSchema>,
+    /// Schemas of the leading items of an array, by position.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub prefix_items: Vec<BoolOrSchema>,
+    /// Schema of the array items not covered by `prefixItems`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub items: Option<BoolOrSchema>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contains: Option<BoolOrSchema>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub properties: IndexMap<Str, BoolOrSchema>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub pattern_properties: IndexMap<Str, BoolOrSchema>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub additional_properties: Option<BoolOrSchema>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub property_names: Option<BoolOrSchema>,
+
+    // Unevaluated locations vocabulary
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unevaluated_items: Option<BoolOrSchema>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unevaluated_properties: Option<BoolOrSchema>,
+
+    // Validation vocabulary
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub schema_type: Option<SchemaType>,
+    #[serde(rename = "enum", default, skip_serializing_if = "Vec::is_empty")]
+    pub enum_values: Vec<serde_json::Value>,
+    #[serde(rename = "const", skip_serializing_if = "Option::is_none")]
+    pub const_value: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multiple_of: Option<serde_json::Number>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<serde_json::Number>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclusive_maximum: Option<serde_json::Number>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<serde_json::Number>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclusive_minimum: Option<serde_json::Number>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<usize>,
+    /// This string SHOULD be a valid regular expression, according to the ECMA-262 regular
+    /// expression dialect.
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub pattern: Str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_items: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_items: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unique_items: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_contains: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_contains: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_properties: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_properties: Option<usize>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required: Vec<Str>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub dependent_required: IndexMap<Str, Vec<Str>>,
+
+    // Format annotation vocabulary
+    /// See [Data Type Formats](https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.1.0.md#dataTypeFormat).
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub format: Str,
+
+    // Content vocabulary
+    /// Encoding of string instances holding binary data, such as `base64`.
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub content_encoding: Str,
+    /// Media type of the contents of string instances, such as `image/png`.
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub content_media_type: Str,
+    /// Schema of the decoded contents of string instances.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_schema: Option<BoolOrSchema>,
+
+    // OpenAPI vocabulary
+    /// Helps differentiating payloads of polymorphic schemas.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discriminator: Option<Discriminator>,
+    /// Describes the XML representation of this property.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xml: Option<Xml>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_docs: Option<ExternalDoc>,
+    /// Deprecated in favor of `examples`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example: Option<serde_json::Value>,
+
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// Aids in serialization, deserialization, and validation when payloads may be one of a number
+/// of different schemas.
+///
+/// See <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.1.0.md#discriminatorObject>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Discriminator {
+    /// The name of the property in the payload that will hold the discriminator value.
+    #[serde(rename = "propertyName")]
+    pub property_name: Str,
+    /// Mappings between payload values and schema names or references.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub mapping: IndexMap<Str, Str>,
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// Metadata for a fine-tuned XML model.
+///
+/// See <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.1.0.md#xmlObject>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Xml {
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub name: Str,
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub namespace: Str,
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub prefix: Str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attribute: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wrapped: Option<bool>,
+    /// Specification extensions, i.e. fields starting with `x-`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spec_deser() {
+        const SPEC: &str = r##"{
+          "openapi": "3.1.0",
+          "jsonSchemaDialect": "https://spec.openapis.org/oas/3.1/dialect/base",
+          "info": {
+            "title": "Webhook Example",
+            "summary": "Pets and their owners",
+            "version": "1.0.0",
+            "license": {"name": "Apache 2.0", "identifier": "Apache-2.0"}
+          },
+          "webhooks": {
+            "newPet": {
+              "post": {
+                "requestBody": {
+                  "content": {
+                    "application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}
+                  }
+                },
+                "responses": {"200": {"description": "ok"}}
+              }
+            }
+          },
+          "components": {
+            "schemas": {
+              "Pet": {
+                "$id": "https://example.com/pet",
+                "type": "object",
+                "required": ["name"],
+                "properties": {
+                  "name": {"type": ["string", "null"], "examples": ["Rex"]},
+                  "tags": {"type": "array", "prefixItems": [{"const": "main"}], "items": false},
+                  "owner": {"$ref": "#/$defs/Owner", "description": "Who feeds it"}
+                },
+                "if": {"properties": {"kind": {"const": "cat"}}},
+                "then": {"required": ["lives"]},
+                "else": true,
+                "unevaluatedProperties": false,
+                "$defs": {
+                  "Owner": {"$anchor": "owner", "type": "object"}
+                }
+              }
+            },
+            "pathItems": {
+              "Health": {"get": {"responses": {"200": {"description": "up"}}}}
+            }
+          }
+        }"##;
+        let spec: Spec = serde_json::from_str(SPEC).unwrap();
+        assert!(spec.paths.is_empty());
+        assert_eq!(spec.info.summary, "Pets and their owners");
+        assert_eq!(spec.info.license.as_ref().unwrap().identifier, "Apache-2.0");
+        assert!(spec.webhooks.contains_key("newPet"));
+
+        let components = spec.components.as_ref().unwrap();
+        assert!(components.path_items.contains_key("Health"));
+        let pet = &components.schemas["Pet"];
+        assert_eq!(pet.id, "https://example.com/pet");
+        assert!(pet.defs.contains_key("Owner"));
+        assert_eq!(pet.unevaluated_properties, Some(BoolOrSchema::Bool(false)));
+        assert_eq!(pet.else_schema, Some(BoolOrSchema::Bool(true)));
+        match &pet.properties["name"] {
+            BoolOrSchema::Schema(name) => {
+                let types = name.schema_type.as_ref().unwrap();
+                assert!(types.contains(Type::String) && types.contains(Type::Null));
+            }
+            other => panic!("unexpected schema {:?}", other),
+        }
+        match &pet.properties["owner"] {
+            BoolOrSchema::Schema(owner) => {
+                assert_eq!(owner.reference, "#/$defs/Owner");
+                assert_eq!(owner.description, "Who feeds it");
+            }
+            other => panic!("unexpected schema {:?}", other),
+        }
+
+        let expected: serde_json::Value = serde_json::from_str(SPEC).unwrap();
+        assert_eq!(serde_json::to_value(&spec).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_reference_with_summary() {
+        let param: ObjectOrReference<Parameter> = serde_json::from_str(
+            r##"{"$ref": "#/components/parameters/limit", "description": "Page size"}"##,
+        )
+        .unwrap();
+        match param {
+            ObjectOrReference::Ref {
+                ref_path,
+                description,
+                ..
+            } => {
+                assert_eq!(ref_path, "#/components/parameters/limit");
+                assert_eq!(description, "Page size");
+            }
+            _ => panic!("expected a reference"),
+        }
+    }
+}
@@ -0,0 +1,1044 @@
+//! Upgrade of OpenAPI 3.0 documents to 3.1.
+//!
+//! Most objects carry over unchanged. Schemas are the exception, as 3.1 replaced the 3.0
+//! dialect with plain JSON Schema 2020-12; every change of that kind is listed in the
+//! [`Report`] returned by [`upgrade`].
+
+use crate::{
+    json_pointer, v3_0,
+    v3_1::{
+        BoolOrSchema, Callback, Components, Encoding, Examples, Header, Info, License, Location,
+        MediaType, ObjectOrReference, Operation, Parameter, ParameterRepresentation, PathItem,
+        RequestBody, Response, Schema, SchemaType, SecurityScheme, Spec, Tag, Type,
+    },
+    Str,
+};
+use indexmap::IndexMap;
+use serde_json::Value;
+use std::fmt;
+
+/// Version written to the `openapi` field of upgraded documents.
+pub const VERSION: &str = "3.1.0";
+
+/// What an upgrade rewrote.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RewriteKind {
+    /// The `openapi` version was bumped.
+    Version,
+    /// `nullable` was replaced by the `null` type.
+    Nullable,
+    /// The schema `example` was moved into `examples`.
+    Example,
+    /// A boolean `exclusiveMinimum` was replaced by its numeric form.
+    ExclusiveMinimum,
+    /// A boolean `exclusiveMaximum` was replaced by its numeric form.
+    ExclusiveMaximum,
+    /// A binary or base64 string was rewritten with `contentMediaType` or `contentEncoding`.
+    FileUpload,
+    /// `type` and `format` of a header were moved into its `schema`.
+    HeaderSchema,
+    /// A `formData` parameter, which 3.0 does not define either, was moved to the query.
+    FormDataParameter,
+    /// A value without 3.1 equivalent was removed.
+    Dropped,
+}
+
+/// A single change made by [`upgrade`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rewrite {
+    /// JSON pointer of the rewritten node in the 3.0 document.
+    pub pointer: String,
+    pub kind: RewriteKind,
+    pub message: String,
+}
+
+impl fmt::Display for Rewrite {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`{}`: {}", self.pointer, self.message)
+    }
+}
+
+/// The changes made by [`upgrade`], in document order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Report {
+    rewrites: Vec<Rewrite>,
+}
+
+impl Report {
+    pub fn rewrites(&self) -> &[Rewrite] {
+        &self.rewrites
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rewrites.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.rewrites.len()
+    }
+
+    /// Rewrites of the given kind.
+    pub fn of_kind(&self, kind: RewriteKind) -> impl Iterator<Item = &Rewrite> {
+        self.rewrites.iter().filter(move |r| r.kind == kind)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for rewrite in &self.rewrites {
+            writeln!(f, "{}", rewrite)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> IntoIterator for &'a Report {
+    type Item = &'a Rewrite;
+    type IntoIter = std::slice::Iter<'a, Rewrite>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.rewrites.iter()
+    }
+}
+
+impl IntoIterator for Report {
+    type Item = Rewrite;
+    type IntoIter = std::vec::IntoIter<Rewrite>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.rewrites.into_iter()
+    }
+}
+
+/// Converts a 3.0 document to 3.1, reporting every rewrite it made.
+///
+/// Schemas are rewritten as follows:
+/// - `nullable: true` adds `null` to `type` (and `enum`), or wraps untyped schemas in an
+///   `anyOf` with a `null` schema.
+/// - `example` becomes a single item of `examples`.
+/// - `exclusiveMinimum: true` and `exclusiveMaximum: true` take the value of `minimum` and
+///   `maximum` respectively.
+/// - `format: binary` and `type: file` become `contentMediaType`, and `format: base64`
+///   becomes `contentEncoding: base64`.
+/// - References to component schemas become `$ref` schemas.
+pub fn upgrade(spec: v3_0::Spec) -> (Spec, Report) {
+    let mut upgrader = Upgrader::default();
+    let spec = upgrader.spec(spec);
+    let report = Report {
+        rewrites: upgrader.rewrites,
+    };
+    (spec, report)
+}
+
+impl From<v3_0::Spec> for Spec {
+    fn from(spec: v3_0::Spec) -> Self {
+        upgrade(spec).0
+    }
+}
+
+#[derive(Default)]
+struct Upgrader {
+    pointer: String,
+    rewrites: Vec<Rewrite>,
+}
+
+impl Upgrader {
+    fn record(&mut self, kind: RewriteKind, message: String) {
+        self.rewrites.push(Rewrite {
+            pointer: self.pointer.clone(),
+            kind,
+            message,
+        });
+    }
+
+    /// Runs `f` with `token` appended to the current pointer.
+    fn at<T>(&mut self, token: &str, f: impl FnOnce(&mut Self) -> T) -> T {
+        let len = self.pointer.len();
+        json_pointer::push(&mut self.pointer, token);
+        let out = f(self);
+        self.pointer.truncate(len);
+        out
+    }
+
+    fn map<T, U>(
+        &mut self,
+        key: &str,
+        map: IndexMap<Str, T>,
+        mut f: impl FnMut(&mut Self, T) -> U,
+    ) -> IndexMap<Str, U> {
+        self.at(key, |u| {
+            map.into_iter()
+                .map(|(name, value)| {
+                    let value = u.at(&name, |u| f(u, value));
+                    (name, value)
+                })
+                .collect()
+        })
+    }
+
+    fn list<T, U>(
+        &mut self,
+        key: &str,
+        list: Vec<T>,
+        mut f: impl FnMut(&mut Self, T) -> U,
+    ) -> Vec<U> {
+        self.at(key, |u| {
+            list.into_iter()
+                .enumerate()
+                .map(|(i, value)| u.at(&i.to_string(), |u| f(u, value)))
+                .collect()
+        })
+    }
+
+    fn reference<T, U>(
+        &mut self,
+        value: v3_0::ObjectOrReference<T>,
+        f: impl FnOnce(&mut Self, T) -> U,
+    ) -> ObjectOrReference<U> {
+        match value {
+            v3_0::ObjectOrReference::Object(object) => ObjectOrReference::Object(f(self, object)),
+            v3_0::ObjectOrReference::Ref { ref_path } => ObjectOrReference::Ref {
+                ref_path,
+                summary: Str::default(),
+                description: Str::default(),
+            },
+        }
+    }
+
+    fn spec(&mut self, spec: v3_0::Spec) -> Spec {
+        let v3_0::Spec {
+            openapi,
+            info,
+            servers,
+            paths,
+            components,
+            security,
+            tags,
+            external_docs,
+            extensions,
+        } = spec;
+        if openapi != VERSION {
+            self.at("openapi", |u| {
+                u.record(
+                    RewriteKind::Version,
+                    format!("changed version `{}` to `{}`", openapi, VERSION),
+                )
+            });
+        }
+        Spec {
+            openapi: Str::Borrowed(VERSION),
+            info: self.info(info),
+            json_schema_dialect: Str::default(),
+            servers,
+            paths: self.map("paths", paths, Self::path_item),
+            webhooks: IndexMap::new(),
+            components: components.map(|c| self.at("components", |u| u.components(c))),
+            security,
+            tags: tags
+                .into_iter()
+                .map(|tag| Tag {
+                    name: tag.name,
+                    description: tag.description,
+                    external_docs: tag.external_docs,
+                    extensions: tag.extensions,
+                })
+                .collect(),
+            external_docs,
+            extensions,
+        }
+    }
+
+    fn info(&mut self, info: v3_0::Info) -> Info {
+        Info {
+            title: info.title,
+            summary: Str::default(),
+            description: info.description,
+            terms_of_service: info.terms_of_service,
+            version: info.version,
+            contact: info.contact,
+            license: info.license.map(|license| License {
+                name: license.name,
+                identifier: Str::default(),
+                url: license.url,
+                extensions: license.extensions,
+            }),
+            extensions: info.extensions,
+        }
+    }
+
+    fn components(&mut self, components: v3_0::Components) -> Components {
+        let v3_0::Components {
+            schemas,
+            responses,
+            parameters,
+            examples,
+            request_bodies,
+            headers,
+            security_schemes,
+            links,
+            callbacks,
+            extensions,
+        } = components;
+        Components {
+            schemas: self.map("schemas", schemas, |u, schema| match schema {
+                v3_0::ObjectOrReference::Object(schema) => u.schema(schema, None),
+                v3_0::ObjectOrReference::Ref { ref_path } => Schema {
+                    reference: ref_path,
+                    ..Schema::default()
+                },
+            }),
+            responses: self.map("responses", responses, |u, r| {
+                u.reference(r, Self::response)
+            }),
+            parameters: self.map("parameters", parameters, |u, p| {
+                u.reference(p, Self::parameter)
+            }),
+            examples: self.map("examples", examples, |u, e| u.reference(e, |_, e| e)),
+            request_bodies: self.map("requestBodies", request_bodies, |u, b| {
+                u.reference(b, Self::request_body)
+            }),
+            headers: self.map("headers", headers, |u, h| u.reference(h, Self::header)),
+            security_schemes: self.map("securitySchemes", security_schemes, |u, s| {
+                u.reference(s, |_, s| security_scheme(s))
+            }),
+            links: self.map("links", links, |u, l| u.reference(l, |_, l| l)),
+            callbacks: self.map("callbacks", callbacks, |u, c| {
+                u.reference(c, Self::callback)
+            }),
+            path_items: IndexMap::new(),
+            extensions,
+        }
+    }
+
+    fn path_item(&mut self, item: v3_0::PathItem) -> PathItem {
+        let v3_0::PathItem {
+            reference,
+            summary,
+            description,
+            get,
+            put,
+            post,
+            delete,
+            options,
+            head,
+            patch,
+            trace,
+            servers,
+            parameters,
+            extensions,
+        } = item;
+        let mut operation = |method: &str, operation: Option<v3_0::Operation>| {
+            operation.map(|op| self.at(method, |u| u.operation(op)))
+        };
+        PathItem {
+            reference,
+            summary,
+            description,
+            get: operation("get", get),
+            put: operation("put", put),
+            post: operation("post", post),
+            delete: operation("delete", delete),
+            options: operation("options", options),
+            head: operation("head", head),
+            patch: operation("patch", patch),
+            trace: operation("trace", trace),
+            servers,
+            parameters: self.list("parameters", parameters, |u, p| {
+                u.reference(p, Self::parameter)
+            }),
+            extensions,
+        }
+    }
+
+    fn operation(&mut self, operation: v3_0::Operation) -> Operation {
+        let v3_0::Operation {
+            tags,
+            summary,
+            description,
+            external_docs,
+            operation_id,
+            parameters,
+            request_body,
+            responses,
+            callbacks,
+            deprecated,
+            security,
+            servers,
+            extensions,
+        } = operation;
+        Operation {
+            tags,
+            summary,
+            description,
+            external_docs,
+            operation_id,
+            parameters: self.list("parameters", parameters, |u, p| {
+                u.reference(p, Self::parameter)
+            }),
+            request_body: request_body
+                .map(|b| self.at("requestBody", |u| u.reference(b, Self::request_body))),
+            responses: self.map("responses", responses, |u, r| {
+                u.reference(r, Self::response)
+            }),
+            callbacks: self.map("callbacks", callbacks, |u, c| {
+                u.reference(c, Self::callback)
+            }),
+            deprecated,
+            security,
+            servers,
+            extensions,
+        }
+    }
+
+    fn callback(&mut self, callback: v3_0::Callback) -> Callback {
+        callback
+            .into_iter()
+            .map(|(expression, item)| {
+                let item = self.at(&expression, |u| u.path_item(item));
+                (expression, item)
+            })
+            .collect()
+    }
+
+    fn parameter(&mut self, parameter: v3_0::Parameter) -> Parameter {
+        let v3_0::Parameter {
+            name,
+            location,
+            description,
+            required,
+            deprecated,
+            style,
+            explode,
+            allow_reserved,
+            representation,
+            example,
+            extensions,
+        } = parameter;
+        let location = match location {
+            v3_0::Location::Query => Location::Query,
+            v3_0::Location::Header => Location::Header,
+            v3_0::Location::Path => Location::Path,
+            v3_0::Location::Cookie => Location::Cookie,
+            v3_0::Location::FormData => {
+                self.at("in", |u| {
+                    u.record(
+                        RewriteKind::FormDataParameter,
+                        "changed `formData` to `query`, form fields belong in the request body"
+                            .to_string(),
+                    )
+                });
+                Location::Query
+            }
+        };
+        Parameter {
+            name,
+            location,
+            description,
+            required,
+            deprecated,
+            allow_empty_value: None,
+            style,
+            explode,
+            allow_reserved,
+            representation: representation.map(|r| self.representation(r)),
+            example: example.and_then(|example| match example {
+                v3_0::ParameterExamples::One { example } => {
+                    example.map(|example| Examples::One { example })
+                }
+                v3_0::ParameterExamples::Multiple { examples } => Some(self.examples(examples)),
+            }),
+            extensions,
+        }
+    }
+
+    fn representation(
+        &mut self,
+        representation: v3_0::ParameterRepresentation,
+    ) -> ParameterRepresentation {
+        match representation {
+            v3_0::ParameterRepresentation::Simple { schema } => ParameterRepresentation::Simple {
+                schema: self.at("schema", |u| u.component_or_inline(schema, None)),
+            },
+            v3_0::ParameterRepresentation::Content { content } => {
+                ParameterRepresentation::Content {
+                    content: self.content(content),
+                }
+            }
+        }
+    }
+
+    fn examples(
+        &mut self,
+        examples: IndexMap<Str, v3_0::ObjectOrReference<v3_0::Example>>,
+    ) -> Examples {
+        Examples::Multiple {
+            examples: self.map("examples", examples, |u, e| u.reference(e, |_, e| e)),
+        }
+    }
+
+    fn header(&mut self, header: v3_0::Header) -> Header {
+        let v3_0::Header {
+            required,
+            schema,
+            unique_items,
+            param_type,
+            format,
+            description,
+            extensions,
+        } = header;
+        let loose = param_type.is_some() || !format.is_empty() || unique_items.is_some();
+        let schema = match schema {
+            Some(schema) => {
+                if loose {
+                    self.record(
+                        RewriteKind::Dropped,
+                        "removed `type`, `format` and `uniqueItems` overridden by `schema`"
+                            .to_string(),
+                    );
+                }
+                Some(self.at("schema", |u| u.component_or_inline(schema, None)))
+            }
+            None if loose => {
+                self.record(
+                    RewriteKind::HeaderSchema,
+                    "moved `type`, `format` and `uniqueItems` into `schema`".to_string(),
+                );
+                let schema = v3_0::Schema {
+                    schema_type: param_type,
+                    format,
+                    unique_items,
+                    ..v3_0::Schema::default()
+                };
+                Some(self.schema(schema, None))
+            }
+            None => None,
+        };
+        Header {
+            description,
+            required,
+            deprecated: None,
+            style: None,
+            explode: None,
+            representation: schema.map(|schema| ParameterRepresentation::Simple { schema }),
+            example: None,
+            extensions,
+        }
+    }
+
+    fn request_body(&mut self, body: v3_0::RequestBody) -> RequestBody {
+        RequestBody {
+            description: body.description,
+            content: self.content(body.content),
+            required: body.required,
+            extensions: body.extensions,
+        }
+    }
+
+    fn response(&mut self, response: v3_0::Response) -> Response {
+        Response {
+            description: response.description,
+            headers: self.map("headers", response.headers, |u, h| {
+                u.reference(h, Self::header)
+            }),
+            content: self.content(response.content),
+            links: self.map("links", response.links, |u, l| u.reference(l, |_, l| l)),
+            extensions: response.extensions,
+        }
+    }
+
+    fn content(&mut self, content: IndexMap<Str, v3_0::MediaType>) -> IndexMap<Str, MediaType> {
+        self.at("content", |u| {
+            content
+                .into_iter()
+                .map(|(name, media)| {
+                    let media = u.at(&name, |u| u.media_type(&name, media));
+                    (name, media)
+                })
+                .collect()
+        })
+    }
+
+    fn media_type(&mut self, name: &str, media: v3_0::MediaType) -> MediaType {
+        MediaType {
+            schema: media
+                .schema
+                .map(|s| self.at("schema", |u| u.component_or_inline(s, Some(name)))),
+            examples: media.examples.map(|examples| match examples {
+                v3_0::MediaTypeExample::Example { example } => Examples::One { example },
+                v3_0::MediaTypeExample::Examples { examples } => self.examples(examples),
+            }),
+            encoding: self.map("encoding", media.encoding, |u, encoding| Encoding {
+                content_type: encoding.content_type,
+                headers: u.map("headers", encoding.headers, |u, h| {
+                    u.reference(h, Self::header)
+                }),
+                style: encoding.style,
+                explode: encoding.explode,
+                allow_reserved: encoding.allow_reserved,
+                extensions: encoding.extensions,
+            }),
+            extensions: media.extensions,
+        }
+    }
+
+    fn component_or_inline(
+        &mut self,
+        schema: v3_0::ComponentOrInlineSchema,
+        media_type: Option<&str>,
+    ) -> Schema {
+        match schema {
+            v3_0::ComponentOrInlineSchema::Component { name } => Schema {
+                reference: format!("#/components/schemas/{}", json_pointer::escape(&name)).into(),
+                ..Schema::default()
+            },
+            v3_0::ComponentOrInlineSchema::ExtRef { reference } => Schema {
+                reference,
+                ..Schema::default()
+            },
+            v3_0::ComponentOrInlineSchema::Inline(schema) => self.schema(schema, media_type),
+        }
+    }
+
+    fn sub_schema(&mut self, schema: v3_0::ComponentOrInlineSchema) -> BoolOrSchema {
+        self.component_or_inline(schema, None).into()
+    }
+
+    /// Converts a numeric keyword, which 3.0 models as any value.
+    fn number(&mut self, key: &str, value: Option<Value>) -> Option<serde_json::Number> {
+        match value? {
+            Value::Number(number) => Some(number),
+            other => {
+                self.at(key, |u| {
+                    u.record(
+                        RewriteKind::Dropped,
+                        format!("removed `{}` which is not a number", other),
+                    )
+                });
+                None
+            }
+        }
+    }
+
+    /// Converts `exclusiveMinimum` or `exclusiveMaximum`, moving `bound` when it is `true`.
+    fn exclusive_bound(
+        &mut self,
+        key: &str,
+        bound_key: &str,
+        kind: RewriteKind,
+        exclusive: Option<Value>,
+        bound: &mut Option<serde_json::Number>,
+    ) -> Option<serde_json::Number> {
+        match exclusive {
+            Some(Value::Bool(true)) => self.at(key, |u| match bound.take() {
+                Some(bound) => {
+                    u.record(
+                        kind,
+                        format!(
+                            "replaced `true` and `{}: {}` by `{}`",
+                            bound_key, bound, bound
+                        ),
+                    );
+                    Some(bound)
+                }
+                None => {
+                    u.record(
+                        RewriteKind::Dropped,
+                        format!("removed `true` which has no `{}`", bound_key),
+                    );
+                    None
+                }
+            }),
+            Some(Value::Bool(false)) => {
+                self.at(key, |u| u.record(kind, "removed `false`".to_string()));
+                None
+            }
+            other => self.number(key, other),
+        }
+    }
+
+    fn schema(&mut self, schema: v3_0::Schema, media_type: Option<&str>) -> Schema {
+        let v3_0::Schema {
+            description,
+            format,
+            items,
+            properties,
+            read_only,
+            write_only,
+            nullable,
+            additional_properties,
+            example,
+            title,
+            default,
+            all_of,
+            one_of,
+            any_of,
+            schema_type,
+            enum_values,
+            const_value,
+            multiple_of,
+            minimum,
+            exclusive_minimum,
+            maximum,
+            exclusive_maximum,
+            max_length,
+            min_length,
+            pattern,
+            max_items,
+            min_items,
+            unique_items,
+            max_properties,
+            min_properties,
+            required,
+            dependent_required,
+            extensions,
+        } = schema;
+
+        let mut out = Schema {
+            title,
+            description,
+            default,
+            read_only,
+            write_only,
+            const_value,
+            max_length,
+            min_length,
+            pattern,
+            max_items,
+            min_items,
+            unique_items,
+            max_properties,
+            min_properties,
+            required,
+            dependent_required,
+            extensions,
+            ..Schema::default()
+        };
+        out.enum_values = enum_values
+            .into_iter()
+            .map(|value| Value::String(value.into_owned()))
+            .collect();
+        out.items = items.map(|s| self.at("items", |u| u.sub_schema(*s)));
+        out.properties = self.map("properties", properties, Self::sub_schema);
+        out.additional_properties =
+            additional_properties.map(|s| self.at("additionalProperties", |u| u.sub_schema(*s)));
+        out.all_of = self.list("allOf", all_of, Self::sub_schema);
+        out.one_of = self.list("oneOf", one_of, Self::sub_schema);
+        out.any_of = self.list("anyOf", any_of, Self::sub_schema);
+
+        out.multiple_of = self.number("multipleOf", multiple_of);
+        let mut minimum = self.number("minimum", minimum);
+        let mut maximum = self.number("maximum", maximum);
+        out.exclusive_minimum = self.exclusive_bound(
+            "exclusiveMinimum",
+            "minimum",
+            RewriteKind::ExclusiveMinimum,
+            exclusive_minimum,
+            &mut minimum,
+        );
+        out.exclusive_maximum = self.exclusive_bound(
+            "exclusiveMaximum",
+            "maximum",
+            RewriteKind::ExclusiveMaximum,
+            exclusive_maximum,
+            &mut maximum,
+        );
+        out.minimum = minimum;
+        out.maximum = maximum;
+
+        if let Some(example) = example {
+            self.at("example", |u| {
+                u.record(RewriteKind::Example, "moved into `examples`".to_string())
+            });
+            out.examples.push(example);
+        }
+
+        let content_media_type = match media_type {
+            Some(name)
+                if !name.starts_with("multipart/")
+                    && name != "application/x-www-form-urlencoded"
+                    && !name.contains("json")
+                    && !name.contains('*') =>
+            {
+                name
+            }
+            _ => "application/octet-stream",
+        };
+        let mut file = false;
+        out.schema_type = schema_type.map(|ty| {
+            SchemaType::Single(match ty {
+                v3_0::Type::String => Type::String,
+                v3_0::Type::Number => Type::Number,
+                v3_0::Type::Integer => Type::Integer,
+                v3_0::Type::Boolean => Type::Boolean,
+                v3_0::Type::Array => Type::Array,
+                v3_0::Type::Object => Type::Object,
+                v3_0::Type::File => {
+                    file = true;
+                    Type::String
+                }
+            })
+        });
+        match &*format {
+            "binary" => file = true,
+            "base64" => {
+                self.at("format", |u| {
+                    u.record(
+                        RewriteKind::FileUpload,
+                        "replaced `base64` by `contentEncoding: base64`".to_string(),
+                    )
+                });
+                out.content_encoding = Str::Borrowed("base64");
+            }
+            _ => out.format = format,
+        }
+        if file {
+            self.record(
+                RewriteKind::FileUpload,
+                format!(
+                    "replaced binary string by `contentMediaType: {}`",
+                    content_media_type
+                ),
+            );
+            out.schema_type = Some(Type::String.into());
+            out.content_media_type = content_media_type.to_string().into();
+        }
+
+        match nullable {
+            Some(true) => match out.schema_type.take() {
+                Some(SchemaType::Single(ty)) => {
+                    self.record(
+                        RewriteKind::Nullable,
+                        "replaced `nullable: true` by the `null` type".to_string(),
+                    );
+                    if !out.enum_values.is_empty() && !out.enum_values.contains(&Value::Null) {
+                        out.enum_values.push(Value::Null);
+                    }
+                    out.schema_type = Some(SchemaType::Multiple(vec![ty, Type::Null]));
+                }
+                other => {
+                    self.record(
+                        RewriteKind::Nullable,
+                        "replaced `nullable: true` by `anyOf` with a `null` schema".to_string(),
+                    );
+                    out.schema_type = other;
+                    let null = Schema {
+                        schema_type: Some(Type::Null.into()),
+                        ..Schema::default()
+                    };
+                    out = Schema {
+                        any_of: vec![out.into(), null.into()],
+                        ..Schema::default()
+                    };
+                }
+            },
+            Some(false) => self.at("nullable", |u| {
+                u.record(RewriteKind::Nullable, "removed `false`".to_string())
+            }),
+            None => {}
+        }
+        out
+    }
+}
+
+fn security_scheme(scheme: v3_0::SecurityScheme) -> SecurityScheme {
+    match scheme {
+        v3_0::SecurityScheme::ApiKey { name, location } => SecurityScheme::ApiKey {
+            name,
+            location,
+            description: Str::default(),
+        },
+        v3_0::SecurityScheme::Http {
+            scheme,
+            bearer_format,
+        } => SecurityScheme::Http {
+            scheme,
+            bearer_format,
+            description: Str::default(),
+        },
+        v3_0::SecurityScheme::OAuth2 { flows } => SecurityScheme::OAuth2 {
+            flows,
+            description: Str::default(),
+        },
+        v3_0::SecurityScheme::OpenIdConnect {
+            open_id_connect_url,
+        } => SecurityScheme::OpenIdConnect {
+            open_id_connect_url,
+            description: Str::default(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const SPEC: &str = r##"{
+      "openapi": "3.0.3",
+      "info": {"title": "Pets", "version": "1.0.0"},
+      "paths": {
+        "/pets/{petId}/photo": {
+          "put": {
+            "requestBody": {
+              "content": {
+                "image/png": {"schema": {"type": "string", "format": "binary"}},
+                "multipart/form-data": {
+                  "schema": {
+                    "type": "object",
+                    "properties": {
+                      "photo": {"type": "string", "format": "binary"},
+                      "thumbnail": {"type": "string", "format": "base64"}
+                    }
+                  }
+                }
+              }
+            },
+            "responses": {
+              "200": {
+                "description": "ok",
+                "headers": {"X-Rate-Limit": {"type": "integer"}},
+                "content": {
+                  "application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}
+                }
+              }
+            }
+          }
+        }
+      },
+      "components": {
+        "schemas": {
+          "Pet": {
+            "type": "object",
+            "properties": {
+              "name": {"type": "string", "nullable": true, "example": "Rex"},
+              "kind": {"type": "string", "enum": ["cat", "dog"], "nullable": true},
+              "age": {"type": "integer", "minimum": 0, "exclusiveMinimum": true},
+              "owner": {"nullable": true, "allOf": [{"$ref": "#/components/schemas/Owner"}]}
+            }
+          }
+        }
+      }
+    }"##;
+
+    #[test]
+    fn test_upgrade_schemas() {
+        let spec: v3_0::Spec = serde_json::from_str(SPEC).unwrap();
+        let (spec, report) = upgrade(spec);
+        assert_eq!(spec.openapi, "3.1.0");
+
+        let pet = serde_json::to_value(&spec.components.as_ref().unwrap().schemas["Pet"]).unwrap();
+        assert_eq!(
+            pet["properties"],
+            json!({
+                "name": {"type": ["string", "null"], "examples": ["Rex"]},
+                "kind": {"type": ["string", "null"], "enum": ["cat", "dog", null]},
+                "age": {"type": "integer", "exclusiveMinimum": 0},
+                "owner": {
+                    "anyOf": [
+                        {"allOf": [{"$ref": "#/components/schemas/Owner"}]},
+                        {"type": "null"}
+                    ]
+                }
+            })
+        );
+
+        let kinds = |kind| {
+            report
+                .of_kind(kind)
+                .map(|r| r.pointer.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(kinds(RewriteKind::Version), vec!["/openapi"]);
+        assert_eq!(
+            kinds(RewriteKind::Nullable),
+            vec![
+                "/components/schemas/Pet/properties/name",
+                "/components/schemas/Pet/properties/kind",
+                "/components/schemas/Pet/properties/owner",
+            ]
+        );
+        assert_eq!(
+            kinds(RewriteKind::Example),
+            vec!["/components/schemas/Pet/properties/name/example"]
+        );
+        assert_eq!(
+            kinds(RewriteKind::ExclusiveMinimum),
+            vec!["/components/schemas/Pet/properties/age/exclusiveMinimum"]
+        );
+    }
+
+    #[test]
+    fn test_upgrade_file_uploads() {
+        let spec: v3_0::Spec = serde_json::from_str(SPEC).unwrap();
+        let (spec, report) = upgrade(spec);
+        let put = spec.paths["/pets/{petId}/photo"].put.as_ref().unwrap();
+        let body = match put.request_body.as_ref().unwrap() {
+            ObjectOrReference::Object(body) => serde_json::to_value(body).unwrap(),
+            other => panic!("unexpected request body {:?}", other),
+        };
+        assert_eq!(
+            body["content"],
+            json!({
+                "image/png": {"schema": {"type": "string", "contentMediaType": "image/png"}},
+                "multipart/form-data": {
+                    "schema": {
+                        "type": "object",
+                        "properties": {
+                            "photo": {
+                                "type": "string",
+                                "contentMediaType": "application/octet-stream"
+                            },
+                            "thumbnail": {"type": "string", "contentEncoding": "base64"}
+                        }
+                    }
+                }
+            })
+        );
+        let response = match &put.responses["200"] {
+            ObjectOrReference::Object(response) => serde_json::to_value(response).unwrap(),
+            other => panic!("unexpected response {:?}", other),
+        };
+        assert_eq!(
+            response["headers"],
+            json!({"X-Rate-Limit": {"schema": {"type": "integer"}}})
+        );
+        assert_eq!(
+            response["content"]["application/json"]["schema"],
+            json!({"$ref": "#/components/schemas/Pet"})
+        );
+
+        assert_eq!(report.of_kind(RewriteKind::FileUpload).count(), 3);
+        assert_eq!(
+            report
+                .of_kind(RewriteKind::HeaderSchema)
+                .next()
+                .unwrap()
+                .to_string(),
+            "`/paths/~1pets~1{petId}~1photo/put/responses/200/headers/X-Rate-Limit`: \
+             moved `type`, `format` and `uniqueItems` into `schema`"
+        );
+    }
+
+    #[test]
+    fn test_upgrade_escaped_component_names() {
+        let spec: v3_0::Spec = serde_json::from_value(json!({
+            "openapi": "3.0.3",
+            "info": {"title": "Pets", "version": "1.0.0"},
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "pets/v1~draft": {"type": "object"},
+                    "Pets": {"type": "array", "items": {"$ref": "#/components/schemas/pets~1v1~0draft"}}
+                }
+            }
+        }))
+        .unwrap();
+        let (spec, _) = upgrade(spec);
+        let pets =
+            serde_json::to_value(&spec.components.as_ref().unwrap().schemas["Pets"]).unwrap();
+        assert_eq!(
+            pets["items"],
+            json!({"$ref": "#/components/schemas/pets~1v1~0draft"})
+        );
+    }
+}